@@ -0,0 +1,76 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use xkpasswd::dict::{load_binary_dict, load_dict};
+use xkpasswd::prelude::*;
+use xkpasswd::settings::*;
+
+const PRESETS: [(&str, Preset); 15] = [
+    ("default", Preset::Default),
+    ("apple-id", Preset::AppleID),
+    ("ntlm", Preset::WindowsNtlmV1),
+    ("secq", Preset::SecurityQuestions),
+    ("web16", Preset::Web16),
+    ("web32", Preset::Web32),
+    ("wifi", Preset::Wifi),
+    ("xkcd", Preset::Xkcd),
+    ("nist", Preset::Nist),
+    ("temporary", Preset::Temporary),
+    ("wifi-easy", Preset::WifiEasyType),
+    ("mobile", Preset::Mobile),
+    ("ssh", Preset::SshPassphrase),
+    ("disk", Preset::DiskEncryption),
+    ("database", Preset::DatabaseSafe),
+];
+
+fn bench_dict_load(c: &mut Criterion) {
+    let text_bytes: &[u8] = include_bytes!("../src/assets/dict_en.txt");
+    let binary_bytes: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/dict_en.bin"));
+
+    let mut group = c.benchmark_group("dict_load");
+    group.bench_function("text", |b| b.iter(|| load_dict(black_box(text_bytes))));
+    group.bench_function("binary", |b| {
+        b.iter(|| load_binary_dict(black_box(binary_bytes)).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_gen_pass(c: &mut Criterion) {
+    let pass = Xkpasswd::default();
+    let settings = Settings::default();
+
+    c.bench_function("gen_pass", |b| {
+        b.iter(|| pass.gen_pass(black_box(&settings)))
+    });
+}
+
+fn bench_gen_passes(c: &mut Criterion) {
+    let pass = Xkpasswd::default();
+    let settings = Settings::default();
+
+    let mut group = c.benchmark_group("gen_passes");
+    for count in [100, 10_000] {
+        group.bench_function(count.to_string(), |b| {
+            b.iter(|| pass.gen_passes(black_box(&settings), count))
+        });
+    }
+    group.finish();
+}
+
+fn bench_presets(c: &mut Criterion) {
+    let pass = Xkpasswd::default();
+
+    let mut group = c.benchmark_group("preset");
+    for (name, preset) in PRESETS {
+        let settings = Settings::from_preset(preset);
+        group.bench_function(name, |b| b.iter(|| pass.gen_pass(black_box(&settings))));
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_dict_load,
+    bench_gen_pass,
+    bench_gen_passes,
+    bench_presets
+);
+criterion_main!(benches);
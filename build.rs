@@ -0,0 +1,101 @@
+// Converts the bundled `len:word,word,...` dictionaries into the binary format
+// read by `dict::load_binary_dict`, so no string splitting happens at runtime.
+//
+// Layout (integers are little-endian):
+//   magic            b"XKD1"
+//   buckets count    u8
+//   per bucket       word length (u8), words count (u16)
+//   per word         byte length (u8)
+//   words            UTF-8 bytes of all words, concatenated
+//
+// Buckets are written in ascending order of word length.
+//
+// With the `compressed_dicts` feature, a deflated copy of each dictionary
+// is written next to it as `dict_<lang>.bin.deflate`.
+//
+// It also sets the `credential_store` cfg when the feature of a credential store
+// is enabled for the OS it's available on, gating `--store` & its options.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+const LANGUAGES: [&str; 5] = ["de", "en", "es", "fr", "pt"];
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+
+    for lang in LANGUAGES {
+        let src_path = format!("src/assets/dict_{}.txt", lang);
+        println!("cargo:rerun-if-changed={}", src_path);
+
+        let dict_str = fs::read_to_string(&src_path).unwrap();
+        let dict_bin = encode_dict(&dict_str);
+
+        #[cfg(feature = "compressed_dicts")]
+        fs::write(
+            Path::new(&out_dir).join(format!("dict_{}.bin.deflate", lang)),
+            miniz_oxide::deflate::compress_to_vec(&dict_bin, 10),
+        )
+        .unwrap();
+
+        fs::write(
+            Path::new(&out_dir).join(format!("dict_{}.bin", lang)),
+            dict_bin,
+        )
+        .unwrap();
+    }
+
+    println!("cargo:rustc-check-cfg=cfg(credential_store)");
+    if has_credential_store() {
+        println!("cargo:rustc-cfg=credential_store");
+    }
+
+    println!("cargo:rerun-if-changed=build.rs");
+}
+
+fn has_credential_store() -> bool {
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    let stores = [
+        ("WINCRED", "windows"),
+        ("KEYCHAIN", "macos"),
+        ("SECRET_SERVICE", "linux"),
+    ];
+
+    stores.iter().any(|(feature, os)| {
+        *os == target_os && env::var_os(format!("CARGO_FEATURE_{}", feature)).is_some()
+    })
+}
+
+fn encode_dict(dict_str: &str) -> Vec<u8> {
+    let mut buckets: Vec<(u8, Vec<&str>)> = dict_str
+        .trim()
+        .lines()
+        .filter_map(|line| {
+            let (len_str, words_csv) = line.trim().split_once(':')?;
+            let len = len_str.parse::<u8>().unwrap();
+            Some((len, words_csv.split(',').collect()))
+        })
+        .collect();
+    buckets.sort_by_key(|(len, _)| *len);
+
+    let mut header: Vec<u8> = b"XKD1".to_vec();
+    let mut lengths: Vec<u8> = vec![];
+    let mut words: Vec<u8> = vec![];
+
+    header.push(u8::try_from(buckets.len()).unwrap());
+
+    for (len, bucket) in &buckets {
+        header.push(*len);
+        header.extend(u16::try_from(bucket.len()).unwrap().to_le_bytes());
+
+        for word in bucket {
+            lengths.push(u8::try_from(word.len()).unwrap());
+            words.extend(word.as_bytes());
+        }
+    }
+
+    header.extend(lengths);
+    header.extend(words);
+    header
+}
@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use xkpasswd::dict;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(dict) = dict::load_dict(data) {
+        dict.stats();
+    }
+
+    // detects the format first, reaching the word list, JSON & CSV loaders too
+    if let Ok(dict) = dict::load_any_dict(data) {
+        for theme in dict.themes() {
+            dict.theme(theme);
+        }
+    }
+});
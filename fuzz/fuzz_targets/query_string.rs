@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use xkpasswd::settings::Settings;
+
+fuzz_target!(|query: &str| {
+    if let Ok(settings) = Settings::from_query_string(query) {
+        let round_trip = Settings::from_query_string(&settings.to_query_string()).unwrap();
+        assert_eq!(settings, round_trip);
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use xkpasswd::settings::Settings;
+
+fuzz_target!(|compact: &str| {
+    if let Ok(settings) = compact.parse::<Settings>() {
+        let round_trip: Settings = settings.compact().to_string().parse().unwrap();
+        assert_eq!(settings, round_trip);
+    }
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use xkpasswd::settings::Settings;
+
+fuzz_target!(|config: &str| {
+    if let Ok(settings) = Settings::from_xkpasswd_json(config) {
+        settings.to_xkpasswd_json().unwrap();
+    }
+});
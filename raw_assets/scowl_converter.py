@@ -0,0 +1,106 @@
+#!/usr/bin/env python3.9
+
+# Regenerates `dict_en.txt` from SCOWL (Spell Checker Oriented Word Lists),
+# the word lists behind aspell & hunspell English dictionaries.
+#
+# Source: http://wordlist.aspell.net/
+# Release: scowl-2020.12.07, https://sourceforge.net/projects/wordlist/files/SCOWL/2020.12.07/
+#
+# Usage: ./scowl_converter.py <path to extracted scowl-2020.12.07> [small|medium|large]
+#
+# SCOWL sorts words into size levels, lower ones holding the more common words.
+# Each tier takes the words up to a level, and when a length has more words than
+# the tier allows, the ones of lower levels are kept first, then alphabetically,
+# so the same release & tier always give the same dictionary.
+
+from typing import Dict, List, Tuple
+import os
+import re
+import sys
+
+MIN_WORD_LENGTH = 4
+MAX_WORD_LENGTH = 10
+
+# tier: (max SCOWL size level, max words per length)
+TIERS = {
+    "small": (35, 1500),
+    "medium": (50, 3000),
+    "large": (70, 6000),
+}
+DEFAULT_TIER = "small"
+
+# proper names, abbreviations & contractions are in other categories, left out
+CATEGORIES = ["english-words", "american-words"]
+WORD_PATTERN = re.compile(r"^[a-z]+$")
+
+
+def read_scowl_words(scowl_dir: str, max_level: int) -> Dict[str, int]:
+    final_dir = os.path.join(scowl_dir, "final")
+    levels: Dict[str, int] = dict()
+
+    for file_name in sorted(os.listdir(final_dir)):
+        category, _, level = file_name.rpartition(".")
+
+        if category not in CATEGORIES or not level.isdigit():
+            continue
+
+        if int(level) > max_level:
+            continue
+
+        # SCOWL files are Latin-1 encoded
+        with open(os.path.join(final_dir, file_name), "r", encoding="iso-8859-1") as file:
+            lines = file.readlines()
+            print(f"Read {len(lines)} lines from {file_name}")
+
+        for line in lines:
+            word = line.strip()
+
+            if WORD_PATTERN.match(word) is None:
+                continue
+
+            levels[word] = min(levels.get(word, int(level)), int(level))
+
+    return levels
+
+
+def group_words_by_length(
+    levels: Dict[str, int], max_words_per_length: int
+) -> Dict[int, List[str]]:
+    grouped: Dict[int, List[Tuple[int, str]]] = dict()
+
+    for word, level in levels.items():
+        length = len(word)
+
+        if length < MIN_WORD_LENGTH or length > MAX_WORD_LENGTH:
+            continue
+
+        grouped.setdefault(length, []).append((level, word))
+
+    return {
+        length: sorted(word for _, word in sorted(words)[:max_words_per_length])
+        for length, words in sorted(grouped.items())
+    }
+
+
+if __name__ == "__main__":
+    if len(sys.argv) not in [2, 3]:
+        print(f"Usage: {sys.argv[0]} <scowl dir> [{'|'.join(TIERS)}]")
+        sys.exit(1)
+
+    scowl_dir = sys.argv[1]
+    tier = sys.argv[2] if len(sys.argv) == 3 else DEFAULT_TIER
+
+    if tier not in TIERS:
+        print(f"Unknown tier '{tier}', expected one of {', '.join(TIERS)}")
+        sys.exit(1)
+
+    max_level, max_words_per_length = TIERS[tier]
+    print(f"Reading SCOWL words up to level {max_level} for tier '{tier}'")
+
+    levels = read_scowl_words(scowl_dir, max_level)
+    grouped_words = group_words_by_length(levels, max_words_per_length)
+
+    with open("dict_en.txt", "w") as file:
+        for length, words in grouped_words.items():
+            print(f"Writing {len(words)} words with length of {length}")
+            file.write(f"{length}:{','.join(words)}\n")
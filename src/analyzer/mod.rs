@@ -0,0 +1,260 @@
+#[cfg(test)]
+mod tests;
+
+use crate::prelude::Xkpasswd;
+use std::collections::HashSet;
+use std::fmt;
+
+// rough pool sizes used to estimate entropy of non-dictionary segments
+const DIGITS_POOL: f64 = 10.0;
+const SYMBOLS_POOL: f64 = 33.0;
+const LETTERS_POOL: f64 = 26.0;
+const MIXED_CASE_LETTERS_POOL: f64 = 52.0;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Segment {
+    Word(String),
+    Digits(String),
+    Symbols(String),
+    // letters not matching any dictionary word
+    Letters(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Analysis {
+    pub segments: Vec<Segment>,
+    pub entropy: usize,
+}
+
+impl Analysis {
+    /// At least 2 dictionary words and no letters outside of the dictionary,
+    /// with all words separated by the same symbol (or none at all).
+    pub fn is_xkpasswd_style(&self) -> bool {
+        let words_count = self
+            .segments
+            .iter()
+            .filter(|segment| matches!(segment, Segment::Word(_)))
+            .count();
+
+        if words_count < 2 {
+            return false;
+        }
+
+        let mut separators: HashSet<&str> = HashSet::new();
+
+        for (idx, segment) in self.segments.iter().enumerate() {
+            match segment {
+                Segment::Letters(_) => return false,
+                Segment::Symbols(symbols) if is_between_words(&self.segments, idx) => {
+                    separators.insert(symbols);
+                }
+                _ => {}
+            }
+        }
+
+        separators.len() <= 1
+    }
+}
+
+fn is_between_words(segments: &[Segment], idx: usize) -> bool {
+    let is_word = |segment: &Segment| matches!(segment, Segment::Word(_));
+    segments[..idx].iter().any(is_word) && segments[idx + 1..].iter().any(is_word)
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Word(word) => write!(f, "word     {}", word),
+            Self::Digits(digits) => write!(f, "digits   {}", digits),
+            Self::Symbols(symbols) => write!(f, "symbols  {}", symbols),
+            Self::Letters(letters) => write!(f, "letters  {}", letters),
+        }
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in &self.segments {
+            writeln!(f, " - {}", segment)?;
+        }
+
+        if self.is_xkpasswd_style() {
+            writeln!(f, "looks like an xkpasswd-style passphrase")?;
+        } else {
+            writeln!(f, "doesn't look like an xkpasswd-style passphrase")?;
+        }
+
+        write!(f, "approximate entropy: {} bits", self.entropy)
+    }
+}
+
+impl<'a> Xkpasswd<'a> {
+    /// Decomposes an arbitrary password into dictionary words, digits & symbols,
+    /// estimating its entropy assuming an attacker knows that structure.
+    pub fn analyze(&self, password: &str) -> Analysis {
+        let all_words = self.dict().pool(0..u8::MAX);
+        let dict: HashSet<&str> = all_words.iter().copied().collect();
+        let max_word_len = all_words
+            .last()
+            .map(|word| word.chars().count())
+            .unwrap_or_default();
+
+        let mut segments: Vec<Segment> = vec![];
+
+        for (kind, run) in split_runs(password) {
+            match kind {
+                CharKind::Digit => segments.push(Segment::Digits(run.to_string())),
+                CharKind::Symbol => segments.push(Segment::Symbols(run.to_string())),
+                CharKind::Letter => segments.extend(split_words(run, &dict, max_word_len)),
+            }
+        }
+
+        let word_bits = (all_words.len().max(1) as f64).log2();
+        let mut separators: HashSet<&str> = HashSet::new();
+        let entropy: f64 = segments
+            .iter()
+            .enumerate()
+            .map(|(idx, segment)| match segment {
+                Segment::Word(word) => {
+                    // one of the case transforms applied
+                    let case_bits = if word.chars().all(char::is_lowercase) {
+                        0.0
+                    } else {
+                        2.0
+                    };
+                    word_bits + case_bits
+                }
+                Segment::Digits(digits) => digits.len() as f64 * DIGITS_POOL.log2(),
+                // xkpasswd picks one separator per password, repeating it between all words
+                Segment::Symbols(symbols)
+                    if is_between_words(&segments, idx) && !separators.insert(symbols) =>
+                {
+                    0.0
+                }
+                Segment::Symbols(symbols) => {
+                    let mut chars = symbols.chars();
+                    let first = chars.next();
+                    // xkpasswd repeats a single padding symbol
+                    if chars.all(|char| Some(char) == first) {
+                        SYMBOLS_POOL.log2()
+                    } else {
+                        symbols.chars().count() as f64 * SYMBOLS_POOL.log2()
+                    }
+                }
+                Segment::Letters(letters) => {
+                    let pool = if letters.chars().any(char::is_uppercase)
+                        && letters.chars().any(char::is_lowercase)
+                    {
+                        MIXED_CASE_LETTERS_POOL
+                    } else {
+                        LETTERS_POOL
+                    };
+                    letters.chars().count() as f64 * pool.log2()
+                }
+            })
+            .sum();
+
+        Analysis {
+            segments,
+            entropy: entropy.floor() as usize,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CharKind {
+    Letter,
+    Digit,
+    Symbol,
+}
+
+fn char_kind(char: char) -> CharKind {
+    if char.is_ascii_digit() {
+        CharKind::Digit
+    } else if char.is_alphabetic() {
+        CharKind::Letter
+    } else {
+        CharKind::Symbol
+    }
+}
+
+fn split_runs(password: &str) -> Vec<(CharKind, &str)> {
+    let mut runs: Vec<(CharKind, &str)> = vec![];
+    let mut start = 0;
+    let mut current: Option<CharKind> = None;
+
+    for (idx, char) in password.char_indices() {
+        let kind = char_kind(char);
+
+        if let Some(current_kind) = current {
+            if current_kind != kind {
+                runs.push((current_kind, &password[start..idx]));
+                start = idx;
+            }
+        }
+
+        current = Some(kind);
+    }
+
+    if let Some(kind) = current {
+        runs.push((kind, &password[start..]));
+    }
+
+    runs
+}
+
+// splits a run of letters into as many dictionary words as needed to cover most letters,
+// preferring fewer, longer words when coverage is equal
+fn split_words(letters: &str, dict: &HashSet<&str>, max_word_len: usize) -> Vec<Segment> {
+    let bounds: Vec<usize> = letters
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .chain([letters.len()])
+        .collect();
+    let chars_count = bounds.len() - 1;
+
+    // best[i] = (covered chars, words count, previous boundary, matched a word) for letters[..i]
+    let mut best: Vec<(usize, usize, usize, bool)> = vec![(0, 0, 0, false); chars_count + 1];
+
+    for end in 1..=chars_count {
+        let (covered, words, _, _) = best[end - 1];
+        best[end] = (covered, words, end - 1, false);
+
+        for start in end.saturating_sub(max_word_len)..end {
+            let word = letters[bounds[start]..bounds[end]].to_lowercase();
+            if !dict.contains(word.as_str()) {
+                continue;
+            }
+
+            let (covered, words, _, _) = best[start];
+            let candidate = (covered + end - start, words + 1);
+            let (best_covered, best_words, _, _) = best[end];
+
+            if candidate.0 > best_covered
+                || (candidate.0 == best_covered && candidate.1 < best_words)
+            {
+                best[end] = (candidate.0, candidate.1, start, true);
+            }
+        }
+    }
+
+    let mut segments: Vec<Segment> = vec![];
+    let mut end = chars_count;
+
+    while end > 0 {
+        let (_, _, start, is_word) = best[end];
+        let text = &letters[bounds[start]..bounds[end]];
+
+        match (is_word, segments.last_mut()) {
+            (true, _) => segments.push(Segment::Word(text.to_string())),
+            // merge consecutive unknown letters, walking backwards
+            (false, Some(Segment::Letters(unknown))) => unknown.insert_str(0, text),
+            (false, _) => segments.push(Segment::Letters(text.to_string())),
+        }
+
+        end = start;
+    }
+
+    segments.reverse();
+    segments
+}
@@ -0,0 +1,137 @@
+use super::*;
+use crate::dict::load_word_list;
+use crate::prelude::Xkpasswd;
+
+fn segments(raw: &[(&str, &str)]) -> Vec<Segment> {
+    raw.iter()
+        .map(|&(kind, text)| match kind {
+            "word" => Segment::Word(text.to_string()),
+            "digits" => Segment::Digits(text.to_string()),
+            "symbols" => Segment::Symbols(text.to_string()),
+            _ => Segment::Letters(text.to_string()),
+        })
+        .collect()
+}
+
+#[test]
+fn test_split_runs() {
+    assert!(split_runs("").is_empty());
+    assert_eq!(
+        vec![
+            (CharKind::Digit, "12"),
+            (CharKind::Letter, "fooBär"),
+            (CharKind::Symbol, "-~"),
+            (CharKind::Digit, "3"),
+        ],
+        split_runs("12fooBär-~3")
+    );
+}
+
+#[test]
+fn test_analyze() {
+    let dict = load_word_list(b"correct\nhorse\nbattery\nstaple\nhorses\ncar\npet\ncarpet\n");
+    let pass_generator = Xkpasswd::from_dict(dict);
+
+    let table = [
+        (
+            "correct-HORSE-battery-Staple",
+            segments(&[
+                ("word", "correct"),
+                ("symbols", "-"),
+                ("word", "HORSE"),
+                ("symbols", "-"),
+                ("word", "battery"),
+                ("symbols", "-"),
+                ("word", "Staple"),
+            ]),
+            true,
+        ),
+        (
+            "%%42.horses.carpet.7%%",
+            segments(&[
+                ("symbols", "%%"),
+                ("digits", "42"),
+                ("symbols", "."),
+                ("word", "horses"),
+                ("symbols", "."),
+                ("word", "carpet"),
+                ("symbols", "."),
+                ("digits", "7"),
+                ("symbols", "%%"),
+            ]),
+            true,
+        ),
+        // concatenated words, unknown letters in between
+        (
+            "correctxyzhorse",
+            segments(&[("word", "correct"), ("letters", "xyz"), ("word", "horse")]),
+            false,
+        ),
+        // mixed separators
+        (
+            "correct-horse.battery",
+            segments(&[
+                ("word", "correct"),
+                ("symbols", "-"),
+                ("word", "horse"),
+                ("symbols", "."),
+                ("word", "battery"),
+            ]),
+            false,
+        ),
+        (
+            "Tr0ub4dor&3",
+            segments(&[
+                ("letters", "Tr"),
+                ("digits", "0"),
+                ("letters", "ub"),
+                ("digits", "4"),
+                ("letters", "dor"),
+                ("symbols", "&"),
+                ("digits", "3"),
+            ]),
+            false,
+        ),
+        ("", vec![], false),
+    ];
+
+    for (password, expected_segments, expected_style) in table {
+        let analysis = pass_generator.analyze(password);
+        assert_eq!(expected_segments, analysis.segments, "{}", password);
+        assert_eq!(expected_style, analysis.is_xkpasswd_style(), "{}", password);
+    }
+}
+
+#[test]
+fn test_analyze_entropy() {
+    let dict = load_word_list(b"correct\nhorse\nbattery\nstaple\n");
+    let pass_generator = Xkpasswd::from_dict(dict);
+
+    // 4 words from a pool of 4: 2 bits each, plus 2 bits for case & 5 bits for the separator,
+    // picked once however many times it's repeated
+    assert_eq!(
+        4 * 2 + 2 + 5,
+        pass_generator
+            .analyze("correct-HORSE-battery-staple")
+            .entropy
+    );
+    assert_eq!(
+        3 * 2 + 2 * 5,
+        pass_generator.analyze("correct-horse.battery").entropy
+    );
+    assert_eq!(0, pass_generator.analyze("").entropy);
+
+    // digits: log2(10) per digit
+    assert_eq!(13, pass_generator.analyze("1234").entropy);
+}
+
+#[test]
+fn test_analysis_display() {
+    let dict = load_word_list(b"correct\nhorse\n");
+    let pass_generator = Xkpasswd::from_dict(dict);
+
+    assert_eq!(
+        " - word     correct\n - symbols  -\n - word     horse\nlooks like an xkpasswd-style passphrase\napproximate entropy: 7 bits",
+        pass_generator.analyze("correct-horse").to_string()
+    );
+}
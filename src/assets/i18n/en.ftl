@@ -0,0 +1,49 @@
+# Messages of the CLI, help texts are only translated in other languages,
+# English ones being those of the clap definitions
+
+warning = warning: { $message }
+warning-single-word = a single word is easily guessed by dictionary attacks
+warning-no-separation = without separators nor padding, passwords are only dictionary words
+warning-low-entropy = entropy of { $entropy } bits with full knowledge is below the recommended { $recommended } bits
+warning-too-many-symbols = { $count } padding symbols add length but little entropy, more than { $max } are hard to type
+warning-unchecked-word-lengths = word lengths of { $min } to { $max } are outside the usual { $usual_min } to { $usual_max }, the dictionary may have few such words
+warning-layout-dependent-symbols = symbols { $symbols } may not be where expected at pre-boot prompts forcing a US keyboard layout
+
+min-entropy-settings = using { $words } words for at least { $bits } bits of entropy: { $settings }
+verify-match = password matches the settings
+verify-mismatch = password doesn't match the settings
+password-prompt = Password:
+clipboard-copied = copied to the clipboard
+clipboard-copied-clearing = copied to the clipboard, clearing in { $seconds } seconds
+store-saved = saved to the credential store
+ssh-keygen-created = created SSH key pair { $file } & { $file }.pub
+
+error-invalid-settings = Invalid settings: { $error }
+error-read-dict = Error reading dictionary '{ $file }': { $error }
+error-write-dict = Error writing cleaned dictionary '{ $file }': { $error }
+error-load-dict = Error loading dictionary: { $error }
+error-read-dice = Error reading dice rolls: { $error }
+error-read-usernames = Error reading usernames '{ $file }': { $error }
+error-config-file = Error parsing config file: { $error }
+error-config-field = Error parsing config file at '{ $field }': { $error }
+error-user-presets = Error parsing user presets: { $error }
+error-user-preset = Error parsing user preset '{ $name }': { $error }
+error-repl = Error in the REPL: { $error }
+error-read-password = Error reading the password: { $error }
+error-gui = Error opening the GUI: { $error }
+error-pass-insert = Error inserting '{ $name }' into pass: { $error }
+error-api-keys = Error loading API keys '{ $file }': { $error }
+error-serve = Error serving on { $address }: { $error }
+error-daemon = Error listening on { $socket }: { $error }
+error-unique = Error generating unique passwords: { $error }
+error-clipboard = Error copying to the clipboard: { $error }
+error-conceal = Error concealing passwords: { $error }
+error-suggest = Error suggesting settings: { $error }
+error-recovery = Error generating recovery codes: { $error }
+error-pin = Error generating PINs: { $error }
+error-codename = Error generating codenames: { $error }
+error-hash = Error hashing passwords: { $error }
+error-encrypt = Error encrypting passwords: { $error }
+error-stdin-jsonl = Error generating passwords from stdin: { $error }
+error-store = Error saving to the credential store: { $error }
+error-ssh-keygen = Error creating SSH key pair { $file }: { $error }
@@ -0,0 +1,124 @@
+# Tiếng Việt
+
+warning = cảnh báo: { $message }
+warning-single-word = mật khẩu chỉ có một từ dễ bị đoán bằng tấn công từ điển
+warning-no-separation = không có ký tự phân cách hay phần đệm, mật khẩu chỉ gồm các từ trong từ điển
+warning-low-entropy = entropy { $entropy } bit khi biết rõ cài đặt thấp hơn mức khuyến nghị { $recommended } bit
+warning-too-many-symbols = { $count } ký hiệu đệm làm mật khẩu dài hơn nhưng tăng ít entropy, hơn { $max } ký hiệu thì khó gõ
+warning-unchecked-word-lengths = độ dài từ từ { $min } đến { $max } nằm ngoài khoảng thông thường từ { $usual_min } đến { $usual_max }, từ điển có thể có ít từ như vậy
+warning-layout-dependent-symbols = các ký hiệu { $symbols } có thể không nằm ở vị trí quen thuộc tại màn hình nhập trước khi khởi động, nơi thường bắt buộc dùng bàn phím US
+
+min-entropy-settings = dùng { $words } từ để đạt ít nhất { $bits } bit entropy: { $settings }
+verify-match = mật khẩu khớp với cài đặt
+verify-mismatch = mật khẩu không khớp với cài đặt
+password-prompt = Mật khẩu:
+clipboard-copied = đã sao chép vào bộ nhớ tạm
+clipboard-copied-clearing = đã sao chép vào bộ nhớ tạm, sẽ xóa sau { $seconds } giây
+store-saved = đã lưu vào kho thông tin đăng nhập
+ssh-keygen-created = đã tạo cặp khóa SSH { $file } & { $file }.pub
+
+error-invalid-settings = Cài đặt không hợp lệ: { $error }
+error-read-dict = Lỗi khi đọc từ điển '{ $file }': { $error }
+error-write-dict = Lỗi khi ghi từ điển đã làm sạch '{ $file }': { $error }
+error-load-dict = Lỗi khi tải từ điển: { $error }
+error-read-dice = Lỗi khi đọc kết quả tung xúc xắc: { $error }
+error-read-usernames = Lỗi khi đọc danh sách tên người dùng '{ $file }': { $error }
+error-config-file = Lỗi khi phân tích tệp cấu hình: { $error }
+error-config-field = Lỗi khi phân tích tệp cấu hình tại '{ $field }': { $error }
+error-user-presets = Lỗi khi phân tích các preset của người dùng: { $error }
+error-user-preset = Lỗi khi phân tích preset '{ $name }' của người dùng: { $error }
+error-repl = Lỗi trong REPL: { $error }
+error-read-password = Lỗi khi đọc mật khẩu: { $error }
+error-gui = Lỗi khi mở giao diện đồ họa: { $error }
+error-pass-insert = Lỗi khi thêm '{ $name }' vào pass: { $error }
+error-api-keys = Lỗi khi tải khóa API '{ $file }': { $error }
+error-serve = Lỗi khi phục vụ tại { $address }: { $error }
+error-daemon = Lỗi khi lắng nghe tại { $socket }: { $error }
+error-unique = Lỗi khi tạo các mật khẩu không trùng nhau: { $error }
+error-clipboard = Lỗi khi sao chép vào bộ nhớ tạm: { $error }
+error-conceal = Lỗi khi ẩn mật khẩu: { $error }
+error-suggest = Lỗi khi đề xuất cài đặt: { $error }
+error-recovery = Lỗi khi tạo mã khôi phục: { $error }
+error-pin = Lỗi khi tạo mã PIN: { $error }
+error-codename = Lỗi khi tạo tên mã: { $error }
+error-hash = Lỗi khi băm mật khẩu: { $error }
+error-encrypt = Lỗi khi mã hóa mật khẩu: { $error }
+error-stdin-jsonl = Lỗi khi tạo mật khẩu từ stdin: { $error }
+error-store = Lỗi khi lưu vào kho thông tin đăng nhập: { $error }
+error-ssh-keygen = Lỗi khi tạo cặp khóa SSH { $file }: { $error }
+
+# Subcommands, by name
+
+about-presets = Liệt kê các preset có sẵn cùng cài đặt, một ví dụ và entropy của chúng
+about-verify = Kiểm tra xem một mật khẩu đọc từ stdin có thể được tạo ra với các cài đặt đã cho hay không
+about-analyze = Phân tích một mật khẩu đọc từ stdin thành các từ trong từ điển, chữ số và ký hiệu, rồi ước tính entropy
+about-compare = So sánh độ dài, entropy và ví dụ của tất cả các preset có sẵn
+about-repl = Điều chỉnh cài đặt theo kiểu tương tác, ví dụ 'set words 5', 'preset wifi' và 'gen 3'
+about-suggest = Đề xuất các cài đặt đạt --min-entropy (mặc định 64 bit) trong các ràng buộc đã cho
+about-recovery = Tạo mã khôi phục như 7KQ2-M9XD-F3TB để cất giữ ngoại tuyến
+about-codename = Tạo tên mã an toàn cho DNS như brave-otter để đặt tên máy chủ và phiên bản phát hành
+about-pin = Tạo mã PIN, loại bỏ các chữ số lặp lại, dãy số liên tiếp, năm và các mã phổ biến
+about-dict = Công cụ cho từ điển tùy chỉnh
+about-pass = Lưu mật khẩu được tạo vào pass, trình quản lý mật khẩu tiêu chuẩn của unix
+about-serve = Cung cấp mật khẩu qua HTTP cho các client có khóa API
+about-gui = Mở cửa sổ để điều chỉnh cài đặt và xem trước mật khẩu
+about-daemon = Giữ từ điển trong bộ nhớ và trả lời các yêu cầu JSON theo dòng trên một unix socket
+
+# Options, by field name
+
+arg-words_count = tổng số từ lấy từ từ điển
+arg-min_entropy = Thêm từ cho đến khi đạt mức entropy này khi biết rõ cài đặt, rồi báo lại cài đặt đã chọn
+arg-word_length_min = Độ dài tối thiểu của một từ
+arg-word_length_max = Độ dài tối đa của một từ
+arg-unchecked_word_lengths = Cho phép độ dài từ ngoài khoảng 4 đến 10, kèm cảnh báo thay vì báo lỗi
+arg-word_transforms = Cách biến đổi từ, có thể kết hợp bằng cách lặp lại tùy chọn
+arg-separators = Danh sách ký tự dùng làm ký tự phân cách
+arg-padding_digits_before = Số chữ số đệm trước các từ
+arg-padding_digits_after = Số chữ số đệm sau các từ
+arg-padding_symbols = Danh sách ký tự dùng làm ký hiệu đệm
+arg-symbol_set = Bộ ký tự chọn sẵn dùng làm cả ký tự phân cách lẫn ký hiệu đệm, trừ khi có --separators hoặc --symbols
+arg-padding_symbols_before = Số ký hiệu đệm trước các từ
+arg-padding_symbols_after = Số ký hiệu đệm sau các từ
+arg-padding = Cách đệm
+arg-join_style = Cách nối các từ với nhau
+arg-pronounceable = Tránh các tổ hợp từ khó đọc thành tiếng
+arg-checksum = Thêm 2 chữ số kiểm tra để phát hiện lỗi gõ, làm mật khẩu dài hơn nhưng không tăng entropy
+arg-avoid_ambiguous = Bỏ các ký tự dễ nhầm lẫn như l, 1, I, O và 0, cho mật khẩu đọc từ giấy
+arg-avoid_homophones = Bỏ các từ phát âm giống từ khác như their và there, cho mật khẩu đọc qua điện thoại
+arg-word_theme = Chỉ chọn các từ nằm dưới '@THEME' trong từ điển, ví dụ animals. Ít từ để chọn hơn nghĩa là entropy thấp hơn
+arg-proper_nouns = Cách xử lý danh từ riêng, các từ viết hoa trong từ điển như Paris
+arg-spelling = Chỉ chọn từ theo chính tả Anh hoặc Mỹ, với các ngôn ngữ có biến thể như vậy
+arg-adaptive_length = Đệm hoặc cắt kết quả cho vừa một độ dài. Bắt buộc với --padding=adaptive
+arg-preset = Bắt đầu từ một cài đặt sẵn, hoặc một bảng có tên trong $XDG_CONFIG_HOME/xkpasswd/presets.toml được ưu tiên hơn
+arg-verbosity = Mức chi tiết: 1 = info, 2+ = debug
+arg-language = Ngôn ngữ của các từ được tạo
+arg-config_file = Đường dẫn tới tệp cấu hình .toml
+arg-dict_file = Đường dẫn tới từ điển tùy chỉnh, dạng danh sách từ, mảng JSON, CSV các từ kèm tần suất hoặc định dạng có sẵn, hoặc tên một từ điển trong $XDG_DATA_HOME/xkpasswd/dicts. Dùng '-' để đọc từ stdin
+arg-user_preset = Dùng một bảng cài đặt có tên trong $XDG_CONFIG_HOME/xkpasswd/presets.toml
+arg-no_user_config = Bỏ qua việc tìm tệp cấu hình, preset và từ điển trong thư mục người dùng, chỉ đọc các đường dẫn được chỉ định
+arg-syllables = Dùng các từ vô nghĩa dễ đọc được tạo ra thay vì từ trong từ điển
+arg-dice = Chọn từ diceware theo kết quả tung xúc xắc thật, 5 con mỗi từ (ví dụ '16325 43512'). Dùng '-' để đọc từ stdin
+arg-count = Số mật khẩu cần tạo
+arg-usernames_file = Đường dẫn tới tệp tên người dùng, mỗi dòng một tên, để tạo một mật khẩu cho mỗi người
+arg-unique = Bảo đảm không có 2 mật khẩu nào giống nhau hoặc có chung một từ
+arg-minimize_typing = Chọn mật khẩu dễ gõ nhất trong số nhiều ứng viên, mất tối đa log2 số ứng viên bit entropy
+arg-typing_candidates = Số ứng viên để chọn mật khẩu dễ gõ nhất
+arg-export = In mật khẩu theo định dạng nhập được vào trình quản lý mật khẩu
+arg-table_format = In mật khẩu thành bảng kèm số thứ tự, độ dài và số bit entropy
+arg-spell = Đánh vần từng ký tự của mật khẩu
+arg-color = Tô màu khác nhau cho từ, ký tự phân cách, chữ số và ký hiệu của mật khẩu
+arg-porcelain = Chỉ in đúng các mật khẩu, mỗi dòng một mật khẩu, dành cho script. Mọi thứ khác được in ra stderr
+arg-print0 = Kết thúc mỗi mật khẩu bằng ký tự NUL thay vì xuống dòng, ví dụ cho 'xargs -0'
+arg-conceal = Trên terminal, ẩn mật khẩu đi khi nhấn Enter, ví dụ khi đang chia sẻ màn hình
+arg-hash = In thêm mã băm của mỗi mật khẩu sau nó, cách nhau bởi một tab, ví dụ cho /etc/shadow hoặc LDAP
+arg-hash_only = Chỉ in mã băm và bỏ đi mật khẩu, ví dụ cho các tài khoản mà chủ sở hữu tự đặt mật khẩu khi đăng nhập lần đầu
+arg-encrypt_to = Mã hóa kết quả cho một người nhận age như age1ql3z..., có thể lặp lại cho nhiều người nhận
+arg-stdin_jsonl = Đọc cài đặt JSON kiểu xkpasswd.net từ stdin, mỗi dòng một đối tượng, và in một mật khẩu cho mỗi dòng, ví dụ cho các pipeline thông lượng cao
+arg-errors = Cách báo lỗi trên stderr, mỗi loại lỗi cũng thoát với mã riêng
+arg-copy = Sao chép mật khẩu vào bộ nhớ tạm, mỗi dòng một mật khẩu, thay vì in ra
+arg-clear_after = Xóa bộ nhớ tạm sau một số giây, trừ khi có thứ khác được sao chép trong lúc đó
+arg-ssh_keygen = Tạo cặp khóa SSH Ed25519 tại KEY_FILE, bảo vệ bằng cụm mật khẩu được tạo và truyền cho ssh-keygen qua stdin. Nên dùng với --preset ssh
+arg-store = Lưu mật khẩu vào kho thông tin đăng nhập thay vì in ra
+arg-store_name = Tên thông tin đăng nhập để ghi với --store wincred, thay thế mục đã có
+arg-store_service = Dịch vụ của mục để lưu với --store keychain hoặc secret-service, ví dụ một trang web
+arg-store_account = Tài khoản của mục để lưu với --store keychain hoặc secret-service, thay thế mục đã có
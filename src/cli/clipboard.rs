@@ -0,0 +1,118 @@
+use arboard::Clipboard;
+use std::env;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[cfg(target_os = "linux")]
+use arboard::SetExtLinux;
+
+// hidden subcommand run by the detached process
+pub const HOLD_CLIPBOARD_COMMAND: &str = "hold-clipboard";
+const READY: &str = "ready";
+
+/// Copies `text` from a detached process, which keeps serving the clipboard after this one exits
+/// as X11 & Wayland require, then clears it after `clear_after` unless something else was
+/// copied in the meantime. `text` goes through a pipe, never showing up in the process list.
+pub fn copy_detached(text: &str, clear_after: Option<Duration>) -> Result<(), String> {
+    let mut command = Command::new(env::current_exe().map_err(|err| err.to_string())?);
+    command.arg(HOLD_CLIPBOARD_COMMAND);
+
+    if let Some(clear_after) = clear_after {
+        command
+            .arg("--clear-after")
+            .arg(clear_after.as_secs().to_string());
+    }
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| err.to_string())?;
+
+    // dropped right after writing, so the child sees the end of it
+    child
+        .stdin
+        .take()
+        .ok_or("no stdin to the clipboard process")?
+        .write_all(text.as_bytes())
+        .map_err(|err| err.to_string())?;
+
+    let mut status = String::new();
+    BufReader::new(
+        child
+            .stdout
+            .take()
+            .ok_or("no stdout from the clipboard process")?,
+    )
+    .read_line(&mut status)
+    .map_err(|err| err.to_string())?;
+
+    // not waited for, it lives on until the clipboard is cleared or taken over
+    match status.trim_end() {
+        READY => Ok(()),
+        "" => Err("the clipboard process exited unexpectedly".to_string()),
+        err => Err(err.to_string()),
+    }
+}
+
+/// The detached side of `copy_detached`: reads the text from stdin, reports whether the clipboard
+/// is available on stdout, then holds the text until `clear_after` or until replaced.
+pub fn hold_clipboard(clear_after: Option<Duration>) -> Result<(), String> {
+    let mut text = String::new();
+    io::stdin()
+        .read_to_string(&mut text)
+        .map_err(|err| err.to_string())?;
+
+    let mut clipboard = match Clipboard::new() {
+        Ok(clipboard) => clipboard,
+        Err(err) => {
+            println!("{}", err);
+            return Err(err.to_string());
+        }
+    };
+
+    println!("{}", READY);
+    io::stdout().flush().map_err(|err| err.to_string())?;
+
+    let deadline = clear_after.map(|clear_after| Instant::now() + clear_after);
+    set_text(&mut clipboard, &text, deadline).map_err(|err| err.to_string())?;
+
+    if let Some(deadline) = deadline {
+        thread::sleep(deadline.saturating_duration_since(Instant::now()));
+
+        // leave whatever was copied since alone
+        if clipboard.get_text().is_ok_and(|current| current == text) {
+            clipboard.clear().map_err(|err| err.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+// blocks until `deadline`, or until replaced without one, as the selection is served by its owner
+#[cfg(target_os = "linux")]
+fn set_text(
+    clipboard: &mut Clipboard,
+    text: &str,
+    deadline: Option<Instant>,
+) -> Result<(), arboard::Error> {
+    let set = clipboard.set().exclude_from_history();
+
+    match deadline {
+        Some(deadline) => set.wait_until(deadline),
+        None => set.wait(),
+    }
+    .text(text)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_text(
+    clipboard: &mut Clipboard,
+    text: &str,
+    _deadline: Option<Instant>,
+) -> Result<(), arboard::Error> {
+    clipboard.set_text(text)
+}
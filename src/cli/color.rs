@@ -0,0 +1,82 @@
+use crate::analyzer::{Analysis, Segment};
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+use std::env;
+use std::io::{self, IsTerminal};
+
+// ANSI SGR codes, words alternate between 2 colors so adjacent ones stay apart
+const WORD_COLORS: [&str; 2] = ["32", "34"];
+const SEPARATOR_COLOR: &str = "2";
+const DIGITS_COLOR: &str = "33";
+const SYMBOLS_COLOR: &str = "35";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ValueEnum for ColorChoice {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Auto, Self::Always, Self::Never]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Auto => PossibleValue::new("auto")
+                .help("Color when printing to a terminal and NO_COLOR isn't set"),
+            Self::Always => PossibleValue::new("always"),
+            Self::Never => PossibleValue::new("never"),
+        })
+    }
+}
+
+impl ColorChoice {
+    pub fn enabled(&self) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => {
+                env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                    && io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+/// Colors words, separators, digits & symbols of an analyzed password differently,
+/// symbols between words count as separators, others as padding.
+pub fn colorize(analysis: &Analysis) -> String {
+    let separator = separator(&analysis.segments);
+    let mut words_count = 0;
+
+    analysis
+        .segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Word(text) | Segment::Letters(text) => {
+                words_count += 1;
+                paint(text, WORD_COLORS[(words_count - 1) % WORD_COLORS.len()])
+            }
+            Segment::Digits(digits) => paint(digits, DIGITS_COLOR),
+            Segment::Symbols(symbols) if Some(symbols) == separator => {
+                paint(symbols, SEPARATOR_COLOR)
+            }
+            Segment::Symbols(symbols) => paint(symbols, SYMBOLS_COLOR),
+        })
+        .collect()
+}
+
+// the first symbols found right between 2 words, also separating words from digits
+fn separator(segments: &[Segment]) -> Option<&String> {
+    segments.windows(3).find_map(|window| match window {
+        [Segment::Word(_), Segment::Symbols(symbols), Segment::Word(_)] => Some(symbols),
+        _ => None,
+    })
+}
+
+fn paint(text: &str, color: &str) -> String {
+    format!("\x1b[{}m{}\x1b[0m", color, text)
+}
@@ -0,0 +1,35 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+const HINT: &str = "Press Enter to hide...";
+
+/// Whether passwords can be shown then wiped out, which needs both ends on a terminal.
+pub fn can_conceal() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// Prints `lines` on the alternate screen until Enter is pressed on `input`, then switches back,
+/// leaving the terminal as it was. Unlike moving the cursor back up, long lines wrapped
+/// by the terminal are cleared too, and nothing is left in the scrollback.
+pub fn conceal<R: BufRead, W: Write>(
+    lines: &[String],
+    mut input: R,
+    output: &mut W,
+) -> io::Result<()> {
+    // ANSI: switch to the alternate screen, cleared, with the cursor at the top
+    write!(output, "\x1b[?1049h\x1b[H\x1b[2J")?;
+
+    for line in lines {
+        writeln!(output, "{}", line)?;
+    }
+
+    write!(output, "{}", HINT)?;
+    output.flush()?;
+
+    let mut answer = String::new();
+    let read = input.read_line(&mut answer);
+
+    // ANSI: back to the main screen, even when reading failed
+    write!(output, "\x1b[?1049l")?;
+    output.flush()?;
+    read.map(|_| ())
+}
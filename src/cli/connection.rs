@@ -0,0 +1,137 @@
+use std::io::{self, Read};
+use std::net::TcpStream;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+// for a whole request, however slowly it trickles in
+pub const READ_TIMEOUT: Duration = Duration::from_secs(5);
+pub const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+// beyond this, new connections are dropped until others finish
+pub const MAX_CONNECTIONS: usize = 64;
+
+/// Streams whose reads can time out, served by `DeadlineReader`.
+pub trait TimedStream {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+}
+
+impl TimedStream for TcpStream {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        TcpStream::set_read_timeout(self, timeout)
+    }
+}
+
+#[cfg(unix)]
+impl TimedStream for UnixStream {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        UnixStream::set_read_timeout(self, timeout)
+    }
+}
+
+// times out once `deadline` is reached, rather than only when a single read stalls
+pub struct DeadlineReader<'a, S> {
+    pub stream: &'a S,
+    pub deadline: Instant,
+}
+
+impl<S: TimedStream> Read for DeadlineReader<'_, S>
+where
+    for<'b> &'b S: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "request not received in time",
+            ));
+        }
+
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+/// Counts connections being served, up to `MAX_CONNECTIONS` at once.
+#[derive(Debug, Default)]
+pub struct ConnectionLimit {
+    active: AtomicUsize,
+}
+
+/// A connection counted by `ConnectionLimit` until dropped.
+pub struct ConnectionSlot<'a> {
+    active: &'a AtomicUsize,
+}
+
+impl ConnectionLimit {
+    pub fn try_acquire(&self) -> Option<ConnectionSlot<'_>> {
+        if self.active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            self.active.fetch_sub(1, Ordering::SeqCst);
+            return None;
+        }
+
+        Some(ConnectionSlot {
+            active: &self.active,
+        })
+    }
+}
+
+impl Drop for ConnectionSlot<'_> {
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_deadline_reader() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+
+        let start = Instant::now();
+        let mut reader = DeadlineReader {
+            stream: &stream,
+            deadline: start + Duration::from_millis(200),
+        };
+
+        // bytes keep coming, yet the deadline stays the same
+        client.write_all(b"GET").unwrap();
+        let mut buf = [0u8; 8];
+        assert_eq!(3, reader.read(&mut buf).unwrap());
+
+        let err = reader.read(&mut buf).unwrap_err();
+        assert!(
+            matches!(
+                err.kind(),
+                io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+            ),
+            "{:?}",
+            err
+        );
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert_eq!(
+            io::ErrorKind::TimedOut,
+            reader.read(&mut buf).unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn test_connection_limit() {
+        let limit = ConnectionLimit::default();
+        let mut slots: Vec<_> = (0..MAX_CONNECTIONS)
+            .map(|_| limit.try_acquire().unwrap())
+            .collect();
+        assert!(limit.try_acquire().is_none());
+
+        // freed once a connection is done
+        slots.pop();
+        assert!(limit.try_acquire().is_some());
+    }
+}
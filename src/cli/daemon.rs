@@ -0,0 +1,316 @@
+use super::connection::{ConnectionLimit, DeadlineReader, READ_TIMEOUT, WRITE_TIMEOUT};
+use crate::prelude::{Randomizer, Xkpasswd};
+use crate::settings::registry::PresetRegistry;
+use crate::settings::Settings;
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::thread;
+use std::time::Instant;
+
+const MAX_COUNT_PER_REQUEST: usize = 1000;
+// far more than any valid request, so a client can't make the daemon buffer without bounds
+const MAX_REQUEST_LEN: usize = 4096;
+
+/// Answers one JSON request per line like `{"preset": "web32", "count": 2}`,
+/// with `{"passwords": [...], "entropy": {...}}` or `{"error": "..."}`.
+/// Preset names are resolved by `presets`, requests without one use `settings`.
+#[cfg_attr(feature = "trace", tracing::instrument(level = "debug", skip_all))]
+pub fn respond<S: Randomizer + Sync>(
+    request: &str,
+    pass_generator: &Xkpasswd,
+    settings: &S,
+    presets: &PresetRegistry,
+) -> String {
+    let response = match parse_request(request, presets) {
+        Ok((preset, count)) => {
+            let (passwds, entropy) = match preset {
+                Some(preset) => pass_generator.gen_passes(&preset, count),
+                None => pass_generator.gen_passes(settings, count),
+            };
+
+            json!({
+                "passwords": passwds,
+                "entropy": {
+                    "blind_min": entropy.blind_min,
+                    "blind_max": entropy.blind_max,
+                    "seen": entropy.seen,
+                },
+            })
+        }
+        Err(message) => json!({ "error": message }),
+    };
+
+    response.to_string()
+}
+
+fn parse_request(
+    request: &str,
+    presets: &PresetRegistry,
+) -> Result<(Option<Settings>, usize), String> {
+    let request: Value =
+        serde_json::from_str(request).map_err(|err| format!("invalid JSON: {}", err))?;
+
+    let Some(request) = request.as_object() else {
+        return Err("request must be a JSON object".to_string());
+    };
+
+    let preset = match request.get("preset") {
+        None | Some(Value::Null) => None,
+        Some(Value::String(name)) => Some(presets.resolve(name)?),
+        Some(_) => return Err("preset must be a string".to_string()),
+    };
+
+    let count = match request.get("count") {
+        None | Some(Value::Null) => 1,
+        Some(count) => count
+            .as_u64()
+            .and_then(|count| usize::try_from(count).ok())
+            .filter(|count| (1..=MAX_COUNT_PER_REQUEST).contains(count))
+            .ok_or_else(|| format!("count must be from 1 to {}", MAX_COUNT_PER_REQUEST))?,
+    };
+
+    Ok((preset, count))
+}
+
+/// Listens on a unix socket only accessible by the current user,
+/// serving each connection on its own thread with the already loaded dictionary.
+/// Connections are closed once a request takes longer than `READ_TIMEOUT` to arrive,
+/// idle ones included, & new ones are dropped while `MAX_CONNECTIONS` are being served.
+pub fn run_daemon<S: Randomizer + Sync>(
+    socket_path: &str,
+    pass_generator: &Xkpasswd,
+    settings: &S,
+    presets: &PresetRegistry,
+) -> io::Result<()> {
+    remove_stale_socket(socket_path)?;
+
+    // bound in a fresh directory only the current user can enter & restricted before being moved
+    // in place, so there's no moment anyone else can connect, whatever the umask
+    let tmp_dir = format!("{}.{}.tmp", socket_path, std::process::id());
+    fs::DirBuilder::new().mode(0o700).create(&tmp_dir)?;
+    let tmp_path = Path::new(&tmp_dir).join("socket");
+    let bound = UnixListener::bind(&tmp_path).and_then(|listener| {
+        fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o600))?;
+        fs::rename(&tmp_path, socket_path)?;
+        Ok(listener)
+    });
+    fs::remove_dir_all(&tmp_dir)?;
+    let listener = bound?;
+    log::info!("listening on {}", socket_path);
+
+    let limit = ConnectionLimit::default();
+
+    thread::scope(|scope| {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    log::warn!("connection failed: {}", err);
+                    continue;
+                }
+            };
+
+            let Some(slot) = limit.try_acquire() else {
+                log::warn!("too many connections, dropped one");
+                continue;
+            };
+
+            scope.spawn(move || {
+                if let Err(err) = serve_connection(stream, pass_generator, settings, presets) {
+                    log::warn!("connection failed: {}", err);
+                }
+                drop(slot);
+            });
+        }
+    });
+
+    Ok(())
+}
+
+fn serve_connection<S: Randomizer + Sync>(
+    stream: UnixStream,
+    pass_generator: &Xkpasswd,
+    settings: &S,
+    presets: &PresetRegistry,
+) -> io::Result<()> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+
+    let mut writer = &stream;
+    let mut reader = BufReader::new(DeadlineReader {
+        stream: &stream,
+        deadline: Instant::now(),
+    });
+
+    loop {
+        // each request gets the same time to arrive, so a stalled client can't hold a thread
+        reader.get_mut().deadline = Instant::now() + READ_TIMEOUT;
+
+        let mut line = String::new();
+        let limit = MAX_REQUEST_LEN as u64 + 1;
+        if (&mut reader).take(limit).read_line(&mut line)? == 0 {
+            break;
+        }
+
+        // the rest of the line can't be told apart from the next request, so it's the last one
+        if line.len() > MAX_REQUEST_LEN && !line.ends_with('\n') {
+            let message = format!("request must be at most {} bytes", MAX_REQUEST_LEN);
+            writeln!(writer, "{}", json!({ "error": message }))?;
+            break;
+        }
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        writeln!(
+            writer,
+            "{}",
+            respond(&line, pass_generator, settings, presets)
+        )?;
+    }
+
+    Ok(())
+}
+
+// a socket left behind by a daemon that didn't shut down cleanly blocks binding,
+// but one that still accepts connections belongs to a running daemon
+fn remove_stale_socket(socket_path: &str) -> io::Result<()> {
+    if !Path::new(socket_path).exists() {
+        return Ok(());
+    }
+
+    if UnixStream::connect(socket_path).is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AddrInUse,
+            "another daemon is listening on this socket",
+        ));
+    }
+
+    log::debug!("removing stale socket {}", socket_path);
+    fs::remove_file(socket_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{Builder, Preset};
+
+    #[test]
+    fn test_respond() {
+        let pass_generator = Xkpasswd::default();
+        let settings = Settings::default();
+        let mut presets = PresetRegistry::new();
+        presets
+            .register("corp-standard", Settings::from_preset(Preset::Web32))
+            .unwrap();
+
+        let response: Value =
+            serde_json::from_str(&respond("{}", &pass_generator, &settings, &presets)).unwrap();
+        assert_eq!(1, response["passwords"].as_array().unwrap().len());
+        assert!(response["entropy"]["seen"].as_u64().unwrap() > 0);
+
+        let request = r#"{"preset": "xkcd", "count": 3}"#;
+        let response: Value =
+            serde_json::from_str(&respond(request, &pass_generator, &settings, &presets)).unwrap();
+        let passwds = response["passwords"].as_array().unwrap();
+        assert_eq!(3, passwds.len());
+
+        let xkcd = Settings::from_preset(Preset::Xkcd);
+        for passwd in passwds {
+            assert!(xkcd.matches(passwd.as_str().unwrap(), &pass_generator));
+        }
+
+        let request = r#"{"preset": "corp-standard"}"#;
+        let response: Value =
+            serde_json::from_str(&respond(request, &pass_generator, &settings, &presets)).unwrap();
+        let passwd = response["passwords"][0].as_str().unwrap();
+        assert!(Settings::from_preset(Preset::Web32).matches(passwd, &pass_generator));
+
+        let expectations = [
+            ("nope", "invalid JSON: expected ident at line 1 column 2"),
+            ("[]", "request must be a JSON object"),
+            (r#"{"preset": "nope"}"#, "unknown preset 'nope'"),
+            (r#"{"preset": 1}"#, "preset must be a string"),
+            (r#"{"count": 0}"#, "count must be from 1 to 1000"),
+            (r#"{"count": 1001}"#, "count must be from 1 to 1000"),
+            (r#"{"count": "2"}"#, "count must be from 1 to 1000"),
+        ];
+
+        for (request, err) in expectations {
+            assert_eq!(
+                json!({ "error": err }).to_string(),
+                respond(request, &pass_generator, &settings, &presets),
+                "{}",
+                request
+            );
+        }
+    }
+
+    #[test]
+    fn test_run_daemon() {
+        let socket_path =
+            std::env::temp_dir().join(format!("xkpasswd-{}.sock", std::process::id()));
+        let socket_path = socket_path.to_str().unwrap().to_string();
+
+        // leftover from a crashed daemon
+        drop(UnixListener::bind(&socket_path).unwrap());
+
+        let daemon_path = socket_path.clone();
+        thread::spawn(move || {
+            let pass_generator = Xkpasswd::default();
+            let presets = PresetRegistry::new();
+            run_daemon(
+                &daemon_path,
+                &pass_generator,
+                &Settings::default(),
+                &presets,
+            )
+            .unwrap();
+        });
+
+        let stream = (0..100)
+            .find_map(|_| {
+                thread::sleep(std::time::Duration::from_millis(10));
+                UnixStream::connect(&socket_path).ok()
+            })
+            .unwrap();
+
+        let mode = fs::metadata(&socket_path).unwrap().permissions().mode();
+        assert_eq!(0o600, mode & 0o777);
+
+        let mut writer = &stream;
+        writeln!(writer, r#"{{"count": 2}}"#).unwrap();
+        writeln!(writer, r#"{{"count": 0}}"#).unwrap();
+
+        let mut lines = BufReader::new(&stream).lines();
+        let response: Value = serde_json::from_str(&lines.next().unwrap().unwrap()).unwrap();
+        assert_eq!(2, response["passwords"].as_array().unwrap().len());
+
+        let response: Value = serde_json::from_str(&lines.next().unwrap().unwrap()).unwrap();
+        assert!(response["error"].is_string());
+
+        // the connection is closed after an overlong request
+        writeln!(writer, "{}", " ".repeat(MAX_REQUEST_LEN)).unwrap();
+        writeln!(writer, "{}", "x".repeat(MAX_REQUEST_LEN + 1)).unwrap();
+        let response: Value = serde_json::from_str(&lines.next().unwrap().unwrap()).unwrap();
+        assert_eq!("request must be at most 4096 bytes", response["error"]);
+        assert!(lines.next().is_none());
+
+        // a stalled request doesn't hold its thread for longer than the read timeout
+        let stalled = UnixStream::connect(&socket_path).unwrap();
+        let start = Instant::now();
+        (&stalled).write_all(br#"{"count""#).unwrap();
+        assert_eq!(0, (&stalled).read(&mut [0u8; 8]).unwrap());
+        assert!(start.elapsed() >= READ_TIMEOUT);
+
+        // refuses to take over a socket in use
+        let err = remove_stale_socket(&socket_path).unwrap_err();
+        assert_eq!(io::ErrorKind::AddrInUse, err.kind());
+
+        fs::remove_file(&socket_path).unwrap();
+    }
+}
@@ -0,0 +1,50 @@
+use crate::dict::lint::{DictIssue, DictLint};
+use crate::dict::DictFormat;
+use clap::Subcommand;
+
+#[derive(Subcommand, Clone, Debug, PartialEq, Eq)]
+pub enum DictCommand {
+    #[command(
+        about = "Report non-alphabetic, misplaced, duplicated & invalid UTF-8 entries of a dictionary"
+    )]
+    Lint {
+        #[arg(
+            help = "Path to the dictionary, in any supported format. Use '-' to read from stdin"
+        )]
+        file: String,
+
+        #[arg(
+            short = 'o',
+            long = "output",
+            value_name = "FILE",
+            help = "Write the cleaned up words in the built-in format to FILE"
+        )]
+        output: Option<String>,
+    },
+}
+
+/// One issue per line with its line number, then a summary.
+pub fn render_dict_lint(lint: &DictLint) -> String {
+    let mut lines: Vec<String> = lint
+        .issues
+        .iter()
+        .map(|(position, issue)| {
+            // JSON syntax errors are the only ones located by line there
+            let unit = match (lint.format, issue) {
+                (DictFormat::Json, DictIssue::InvalidJson(_)) => "line",
+                (DictFormat::Json, _) => "entry",
+                _ => "line",
+            };
+
+            format!("{} {}: {}", unit, position, issue)
+        })
+        .collect();
+
+    lines.push(format!(
+        "{} issue(s), {} clean word(s)",
+        lint.issues.len(),
+        lint.words.len()
+    ));
+
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
@@ -0,0 +1,47 @@
+use age::armor::{ArmoredWriter, Format};
+use age::{x25519, Encryptor, Recipient};
+use std::io::Write;
+
+/// Checks an age recipient like `age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p`,
+/// for clap to report invalid ones along with other arguments.
+pub fn parse_age_recipient(recipient: &str) -> Result<String, String> {
+    recipient
+        .parse::<x25519::Recipient>()
+        .map(|_| recipient.to_string())
+        .map_err(|err| format!("invalid age recipient '{}': {}", recipient, err))
+}
+
+/// Encrypts `plaintext` to all `recipients` in the ASCII armored age format,
+/// decrypted with e.g. `age -d -i key.txt`.
+pub fn encrypt_to(recipients: &[String], plaintext: &str) -> Result<String, String> {
+    let recipients = recipients
+        .iter()
+        .map(|recipient| {
+            recipient
+                .parse::<x25519::Recipient>()
+                .map_err(|err| format!("invalid age recipient '{}': {}", recipient, err))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let encryptor = Encryptor::with_recipients(
+        recipients
+            .iter()
+            .map(|recipient| recipient as &dyn Recipient),
+    )
+    .map_err(|err| err.to_string())?;
+
+    let mut ciphertext = vec![];
+    let armored = ArmoredWriter::wrap_output(&mut ciphertext, Format::AsciiArmor)
+        .map_err(|err| err.to_string())?;
+    let mut writer = encryptor
+        .wrap_output(armored)
+        .map_err(|err| err.to_string())?;
+
+    writer
+        .write_all(plaintext.as_bytes())
+        .and_then(|_| writer.finish())
+        .and_then(|armored| armored.finish())
+        .map_err(|err| err.to_string())?;
+
+    String::from_utf8(ciphertext).map_err(|err| err.to_string())
+}
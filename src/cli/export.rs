@@ -0,0 +1,214 @@
+use crate::prelude::Entropy;
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+#[cfg(feature = "json")]
+use serde_json::{json, Value};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    KeepassCsv,
+    KeepassXml,
+    #[cfg(feature = "json")]
+    BitwardenJson,
+}
+
+impl ValueEnum for ExportFormat {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            Self::KeepassCsv,
+            Self::KeepassXml,
+            #[cfg(feature = "json")]
+            Self::BitwardenJson,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::KeepassCsv => {
+                PossibleValue::new("keepass-csv").help("CSV importable into KeePass & KeePassXC")
+            }
+            Self::KeepassXml => {
+                PossibleValue::new("keepass-xml").help("KeePass 2.x XML, importable into KeePass")
+            }
+            #[cfg(feature = "json")]
+            Self::BitwardenJson => PossibleValue::new("bitwarden-json")
+                .help("Unencrypted Bitwarden JSON, importable into a Bitwarden vault"),
+        })
+    }
+}
+
+/// A generated password with the account it's meant for, ready to be exported.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Credential {
+    pub title: String,
+    pub username: String,
+    pub password: String,
+}
+
+impl Credential {
+    /// Pairs passwords with `usernames` when given, titled after them,
+    /// otherwise numbers them with empty usernames.
+    pub fn pair(passwords: Vec<String>, usernames: Option<&[String]>) -> Vec<Self> {
+        passwords
+            .into_iter()
+            .enumerate()
+            .map(|(idx, password)| {
+                let username = usernames
+                    .and_then(|usernames| usernames.get(idx))
+                    .cloned()
+                    .unwrap_or_default();
+
+                let title = if username.is_empty() {
+                    format!("Generated password {}", idx + 1)
+                } else {
+                    username.clone()
+                };
+
+                Credential {
+                    title,
+                    username,
+                    password,
+                }
+            })
+            .collect()
+    }
+}
+
+pub fn export_credentials(
+    format: ExportFormat,
+    credentials: &[Credential],
+    entropy: &Entropy,
+) -> String {
+    let notes = format!("Generated by xkpasswd, entropy: {}", entropy);
+
+    match format {
+        ExportFormat::KeepassCsv => keepass_csv(credentials, &notes),
+        ExportFormat::KeepassXml => keepass_xml(credentials, &notes),
+        #[cfg(feature = "json")]
+        ExportFormat::BitwardenJson => bitwarden_json(credentials, &notes),
+    }
+}
+
+fn keepass_csv(credentials: &[Credential], notes: &str) -> String {
+    let mut lines = vec![r#""Title","Username","Password","URL","Notes""#.to_string()];
+
+    for credential in credentials {
+        let fields = [
+            credential.title.as_str(),
+            credential.username.as_str(),
+            credential.password.as_str(),
+            "",
+            notes,
+        ];
+
+        lines.push(fields.map(csv_field).join(","));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn keepass_xml(credentials: &[Credential], notes: &str) -> String {
+    let mut xml = vec![
+        r#"<?xml version="1.0" encoding="utf-8" standalone="yes"?>"#.to_string(),
+        "<KeePassFile>".to_string(),
+        "\t<Root>".to_string(),
+        "\t\t<Group>".to_string(),
+        "\t\t\t<Name>xkpasswd</Name>".to_string(),
+    ];
+
+    for credential in credentials {
+        xml.push("\t\t\t<Entry>".to_string());
+
+        let fields = [
+            ("Title", credential.title.as_str(), false),
+            ("UserName", credential.username.as_str(), false),
+            ("Password", credential.password.as_str(), true),
+            ("URL", "", false),
+            ("Notes", notes, false),
+        ];
+
+        for (key, value, protected) in fields {
+            let value_tag = if protected {
+                r#"<Value ProtectInMemory="True">"#
+            } else {
+                "<Value>"
+            };
+
+            xml.push(format!(
+                "\t\t\t\t<String><Key>{}</Key>{}{}</Value></String>",
+                key,
+                value_tag,
+                xml_escape(value)
+            ));
+        }
+
+        xml.push("\t\t\t</Entry>".to_string());
+    }
+
+    xml.extend([
+        "\t\t</Group>".to_string(),
+        "\t</Root>".to_string(),
+        "</KeePassFile>".to_string(),
+    ]);
+
+    xml.join("\n") + "\n"
+}
+
+#[cfg(feature = "json")]
+fn bitwarden_json(credentials: &[Credential], notes: &str) -> String {
+    let items: Vec<Value> = credentials
+        .iter()
+        .map(|credential| bitwarden_item(credential, notes))
+        .collect();
+
+    let export = json!({
+        "encrypted": false,
+        "folders": [],
+        "items": items,
+    });
+
+    serde_json::to_string_pretty(&export).expect("serializing JSON values never fails") + "\n"
+}
+
+// a login item as in Bitwarden's own unencrypted exports,
+// ids are left out for the vault to assign new ones on import
+#[cfg(feature = "json")]
+fn bitwarden_item(credential: &Credential, notes: &str) -> Value {
+    const LOGIN_ITEM_TYPE: u8 = 1;
+
+    json!({
+        "type": LOGIN_ITEM_TYPE,
+        "name": credential.title,
+        "notes": notes,
+        "favorite": false,
+        "reprompt": 0,
+        "folderId": null,
+        "organizationId": null,
+        "collectionIds": null,
+        "login": {
+            "username": non_empty(&credential.username),
+            "password": credential.password,
+            "uris": [],
+            "totp": null,
+        },
+    })
+}
+
+#[cfg(feature = "json")]
+fn non_empty(value: &str) -> Option<&str> {
+    Some(value).filter(|value| !value.is_empty())
+}
+
+// every field quoted, as KeePass writes them, with embedded quotes doubled
+fn csv_field(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -0,0 +1,81 @@
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+
+/// Kinds of failures, each exiting with its own code so scripts can branch on them.
+/// Usage errors like unknown options keep clap's exit code 2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// Anything not covered below, e.g. a failing clipboard or a password not verified
+    Other,
+    /// Settings, config files or presets that can't be used as they are
+    InvalidSettings,
+    /// A dictionary that can't be loaded, lacks the words asked for or fails `dict lint`
+    Dictionary,
+    /// Reading or writing files, stdin, stdout or sockets
+    Io,
+    /// Valid settings that can't meet a requirement, e.g. a target entropy
+    /// or a number of distinct passwords
+    Unsatisfiable,
+}
+
+impl Failure {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Other => 1,
+            Self::InvalidSettings => 3,
+            Self::Dictionary => 4,
+            Self::Io => 5,
+            Self::Unsatisfiable => 6,
+        }
+    }
+
+    #[cfg(feature = "json")]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Other => "other",
+            Self::InvalidSettings => "invalid_settings",
+            Self::Dictionary => "dictionary",
+            Self::Io => "io",
+            Self::Unsatisfiable => "unsatisfiable",
+        }
+    }
+
+    /// One line like `{"error": "io", "code": 5, "message": "..."}`.
+    #[cfg(feature = "json")]
+    pub fn to_json(self, message: &str) -> String {
+        serde_json::json!({
+            "error": self.name(),
+            "code": self.exit_code(),
+            "message": message,
+        })
+        .to_string()
+    }
+}
+
+/// How failures are reported on stderr.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ErrorFormat {
+    #[default]
+    Text,
+    #[cfg(feature = "json")]
+    Json,
+}
+
+impl ValueEnum for ErrorFormat {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            Self::Text,
+            #[cfg(feature = "json")]
+            Self::Json,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Text => PossibleValue::new("text").help("Localized messages"),
+            #[cfg(feature = "json")]
+            Self::Json => PossibleValue::new("json")
+                .help("One JSON object per failure, with the kind, exit code & message"),
+        })
+    }
+}
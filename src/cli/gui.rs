@@ -0,0 +1,224 @@
+use crate::prelude::{Builder, Entropy, Preset, Randomizer, Xkpasswd};
+use crate::settings::{Settings, SettingsPatch};
+use eframe::egui;
+
+const WINDOW_SIZE: [f32; 2] = [480.0, 420.0];
+// the meter is full well above every built-in preset
+const ENTROPY_METER_BITS: usize = 128;
+const MAX_PREVIEW_COUNT: usize = 10;
+const MAX_WORDS_COUNT: u8 = 10;
+const MAX_PADDING_DIGITS: u8 = 10;
+// same bounds as the checked word lengths of settings
+const WORD_LENGTH_RANGE: std::ops::RangeInclusive<u8> = 4..=10;
+
+/// Values of the sliders, laid on top of the settings they were seeded from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GuiControls {
+    pub count: usize,
+    pub words_count: u8,
+    pub word_lengths: (u8, u8),
+    pub padding_digits: (u8, u8),
+}
+
+impl GuiControls {
+    pub fn from_settings(settings: &Settings, count: usize) -> Self {
+        let word_lengths = settings.word_lengths();
+
+        GuiControls {
+            count: count.clamp(1, MAX_PREVIEW_COUNT),
+            words_count: settings.words_count(),
+            word_lengths: (word_lengths.start, word_lengths.end - 1),
+            padding_digits: settings.padding_digits(),
+        }
+    }
+
+    /// Settings as `base` with the slider values applied.
+    pub fn apply(&self, base: &Settings) -> Result<Settings, String> {
+        let patch = SettingsPatch {
+            words_count: Some(self.words_count),
+            word_length_min: Some(self.word_lengths.0),
+            word_length_max: Some(self.word_lengths.1),
+            padding_digits_before: Some(self.padding_digits.0),
+            padding_digits_after: Some(self.padding_digits.1),
+            ..SettingsPatch::default()
+        };
+
+        patch.apply(base)
+    }
+}
+
+struct GuiApp<'a> {
+    pass_generator: &'a Xkpasswd<'a>,
+    preset: Option<Preset>,
+    base: Settings,
+    controls: GuiControls,
+    settings: Settings,
+    passwds: Vec<String>,
+    entropy: Entropy,
+    warnings: Vec<String>,
+}
+
+impl<'a> GuiApp<'a> {
+    fn new(pass_generator: &'a Xkpasswd<'a>, settings: &Settings, count: usize) -> Self {
+        let mut app = GuiApp {
+            pass_generator,
+            preset: settings.preset(),
+            base: settings.clone(),
+            controls: GuiControls::from_settings(settings, count),
+            settings: settings.clone(),
+            passwds: vec![],
+            entropy: Entropy::default(),
+            warnings: vec![],
+        };
+
+        app.regenerate();
+        app
+    }
+
+    fn pick_preset(&mut self, preset: Preset) {
+        self.preset = Some(preset);
+        self.base = Settings::from_preset(preset);
+        self.controls = GuiControls::from_settings(&self.base, self.controls.count);
+        self.regenerate();
+    }
+
+    // sliders can't produce invalid settings, keep the last valid ones just in case
+    fn regenerate(&mut self) {
+        match self.controls.apply(&self.base) {
+            Ok(settings) => self.settings = settings,
+            Err(err) => log::warn!("ignoring invalid settings: {}", err),
+        }
+
+        let (passwds, entropy) = self
+            .pass_generator
+            .gen_passes(&self.settings, self.controls.count);
+        self.passwds = passwds;
+        self.entropy = entropy;
+        self.warnings = self
+            .settings
+            .lint(self.pass_generator)
+            .iter()
+            .map(|warning| warning.to_string())
+            .collect();
+    }
+
+    fn preset_picker(&mut self, ui: &mut egui::Ui) {
+        let selected_text = self
+            .preset
+            .map_or("custom".to_string(), |preset| preset.to_string());
+        let mut picked: Option<Preset> = None;
+
+        egui::ComboBox::from_label("Preset")
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                for preset in Preset::ALL {
+                    if ui
+                        .selectable_label(self.preset == Some(preset), preset.to_string())
+                        .on_hover_text(preset.description())
+                        .clicked()
+                    {
+                        picked = Some(preset);
+                    }
+                }
+            });
+
+        if let Some(preset) = picked {
+            self.pick_preset(preset);
+        }
+    }
+
+    // returns whether any slider was moved
+    fn sliders(&mut self, ui: &mut egui::Ui) -> bool {
+        let controls = &mut self.controls;
+        let (min_length, max_length) = &mut controls.word_lengths;
+        let (digits_before, digits_after) = &mut controls.padding_digits;
+
+        let changed = [
+            ui.add(egui::Slider::new(&mut controls.count, 1..=MAX_PREVIEW_COUNT).text("passwords")),
+            ui.add(egui::Slider::new(&mut controls.words_count, 1..=MAX_WORDS_COUNT).text("words")),
+            ui.add(egui::Slider::new(min_length, WORD_LENGTH_RANGE).text("min word length")),
+            ui.add(egui::Slider::new(max_length, WORD_LENGTH_RANGE).text("max word length")),
+            ui.add(egui::Slider::new(digits_before, 0..=MAX_PADDING_DIGITS).text("digits before")),
+            ui.add(egui::Slider::new(digits_after, 0..=MAX_PADDING_DIGITS).text("digits after")),
+        ]
+        .iter()
+        .any(egui::Response::changed);
+
+        if changed && controls.word_lengths.0 > controls.word_lengths.1 {
+            controls.word_lengths.1 = controls.word_lengths.0;
+        }
+
+        changed
+    }
+
+    fn entropy_meter(&self, ui: &mut egui::Ui) {
+        let progress = self.entropy.seen.min(ENTROPY_METER_BITS) as f32 / ENTROPY_METER_BITS as f32;
+        let fill = if self.warnings.is_empty() {
+            egui::Color32::DARK_GREEN
+        } else {
+            egui::Color32::DARK_RED
+        };
+
+        ui.add(
+            egui::ProgressBar::new(progress)
+                .fill(fill)
+                .text(format!("{} bits seen", self.entropy.seen)),
+        )
+        .on_hover_text(self.entropy.to_string());
+        ui.label(format!("guess time: {}", self.entropy.guess_time));
+
+        for warning in &self.warnings {
+            ui.colored_label(egui::Color32::DARK_RED, format!("warning: {}", warning));
+        }
+    }
+
+    fn preview(&self, ui: &mut egui::Ui) {
+        for passwd in &self.passwds {
+            ui.horizontal(|ui| {
+                if ui.button("Copy").clicked() {
+                    ui.ctx().copy_text(passwd.clone());
+                }
+                ui.monospace(passwd);
+            });
+        }
+    }
+}
+
+impl eframe::App for GuiApp<'_> {
+    fn ui(&mut self, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ui, |ui| {
+            self.preset_picker(ui);
+
+            if self.sliders(ui) {
+                // moving a slider departs from the preset, unless it's moved back
+                self.regenerate();
+                self.preset = self.settings.preset();
+            }
+
+            ui.separator();
+            self.entropy_meter(ui);
+
+            ui.separator();
+            if ui.button("Regenerate").clicked() {
+                self.regenerate();
+            }
+            self.preview(ui);
+        });
+    }
+}
+
+/// Opens a window to tweak `settings` with a live preview of `count` passwords,
+/// until the window is closed.
+pub fn run_gui(pass_generator: &Xkpasswd, settings: &Settings, count: usize) -> Result<(), String> {
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size(WINDOW_SIZE),
+        ..eframe::NativeOptions::default()
+    };
+
+    eframe::run_native(
+        "xkpasswd",
+        options,
+        Box::new(|_| Ok(Box::new(GuiApp::new(pass_generator, settings, count)))),
+    )
+    .map_err(|err| err.to_string())
+}
@@ -0,0 +1,54 @@
+#[cfg(feature = "hash_argon2")]
+use argon2::password_hash::{PasswordHasher, SaltString};
+#[cfg(feature = "hash_argon2")]
+use argon2::Argon2;
+#[cfg(feature = "hash_argon2")]
+use rand::RngCore;
+
+/// Schemes to hash generated passwords with, each behind its own feature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    #[cfg(feature = "hash_argon2")]
+    Argon2id,
+    #[cfg(feature = "hash_bcrypt")]
+    Bcrypt,
+    #[cfg(feature = "hash_sha512_crypt")]
+    Sha512Crypt,
+}
+
+impl HashAlgorithm {
+    /// Hashes `passwd` with a random salt & the default cost of each scheme, in the
+    /// `$id$...` format of crypt(3), understood by `/etc/shadow` & LDAP's `{CRYPT}`.
+    #[cfg_attr(
+        not(any(
+            feature = "hash_argon2",
+            feature = "hash_bcrypt",
+            feature = "hash_sha512_crypt"
+        )),
+        allow(unused_variables)
+    )]
+    pub fn hash(&self, passwd: &str) -> Result<String, String> {
+        match *self {
+            #[cfg(feature = "hash_argon2")]
+            Self::Argon2id => {
+                let mut salt = [0u8; 16];
+                crate::rng::rng().fill_bytes(&mut salt);
+                let salt = SaltString::encode_b64(&salt).map_err(|err| err.to_string())?;
+
+                Argon2::default()
+                    .hash_password(passwd.as_bytes(), &salt)
+                    .map(|hash| hash.to_string())
+                    .map_err(|err| err.to_string())
+            }
+            // bcrypt silently ignores anything past 72 bytes, failing is safer
+            #[cfg(feature = "hash_bcrypt")]
+            Self::Bcrypt => bcrypt::non_truncating_hash(passwd, bcrypt::DEFAULT_COST)
+                .map_err(|err| err.to_string()),
+            #[cfg(feature = "hash_sha512_crypt")]
+            Self::Sha512Crypt => {
+                sha_crypt::sha512_simple(passwd, &sha_crypt::Sha512Params::default())
+                    .map_err(|err| format!("{:?}", err))
+            }
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use crate::settings::lint::{Warning, MAX_RECOMMENDED_SYMBOLS, MIN_RECOMMENDED_ENTROPY};
+use crate::settings::Settings;
+use clap::Command;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use std::env;
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+const EN_MESSAGES: &str = include_str!("../assets/i18n/en.ftl");
+const VI_MESSAGES: &str = include_str!("../assets/i18n/vi.ftl");
+
+/// Language of the CLI's own messages, unrelated to the language of generated words.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    English,
+    Vietnamese,
+}
+
+impl Locale {
+    /// From `XKPASSWD_UI_LANG`, then the usual `LC_ALL`, `LC_MESSAGES` & `LANG`,
+    /// e.g. `vi_VN.UTF-8`, falling back to English for unsupported languages.
+    pub fn from_env() -> Self {
+        ["XKPASSWD_UI_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map(|value| Self::from_tag(&value))
+            .unwrap_or_default()
+    }
+
+    /// From a language tag or POSIX locale like `vi`, `vi-VN` or `vi_VN.UTF-8`.
+    pub fn from_tag(tag: &str) -> Self {
+        let language = tag.split(['_', '-', '.', '@']).next().unwrap_or_default();
+
+        match language.to_lowercase().as_str() {
+            "vi" => Self::Vietnamese,
+            _ => Self::English,
+        }
+    }
+
+    fn messages(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::English => ("en", EN_MESSAGES),
+            Self::Vietnamese => ("vi", VI_MESSAGES),
+        }
+    }
+}
+
+/// Messages of a locale, falling back to English ones missing a translation.
+pub struct Messages {
+    bundle: FluentBundle<FluentResource>,
+    fallback: Option<FluentBundle<FluentResource>>,
+}
+
+impl Messages {
+    pub fn new(locale: Locale) -> Self {
+        let fallback = (locale != Locale::English).then(|| bundle(Locale::English));
+
+        Messages {
+            bundle: bundle(locale),
+            fallback,
+        }
+    }
+
+    /// The message under `key`, or `key` itself when there's none even in English.
+    pub fn format(&self, key: &str, args: Option<&FluentArgs>) -> String {
+        translate(&self.bundle, key, args)
+            .or_else(|| translate(self.fallback.as_ref()?, key, args))
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    /// Replaces help texts of options & subcommands translated as `arg-<field>` & `about-<name>`,
+    /// English ones being left as written in the clap definitions.
+    pub fn localize_command(&self, mut command: Command) -> Command {
+        let arg_ids: Vec<String> = command
+            .get_arguments()
+            .map(|arg| arg.get_id().to_string())
+            .collect();
+
+        for id in arg_ids {
+            if let Some(help) = translate(&self.bundle, &format!("arg-{}", id), None) {
+                command = command.mut_arg(id, |arg| arg.help(help));
+            }
+        }
+
+        let names: Vec<String> = command
+            .get_subcommands()
+            .map(|subcommand| subcommand.get_name().to_string())
+            .collect();
+
+        for name in names {
+            if let Some(about) = translate(&self.bundle, &format!("about-{}", name), None) {
+                command = command.mut_subcommand(name, |subcommand| subcommand.about(about));
+            }
+        }
+
+        command
+    }
+
+    pub fn warning(&self, warning: &Warning) -> String {
+        let mut args = FluentArgs::new();
+
+        let key = match warning {
+            Warning::SingleWord => "warning-single-word",
+            Warning::NoSeparation => "warning-no-separation",
+            Warning::LowEntropy(entropy) => {
+                args.set("entropy", *entropy);
+                args.set("recommended", MIN_RECOMMENDED_ENTROPY);
+                "warning-low-entropy"
+            }
+            Warning::TooManySymbols(count) => {
+                args.set("count", *count);
+                args.set("max", MAX_RECOMMENDED_SYMBOLS);
+                "warning-too-many-symbols"
+            }
+            Warning::UncheckedWordLengths(min, max) => {
+                args.set("min", *min);
+                args.set("max", *max);
+                args.set("usual_min", Settings::MIN_WORD_LENGTH);
+                args.set("usual_max", Settings::MAX_WORD_LENGTH);
+                "warning-unchecked-word-lengths"
+            }
+            Warning::LayoutDependentSymbols(symbols) => {
+                args.set("symbols", symbols.clone());
+                "warning-layout-dependent-symbols"
+            }
+        };
+
+        let mut warning_args = FluentArgs::new();
+        warning_args.set("message", self.format(key, Some(&args)));
+        self.format("warning", Some(&warning_args))
+    }
+}
+
+fn translate(
+    bundle: &FluentBundle<FluentResource>,
+    key: &str,
+    args: Option<&FluentArgs>,
+) -> Option<String> {
+    let pattern = bundle.get_message(key)?.value()?;
+    let mut errors = vec![];
+    let message = bundle.format_pattern(pattern, args, &mut errors);
+
+    for error in errors {
+        log::debug!("formatting message '{}': {}", key, error);
+    }
+
+    Some(message.into_owned())
+}
+
+fn bundle(locale: Locale) -> FluentBundle<FluentResource> {
+    let (tag, messages) = locale.messages();
+    let langid: LanguageIdentifier = tag.parse().expect("valid language tag");
+    let resource = FluentResource::try_new(messages.to_string()).expect("valid messages");
+
+    let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+    // isolation marks around arguments show up as garbage in most terminals
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .expect("messages without duplicated keys");
+    bundle
+}
+
+/// Messages in the locale of the environment, see `Locale::from_env`.
+pub fn messages() -> &'static Messages {
+    static MESSAGES: OnceLock<Messages> = OnceLock::new();
+    MESSAGES.get_or_init(|| Messages::new(Locale::from_env()))
+}
+
+/// Formats a message of the environment's locale, e.g. `tr!("error-load-dict", error = err)`.
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::cli::messages().format($key, None)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value.to_string());)+
+        $crate::cli::messages().format($key, Some(&args))
+    }};
+}
+
+pub(crate) use tr;
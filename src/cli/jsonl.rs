@@ -0,0 +1,71 @@
+use crate::prelude::Xkpasswd;
+use crate::settings::Settings;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+#[derive(Debug)]
+pub enum JsonlError {
+    /// Reading the settings or writing the passwords failed
+    Io(io::Error),
+    /// Settings that can't be used, at the given line number
+    InvalidSettings(usize, String),
+}
+
+impl fmt::Display for JsonlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::InvalidSettings(line, err) => write!(f, "line {}: {}", line, err),
+        }
+    }
+}
+
+impl From<io::Error> for JsonlError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Prints one password per line of `input`, each line being xkpasswd.net-style JSON settings
+/// like `{"num_words": 3, "separator_character": "-"}`, `{}` for the default settings.
+/// Blank lines are skipped. Stops at the first invalid line, so every printed password
+/// still lines up with the line it was generated for.
+pub fn gen_from_jsonl<R: BufRead, W: Write>(
+    pass_generator: &Xkpasswd,
+    input: R,
+    output: &mut W,
+    terminator: char,
+) -> Result<usize, JsonlError> {
+    // pipelines tend to repeat the same settings, parsing & validating them once is enough
+    let mut last: Option<(String, Settings)> = None;
+    let mut passwd = String::new();
+    let mut count = 0;
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let settings = match last.take() {
+            Some((last_line, settings)) if last_line == line => settings,
+            _ => Settings::from_xkpasswd_json(line)
+                .and_then(|settings| {
+                    settings.validate_against(pass_generator)?;
+                    Ok(settings)
+                })
+                .map_err(|err| JsonlError::InvalidSettings(index + 1, err))?,
+        };
+
+        passwd.clear();
+        pass_generator.gen_pass_into(&settings, &mut passwd);
+        write!(output, "{}{}", passwd, terminator)?;
+
+        last = Some((line.to_string(), settings));
+        count += 1;
+    }
+
+    output.flush()?;
+    Ok(count)
+}
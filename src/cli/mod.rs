@@ -1,14 +1,89 @@
+#[cfg(feature = "clipboard")]
+mod clipboard;
+mod color;
+mod conceal;
+#[cfg(any(feature = "serve", all(unix, feature = "daemon")))]
+mod connection;
+#[cfg(all(unix, feature = "daemon"))]
+mod daemon;
+mod dict_lint;
+#[cfg(feature = "encrypt")]
+mod encrypt;
+mod export;
+mod failure;
+#[cfg(feature = "gui")]
+mod gui;
+#[cfg(feature = "hash")]
+mod hash;
+mod i18n;
+#[cfg(feature = "json")]
+mod jsonl;
+#[cfg(feature = "pass")]
+mod pass;
+mod password_input;
+mod presets;
+mod repl;
+#[cfg(feature = "serve")]
+mod serve;
+mod spell;
+#[cfg(all(unix, feature = "ssh_keygen"))]
+mod ssh_keygen;
+#[cfg(credential_store)]
+mod store;
+mod table;
 #[cfg(test)]
 mod tests;
 mod toml_conf;
+mod user_dirs;
 
 use crate::bit_flags::*;
+use crate::codename;
 use crate::prelude::*;
+use crate::recovery::{CodeAlphabet, CodeFormat};
+use crate::settings::registry::PresetRegistry;
+use crate::settings::{Settings, SettingsPatch};
+use crate::typing::KeyboardLayout;
+#[cfg(feature = "clipboard")]
+pub use clipboard::*;
+pub use color::*;
+pub use conceal::*;
+#[cfg(all(unix, feature = "daemon"))]
+pub use daemon::*;
+pub use dict_lint::*;
+#[cfg(feature = "encrypt")]
+pub use encrypt::*;
+pub use export::*;
+pub use failure::*;
+#[cfg(feature = "gui")]
+pub use gui::*;
+#[cfg(feature = "hash")]
+pub use hash::*;
+pub use i18n::*;
+#[cfg(feature = "json")]
+pub use jsonl::*;
+#[cfg(feature = "pass")]
+pub use pass::*;
+pub use password_input::*;
+pub use presets::*;
+pub use repl::*;
+#[cfg(feature = "serve")]
+pub use serve::*;
+pub use spell::*;
+#[cfg(all(unix, feature = "ssh_keygen"))]
+pub use ssh_keygen::*;
+#[cfg(credential_store)]
+pub use store::*;
+pub use table::*;
 use toml_conf::*;
 
-use clap::builder::PossibleValue;
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
 use clap::error::ErrorKind;
-use clap::{ArgAction, CommandFactory, Parser, ValueEnum};
+use clap::{ArgAction, ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process;
 
 #[derive(Clone, Copy, Debug)]
 pub enum CliPadding {
@@ -16,25 +91,231 @@ pub enum CliPadding {
     Adaptive,
 }
 
-#[derive(Parser, Debug)]
+#[derive(Subcommand, Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    #[command(about = "List built-in presets with their settings, an example and entropy")]
+    Presets,
+
+    #[command(
+        about = "Check whether a password read from stdin could have been generated with the given settings"
+    )]
+    Verify,
+
+    #[command(
+        about = "Break a password read from stdin down into dictionary words, digits & symbols and estimate its entropy"
+    )]
+    Analyze,
+
+    #[command(about = "Compare lengths, entropy & examples of all built-in presets side by side")]
+    Compare,
+
+    #[command(about = "Tweak settings interactively, e.g. 'set words 5', 'preset wifi' & 'gen 3'")]
+    Repl,
+
+    #[command(
+        about = "Suggest settings reaching --min-entropy (64 bits by default) within constraints"
+    )]
+    Suggest {
+        #[arg(long = "max-length", value_name = "LENGTH")]
+        max_length: Option<usize>,
+
+        #[arg(long = "no-symbols", help = "Neither separators nor padding symbols")]
+        no_symbols: bool,
+
+        #[arg(long = "no-digits", help = "No padding digits")]
+        no_digits: bool,
+
+        #[arg(
+            long = "limit",
+            default_value_t = 5,
+            help = "Number of settings to suggest"
+        )]
+        limit: usize,
+    },
+
+    #[command(about = "Generate recovery codes like 7KQ2-M9XD-F3TB, to keep offline")]
+    Recovery {
+        #[arg(
+            short = 'n',
+            long = "count",
+            default_value_t = 10,
+            help = "Number of codes to generate, all distinct"
+        )]
+        count: usize,
+
+        #[arg(long = "alphabet", default_value = "base32")]
+        alphabet: CodeAlphabet,
+
+        #[arg(
+            long = "chars",
+            conflicts_with = "alphabet",
+            help = "Custom characters to pick from instead of a built-in alphabet"
+        )]
+        chars: Option<String>,
+
+        #[arg(long = "groups", default_value_t = 3)]
+        groups: usize,
+
+        #[arg(long = "group-length", default_value_t = 4)]
+        group_length: usize,
+
+        #[arg(long = "separator", default_value = "-")]
+        separator: String,
+    },
+
+    #[command(about = "Generate DNS-safe codenames like brave-otter for servers & releases")]
+    Codename {
+        #[arg(
+            long = "themes",
+            value_delimiter = ',',
+            default_values = codename::DEFAULT_THEMES,
+            help = "Themes of the dictionary to pick a word from in turn, built-in ones being adjectives, nouns, animals & colors"
+        )]
+        themes: Vec<String>,
+
+        #[arg(
+            short = 'n',
+            long = "count",
+            default_value_t = 1,
+            help = "Number of codenames to generate"
+        )]
+        count: usize,
+    },
+
+    #[command(about = "Generate numeric PINs, rejecting repeats, straights, years & common ones")]
+    Pin {
+        #[arg(long = "digits", default_value_t = 6)]
+        digits: usize,
+
+        #[arg(
+            short = 'n',
+            long = "count",
+            default_value_t = 1,
+            help = "Number of PINs to generate"
+        )]
+        count: usize,
+    },
+
+    #[command(about = "Tools for custom dictionaries")]
+    Dict {
+        #[command(subcommand)]
+        action: DictCommand,
+    },
+
+    #[cfg(feature = "pass")]
+    #[command(about = "Store generated passwords with pass, the standard unix password manager")]
+    Pass {
+        #[command(subcommand)]
+        action: PassCommand,
+    },
+
+    #[cfg(feature = "serve")]
+    #[command(about = "Serve generated passwords over HTTP to clients holding an API key")]
+    Serve {
+        #[arg(long = "listen", default_value = "127.0.0.1:8080")]
+        listen: String,
+
+        #[arg(
+            long = "keys",
+            value_name = "FILE",
+            help = "Path to API keys, one 'name:key' pair per line"
+        )]
+        keys_file: String,
+
+        #[arg(
+            long = "rate",
+            default_value_t = 60,
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Requests allowed per minute for each key"
+        )]
+        per_minute: u32,
+
+        #[arg(
+            long = "burst",
+            default_value_t = 10,
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Requests allowed at once for each key before rate limiting"
+        )]
+        burst: u32,
+    },
+
+    // run by --copy as a detached process
+    #[cfg(feature = "clipboard")]
+    #[command(hide = true)]
+    HoldClipboard {
+        #[arg(long = "clear-after", value_name = "SECONDS")]
+        clear_after: Option<u64>,
+    },
+
+    #[cfg(feature = "gui")]
+    #[command(about = "Open a window to tweak settings with a live preview of passwords")]
+    Gui,
+
+    #[cfg(all(unix, feature = "daemon"))]
+    #[command(
+        about = "Keep the dictionary loaded and answer JSON lines requests on a unix socket"
+    )]
+    Daemon {
+        #[arg(long = "socket", value_name = "PATH")]
+        socket: String,
+    },
+}
+
+#[derive(Parser, Debug, Default)]
 #[command(version)]
 pub struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     #[arg(
         short = 'w',
         long = "words",
+        env = "XKPASSWD_WORDS",
+        global = true,
         help = "total number of words from dictionary"
     )]
     words_count: Option<u8>,
 
-    #[arg(short = 'l', long = "word-min", help = "Minimum length of a word")]
+    #[arg(
+        long = "min-entropy",
+        value_name = "BITS",
+        global = true,
+        help = "Add words until reaching this entropy with full knowledge, reporting the chosen settings"
+    )]
+    min_entropy: Option<usize>,
+
+    #[arg(
+        short = 'l',
+        long = "word-min",
+        env = "XKPASSWD_WORD_MIN",
+        global = true,
+        help = "Minimum length of a word"
+    )]
     word_length_min: Option<u8>,
 
-    #[arg(short = 'u', long = "word-max", help = "Maximum length of a word")]
+    #[arg(
+        short = 'u',
+        long = "word-max",
+        env = "XKPASSWD_WORD_MAX",
+        global = true,
+        help = "Maximum length of a word"
+    )]
     word_length_max: Option<u8>,
 
+    #[arg(
+        long = "unchecked-word-lengths",
+        env = "XKPASSWD_UNCHECKED_WORD_LENGTHS",
+        global = true,
+        help = "Allow word lengths outside of 4 to 10, with a warning instead of an error"
+    )]
+    unchecked_word_lengths: bool,
+
     #[arg(
         short = 't',
         long = "transforms",
+        env = "XKPASSWD_TRANSFORMS",
+        value_delimiter = ',',
+        global = true,
         value_enum,
         help = "Word transformations, can be combined with multiple occurrences"
     )]
@@ -43,18 +324,24 @@ pub struct Cli {
     #[arg(
         short = 's',
         long = "separators",
+        env = "XKPASSWD_SEPARATORS",
+        global = true,
         help = "List of characters to be used as separator"
     )]
     separators: Option<String>,
 
     #[arg(
         long = "digits-before",
+        env = "XKPASSWD_DIGITS_BEFORE",
+        global = true,
         help = "How many digits to be padded before the words"
     )]
     padding_digits_before: Option<u8>,
 
     #[arg(
         long = "digits-after",
+        env = "XKPASSWD_DIGITS_AFTER",
+        global = true,
         help = "How many digits to be padded after the words"
     )]
     padding_digits_after: Option<u8>,
@@ -62,52 +349,481 @@ pub struct Cli {
     #[arg(
         short = 'y',
         long = "symbols",
+        env = "XKPASSWD_SYMBOLS",
+        global = true,
         help = "List of characters to be used as padding symbols"
     )]
     padding_symbols: Option<String>,
 
+    #[arg(
+        long = "symbol-set",
+        env = "XKPASSWD_SYMBOL_SET",
+        global = true,
+        help = "Curated characters to be used as both separators & padding symbols, unless --separators or --symbols are given",
+        value_enum
+    )]
+    symbol_set: Option<SymbolSet>,
+
     #[arg(
         long = "symbols-before",
+        env = "XKPASSWD_SYMBOLS_BEFORE",
+        global = true,
         help = "How many symbols to be padded before the words"
     )]
     padding_symbols_before: Option<u8>,
 
     #[arg(
         long = "symbols-after",
+        env = "XKPASSWD_SYMBOLS_AFTER",
+        global = true,
         help = "How many symbols to be padded after the words"
     )]
     padding_symbols_after: Option<u8>,
 
-    #[arg(short = 'p', long = "padding", help = "Padding strategy", value_enum)]
+    #[arg(
+        short = 'p',
+        long = "padding",
+        env = "XKPASSWD_PADDING",
+        global = true,
+        help = "Padding strategy",
+        value_enum
+    )]
     padding: Option<CliPadding>,
 
+    #[arg(
+        short = 'j',
+        long = "join",
+        env = "XKPASSWD_JOIN",
+        global = true,
+        help = "How words are joined together",
+        value_enum
+    )]
+    join_style: Option<JoinStyle>,
+
+    #[arg(
+        long = "pronounceable",
+        env = "XKPASSWD_PRONOUNCEABLE",
+        global = true,
+        help = "Avoid word combinations that are awkward to say aloud"
+    )]
+    pronounceable: bool,
+
+    #[arg(
+        long = "checksum",
+        env = "XKPASSWD_CHECKSUM",
+        global = true,
+        help = "Append 2 checksum digits to catch typos, adding length but no entropy"
+    )]
+    checksum: bool,
+
+    #[arg(
+        long = "avoid-ambiguous",
+        env = "XKPASSWD_AVOID_AMBIGUOUS",
+        global = true,
+        help = "Leave out characters easily mistaken for one another like l, 1, I, O & 0, for passwords read from paper"
+    )]
+    avoid_ambiguous: bool,
+
+    #[arg(
+        long = "avoid-homophones",
+        env = "XKPASSWD_AVOID_HOMOPHONES",
+        global = true,
+        help = "Leave out words sounding like others like their & there, for passwords dictated over the phone"
+    )]
+    avoid_homophones: bool,
+
+    #[arg(
+        long = "theme",
+        env = "XKPASSWD_THEME",
+        value_name = "THEME",
+        global = true,
+        help = "Only pick words listed under '@THEME' in the dictionary, e.g. animals. Fewer words to pick from means less entropy"
+    )]
+    word_theme: Option<String>,
+
+    #[arg(
+        long = "proper-nouns",
+        env = "XKPASSWD_PROPER_NOUNS",
+        global = true,
+        help = "What to do with proper nouns, capitalized words of the dictionary like Paris",
+        value_enum
+    )]
+    proper_nouns: Option<ProperNouns>,
+
+    #[arg(
+        long = "spelling",
+        env = "XKPASSWD_SPELLING",
+        global = true,
+        help = "Only pick words in British or American spelling, for languages with such variants",
+        value_enum
+    )]
+    spelling: Option<SpellingVariant>,
+
     #[arg(
         short = 'a',
         long = "adaptive-length",
+        env = "XKPASSWD_ADAPTIVE_LENGTH",
+        global = true,
         help = "Pad or trim the final output to fit a length. Required for --padding=adaptive"
     )]
     adaptive_length: Option<usize>,
 
-    #[arg(short = 'P', long = "preset", value_enum)]
-    preset: Option<Preset>,
+    #[arg(
+        short = 'P',
+        long = "preset",
+        env = "XKPASSWD_PRESET",
+        global = true,
+        value_parser = PresetParser,
+        help = "Start from a built-in preset, or one named in $XDG_CONFIG_HOME/xkpasswd/presets.toml which comes first"
+    )]
+    preset: Option<String>,
+
+    // set once `preset` resolves to a user preset rather than a built-in one
+    #[arg(skip)]
+    preset_is_user: bool,
 
-    #[arg(short = 'v', long = "verbose", help = "Verbosity: 1 = info, 2+ = debug", action = ArgAction::Count)]
+    #[arg(short = 'v', long = "verbose", global = true, help = "Verbosity: 1 = info, 2+ = debug", action = ArgAction::Count)]
     verbosity: u8,
 
-    #[arg(short = 'z', long = "lang", help = "Language of generated words")]
+    #[arg(
+        long = "errors",
+        value_name = "FORMAT",
+        value_enum,
+        global = true,
+        default_value_t = ErrorFormat::Text,
+        help = "How to report failures on stderr, each kind also exits with its own code"
+    )]
+    errors: ErrorFormat,
+
+    #[arg(
+        short = 'z',
+        long = "lang",
+        env = "XKPASSWD_LANG",
+        global = true,
+        help = "Language of generated words"
+    )]
     language: Option<Language>,
 
-    #[arg(short = 'c', long = "config", help = "Path to .toml config file")]
+    #[arg(
+        short = 'c',
+        long = "config",
+        env = "XKPASSWD_CONFIG",
+        global = true,
+        help = "Path to .toml config file"
+    )]
     config_file: Option<String>,
+
+    #[arg(
+        short = 'd',
+        long = "dict",
+        env = "XKPASSWD_DICT",
+        global = true,
+        help = "Path to a custom dictionary, as a plain words list, a JSON array, a CSV of words with optional frequencies or in the built-in format, or the name of one in $XDG_DATA_HOME/xkpasswd/dicts. Use '-' to read from stdin"
+    )]
+    dict_file: Option<String>,
+
+    #[arg(
+        long = "user-preset",
+        value_name = "NAME",
+        env = "XKPASSWD_USER_PRESET",
+        global = true,
+        help = "Use a named table of settings from $XDG_CONFIG_HOME/xkpasswd/presets.toml"
+    )]
+    user_preset: Option<String>,
+
+    #[arg(
+        long = "no-user-config",
+        global = true,
+        help = "Skip config file, presets & dictionaries discovery in user directories, only explicitly given paths are read"
+    )]
+    no_user_config: bool,
+
+    #[arg(
+        long = "syllables",
+        global = true,
+        conflicts_with = "dict_file",
+        help = "Use generated pronounceable nonsense words instead of dictionary words"
+    )]
+    syllables: bool,
+
+    #[arg(
+        long = "dice",
+        value_name = "ROLLS",
+        conflicts_with_all = ["dict_file", "syllables"],
+        help = "Pick diceware words from physical dice rolls instead, 5 dice per word (e.g. '16325 43512'). Use '-' to read from stdin"
+    )]
+    dice: Option<String>,
+
+    #[arg(
+        short = 'n',
+        long = "count",
+        default_value_t = 1,
+        help = "Number of passwords to generate"
+    )]
+    count: usize,
+
+    #[arg(
+        long = "usernames",
+        value_name = "FILE",
+        conflicts_with = "count",
+        help = "Path to a file of usernames, one per line, to generate a password for each"
+    )]
+    usernames_file: Option<String>,
+
+    #[arg(
+        long = "unique",
+        value_enum,
+        help = "Guarantee no 2 generated passwords are identical or share a word"
+    )]
+    unique: Option<Uniqueness>,
+
+    #[arg(
+        long = "minimize-typing",
+        value_name = "LAYOUT",
+        value_enum,
+        num_args = 0..=1,
+        default_missing_value = "desktop",
+        conflicts_with = "unique",
+        help = "Pick the easiest to type of several candidates, costing up to log2 of their number in entropy bits"
+    )]
+    minimize_typing: Option<KeyboardLayout>,
+
+    #[arg(
+        long = "typing-candidates",
+        value_name = "COUNT",
+        default_value_t = 16,
+        requires = "minimize_typing",
+        help = "Number of candidates to pick the easiest to type from"
+    )]
+    typing_candidates: usize,
+
+    #[arg(
+        long = "export",
+        value_enum,
+        help = "Print generated passwords in a format importable into password managers"
+    )]
+    export: Option<ExportFormat>,
+
+    #[arg(
+        long = "format",
+        value_enum,
+        conflicts_with_all = ["export", "spell"],
+        help = "Print generated passwords as a table with their index, length & entropy bits"
+    )]
+    table_format: Option<TableFormat>,
+
+    #[arg(
+        long = "spell",
+        value_enum,
+        help = "Spell out each character of the generated passwords"
+    )]
+    spell: Option<SpellMode>,
+
+    #[arg(
+        long = "color",
+        value_enum,
+        value_name = "WHEN",
+        default_value_t = ColorChoice::Auto,
+        help = "Color words, separators, digits & symbols of generated passwords differently"
+    )]
+    color: ColorChoice,
+
+    #[arg(
+        long = "porcelain",
+        conflicts_with_all = ["export", "table_format", "spell", "color"],
+        help = "Print exactly the generated passwords, one per line and nothing else, for scripts. Anything else goes to stderr"
+    )]
+    #[cfg_attr(feature = "hash", arg(conflicts_with = "hash"))]
+    #[cfg_attr(feature = "encrypt", arg(conflicts_with = "encrypt_to"))]
+    porcelain: bool,
+
+    #[arg(
+        long = "print0",
+        conflicts_with_all = ["export", "table_format", "spell"],
+        help = "End each generated password with a NUL character instead of a newline, e.g. for 'xargs -0'"
+    )]
+    print0: bool,
+
+    #[arg(
+        long = "conceal",
+        conflicts_with_all = ["export", "table_format", "spell", "porcelain", "print0"],
+        help = "On a terminal, hide generated passwords again once Enter is pressed, e.g. while sharing the screen"
+    )]
+    conceal: bool,
+
+    #[cfg(feature = "hash")]
+    #[arg(
+        long = "hash",
+        value_name = "ALGORITHM",
+        conflicts_with_all = ["export", "table_format", "spell", "conceal"],
+        help = "Print a hash of each generated password after it, separated by a tab, e.g. for /etc/shadow or LDAP"
+    )]
+    hash: Option<HashAlgorithm>,
+
+    #[cfg(feature = "hash")]
+    #[arg(
+        long = "hash-only",
+        requires = "hash",
+        help = "Print only the hashes & discard the passwords, e.g. for accounts whose owners set their own on first login"
+    )]
+    hash_only: bool,
+
+    #[cfg(feature = "encrypt")]
+    #[arg(
+        long = "encrypt-to",
+        value_name = "RECIPIENT",
+        value_parser = parse_age_recipient,
+        conflicts_with_all = ["spell", "color", "conceal"],
+        help = "Encrypt the output to an age recipient like age1ql3z..., may be repeated for more recipients"
+    )]
+    #[cfg_attr(feature = "hash", arg(conflicts_with = "hash"))]
+    encrypt_to: Vec<String>,
+
+    #[cfg(feature = "json")]
+    #[arg(
+        long = "stdin-jsonl",
+        conflicts_with_all = ["count", "export", "table_format", "spell", "color", "conceal"],
+        help = "Read xkpasswd.net-style JSON settings from stdin, one object per line, and print a password for each, e.g. for high-throughput pipelines"
+    )]
+    #[cfg_attr(feature = "hash", arg(conflicts_with = "hash"))]
+    #[cfg_attr(feature = "encrypt", arg(conflicts_with = "encrypt_to"))]
+    stdin_jsonl: bool,
+
+    #[cfg(feature = "clipboard")]
+    #[arg(
+        long = "copy",
+        conflicts_with_all = ["export", "table_format", "spell", "porcelain", "print0", "conceal"],
+        help = "Copy generated passwords to the clipboard, one per line, instead of printing them"
+    )]
+    #[cfg_attr(feature = "hash", arg(conflicts_with = "hash"))]
+    #[cfg_attr(feature = "encrypt", arg(conflicts_with = "encrypt_to"))]
+    #[cfg_attr(feature = "json", arg(conflicts_with = "stdin_jsonl"))]
+    copy: bool,
+
+    #[cfg(feature = "clipboard")]
+    #[arg(
+        long = "clear-after",
+        value_name = "SECONDS",
+        requires = "copy",
+        help = "Clear the clipboard after some seconds, unless something else was copied in the meantime"
+    )]
+    clear_after: Option<u64>,
+
+    #[cfg(all(unix, feature = "ssh_keygen"))]
+    #[arg(
+        long = "ssh-keygen",
+        value_name = "KEY_FILE",
+        conflicts_with_all = ["count", "usernames_file"],
+        help = "Create an Ed25519 SSH key pair at KEY_FILE protected by the generated passphrase, piped to ssh-keygen. Best with --preset ssh"
+    )]
+    #[cfg_attr(feature = "json", arg(conflicts_with = "stdin_jsonl"))]
+    ssh_keygen: Option<String>,
+
+    #[cfg(credential_store)]
+    #[arg(
+        long = "store",
+        value_name = "BACKEND",
+        conflicts_with_all = ["count", "usernames_file", "export", "table_format", "spell", "print0", "conceal"],
+        help = "Save the generated password into a credential store instead of printing it"
+    )]
+    #[cfg_attr(feature = "hash", arg(conflicts_with = "hash"))]
+    #[cfg_attr(feature = "encrypt", arg(conflicts_with = "encrypt_to"))]
+    #[cfg_attr(feature = "json", arg(conflicts_with = "stdin_jsonl"))]
+    #[cfg_attr(feature = "clipboard", arg(conflicts_with = "copy"))]
+    store: Option<Store>,
+
+    #[cfg(all(windows, feature = "wincred"))]
+    #[arg(
+        long = "name",
+        value_name = "TARGET",
+        requires = "store",
+        required_if_eq("store", "wincred"),
+        help = "Name of the credential to write with --store wincred, replacing any existing one"
+    )]
+    store_name: Option<String>,
+
+    #[cfg(any(
+        all(target_os = "macos", feature = "keychain"),
+        all(target_os = "linux", feature = "secret_service")
+    ))]
+    #[arg(
+        long = "service",
+        value_name = "SERVICE",
+        requires = "store",
+        required_if_eq_any([("store", "keychain"), ("store", "secret-service")]),
+        help = "Service of the item to save with --store keychain or secret-service, e.g. a website"
+    )]
+    store_service: Option<String>,
+
+    #[cfg(any(
+        all(target_os = "macos", feature = "keychain"),
+        all(target_os = "linux", feature = "secret_service")
+    ))]
+    #[arg(
+        long = "account",
+        value_name = "ACCOUNT",
+        requires = "store",
+        required_if_eq_any([("store", "keychain"), ("store", "secret-service")]),
+        help = "Account of the item to save with --store keychain or secret-service, replacing any existing one"
+    )]
+    store_account: Option<String>,
 }
 
 impl Cli {
     pub fn init() -> Self {
-        let cli = Self::parse();
+        let mut command = messages().localize_command(Self::command());
+        let matches = command.get_matches_mut();
+        let cli = Self::from_matches(&mut command, &matches).unwrap_or_else(|err| err.exit());
         cli.init_logger();
         cli
     }
 
+    fn from_matches(
+        command: &mut clap::Command,
+        matches: &ArgMatches,
+    ) -> Result<Self, clap::Error> {
+        let cli = Self::from_arg_matches(matches)?;
+
+        // clap only lets arguments conflict with other arguments, not with subcommands
+        if let (true, Some(name)) = (cli.porcelain, matches.subcommand_name()) {
+            return Err(command.error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "the argument '--porcelain' cannot be used with the '{}' subcommand",
+                    name
+                ),
+            ));
+        }
+
+        Ok(cli)
+    }
+
+    /// Reports a failure on stderr in the format of `--errors`, then exits with its code.
+    pub fn fail(&self, failure: Failure, message: &str) -> ! {
+        match self.errors {
+            ErrorFormat::Text => eprintln!("{}", message),
+            #[cfg(feature = "json")]
+            ErrorFormat::Json => eprintln!("{}", failure.to_json(message)),
+        }
+
+        process::exit(failure.exit_code())
+    }
+
+    // clap's formatting with a usage hint, for failures caused by the arguments themselves
+    fn fail_args(&self, failure: Failure, kind: ErrorKind, message: String) -> ! {
+        if self.errors == ErrorFormat::Text {
+            // stderr going away leaves nowhere to report it anyway
+            let _ = Self::command().error(kind, message).print();
+            process::exit(failure.exit_code());
+        }
+
+        self.fail(failure, &message)
+    }
+
+    pub fn subcommand(&self) -> Option<&Command> {
+        self.command.as_ref()
+    }
+
     pub fn language(&self) -> Language {
         match self.language {
             Some(language) => language,
@@ -115,24 +831,239 @@ impl Cli {
         }
     }
 
-    pub fn parse_settings<B: Builder + Randomizer>(&mut self) -> B {
+    pub fn read_dict(&self) -> Option<Vec<u8>> {
+        let dict_file = self.dict_file.as_ref()?;
+
+        match read_dict_file(&self.resolve_dict_path(dict_file)) {
+            Ok(dict_bytes) => Some(dict_bytes),
+            Err(err) => {
+                self.fail_args(
+                    Failure::Io,
+                    ErrorKind::Io,
+                    tr!("error-read-dict", file = dict_file, error = err),
+                );
+            }
+        }
+    }
+
+    // a plain name not found as a path is looked up among the user's dictionaries
+    fn resolve_dict_path(&self, dict_file: &str) -> String {
+        if self.no_user_config || dict_file == "-" || Path::new(dict_file).exists() {
+            return dict_file.to_string();
+        }
+
+        user_dirs::dict_file(dict_file)
+            .and_then(|path| path.into_os_string().into_string().ok())
+            .unwrap_or_else(|| dict_file.to_string())
+    }
+
+    pub fn syllables(&self) -> bool {
+        self.syllables
+    }
+
+    pub fn dice_words(&self) -> Option<Vec<&'static str>> {
+        let dice = self.dice.as_ref()?;
+
+        let rolls = match read_dice_rolls(dice) {
+            Ok(rolls) => rolls,
+            Err(err) => {
+                self.fail_args(
+                    Failure::Io,
+                    ErrorKind::Io,
+                    tr!("error-read-dice", error = err),
+                );
+            }
+        };
+
+        match lookup_diceware(&rolls) {
+            Ok(words) => Some(words),
+            Err(message) => {
+                self.fail_args(Failure::InvalidSettings, ErrorKind::InvalidValue, message);
+            }
+        }
+    }
+
+    pub fn read_usernames(&self) -> Option<Vec<String>> {
+        let usernames_file = self.usernames_file.as_ref()?;
+
+        match fs::read_to_string(usernames_file) {
+            Ok(content) => Some(
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(String::from)
+                    .collect(),
+            ),
+            Err(err) => {
+                self.fail_args(
+                    Failure::Io,
+                    ErrorKind::Io,
+                    tr!("error-read-usernames", file = usernames_file, error = err),
+                );
+            }
+        }
+    }
+
+    pub fn export(&self) -> Option<ExportFormat> {
+        self.export
+    }
+
+    pub fn table_format(&self) -> Option<TableFormat> {
+        self.table_format
+    }
+
+    /// The built-in preset named by `--preset`, none for user presets, even when overriding one.
+    pub fn preset(&self) -> Option<Preset> {
+        if self.preset_is_user {
+            return None;
+        }
+
+        self.preset.as_deref().and_then(|name| name.parse().ok())
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Format of the codes of the `recovery` subcommand.
+    pub fn code_format(&self) -> Option<Result<CodeFormat, String>> {
+        let Some(Command::Recovery {
+            alphabet,
+            chars,
+            groups,
+            group_length,
+            separator,
+            ..
+        }) = &self.command
+        else {
+            return None;
+        };
+
+        let format = CodeFormat::default().with_separator(separator);
+        let format = match chars {
+            Some(chars) => format.with_chars(chars),
+            None => Ok(format.with_alphabet(*alphabet)),
+        };
+
+        Some(format.and_then(|format| format.with_groups(*groups, *group_length)))
+    }
+
+    pub fn min_entropy(&self) -> Option<usize> {
+        self.min_entropy
+    }
+
+    pub fn unique(&self) -> Option<Uniqueness> {
+        self.unique
+    }
+
+    /// The layout to minimize typing effort on, with the number of candidates to pick from.
+    pub fn minimize_typing(&self) -> Option<(KeyboardLayout, usize)> {
+        self.minimize_typing
+            .map(|layout| (layout, self.typing_candidates))
+    }
+
+    pub fn spell(&self) -> Option<SpellMode> {
+        self.spell
+    }
+
+    pub fn terminator(&self) -> char {
+        if self.print0 {
+            '\0'
+        } else {
+            '\n'
+        }
+    }
+
+    /// Whether to conceal passwords, they are simply printed when not on a terminal.
+    pub fn conceal(&self) -> bool {
+        self.conceal && can_conceal()
+    }
+
+    #[cfg(feature = "hash")]
+    pub fn hash(&self) -> Option<HashAlgorithm> {
+        self.hash
+    }
+
+    #[cfg(feature = "hash")]
+    pub fn hash_only(&self) -> bool {
+        self.hash_only
+    }
+
+    #[cfg(feature = "encrypt")]
+    pub fn encrypt_to(&self) -> &[String] {
+        &self.encrypt_to
+    }
+
+    #[cfg(feature = "json")]
+    pub fn stdin_jsonl(&self) -> bool {
+        self.stdin_jsonl
+    }
+
+    #[cfg(feature = "clipboard")]
+    pub fn copy(&self) -> bool {
+        self.copy
+    }
+
+    #[cfg(feature = "clipboard")]
+    pub fn clear_after(&self) -> Option<std::time::Duration> {
+        self.clear_after.map(std::time::Duration::from_secs)
+    }
+
+    #[cfg(all(unix, feature = "ssh_keygen"))]
+    pub fn ssh_keygen(&self) -> Option<&str> {
+        self.ssh_keygen.as_deref()
+    }
+
+    #[cfg(credential_store)]
+    pub fn store(&self) -> Option<(Store, StoreEntry)> {
+        let entry = StoreEntry {
+            #[cfg(all(windows, feature = "wincred"))]
+            name: self.store_name.clone().unwrap_or_default(),
+            #[cfg(any(
+                all(target_os = "macos", feature = "keychain"),
+                all(target_os = "linux", feature = "secret_service")
+            ))]
+            service: self.store_service.clone().unwrap_or_default(),
+            #[cfg(any(
+                all(target_os = "macos", feature = "keychain"),
+                all(target_os = "linux", feature = "secret_service")
+            ))]
+            account: self.store_account.clone().unwrap_or_default(),
+        };
+        self.store.map(|store| (store, entry))
+    }
+
+    pub fn color(&self) -> bool {
+        !self.porcelain && self.color.enabled()
+    }
+
+    pub fn parse_settings(&mut self) -> Settings {
         let parse_result = match self.parse_config_file() {
             Ok(_) => Ok(()),
             Err(err) => match err {
                 ConfigParseError::Ignore => Ok(()),
-                ConfigParseError::InvalidFile(err) => {
-                    Err(format!("Error parsing config file: {}", err))
-                }
+                ConfigParseError::InvalidFile(err) => Err(tr!("error-config-file", error = err)),
                 ConfigParseError::InvalidConfig(field, err) => {
-                    Err(format!("Error parsing config file at '{}': {}", field, err))
+                    Err(tr!("error-config-field", field = field, error = err))
                 }
             },
         };
 
+        // user presets only matter, & are only read, for a named one
+        let presets = match &self.preset {
+            Some(name) => {
+                let presets = self.preset_registry();
+                self.preset_is_user = presets.names().contains(&name.trim());
+                presets
+            }
+            None => PresetRegistry::new(),
+        };
+
         let result = match parse_result {
             Err(message) => Err(message),
-            Ok(_) => match self.build_settings::<B>() {
-                Err(err) => Err(format!("Invalid settings: {}", err)),
+            Ok(_) => match self.build_settings(&presets) {
+                Err(err) => Err(tr!("error-invalid-settings", error = err)),
                 Ok(settings) => Ok(settings),
             },
         };
@@ -140,63 +1071,88 @@ impl Cli {
         match result {
             Ok(settings) => settings,
             Err(message) => {
-                Self::command()
-                    .error(ErrorKind::InvalidValue, message)
-                    .exit();
+                self.fail_args(Failure::InvalidSettings, ErrorKind::InvalidValue, message);
             }
         }
     }
 
-    fn build_settings<B: Builder + Randomizer>(&self) -> Result<B, String> {
-        let mut settings = if let Some(preset) = self.preset {
-            B::from_preset(preset)
-        } else {
-            B::default()
-        };
-
-        settings = settings
-            .with_word_lengths(self.word_length_min, self.word_length_max)?
-            .with_padding_digits(self.padding_digits_before, self.padding_digits_after)
-            .with_padding_symbol_lengths(self.padding_symbols_before, self.padding_symbols_after);
-
-        if let Some(words_count) = self.words_count {
-            settings = settings.with_words_count(words_count)?
-        }
+    pub fn preset_registry(&self) -> PresetRegistry {
+        match self.read_preset_registry() {
+            Ok(registry) => registry,
+            Err(err) => {
+                let message = match err {
+                    ConfigParseError::Ignore => unreachable!("missing presets are not an error"),
+                    ConfigParseError::InvalidFile(err) => {
+                        tr!("error-user-presets", error = err)
+                    }
+                    ConfigParseError::InvalidConfig(name, err) => {
+                        tr!("error-user-preset", name = name, error = err)
+                    }
+                };
 
-        if let Some(word_transforms) = &self.word_transforms {
-            let transforms: FieldSize = word_transforms
-                .iter()
-                .fold(0 as FieldSize, |acc, cur| acc | *cur);
-            settings = settings.with_word_transforms(transforms)?;
+                self.fail_args(Failure::InvalidSettings, ErrorKind::InvalidValue, message);
+            }
         }
+    }
 
-        if let Some(separators) = &self.separators {
-            settings = settings.with_separators(separators);
-        }
+    fn build_settings(&self, presets: &PresetRegistry) -> Result<Settings, String> {
+        let base = match &self.preset {
+            Some(name) => presets.resolve(name)?,
+            None => Settings::default(),
+        };
 
-        if let Some(padding_symbols) = &self.padding_symbols {
-            settings = settings.with_padding_symbols(padding_symbols);
-        }
+        self.settings_patch()?.apply(&base)
+    }
 
-        if let Some(padding) = &self.padding {
-            match padding {
-                CliPadding::Fixed => {
-                    settings = settings.with_padding_strategy(PaddingStrategy::Fixed)?
-                }
-                CliPadding::Adaptive => {
-                    if let Some(adaptive_length) = &self.adaptive_length {
-                        settings = settings
-                            .with_padding_strategy(PaddingStrategy::Adaptive(*adaptive_length))?
-                    } else {
-                        return Err(
-                            "adaptive length is required for adaptive padding strategy".to_string()
-                        );
-                    }
+    fn settings_patch(&self) -> Result<SettingsPatch, String> {
+        let padding_strategy = match self.padding {
+            None => None,
+            Some(CliPadding::Fixed) => Some(PaddingStrategy::Fixed),
+            Some(CliPadding::Adaptive) => match self.adaptive_length {
+                Some(adaptive_length) => Some(PaddingStrategy::Adaptive(adaptive_length)),
+                None => {
+                    return Err(
+                        "adaptive length is required for adaptive padding strategy".to_string()
+                    )
                 }
-            }
-        }
+            },
+        };
 
-        Ok(settings)
+        Ok(SettingsPatch {
+            words_count: self.words_count,
+            word_length_min: self.word_length_min,
+            word_length_max: self.word_length_max,
+            unchecked_word_lengths: self.unchecked_word_lengths.then_some(true),
+            word_transforms: self.word_transforms.as_ref().map(|word_transforms| {
+                word_transforms
+                    .iter()
+                    .fold(0 as FieldSize, |acc, cur| acc | *cur)
+            }),
+            separators: self
+                .separators
+                .clone()
+                .or_else(|| self.symbol_set.map(|set| set.symbols().to_string())),
+            padding_digits_before: self.padding_digits_before,
+            padding_digits_after: self.padding_digits_after,
+            padding_symbols: self
+                .padding_symbols
+                .clone()
+                .or_else(|| self.symbol_set.map(|set| set.symbols().to_string())),
+            padding_symbols_before: self.padding_symbols_before,
+            padding_symbols_after: self.padding_symbols_after,
+            padding_strategy,
+            join_style: self.join_style,
+            // flags can only turn these on
+            pronounceable: self.pronounceable.then_some(true),
+            #[cfg(feature = "extra_entropy")]
+            extra_entropy: None,
+            checksum: self.checksum.then_some(true),
+            avoid_ambiguous: self.avoid_ambiguous.then_some(true),
+            avoid_homophones: self.avoid_homophones.then_some(true),
+            word_theme: self.word_theme.clone(),
+            proper_nouns: self.proper_nouns,
+            spelling: self.spelling,
+        })
     }
 
     #[cfg(test)]
@@ -213,7 +1169,96 @@ impl Cli {
             .verbosity((self.verbosity + 1) as usize)
             .init()
             .unwrap();
+
+        // spans are timed when closed, they carry sizes & counts but never words or passwords
+        #[cfg(feature = "trace")]
+        if self.verbosity >= 2 {
+            tracing_subscriber::fmt()
+                .with_writer(io::stderr)
+                .with_max_level(tracing::Level::DEBUG)
+                .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
+                .init();
+        }
+    }
+}
+
+const RANDOM_PRESET: &str = "random";
+
+// the preset names & aliases, plus one picked at random once parsed
+#[derive(Clone)]
+struct PresetParser;
+
+impl TypedValueParser for PresetParser {
+    type Value = String;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let name = value.to_str();
+
+        if name == Some(RANDOM_PRESET) {
+            return Ok(Preset::random(&mut crate::rng::rng()).name().to_string());
+        }
+
+        // any other name may be a user preset, resolved along with the settings
+        match name.map(str::trim) {
+            Some(name) if !name.is_empty() => Ok(name.to_string()),
+            // reports the invalid value along with possible ones
+            _ => PossibleValuesParser::new(self.possible_values().unwrap())
+                .parse_ref(cmd, arg, value)
+                .map(|_| unreachable!("invalid presets are never possible values")),
+        }
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        let random =
+            PossibleValue::new(RANDOM_PRESET).help("Any of the presets above, picked at random");
+
+        Some(Box::new(
+            Preset::value_variants()
+                .iter()
+                .filter_map(ValueEnum::to_possible_value)
+                .chain([random]),
+        ))
+    }
+}
+
+pub fn read_dict_file(dict_file: &str) -> io::Result<Vec<u8>> {
+    if dict_file == "-" {
+        log::debug!("reading dictionary from stdin");
+        let mut dict_bytes: Vec<u8> = vec![];
+        io::stdin().read_to_end(&mut dict_bytes)?;
+        return Ok(dict_bytes);
+    }
+
+    log::debug!("reading dictionary from {}", dict_file);
+    fs::read(dict_file)
+}
+
+fn read_dice_rolls(dice: &str) -> io::Result<String> {
+    if dice != "-" {
+        return Ok(dice.to_string());
     }
+
+    log::debug!("reading dice rolls from stdin");
+    let mut rolls = String::new();
+    io::stdin().read_to_string(&mut rolls)?;
+    Ok(rolls)
+}
+
+#[cfg(feature = "lang_en")]
+fn lookup_diceware(rolls: &str) -> Result<Vec<&'static str>, String> {
+    use crate::dict::diceware::*;
+
+    DicewareTable::parse(DICEWARE_EN)?.lookup_all(rolls)
+}
+
+#[cfg(not(feature = "lang_en"))]
+fn lookup_diceware(_rolls: &str) -> Result<Vec<&'static str>, String> {
+    Err("diceware table requires the 'lang_en' feature".to_string())
 }
 
 impl ValueEnum for CliPadding {
@@ -233,6 +1278,180 @@ Notes: setting this will disable --symbols-before and --symbols-after options"#,
     }
 }
 
+impl ValueEnum for Uniqueness {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Passwords, Self::Words]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Passwords => PossibleValue::new("passwords").help("No 2 identical passwords"),
+            Self::Words => PossibleValue::new("words")
+                .help("No 2 passwords sharing a word, failing if the dictionary is too small"),
+        })
+    }
+}
+
+impl ValueEnum for CodeAlphabet {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Base32, Self::Digits, Self::Hex, Self::Unambiguous]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Base32 => PossibleValue::new("base32")
+                .help("Uppercase letters & digits without I, L, O & U, e.g. 7KQ2"),
+            Self::Digits => PossibleValue::new("digits").help("Digits only, e.g. 4071"),
+            Self::Hex => PossibleValue::new("hex").help("Lowercase hexadecimal digits, e.g. 9f3a"),
+            Self::Unambiguous => PossibleValue::new("unambiguous")
+                .help("Letters & digits without those easily mistaken, e.g. 7kQm"),
+        })
+    }
+}
+
+#[cfg(feature = "hash")]
+impl ValueEnum for HashAlgorithm {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            #[cfg(feature = "hash_argon2")]
+            Self::Argon2id,
+            #[cfg(feature = "hash_bcrypt")]
+            Self::Bcrypt,
+            #[cfg(feature = "hash_sha512_crypt")]
+            Self::Sha512Crypt,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        // each arm wraps its own value, as there are none without any of the algorithms
+        match *self {
+            #[cfg(feature = "hash_argon2")]
+            Self::Argon2id => {
+                Some(PossibleValue::new("argon2id").help("Argon2id in the PHC format"))
+            }
+            #[cfg(feature = "hash_bcrypt")]
+            Self::Bcrypt => Some(
+                PossibleValue::new("bcrypt")
+                    .help("bcrypt with a cost of 12, for passwords up to 72 bytes"),
+            ),
+            #[cfg(feature = "hash_sha512_crypt")]
+            Self::Sha512Crypt => Some(
+                PossibleValue::new("sha512-crypt")
+                    .help("SHA-512 crypt with 5000 rounds, the $6$ of /etc/shadow"),
+            ),
+        }
+    }
+}
+
+#[cfg(credential_store)]
+impl ValueEnum for Store {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            #[cfg(all(windows, feature = "wincred"))]
+            Self::Wincred,
+            #[cfg(all(target_os = "macos", feature = "keychain"))]
+            Self::Keychain,
+            #[cfg(all(target_os = "linux", feature = "secret_service"))]
+            Self::SecretService,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match *self {
+            #[cfg(all(windows, feature = "wincred"))]
+            Self::Wincred => {
+                PossibleValue::new("wincred").help("Windows Credential Manager, named by --name")
+            }
+            #[cfg(all(target_os = "macos", feature = "keychain"))]
+            Self::Keychain => PossibleValue::new("keychain")
+                .help("macOS Keychain, as a password for --service & --account"),
+            #[cfg(all(target_os = "linux", feature = "secret_service"))]
+            Self::SecretService => PossibleValue::new("secret-service")
+                .help("GNOME Keyring, KWallet or any Secret Service, for --service & --account"),
+        })
+    }
+}
+
+impl ValueEnum for KeyboardLayout {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Desktop, Self::Mobile]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Desktop => PossibleValue::new("desktop").help("A US QWERTY keyboard"),
+            Self::Mobile => PossibleValue::new("mobile").help("A phone's on-screen keyboard"),
+        })
+    }
+}
+
+impl ValueEnum for JoinStyle {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Separated, Self::Camel]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Separated => {
+                PossibleValue::new("separated").help("Words joined by one of the separators")
+            }
+            Self::Camel => PossibleValue::new("camel").help(
+                "Titlecase all words, e.g. CorrectHorseBatteryStaple when combined with --separators ''",
+            ),
+        })
+    }
+}
+
+impl ValueEnum for ProperNouns {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Include, Self::Exclude, Self::Preserve]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Include => PossibleValue::new("include").help("Transformed like other words"),
+            Self::Exclude => PossibleValue::new("exclude").help("Never picked"),
+            Self::Preserve => PossibleValue::new("preserve")
+                .help("Kept in their canonical capitalization whatever the transforms"),
+        })
+    }
+}
+
+impl ValueEnum for SymbolSet {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::UsShiftless, Self::IntlSafe, Self::AsciiOnlyNoQuotes]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::UsShiftless => PossibleValue::new("us-shiftless")
+                .help("Typed without Shift on a US keyboard, e.g. at BIOS prompts"),
+            Self::IntlSafe => PossibleValue::new("intl-safe")
+                .help("Typed without AltGr or dead keys on most European layouts"),
+            Self::AsciiOnlyNoQuotes => PossibleValue::new("ascii-no-quotes")
+                .help("Printable ASCII without quotes nor backslash"),
+        })
+    }
+}
+
+impl ValueEnum for SpellingVariant {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Any, Self::British, Self::American]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Any => PossibleValue::new("any").help("Either spelling"),
+            Self::British => PossibleValue::new("british")
+                .alias("uk")
+                .help("e.g. colour, centre & organise"),
+            Self::American => PossibleValue::new("american")
+                .alias("us")
+                .help("e.g. color, center & organize"),
+        })
+    }
+}
+
 impl ValueEnum for Preset {
     fn value_variants<'a>() -> &'a [Self] {
         &[
@@ -244,26 +1463,22 @@ impl ValueEnum for Preset {
             Self::Web32,
             Self::Wifi,
             Self::Xkcd,
+            Self::Nist,
+            Self::Temporary,
+            Self::WifiEasyType,
+            Self::Mobile,
+            Self::SshPassphrase,
+            Self::DiskEncryption,
+            Self::DatabaseSafe,
         ]
     }
 
     fn to_possible_value(&self) -> Option<PossibleValue> {
-        Some(match self {
-            Self::Default => PossibleValue::new("default").help("Some sensible default values"),
-            Self::AppleID => PossibleValue::new("apple-id").help("Apple ID passwords"),
-            Self::WindowsNtlmV1 => PossibleValue::new("ntlm").help("Windows NTLM v1"),
-            Self::SecurityQuestions => PossibleValue::new("secq").help("Security questions"),
-            Self::Web16 => {
-                PossibleValue::new("web16").help("Maxium 16 characters for older websites")
-            }
-            Self::Web32 => {
-                PossibleValue::new("web32").help("Maximum 32 characters for modern websites")
-            }
-            Self::Wifi => PossibleValue::new("wifi").help("Fixed 63 characters for Wifi WPA2 keys"),
-            Self::Xkcd => {
-                PossibleValue::new("xkcd").help("As described in the original XKCD comic")
-            }
-        })
+        Some(
+            PossibleValue::new(self.name())
+                .aliases(self.aliases())
+                .help(self.description()),
+        )
     }
 }
 
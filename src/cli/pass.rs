@@ -0,0 +1,80 @@
+use clap::Subcommand;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+#[derive(Subcommand, Clone, Debug, PartialEq, Eq)]
+pub enum PassCommand {
+    #[command(about = "Generate a password and insert it into the store as NAME")]
+    Insert {
+        name: String,
+
+        #[arg(
+            long = "pass-bin",
+            default_value = "pass",
+            help = "Path to the pass binary"
+        )]
+        pass_bin: String,
+
+        #[arg(short = 'f', long = "force", help = "Overwrite an existing entry")]
+        force: bool,
+    },
+}
+
+/// Pipes `passwd` into `pass insert -e`, so it never shows up in process arguments.
+pub fn insert_into_pass(pass_bin: &str, name: &str, passwd: &str, force: bool) -> io::Result<()> {
+    log::debug!("inserting password into {} as {}", pass_bin, name);
+
+    let mut child = Command::new(pass_bin)
+        .args(pass_insert_args(name, force))
+        .stdin(Stdio::piped())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "{}", passwd)?;
+    }
+
+    let status = child.wait()?;
+
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "{} insert exited with {}",
+            pass_bin, status
+        )));
+    }
+
+    Ok(())
+}
+
+fn pass_insert_args(name: &str, force: bool) -> Vec<&str> {
+    let mut args = vec!["insert", "--echo"];
+
+    if force {
+        args.push("--force");
+    }
+
+    args.push(name);
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pass_insert_args() {
+        assert_eq!(
+            vec!["insert", "--echo", "Mail/gmail"],
+            pass_insert_args("Mail/gmail", false)
+        );
+        assert_eq!(
+            vec!["insert", "--echo", "--force", "Mail/gmail"],
+            pass_insert_args("Mail/gmail", true)
+        );
+    }
+
+    #[test]
+    fn test_insert_into_pass_missing_binary() {
+        let err = insert_into_pass("./no-such-pass-bin", "Mail/gmail", "passwd", false);
+        assert_eq!(io::ErrorKind::NotFound, err.unwrap_err().kind());
+    }
+}
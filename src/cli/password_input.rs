@@ -0,0 +1,83 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+#[cfg(unix)]
+use std::process::{Command, Stdio};
+
+/// Reads a password to check from stdin rather than from arguments, which would leave it
+/// in the shell history & the process list. On a terminal, it's prompted for without echo.
+pub fn read_password(prompt: &str) -> io::Result<String> {
+    let stdin = io::stdin();
+
+    if !stdin.is_terminal() {
+        return read_first_line(stdin.lock());
+    }
+
+    eprint!("{} ", prompt);
+    io::stderr().flush()?;
+
+    let echo_off = set_echo(false);
+    let password = read_first_line(stdin.lock());
+
+    if echo_off {
+        set_echo(true);
+        // the newline typed after the password wasn't echoed either
+        eprintln!();
+    }
+
+    password
+}
+
+fn read_first_line(mut input: impl BufRead) -> io::Result<String> {
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+
+    let password = line.strip_suffix('\n').unwrap_or(&line);
+    let password = password.strip_suffix('\r').unwrap_or(password);
+
+    if password.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no password given",
+        ));
+    }
+
+    Ok(password.to_string())
+}
+
+// stty acts on its stdin, the terminal the password is typed on
+#[cfg(unix)]
+fn set_echo(echo: bool) -> bool {
+    Command::new("stty")
+        .arg(if echo { "echo" } else { "-echo" })
+        .stdin(Stdio::inherit())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(not(unix))]
+fn set_echo(_echo: bool) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_first_line() {
+        let table: [(&[u8], Result<&str, io::ErrorKind>); 5] = [
+            (b"correct-horse\n", Ok("correct-horse")),
+            (b"correct-horse\r\nbattery\n", Ok("correct-horse")),
+            (b"correct-horse", Ok("correct-horse")),
+            (b" spaced out \n", Ok(" spaced out ")),
+            (b"\n", Err(io::ErrorKind::InvalidInput)),
+        ];
+
+        for (input, expected) in table {
+            let password = read_first_line(input).map_err(|err| err.kind());
+            assert_eq!(expected.map(String::from), password);
+        }
+
+        let empty: &[u8] = b"";
+        assert!(read_first_line(empty).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use crate::prelude::*;
+use crate::settings::{Settings, Suggestion};
+
+use std::fmt::Write;
+
+pub fn render_presets(pass_generator: &Xkpasswd) -> String {
+    let mut output = String::new();
+
+    for (preset, example) in pass_generator.gen_all_presets() {
+        let settings = preset.example_settings();
+        let entropy = calc_entropy(pass_generator, &settings);
+
+        let name = preset.to_string();
+
+        let blind_entropy = if entropy.blind_min == entropy.blind_max {
+            format!("{} bits", entropy.blind_min)
+        } else {
+            format!("{} - {} bits", entropy.blind_min, entropy.blind_max)
+        };
+
+        if !output.is_empty() {
+            output.push('\n');
+        }
+
+        // writing into a String never fails
+        let _ = writeln!(output, "{}: {}", name, preset.description());
+        let _ = writeln!(output, "  recommended for: {}", preset.recommended_use());
+        let _ = writeln!(output, "  example: {}", example);
+        let _ = writeln!(
+            output,
+            "  entropy: {} blind, {} bits seen",
+            blind_entropy, entropy.seen
+        );
+        let _ = writeln!(
+            output,
+            "  settings:{}",
+            settings.to_string().replace('\n', "\n  ")
+        );
+    }
+
+    output
+}
+
+pub fn render_comparison(pass_generator: &Xkpasswd) -> String {
+    let header = ["PRESET", "LENGTH", "BLIND BITS", "SEEN BITS", "EXAMPLE"];
+    let mut rows: Vec<[String; 5]> = vec![header.map(String::from)];
+
+    for (preset, example) in pass_generator.gen_all_presets() {
+        let settings = preset.example_settings();
+        let entropy = calc_entropy(pass_generator, &settings);
+
+        let (min_length, max_length) = settings.length_range();
+
+        rows.push([
+            preset.to_string(),
+            format_range(min_length, max_length),
+            format_range(entropy.blind_min, entropy.blind_max),
+            entropy.seen.to_string(),
+            example,
+        ]);
+    }
+
+    render_rows(&rows)
+}
+
+pub fn render_suggestions(pass_generator: &Xkpasswd, suggestions: &[Suggestion]) -> String {
+    let header = ["LENGTH", "SEEN BITS", "SETTINGS", "EXAMPLE"];
+    let mut rows: Vec<[String; 4]> = vec![header.map(String::from)];
+
+    for suggestion in suggestions {
+        let (min_length, max_length) = suggestion.length_range;
+        let (example, _) = pass_generator.gen_pass(&suggestion.settings);
+
+        rows.push([
+            format_range(min_length, max_length),
+            suggestion.entropy.seen.to_string(),
+            suggestion.settings.compact().to_string(),
+            example,
+        ]);
+    }
+
+    render_rows(&rows)
+}
+
+// left aligned columns as wide as their widest cell
+fn render_rows<const N: usize>(rows: &[[String; N]]) -> String {
+    let widths: Vec<usize> = (0..N)
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut output = String::new();
+
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+
+        let _ = writeln!(output, "{}", cells.join("  ").trim_end());
+    }
+
+    output
+}
+
+fn calc_entropy(pass_generator: &Xkpasswd, settings: &Settings) -> Entropy {
+    let pool_size = pass_generator.words_pool(settings).len();
+    settings.calc_entropy(pool_size)
+}
+
+fn format_range(min: usize, max: usize) -> String {
+    if min == max {
+        min.to_string()
+    } else {
+        format!("{}-{}", min, max)
+    }
+}
@@ -0,0 +1,102 @@
+use crate::prelude::{Builder, Preset, Randomizer, Xkpasswd};
+use crate::settings::Settings;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+const PROMPT: &str = "xkpasswd> ";
+const HELP: &str = "\
+set <PARAM> [VALUE] change a setting, named like in query strings, e.g. set words 5
+preset <NAME>       start over from a built-in preset
+reset               start over from the default settings
+gen [COUNT]         generate passwords, 1 by default
+entropy             show the entropy of the current settings
+show                show the current settings
+help                show this help
+quit                leave, also on Ctrl-D";
+
+/// An interactive session tweaking one settings object, one command per line.
+pub struct Repl<'a> {
+    pass_generator: &'a Xkpasswd<'a>,
+    settings: Settings,
+}
+
+impl<'a> Repl<'a> {
+    pub fn new(pass_generator: &'a Xkpasswd<'a>, settings: Settings) -> Self {
+        Repl {
+            pass_generator,
+            settings,
+        }
+    }
+
+    /// Runs a single command, returning what to print or `None` to leave the session.
+    pub fn eval(&mut self, line: &str) -> Result<Option<String>, String> {
+        let mut args = line.split_whitespace();
+        let command = args.next().unwrap_or_default();
+        let args: Vec<&str> = args.collect();
+
+        let output = match (command, args.as_slice()) {
+            ("", []) => String::new(),
+            ("quit" | "exit", []) => return Ok(None),
+            ("help", []) => HELP.to_string(),
+            // without a value to clear separators or symbols
+            ("set", [param] | [param, _]) => {
+                let value = args.get(1).copied().unwrap_or_default();
+                self.settings = self.settings.with_query_param(param, value)?;
+                self.settings.compact().to_string()
+            }
+            ("preset", [name]) => {
+                self.settings = Settings::from_preset(Preset::from_str(name)?);
+                self.settings.compact().to_string()
+            }
+            ("reset", []) => {
+                self.settings = Settings::default();
+                self.settings.compact().to_string()
+            }
+            ("gen", [] | [_]) => {
+                let count = match args.first() {
+                    Some(count) => count
+                        .parse()
+                        .map_err(|_| format!("invalid count '{}'", count))?,
+                    None => 1,
+                };
+
+                self.settings.validate_against(self.pass_generator)?;
+                let (passwds, _) = self.pass_generator.gen_passes(&self.settings, count);
+                passwds.join("\n")
+            }
+            ("entropy", []) => {
+                let pool_size = self.pass_generator.words_pool(&self.settings).len();
+                self.settings.calc_entropy(pool_size).to_string()
+            }
+            ("show", []) => format!("{}\n{}", self.settings, self.settings.to_query_string()),
+            _ => return Err(format!("invalid command '{}', see 'help'", line.trim())),
+        };
+
+        Ok(Some(output))
+    }
+
+    /// Reads commands from `input` until it ends or `quit`, errors don't end the session.
+    pub fn run<R: BufRead, W: Write>(&mut self, input: R, output: &mut W) -> io::Result<()> {
+        let mut lines = input.lines();
+
+        loop {
+            write!(output, "{}", PROMPT)?;
+            output.flush()?;
+
+            let line = match lines.next() {
+                Some(line) => line?,
+                None => break,
+            };
+
+            match self.eval(&line) {
+                Ok(None) => break,
+                Ok(Some(text)) if text.is_empty() => {}
+                Ok(Some(text)) => writeln!(output, "{}", text)?,
+                Err(err) => writeln!(output, "error: {}", err)?,
+            }
+        }
+
+        // leaves the shell prompt on its own line after Ctrl-D
+        writeln!(output)
+    }
+}
@@ -0,0 +1,524 @@
+use super::connection::{ConnectionLimit, DeadlineReader, READ_TIMEOUT, WRITE_TIMEOUT};
+use crate::prelude::{Randomizer, Xkpasswd};
+use crate::settings::registry::PresetRegistry;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// generating more per request makes each key's rate limit meaningless
+const MAX_COUNT_PER_REQUEST: usize = 100;
+// requests have no body, anything beyond a few headers is not a genuine client
+const MAX_HEAD_BYTES: u64 = 8 * 1024;
+
+/// API keys allowed to request passwords, each with a name for logs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ApiKeys {
+    names_by_key: HashMap<String, String>,
+}
+
+impl ApiKeys {
+    /// Parses one `name:key` pair per line, blank lines & `#` comments ignored.
+    pub fn parse(keys_str: &str) -> Result<Self, String> {
+        let mut names_by_key: HashMap<String, String> = HashMap::new();
+
+        for (idx, line) in keys_str.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (name, key) = match line.split_once(':') {
+                Some((name, key)) if !name.trim().is_empty() && !key.trim().is_empty() => {
+                    (name.trim(), key.trim())
+                }
+                _ => return Err(format!("expected 'name:key' at line {}", idx + 1)),
+            };
+
+            if names_by_key
+                .insert(key.to_string(), name.to_string())
+                .is_some()
+            {
+                return Err(format!("duplicated key at line {}", idx + 1));
+            }
+        }
+
+        if names_by_key.is_empty() {
+            return Err("no API keys found".to_string());
+        }
+
+        Ok(ApiKeys { names_by_key })
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let keys_str = fs::read_to_string(path).map_err(|err| err.to_string())?;
+        Self::parse(&keys_str)
+    }
+
+    pub fn name(&self, key: &str) -> Option<&str> {
+        self.names_by_key.get(key).map(String::as_str)
+    }
+}
+
+/// Allows bursts of up to `capacity` requests, refilled continuously at `refill_per_sec`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: u32, refill_per_sec: f64, now: Instant) -> Self {
+        TokenBucket {
+            capacity: capacity as f64,
+            refill_per_sec,
+            tokens: capacity as f64,
+            last_refill: now,
+        }
+    }
+
+    /// Takes a token if available, otherwise tells how long until the next one.
+    pub fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = self
+            .capacity
+            .min(self.tokens + elapsed * self.refill_per_sec);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+
+        Err(Duration::from_secs_f64(
+            (1.0 - self.tokens) / self.refill_per_sec,
+        ))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: String,
+}
+
+impl Response {
+    fn new(status: u16, body: &str) -> Self {
+        Response {
+            status,
+            headers: vec![],
+            body: format!("{}\n", body),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            429 => "Too Many Requests",
+            _ => "Internal Server Error",
+        }
+    }
+
+    fn write_to<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        write!(out, "HTTP/1.1 {} {}\r\n", self.status, self.reason())?;
+        write!(out, "Content-Type: text/plain; charset=utf-8\r\n")?;
+        write!(out, "Content-Length: {}\r\n", self.body.len())?;
+        write!(out, "Cache-Control: no-store\r\n")?;
+        write!(out, "Connection: close\r\n")?;
+
+        for (name, value) in &self.headers {
+            write!(out, "{}: {}\r\n", name, value)?;
+        }
+
+        write!(out, "\r\n{}", self.body)
+    }
+}
+
+/// Serves `GET /passwords?count=N&preset=NAME` to clients holding an API key,
+/// each key limited by its own token bucket.
+/// Preset names are resolved by `presets`, requests without one use `settings`.
+pub struct Server<'a, S: Randomizer + Sync> {
+    pass_generator: &'a Xkpasswd<'a>,
+    settings: &'a S,
+    presets: &'a PresetRegistry,
+    api_keys: ApiKeys,
+    burst: u32,
+    refill_per_sec: f64,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl<'a, S: Randomizer + Sync> Server<'a, S> {
+    pub fn new(
+        pass_generator: &'a Xkpasswd<'a>,
+        settings: &'a S,
+        presets: &'a PresetRegistry,
+        api_keys: ApiKeys,
+        burst: u32,
+        per_minute: u32,
+    ) -> Self {
+        Server {
+            pass_generator,
+            settings,
+            presets,
+            api_keys,
+            burst,
+            refill_per_sec: per_minute as f64 / 60.0,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn listen(&self, addr: &str) -> io::Result<()> {
+        let listener = TcpListener::bind(addr)?;
+        log::info!("listening on {}", addr);
+
+        let limit = ConnectionLimit::default();
+
+        // each connection on its own thread, so a stalled client doesn't hold up the others
+        thread::scope(|scope| {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(err) => {
+                        log::warn!("connection failed: {}", err);
+                        continue;
+                    }
+                };
+
+                let Some(slot) = limit.try_acquire() else {
+                    log::warn!("too many connections, dropped one");
+                    continue;
+                };
+
+                scope.spawn(move || {
+                    if let Err(err) = self.serve_connection(stream) {
+                        log::warn!("connection failed: {}", err);
+                    }
+                    drop(slot);
+                });
+            }
+        });
+
+        Ok(())
+    }
+
+    fn serve_connection(&self, mut stream: TcpStream) -> io::Result<()> {
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+
+        let deadline = Instant::now() + READ_TIMEOUT;
+        let mut reader = BufReader::new(
+            DeadlineReader {
+                stream: &stream,
+                deadline,
+            }
+            .take(MAX_HEAD_BYTES),
+        );
+        let mut head: Vec<String> = vec![];
+
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+                break;
+            }
+            head.push(line.trim_end().to_string());
+        }
+
+        let response = self.respond(&head, Instant::now());
+        response.write_to(&mut stream)
+    }
+
+    /// Builds the response for a request, given its request line & headers.
+    #[cfg_attr(feature = "trace", tracing::instrument(level = "debug", skip_all))]
+    pub fn respond(&self, head: &[String], now: Instant) -> Response {
+        let mut request_line = head
+            .first()
+            .map(|line| line.split(' '))
+            .into_iter()
+            .flatten();
+        let (method, target) = (request_line.next(), request_line.next());
+
+        let (path, query) = match target {
+            Some(target) => target.split_once('?').unwrap_or((target, "")),
+            None => return Response::new(400, "malformed request"),
+        };
+
+        if path != "/passwords" {
+            return Response::new(404, "not found");
+        }
+
+        if method != Some("GET") {
+            return Response::new(405, "only GET is allowed");
+        }
+
+        let Some(key) = head.iter().skip(1).find_map(|header| api_key(header)) else {
+            return Response::new(401, "missing API key");
+        };
+
+        let Some(name) = self.api_keys.name(key) else {
+            return Response::new(401, "invalid API key");
+        };
+
+        let count = match parse_count(query) {
+            Ok(count) => count,
+            Err(message) => return Response::new(400, &message),
+        };
+
+        let preset = match query_param(query, "preset")
+            .map(|name| self.presets.resolve(name))
+            .transpose()
+        {
+            Ok(preset) => preset,
+            Err(message) => return Response::new(400, &message),
+        };
+
+        let limited = self
+            .buckets
+            .lock()
+            .expect("rate limiter poisoned")
+            .entry(key.to_string())
+            .or_insert_with(|| TokenBucket::new(self.burst, self.refill_per_sec, now))
+            .try_take(now);
+
+        if let Err(retry_after) = limited {
+            log::info!("rate limited key '{}'", name);
+            let mut response = Response::new(429, "rate limit exceeded");
+            let retry_secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            response
+                .headers
+                .push(("Retry-After", retry_secs.to_string()));
+            return response;
+        }
+
+        log::info!("generating {} password(s) for key '{}'", count, name);
+        let (passwds, _) = match &preset {
+            Some(preset) => self.pass_generator.gen_passes(preset, count),
+            None => self.pass_generator.gen_passes(self.settings, count),
+        };
+        Response::new(200, &passwds.join("\n"))
+    }
+}
+
+// accepts both "Authorization: Bearer <key>" & "X-Api-Key: <key>"
+fn api_key(header: &str) -> Option<&str> {
+    let (name, value) = header.split_once(':')?;
+    let value = value.trim();
+
+    if name.eq_ignore_ascii_case("authorization") {
+        return value.strip_prefix("Bearer ").map(str::trim);
+    }
+
+    if name.eq_ignore_ascii_case("x-api-key") {
+        return Some(value);
+    }
+
+    None
+}
+
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query.split('&').find_map(|param| {
+        param
+            .split_once('=')
+            .filter(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    })
+}
+
+fn parse_count(query: &str) -> Result<usize, String> {
+    let count = query_param(query, "count")
+        .map(|count| {
+            count
+                .parse::<usize>()
+                .map_err(|_| "invalid count".to_string())
+        })
+        .transpose()?
+        .unwrap_or(1);
+
+    if count == 0 || count > MAX_COUNT_PER_REQUEST {
+        return Err(format!("count must be from 1 to {}", MAX_COUNT_PER_REQUEST));
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{Builder, Preset};
+    use crate::settings::Settings;
+
+    #[test]
+    fn test_api_keys() {
+        let keys = ApiKeys::parse("# provisioning\nci:abc123\n\n  laptops : def456 \n").unwrap();
+        assert_eq!(Some("ci"), keys.name("abc123"));
+        assert_eq!(Some("laptops"), keys.name("def456"));
+        assert_eq!(None, keys.name("ci"));
+
+        let expectations = [
+            ("", "no API keys found"),
+            ("# nothing", "no API keys found"),
+            ("abc123", "expected 'name:key' at line 1"),
+            ("ci:abc\nci:", "expected 'name:key' at line 2"),
+            ("ci:abc\nlaptops:abc", "duplicated key at line 2"),
+        ];
+
+        for (keys_str, err) in expectations {
+            assert_eq!(
+                Err(err.to_string()),
+                ApiKeys::parse(keys_str),
+                "{}",
+                keys_str
+            );
+        }
+    }
+
+    #[test]
+    fn test_token_bucket() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(2, 0.5, start);
+
+        assert_eq!(Ok(()), bucket.try_take(start));
+        assert_eq!(Ok(()), bucket.try_take(start));
+        assert_eq!(Err(Duration::from_secs(2)), bucket.try_take(start));
+
+        // half a token refilled after a second
+        let later = start + Duration::from_secs(1);
+        assert_eq!(Err(Duration::from_secs(1)), bucket.try_take(later));
+
+        let later = start + Duration::from_secs(2);
+        assert_eq!(Ok(()), bucket.try_take(later));
+
+        // never refilled above capacity
+        let much_later = start + Duration::from_secs(3600);
+        assert_eq!(Ok(()), bucket.try_take(much_later));
+        assert_eq!(Ok(()), bucket.try_take(much_later));
+        assert!(bucket.try_take(much_later).is_err());
+    }
+
+    fn request(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_respond() {
+        let pass_generator = Xkpasswd::default();
+        let settings = Settings::default();
+        let keys = ApiKeys::parse("ci:abc123\nlaptops:def456").unwrap();
+        let mut presets = PresetRegistry::new();
+        presets
+            .register("corp-standard", Settings::from_preset(Preset::Web32))
+            .unwrap();
+        let server = Server::new(&pass_generator, &settings, &presets, keys, 2, 60);
+        let now = Instant::now();
+
+        let response = server.respond(
+            &request(&[
+                "GET /passwords?count=3 HTTP/1.1",
+                "Authorization: Bearer abc123",
+            ]),
+            now,
+        );
+        assert_eq!(200, response.status);
+        assert_eq!(3, response.body.lines().count());
+
+        let response = server.respond(
+            &request(&["GET /passwords HTTP/1.1", "x-api-key: abc123"]),
+            now,
+        );
+        assert_eq!(200, response.status);
+        assert_eq!(1, response.body.lines().count());
+
+        // burst of 2 used up by key "ci", but not by key "laptops"
+        let response = server.respond(
+            &request(&["GET /passwords HTTP/1.1", "Authorization: Bearer abc123"]),
+            now,
+        );
+        assert_eq!(429, response.status);
+        assert_eq!(vec![("Retry-After", "1".to_string())], response.headers);
+
+        let response = server.respond(
+            &request(&["GET /passwords HTTP/1.1", "X-Api-Key: def456"]),
+            now,
+        );
+        assert_eq!(200, response.status);
+
+        let response = server.respond(
+            &request(&[
+                "GET /passwords?preset=corp-standard HTTP/1.1",
+                "X-Api-Key: def456",
+            ]),
+            now,
+        );
+        assert_eq!(200, response.status);
+        assert!(
+            Settings::from_preset(Preset::Web32).matches(response.body.trim_end(), &pass_generator)
+        );
+
+        let expectations = [
+            (vec!["GET /passwords HTTP/1.1"], 401),
+            (vec!["GET /passwords HTTP/1.1", "X-Api-Key: nope"], 401),
+            (
+                vec!["GET /passwords HTTP/1.1", "Authorization: abc123"],
+                401,
+            ),
+            (vec!["GET / HTTP/1.1", "X-Api-Key: def456"], 404),
+            (vec!["POST /passwords HTTP/1.1", "X-Api-Key: def456"], 405),
+            (
+                vec!["GET /passwords?count=0 HTTP/1.1", "X-Api-Key: def456"],
+                400,
+            ),
+            (
+                vec!["GET /passwords?count=101 HTTP/1.1", "X-Api-Key: def456"],
+                400,
+            ),
+            (
+                vec!["GET /passwords?count=x HTTP/1.1", "X-Api-Key: def456"],
+                400,
+            ),
+            (
+                vec!["GET /passwords?preset=nope HTTP/1.1", "X-Api-Key: def456"],
+                400,
+            ),
+            (vec![], 400),
+        ];
+
+        for (head, status) in expectations {
+            let response = server.respond(&request(&head), now);
+            assert_eq!(status, response.status, "{:?}", head);
+        }
+    }
+
+    #[test]
+    fn test_write_response() {
+        let mut response = Response::new(429, "rate limit exceeded");
+        response.headers.push(("Retry-After", "3".to_string()));
+
+        let mut out: Vec<u8> = vec![];
+        response.write_to(&mut out).unwrap();
+
+        assert_eq!(
+            [
+                "HTTP/1.1 429 Too Many Requests",
+                "Content-Type: text/plain; charset=utf-8",
+                "Content-Length: 20",
+                "Cache-Control: no-store",
+                "Connection: close",
+                "Retry-After: 3",
+                "",
+                "rate limit exceeded\n",
+            ]
+            .join("\r\n"),
+            String::from_utf8(out).unwrap()
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpellMode {
+    Plain,
+}
+
+impl ValueEnum for SpellMode {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Plain]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Plain => PossibleValue::new("plain")
+                .help("Name each character, e.g. 'capital A', 'digit one', 'symbol exclamation'"),
+        })
+    }
+}
+
+pub fn spell_out(passwd: &str, mode: SpellMode) -> String {
+    match mode {
+        SpellMode::Plain => passwd
+            .chars()
+            .enumerate()
+            .map(|(idx, char)| match homoglyph_hint(char) {
+                Some(hint) => format!("{:>3}. {} ({})", idx + 1, spell_char(char), hint),
+                None => format!("{:>3}. {}", idx + 1, spell_char(char)),
+            })
+            .collect::<Vec<String>>()
+            .join("\n"),
+    }
+}
+
+fn spell_char(char: char) -> String {
+    if let Some(digit) = char.to_digit(10) {
+        const DIGITS: [&str; 10] = [
+            "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        return format!("digit {}", DIGITS[digit as usize]);
+    }
+
+    if char.is_uppercase() {
+        return format!("capital {}", char);
+    }
+
+    if char.is_lowercase() {
+        return format!("lowercase {}", char);
+    }
+
+    let name = match char {
+        ' ' => "space",
+        '!' => "exclamation",
+        '"' => "double quote",
+        '#' => "hash",
+        '$' => "dollar",
+        '%' => "percent",
+        '&' => "ampersand",
+        '\'' => "apostrophe",
+        '(' => "open parenthesis",
+        ')' => "close parenthesis",
+        '*' => "asterisk",
+        '+' => "plus",
+        ',' => "comma",
+        '-' => "hyphen",
+        '.' => "period",
+        '/' => "slash",
+        ':' => "colon",
+        ';' => "semicolon",
+        '<' => "less than",
+        '=' => "equals",
+        '>' => "greater than",
+        '?' => "question mark",
+        '@' => "at",
+        '[' => "open bracket",
+        '\\' => "backslash",
+        ']' => "close bracket",
+        '^' => "caret",
+        '_' => "underscore",
+        '`' => "backtick",
+        '{' => "open brace",
+        '|' => "vertical bar",
+        '}' => "close brace",
+        '~' => "tilde",
+        _ => return format!("symbol {} (U+{:04X})", char, char as u32),
+    };
+
+    format!("symbol {}", name)
+}
+
+// characters easily confused with others when read out or written down
+fn homoglyph_hint(char: char) -> Option<&'static str> {
+    match char {
+        'l' => Some("not capital I, digit one or vertical bar"),
+        'I' => Some("not lowercase l, digit one or vertical bar"),
+        '1' => Some("not lowercase l or capital I"),
+        '|' => Some("not lowercase l, capital I or digit one"),
+        'O' => Some("not digit zero"),
+        'o' => Some("not digit zero"),
+        '0' => Some("not capital O or lowercase o"),
+        _ => None,
+    }
+}
@@ -0,0 +1,194 @@
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+pub const SSH_KEYGEN_BIN: &str = "ssh-keygen";
+
+// set for ssh-keygen & inherited by the askpass program, telling this executable to act as one
+pub const SSH_ASKPASS_ENV: &str = "XKPASSWD_SSH_ASKPASS";
+
+/// Creates an Ed25519 key pair at `key_file` & `key_file.pub`, protected by `passphrase`.
+/// ssh-keygen prefers the terminal over stdin, so the passphrase is handed over by `askpass_bin`,
+/// run by ssh-keygen for each prompt & reading it from the stdin it inherits. Either way it goes
+/// through a pipe, never showing up in the process list, and existing keys are never overwritten.
+/// Requires OpenSSH 8.4 or later for `SSH_ASKPASS_REQUIRE=force`.
+pub fn ssh_keygen(
+    ssh_keygen_bin: &str,
+    askpass_bin: &Path,
+    key_file: &str,
+    passphrase: &str,
+) -> io::Result<()> {
+    if Path::new(key_file).exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", key_file),
+        ));
+    }
+
+    log::debug!("creating SSH key pair at {}", key_file);
+
+    let mut child = Command::new(ssh_keygen_bin)
+        .args(["-q", "-t", "ed25519", "-f", key_file])
+        .env("SSH_ASKPASS", askpass_bin)
+        .env("SSH_ASKPASS_REQUIRE", "force")
+        .env(SSH_ASKPASS_ENV, "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // asked twice, the second time to confirm, each by its own askpass process
+    if let Some(mut stdin) = child.stdin.take() {
+        write!(stdin, "{}\n{}\n", passphrase, passphrase)?;
+    }
+
+    let output = child.wait_with_output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "{} exited with {}: {}",
+            ssh_keygen_bin,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(())
+}
+
+/// The askpass side of `ssh_keygen`: prints the next passphrase line from stdin for ssh-keygen.
+pub fn ssh_askpass() -> io::Result<()> {
+    // unbuffered, leaving the confirmation line to the next askpass process
+    let passphrase = read_line(File::open("/dev/stdin")?)?;
+    println!("{}", passphrase);
+    Ok(())
+}
+
+fn read_line(mut reader: impl Read) -> io::Result<String> {
+    let mut line = vec![];
+    let mut byte = [0u8; 1];
+
+    while reader.read(&mut byte)? == 1 && byte[0] != b'\n' {
+        line.push(byte[0]);
+    }
+
+    String::from_utf8(line).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    #[cfg(target_os = "linux")]
+    use std::thread;
+    #[cfg(target_os = "linux")]
+    use std::time::{Duration, Instant};
+
+    const PASSPHRASE: &str = "correct-Horse-battery-Staple-42";
+    const KEY_DIR_ENV: &str = "XKPASSWD_TEST_SSH_KEY_DIR";
+
+    #[test]
+    fn test_read_line() {
+        let mut input: &[u8] = b"first line\nsecond line\n";
+        assert_eq!("first line", read_line(&mut input).unwrap());
+        assert_eq!("second line", read_line(&mut input).unwrap());
+        assert_eq!("", read_line(&mut input).unwrap());
+    }
+
+    #[test]
+    fn test_ssh_keygen_checks() {
+        let askpass = Path::new("./no-such-askpass");
+
+        let err =
+            ssh_keygen("./no-such-ssh-keygen", askpass, "Cargo.toml", "passphrase").unwrap_err();
+        assert_eq!(io::ErrorKind::AlreadyExists, err.kind());
+
+        let err =
+            ssh_keygen("./no-such-ssh-keygen", askpass, "no-such-key", "passphrase").unwrap_err();
+        assert_eq!(io::ErrorKind::NotFound, err.kind());
+    }
+
+    // run by `test_ssh_keygen_with_tty` under a pseudo terminal
+    #[test]
+    #[ignore]
+    fn ssh_keygen_in_tty() {
+        let dir = PathBuf::from(env::var(KEY_DIR_ENV).unwrap());
+        let key_file = dir.join("id_test");
+        ssh_keygen(
+            SSH_KEYGEN_BIN,
+            &dir.join("askpass.sh"),
+            key_file.to_str().unwrap(),
+            PASSPHRASE,
+        )
+        .unwrap();
+    }
+
+    // util-linux flavour of `script`
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_ssh_keygen_with_tty() {
+        let available = |bin: &str, arg: &str| Command::new(bin).arg(arg).output().is_ok();
+        if !available("script", "-V") || !available(SSH_KEYGEN_BIN, "-?") {
+            eprintln!("script or ssh-keygen not found, skipped");
+            return;
+        }
+
+        let dir = env::temp_dir().join(format!("xkpasswd-ssh-keygen-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        // standing in for this executable, which the test harness can't act as
+        let askpass = dir.join("askpass.sh");
+        fs::write(
+            &askpass,
+            "#!/bin/sh\nIFS= read -r line\nprintf '%s\\n' \"$line\"\n",
+        )
+        .unwrap();
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&askpass, fs::Permissions::from_mode(0o700)).unwrap();
+        }
+
+        // a terminal on stdin, which ssh-keygen would otherwise wait on for typed input
+        let test_command = format!(
+            "{} --exact --ignored cli::ssh_keygen::tests::ssh_keygen_in_tty",
+            env::current_exe().unwrap().display()
+        );
+        let mut child = Command::new("script")
+            .args(["-qec", &test_command, "/dev/null"])
+            .env(KEY_DIR_ENV, &dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(20);
+        let status = loop {
+            if let Some(status) = child.try_wait().unwrap() {
+                break Some(status);
+            }
+            if Instant::now() > deadline {
+                child.kill().unwrap();
+                break None;
+            }
+            thread::sleep(Duration::from_millis(50));
+        };
+
+        let key_file = dir.join("id_test");
+        let decrypted = Command::new(SSH_KEYGEN_BIN)
+            .args(["-y", "-P", PASSPHRASE, "-f"])
+            .arg(&key_file)
+            .output()
+            .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(
+            status.is_some_and(|status| status.success()),
+            "{:?}",
+            status
+        );
+        assert!(decrypted.status.success(), "{:?}", decrypted);
+    }
+}
@@ -0,0 +1,177 @@
+use std::io;
+
+/// Credential stores of the OS to save a generated password into instead of printing it,
+/// each behind its own feature & only on its platform.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Store {
+    #[cfg(all(windows, feature = "wincred"))]
+    Wincred,
+    #[cfg(all(target_os = "macos", feature = "keychain"))]
+    Keychain,
+    #[cfg(all(target_os = "linux", feature = "secret_service"))]
+    SecretService,
+}
+
+/// Where the password goes in its store, from `--name`, `--service` & `--account`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StoreEntry {
+    #[cfg(all(windows, feature = "wincred"))]
+    pub name: String,
+    #[cfg(any(
+        all(target_os = "macos", feature = "keychain"),
+        all(target_os = "linux", feature = "secret_service")
+    ))]
+    pub service: String,
+    #[cfg(any(
+        all(target_os = "macos", feature = "keychain"),
+        all(target_os = "linux", feature = "secret_service")
+    ))]
+    pub account: String,
+}
+
+impl Store {
+    pub fn save(&self, entry: &StoreEntry, passwd: &str) -> io::Result<()> {
+        match *self {
+            #[cfg(all(windows, feature = "wincred"))]
+            Self::Wincred => wincred::write(&entry.name, passwd),
+            // a generic password item, created or updated in the default keychain
+            #[cfg(all(target_os = "macos", feature = "keychain"))]
+            Self::Keychain => {
+                log::debug!(
+                    "saving password into the keychain for {} as {}",
+                    entry.service,
+                    entry.account
+                );
+                security_framework::passwords::set_generic_password(
+                    &entry.service,
+                    &entry.account,
+                    passwd.as_bytes(),
+                )
+                .map_err(|err| io::Error::other(err.to_string()))
+            }
+            #[cfg(all(target_os = "linux", feature = "secret_service"))]
+            Self::SecretService => secret_service::write(&entry.service, &entry.account, passwd)
+                .map_err(|err| io::Error::other(err.to_string())),
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "wincred"))]
+mod wincred {
+    use std::io;
+    use windows_sys::Win32::Security::Credentials::{
+        CredWriteW, CREDENTIALW, CRED_PERSIST_LOCAL_MACHINE, CRED_TYPE_GENERIC,
+    };
+
+    /// Writes a generic credential named `target`, replacing any existing one,
+    /// like `cmdkey /generic:<target>` but without the password in process arguments.
+    pub fn write(target: &str, passwd: &str) -> io::Result<()> {
+        log::debug!(
+            "writing password into the Windows Credential Manager as {}",
+            target
+        );
+
+        let mut target_name = wide_str(target);
+        let mut blob = credential_blob(passwd);
+
+        let credential = CREDENTIALW {
+            Type: CRED_TYPE_GENERIC,
+            TargetName: target_name.as_mut_ptr(),
+            CredentialBlobSize: blob.len() as u32,
+            CredentialBlob: blob.as_mut_ptr(),
+            Persist: CRED_PERSIST_LOCAL_MACHINE,
+            ..Default::default()
+        };
+
+        // SAFETY: all pointers outlive the call, CredWriteW copies what it keeps
+        let written = unsafe { CredWriteW(&credential, 0) };
+        blob.fill(0);
+
+        if written == 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    fn wide_str(text: &str) -> Vec<u16> {
+        text.encode_utf16().chain([0]).collect()
+    }
+
+    // UTF-16 without terminator, as read back by Credential Manager & PowerShell modules
+    fn credential_blob(passwd: &str) -> Vec<u8> {
+        passwd.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_credential_blob() {
+            assert_eq!(
+                vec![b'a', 0, b'B', 0, b'1', 0, 0xe9, 0],
+                credential_blob("aB1é")
+            );
+        }
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "secret_service"))]
+mod secret_service {
+    use ::secret_service::blocking::SecretService;
+    use ::secret_service::{EncryptionType, Error};
+    use std::collections::HashMap;
+
+    /// Saves an item looked up by its `service` & `account` attributes into the default
+    /// collection, e.g. the login keyring of GNOME Keyring or KWallet, replacing any existing one.
+    /// The collection is unlocked first if needed, which may prompt for its password.
+    pub fn write(service: &str, account: &str, passwd: &str) -> Result<(), Error> {
+        log::debug!(
+            "saving password into the Secret Service for {} as {}",
+            service,
+            account
+        );
+
+        // the password is encrypted on the bus, not only by the keyring daemon
+        let secret_service = SecretService::connect(EncryptionType::Dh)?;
+        let collection = secret_service.get_default_collection()?;
+
+        if collection.is_locked()? {
+            collection.unlock()?;
+        }
+
+        collection.create_item(
+            &item_label(service, account),
+            item_attributes(service, account),
+            passwd.as_bytes(),
+            true,
+            "text/plain",
+        )?;
+
+        Ok(())
+    }
+
+    // shown by Seahorse & KWallet Manager, searchable with `secret-tool search service <svc>`
+    fn item_label(service: &str, account: &str) -> String {
+        format!("{} ({})", service, account)
+    }
+
+    fn item_attributes<'a>(service: &'a str, account: &'a str) -> HashMap<&'a str, &'a str> {
+        HashMap::from([("service", service), ("account", account)])
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_item() {
+            assert_eq!("example.com (alice)", item_label("example.com", "alice"));
+            assert_eq!(
+                HashMap::from([("service", "example.com"), ("account", "alice")]),
+                item_attributes("example.com", "alice")
+            );
+        }
+    }
+}
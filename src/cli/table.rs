@@ -0,0 +1,72 @@
+use crate::prelude::Entropy;
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableFormat {
+    Csv,
+    Tsv,
+}
+
+impl TableFormat {
+    fn delimiter(&self) -> char {
+        match self {
+            Self::Csv => ',',
+            Self::Tsv => '\t',
+        }
+    }
+}
+
+impl ValueEnum for TableFormat {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Csv, Self::Tsv]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Csv => PossibleValue::new("csv").help("Comma separated values"),
+            Self::Tsv => PossibleValue::new("tsv").help("Tab separated values"),
+        })
+    }
+}
+
+/// One row per password with its index, length & entropy bits (with full knowledge of settings),
+/// headed by column names, for spreadsheets to ingest directly. Passwords starting like a formula,
+/// e.g. `=` or `@`, are prefixed with a single quote so spreadsheets never evaluate them.
+pub fn render_table(format: TableFormat, passwds: &[String], entropy: &Entropy) -> String {
+    let delimiter = format.delimiter();
+    let mut rows =
+        vec![["index", "password", "length", "entropy_bits"].join(&delimiter.to_string())];
+
+    for (idx, passwd) in passwds.iter().enumerate() {
+        let cells = [
+            (idx + 1).to_string(),
+            quote_cell(passwd, delimiter),
+            passwd.chars().count().to_string(),
+            entropy.seen.to_string(),
+        ];
+
+        rows.push(cells.join(&delimiter.to_string()));
+    }
+
+    rows.join("\n") + "\n"
+}
+
+// spreadsheets evaluate cells starting like these as formulas, e.g. `=HYPERLINK(...)`
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+// quoted only when needed, with embedded quotes doubled as in RFC 4180,
+// & prefixed with a single quote when starting like a formula, which isn't part of the password
+fn quote_cell(value: &str, delimiter: char) -> String {
+    let value = if value.starts_with(FORMULA_PREFIXES) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if value.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
@@ -1,23 +1,83 @@
 use super::*;
+use crate::settings::lint::Warning;
 use crate::settings::*;
 
 const DEFAULT_CLI: Cli = Cli {
+    command: None,
     words_count: None,
+    min_entropy: None,
     word_length_min: None,
     word_length_max: None,
+    unchecked_word_lengths: false,
     word_transforms: None,
     separators: None,
     padding_digits_before: None,
     padding_digits_after: None,
     padding_symbols: None,
+    symbol_set: None,
     padding_symbols_before: None,
     padding_symbols_after: None,
     padding: None,
+    join_style: None,
+    pronounceable: false,
+    checksum: false,
+    avoid_ambiguous: false,
+    avoid_homophones: false,
+    word_theme: None,
+    proper_nouns: None,
+    spelling: None,
     adaptive_length: None,
     preset: None,
+    preset_is_user: false,
     verbosity: 0,
+    errors: ErrorFormat::Text,
     language: None,
     config_file: None,
+    dict_file: None,
+    user_preset: None,
+    no_user_config: false,
+    syllables: false,
+    dice: None,
+    count: 1,
+    usernames_file: None,
+    unique: None,
+    minimize_typing: None,
+    typing_candidates: 16,
+    export: None,
+    table_format: None,
+    spell: None,
+    color: ColorChoice::Auto,
+    porcelain: false,
+    print0: false,
+    conceal: false,
+    #[cfg(feature = "hash")]
+    hash: None,
+    #[cfg(feature = "hash")]
+    hash_only: false,
+    #[cfg(feature = "encrypt")]
+    encrypt_to: vec![],
+    #[cfg(feature = "json")]
+    stdin_jsonl: false,
+    #[cfg(feature = "clipboard")]
+    copy: false,
+    #[cfg(feature = "clipboard")]
+    clear_after: None,
+    #[cfg(all(unix, feature = "ssh_keygen"))]
+    ssh_keygen: None,
+    #[cfg(credential_store)]
+    store: None,
+    #[cfg(all(windows, feature = "wincred"))]
+    store_name: None,
+    #[cfg(any(
+        all(target_os = "macos", feature = "keychain"),
+        all(target_os = "linux", feature = "secret_service")
+    ))]
+    store_service: None,
+    #[cfg(any(
+        all(target_os = "macos", feature = "keychain"),
+        all(target_os = "linux", feature = "secret_service")
+    ))]
+    store_account: None,
 };
 
 #[test]
@@ -27,12 +87,12 @@ fn test_build_settings_panic() {
         words_count: Some(0),
         ..DEFAULT_CLI
     };
-    let _: Settings = cli.build_settings().unwrap();
+    let _: Settings = cli.build_settings(&PresetRegistry::new()).unwrap();
 }
 
 #[test]
 fn test_build_settings_default() {
-    let settings: Settings = DEFAULT_CLI.build_settings().unwrap();
+    let settings: Settings = DEFAULT_CLI.build_settings(&PresetRegistry::new()).unwrap();
     assert_eq!(Settings::default(), settings);
 }
 
@@ -47,15 +107,22 @@ fn test_build_settings_from_preset() {
         Preset::Web32,
         Preset::Wifi,
         Preset::Xkcd,
+        Preset::Nist,
+        Preset::Temporary,
+        Preset::WifiEasyType,
+        Preset::Mobile,
+        Preset::SshPassphrase,
+        Preset::DiskEncryption,
+        Preset::DatabaseSafe,
     ];
 
     for preset in presets {
         let cli = Cli {
-            preset: Some(preset),
+            preset: Some(preset.name().to_string()),
             ..DEFAULT_CLI
         };
 
-        let settings: Settings = cli.build_settings().unwrap();
+        let settings: Settings = cli.build_settings(&PresetRegistry::new()).unwrap();
         assert_eq!(Settings::from_preset(preset), settings);
     }
 }
@@ -95,5 +162,1298 @@ fn test_build_settings_custom() {
         .with_padding_strategy(PaddingStrategy::Adaptive(17))
         .unwrap();
 
-    assert_eq!(expected_settings, cli.build_settings::<Settings>().unwrap());
+    assert_eq!(
+        expected_settings,
+        cli.build_settings(&PresetRegistry::new()).unwrap()
+    );
+}
+
+#[test]
+fn test_read_dict_file() {
+    let path = std::env::temp_dir().join(format!("xkpasswd-cli-dict-{}.txt", std::process::id()));
+    fs::write(&path, "foo\nbar\n").unwrap();
+
+    let dict_bytes = read_dict_file(path.to_str().unwrap()).unwrap();
+    assert_eq!("foo\nbar\n".as_bytes(), dict_bytes);
+
+    fs::remove_file(&path).unwrap();
+    assert!(read_dict_file(path.to_str().unwrap()).is_err());
+}
+
+#[test]
+fn test_parse_presets_subcommand() {
+    let cli = Cli::try_parse_from(["xkpasswd", "presets", "--lang", "fr"]).unwrap();
+    assert_eq!(Some(&Command::Presets), cli.subcommand());
+    assert_eq!(Language::French, cli.language());
+
+    let cli = Cli::try_parse_from(["xkpasswd", "-n", "3"]).unwrap();
+    assert_eq!(None, cli.subcommand());
+}
+
+#[test]
+fn test_dict_lint_subcommand() {
+    let cli =
+        Cli::try_parse_from(["xkpasswd", "dict", "lint", "words.txt", "-o", "clean.txt"]).unwrap();
+    assert_eq!(
+        Some(&Command::Dict {
+            action: DictCommand::Lint {
+                file: "words.txt".to_string(),
+                output: Some("clean.txt".to_string()),
+            }
+        }),
+        cli.subcommand()
+    );
+
+    let lint = crate::dict::lint::lint_dict(b"3:foo,ba\n3:Foo");
+    assert_eq!(
+        [
+            "line 1: 'ba' is listed with length 3 but has 2 chars",
+            "line 2: 'Foo' duplicates line 1",
+            "2 issue(s), 2 clean word(s)",
+            "",
+        ]
+        .join("\n"),
+        render_dict_lint(&lint)
+    );
+
+    #[cfg(feature = "json")]
+    {
+        let lint = crate::dict::lint::lint_dict(br#"["foo", "b4r"]"#);
+        assert_eq!(
+            "entry 2: 'b4r' has non-alphabetic characters\n1 issue(s), 1 clean word(s)\n",
+            render_dict_lint(&lint)
+        );
+    }
+}
+
+#[test]
+fn test_render_presets() {
+    let output = render_presets(&Xkpasswd::default());
+
+    for preset in Preset::value_variants() {
+        let name = preset.to_possible_value().unwrap().get_name().to_string();
+        assert!(output.contains(&format!("{}: ", name)), "missing {}", name);
+    }
+
+    let count = Preset::value_variants().len();
+    assert_eq!(count, output.matches("  example: ").count());
+    assert_eq!(count, output.matches("  entropy: ").count());
+    assert_eq!(count, output.matches("  settings:").count());
+}
+
+#[test]
+fn test_parse_verify_subcommand() {
+    let cli = Cli::try_parse_from(["xkpasswd", "verify", "-P", "xkcd"]).unwrap();
+    assert_eq!(Some(&Command::Verify), cli.subcommand());
+
+    let settings: Settings = cli.build_settings(&PresetRegistry::new()).unwrap();
+    assert_eq!(Settings::from_preset(Preset::Xkcd), settings);
+
+    // read from stdin, never from arguments ending up in the shell history
+    assert!(Cli::try_parse_from(["xkpasswd", "verify", "foo-bar-baz"]).is_err());
+}
+
+#[test]
+fn test_parse_analyze_subcommand() {
+    let cli = Cli::try_parse_from(["xkpasswd", "analyze"]).unwrap();
+    assert_eq!(Some(&Command::Analyze), cli.subcommand());
+
+    // read from stdin, never from arguments ending up in the shell history
+    assert!(Cli::try_parse_from(["xkpasswd", "analyze", "Tr0ub4dor&3"]).is_err());
+}
+
+#[test]
+fn test_render_comparison() {
+    let output = render_comparison(&Xkpasswd::default());
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(Preset::value_variants().len() + 1, lines.len());
+    assert!(lines[0].starts_with("PRESET"));
+
+    for (preset, line) in Preset::value_variants().iter().zip(&lines[1..]) {
+        let name = preset.to_possible_value().unwrap().get_name().to_string();
+        assert!(line.starts_with(&name), "{}", line);
+    }
+
+    assert!(lines[1..].iter().any(|line| line.contains("  14  ")));
+}
+
+#[test]
+fn test_codename() {
+    let cli = Cli::try_parse_from(["xkpasswd", "codename"]).unwrap();
+    assert_eq!(
+        Some(&Command::Codename {
+            themes: vec!["adjectives".to_string(), "nouns".to_string()],
+            count: 1
+        }),
+        cli.subcommand()
+    );
+
+    let cli = Cli::try_parse_from([
+        "xkpasswd",
+        "codename",
+        "--themes",
+        "colors,animals",
+        "-n",
+        "5",
+    ])
+    .unwrap();
+    assert_eq!(
+        Some(&Command::Codename {
+            themes: vec!["colors".to_string(), "animals".to_string()],
+            count: 5
+        }),
+        cli.subcommand()
+    );
+}
+
+#[test]
+fn test_pin() {
+    let cli = Cli::try_parse_from(["xkpasswd", "pin"]).unwrap();
+    assert_eq!(
+        Some(&Command::Pin {
+            digits: 6,
+            count: 1
+        }),
+        cli.subcommand()
+    );
+
+    let cli = Cli::try_parse_from(["xkpasswd", "pin", "--digits", "4", "-n", "3"]).unwrap();
+    assert_eq!(
+        Some(&Command::Pin {
+            digits: 4,
+            count: 3
+        }),
+        cli.subcommand()
+    );
+}
+
+#[test]
+fn test_recovery() {
+    let cli = Cli::try_parse_from(["xkpasswd", "recovery"]).unwrap();
+    assert!(matches!(
+        cli.subcommand(),
+        Some(Command::Recovery { count: 10, .. })
+    ));
+    assert_eq!(Some(Ok(CodeFormat::default())), cli.code_format());
+
+    let cli = Cli::try_parse_from([
+        "xkpasswd",
+        "recovery",
+        "-n",
+        "4",
+        "--alphabet",
+        "digits",
+        "--groups",
+        "2",
+        "--group-length",
+        "5",
+        "--separator",
+        " ",
+    ])
+    .unwrap();
+    let expected = CodeFormat::default()
+        .with_alphabet(CodeAlphabet::Digits)
+        .with_groups(2, 5)
+        .unwrap()
+        .with_separator(" ");
+    assert_eq!(Some(Ok(expected)), cli.code_format());
+
+    let cli = Cli::try_parse_from(["xkpasswd", "recovery", "--chars", "ABC"]).unwrap();
+    assert_eq!(
+        Some(CodeFormat::default().with_chars("ABC")),
+        cli.code_format()
+    );
+
+    let cli = Cli::try_parse_from(["xkpasswd", "recovery", "--groups", "0"]).unwrap();
+    assert!(matches!(cli.code_format(), Some(Err(_))));
+
+    assert!(
+        Cli::try_parse_from(["xkpasswd", "recovery", "--chars", "ab", "--alphabet", "hex"])
+            .is_err()
+    );
+    assert_eq!(
+        None,
+        Cli::try_parse_from(["xkpasswd"]).unwrap().code_format()
+    );
+}
+
+#[test]
+fn test_suggest() {
+    let cli = Cli::try_parse_from([
+        "xkpasswd",
+        "suggest",
+        "--max-length",
+        "20",
+        "--no-symbols",
+        "--min-entropy",
+        "50",
+    ])
+    .unwrap();
+    assert_eq!(
+        Some(&Command::Suggest {
+            max_length: Some(20),
+            no_symbols: true,
+            no_digits: false,
+            limit: 5,
+        }),
+        cli.subcommand()
+    );
+    assert_eq!(Some(50), cli.min_entropy());
+
+    let pass_generator = Xkpasswd::default();
+    let suggestions = SettingsSolver::default()
+        .with_max_length(24)
+        .solve(&pass_generator, 2)
+        .unwrap();
+
+    let output = render_suggestions(&pass_generator, &suggestions);
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(3, lines.len());
+    assert!(lines[0].starts_with("LENGTH"));
+
+    for (suggestion, line) in suggestions.iter().zip(&lines[1..]) {
+        assert!(
+            line.contains(&suggestion.settings.compact().to_string()),
+            "{}",
+            line
+        );
+    }
+}
+
+#[test]
+fn test_repl() {
+    let cli = Cli::try_parse_from(["xkpasswd", "repl", "--preset", "xkcd"]).unwrap();
+    assert_eq!(Some(&Command::Repl), cli.subcommand());
+
+    let pass_generator = Xkpasswd::default();
+    let mut repl = Repl::new(&pass_generator, Settings::default());
+
+    let xkcd = Settings::from_preset(Preset::Xkcd);
+    assert_eq!(
+        Ok(Some(xkcd.compact().to_string())),
+        repl.eval("preset xkcd")
+    );
+
+    let five_words = xkcd.with_words_count(5).unwrap();
+    assert_eq!(
+        Ok(Some(five_words.compact().to_string())),
+        repl.eval("  set words 5 ")
+    );
+
+    let passwds = repl.eval("gen 3").unwrap().unwrap();
+    assert_eq!(3, passwds.lines().count());
+    for passwd in passwds.lines() {
+        assert_eq!(5, passwd.split('-').count(), "{}", passwd);
+    }
+
+    let pool_size = pass_generator.words_pool(&five_words).len();
+    assert_eq!(
+        Ok(Some(five_words.calc_entropy(pool_size).to_string())),
+        repl.eval("entropy")
+    );
+
+    let expectations = [
+        ("bogus", "invalid command 'bogus', see 'help'"),
+        ("gen many", "invalid count 'many'"),
+        ("set words", "invalid value '' for 'words'"),
+        ("preset unknown", "unknown preset 'unknown'"),
+    ];
+    for (line, err) in expectations {
+        assert_eq!(Err(err.to_string()), repl.eval(line), "{}", line);
+    }
+
+    assert_eq!(Ok(Some(String::new())), repl.eval(""));
+    assert_eq!(Ok(None), repl.eval("quit"));
+
+    // errors don't end the session, nor does a missing quit
+    let mut output: Vec<u8> = vec![];
+    repl.run("reset\nbogus\nshow".as_bytes(), &mut output)
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.starts_with(&format!(
+        "xkpasswd> {}\nxkpasswd> error: invalid command 'bogus', see 'help'\n",
+        Settings::default().compact()
+    )));
+    assert!(output.ends_with(&format!(
+        "{}\nxkpasswd> \n",
+        Settings::default().to_query_string()
+    )));
+}
+
+#[test]
+fn test_conceal() {
+    let cli = Cli::try_parse_from(["xkpasswd", "--conceal", "-n", "2"]).unwrap();
+    assert!(cli.conceal);
+    assert!(Cli::try_parse_from(["xkpasswd", "--conceal", "--porcelain"]).is_err());
+
+    let lines = ["foo-bar".to_string(), "baz-qux".to_string()];
+    let mut output = Vec::new();
+    conceal(&lines, "\n".as_bytes(), &mut output).unwrap();
+    assert_eq!(
+        "\x1b[?1049h\x1b[H\x1b[2Jfoo-bar\nbaz-qux\nPress Enter to hide...\x1b[?1049l",
+        String::from_utf8(output).unwrap()
+    );
+
+    // lines longer than the terminal is wide are wrapped, & still go away with the alternate screen
+    let lines = ["correct-horse-battery-staple-".repeat(10)];
+    let mut output = Vec::new();
+    conceal(&lines, "".as_bytes(), &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with("\x1b[?1049h"), "{:?}", output);
+    assert!(output.ends_with("\x1b[?1049l"), "{:?}", output);
+}
+
+#[test]
+fn test_messages() {
+    assert_eq!(Locale::Vietnamese, Locale::from_tag("vi_VN.UTF-8"));
+    assert_eq!(Locale::Vietnamese, Locale::from_tag("VI-vn"));
+    assert_eq!(Locale::English, Locale::from_tag("en_US.UTF-8"));
+    assert_eq!(Locale::English, Locale::from_tag("C"));
+
+    let english = Messages::new(Locale::English);
+    let vietnamese = Messages::new(Locale::Vietnamese);
+
+    let keys = include_str!("../assets/i18n/en.ftl")
+        .lines()
+        .filter_map(|line| line.split_once(" = "))
+        .map(|(key, _)| key);
+
+    for key in keys {
+        assert_ne!(
+            english.format(key, None),
+            vietnamese.format(key, None),
+            "{}",
+            key
+        );
+    }
+
+    // the same as the library's own English messages
+    let warnings = [
+        Warning::SingleWord,
+        Warning::NoSeparation,
+        Warning::LowEntropy(32),
+        Warning::TooManySymbols(12),
+        Warning::UncheckedWordLengths(2, 12),
+        Warning::LayoutDependentSymbols("!-".to_string()),
+    ];
+
+    for warning in warnings {
+        assert_eq!(format!("warning: {}", warning), english.warning(&warning));
+    }
+
+    assert_eq!(
+        "cảnh báo: entropy 32 bit khi biết rõ cài đặt thấp hơn mức khuyến nghị 40 bit",
+        vietnamese.warning(&Warning::LowEntropy(32))
+    );
+
+    let help = |messages: &Messages| {
+        let command = messages.localize_command(Cli::command());
+        let count = command.get_arguments().find(|arg| arg.get_id() == "count");
+        count
+            .and_then(|arg| arg.get_help())
+            .map(|help| help.to_string())
+    };
+
+    assert_eq!(
+        Some("Number of passwords to generate".to_string()),
+        help(&english)
+    );
+    assert_eq!(Some("Số mật khẩu cần tạo".to_string()), help(&vietnamese));
+}
+
+#[test]
+fn test_build_settings_join_style() {
+    let cli = Cli::try_parse_from(["xkpasswd", "--join", "camel", "--separators", ""]).unwrap();
+    let settings: Settings = cli.build_settings(&PresetRegistry::new()).unwrap();
+
+    assert_eq!(
+        Settings::default()
+            .with_separators("")
+            .with_join_style(JoinStyle::Camel),
+        settings
+    );
+}
+
+#[test]
+fn test_spell_out() {
+    assert_eq!("", spell_out("", SpellMode::Plain));
+
+    assert_eq!(
+        [
+            "  1. capital A",
+            "  2. lowercase l (not capital I, digit one or vertical bar)",
+            "  3. digit one (not lowercase l or capital I)",
+            "  4. symbol exclamation",
+            "  5. lowercase é",
+            "  6. digit zero (not capital O or lowercase o)",
+            "  7. symbol space",
+            "  8. symbol 🔑 (U+1F511)",
+        ]
+        .join("\n"),
+        spell_out("Al1!é0 🔑", SpellMode::Plain)
+    );
+}
+
+#[cfg(feature = "lang_en")]
+#[test]
+fn test_colorize() {
+    let pass_generator = Xkpasswd::default();
+    let analysis = pass_generator.analyze("!!12-correct-Horse-battery-34!!");
+
+    assert_eq!(
+        [
+            "\x1b[35m!!\x1b[0m",
+            "\x1b[33m12\x1b[0m",
+            "\x1b[2m-\x1b[0m",
+            "\x1b[32mcorrect\x1b[0m",
+            "\x1b[2m-\x1b[0m",
+            "\x1b[34mHorse\x1b[0m",
+            "\x1b[2m-\x1b[0m",
+            "\x1b[32mbattery\x1b[0m",
+            "\x1b[2m-\x1b[0m",
+            "\x1b[33m34\x1b[0m",
+            "\x1b[35m!!\x1b[0m",
+        ]
+        .join(""),
+        colorize(&analysis)
+    );
+
+    assert!(!ColorChoice::Never.enabled());
+    assert!(ColorChoice::Always.enabled());
+}
+
+#[test]
+fn test_porcelain() {
+    let cli = Cli::try_parse_from(["xkpasswd", "--porcelain", "-n", "3"]).unwrap();
+    assert!(!cli.color());
+    assert_eq!(None, cli.spell());
+    assert_eq!(None, cli.export());
+    assert_eq!(None, cli.table_format());
+
+    for flags in [
+        ["--spell", "plain"],
+        ["--export", "keepass-csv"],
+        ["--format", "csv"],
+        ["--color", "always"],
+    ] {
+        let err = Cli::try_parse_from(["xkpasswd", "--porcelain", flags[0], flags[1]]).unwrap_err();
+        assert_eq!(ErrorKind::ArgumentConflict, err.kind(), "{:?}", flags);
+    }
+
+    #[cfg(feature = "hash_sha512_crypt")]
+    {
+        let args = ["xkpasswd", "--porcelain", "--hash", "sha512-crypt"];
+        let err = Cli::try_parse_from(args).unwrap_err();
+        assert_eq!(ErrorKind::ArgumentConflict, err.kind());
+    }
+
+    let parse = |args: &[&str]| {
+        let mut command = Cli::command();
+        let matches = command.try_get_matches_from_mut(args)?;
+        Cli::from_matches(&mut command, &matches)
+    };
+    assert!(parse(&["xkpasswd", "--porcelain", "-n", "3"]).is_ok());
+
+    for subcommand in ["presets", "compare", "analyze"] {
+        let err = parse(&["xkpasswd", "--porcelain", subcommand]).unwrap_err();
+        assert_eq!(ErrorKind::ArgumentConflict, err.kind(), "{}", subcommand);
+    }
+}
+
+#[test]
+fn test_print0() {
+    assert_eq!('\n', DEFAULT_CLI.terminator());
+
+    let cli = Cli::try_parse_from(["xkpasswd", "--print0", "--porcelain"]).unwrap();
+    assert_eq!('\0', cli.terminator());
+
+    let err = Cli::try_parse_from(["xkpasswd", "--print0", "--spell", "plain"]).unwrap_err();
+    assert_eq!(ErrorKind::ArgumentConflict, err.kind());
+}
+
+#[test]
+fn test_minimize_typing() {
+    assert_eq!(None, DEFAULT_CLI.minimize_typing());
+
+    let cli = Cli::try_parse_from(["xkpasswd", "--minimize-typing"]).unwrap();
+    assert_eq!(Some((KeyboardLayout::Desktop, 16)), cli.minimize_typing());
+
+    let cli = Cli::try_parse_from([
+        "xkpasswd",
+        "--minimize-typing",
+        "mobile",
+        "--typing-candidates",
+        "8",
+    ])
+    .unwrap();
+    assert_eq!(Some((KeyboardLayout::Mobile, 8)), cli.minimize_typing());
+
+    let err =
+        Cli::try_parse_from(["xkpasswd", "--minimize-typing", "--unique", "words"]).unwrap_err();
+    assert_eq!(ErrorKind::ArgumentConflict, err.kind());
+
+    let err = Cli::try_parse_from(["xkpasswd", "--typing-candidates", "8"]).unwrap_err();
+    assert_eq!(ErrorKind::MissingRequiredArgument, err.kind());
+}
+
+#[test]
+fn test_random_preset() {
+    let cli = Cli::try_parse_from(["xkpasswd", "-P", "random"]).unwrap();
+    assert!(Preset::ALL.contains(&cli.preset().unwrap()));
+
+    let cli = Cli::try_parse_from(["xkpasswd", "-P", "web32"]).unwrap();
+    assert_eq!(Some(Preset::Web32), cli.preset());
+
+    // historical names still work
+    let cli = Cli::try_parse_from(["xkpasswd", "-P", "WindowsNTLMv1"]).unwrap();
+    assert_eq!(Some(Preset::WindowsNtlmV1), cli.preset());
+
+    let err = Cli::try_parse_from(["xkpasswd", "-P", " "]).unwrap_err();
+    assert_eq!(ErrorKind::InvalidValue, err.kind());
+}
+
+#[test]
+fn test_user_preset_names() {
+    let mut presets = PresetRegistry::new();
+    let corp_standard = Settings::from_preset(Preset::Web32).with_separators("+");
+    presets
+        .register("corp-standard", corp_standard.clone())
+        .unwrap();
+    presets
+        .register("xkcd", Settings::from_preset(Preset::Nist))
+        .unwrap();
+
+    // resolved along with the settings, user presets first
+    let cli = Cli::try_parse_from(["xkpasswd", "-P", "corp-standard"]).unwrap();
+    assert_eq!(None, cli.preset());
+    assert_eq!(Ok(corp_standard), cli.build_settings(&presets));
+
+    let cli = Cli::try_parse_from(["xkpasswd", "-P", "xkcd", "-w", "5"]).unwrap();
+    assert_eq!(
+        Settings::from_preset(Preset::Nist).with_words_count(5),
+        cli.build_settings(&presets)
+    );
+
+    let cli = Cli::try_parse_from(["xkpasswd", "-P", "web16"]).unwrap();
+    assert_eq!(
+        Ok(Settings::from_preset(Preset::Web16)),
+        cli.build_settings(&presets)
+    );
+
+    let cli = Cli::try_parse_from(["xkpasswd", "-P", "nope"]).unwrap();
+    assert_eq!(None, cli.preset());
+    assert_eq!(
+        Err("unknown preset 'nope'".to_string()),
+        cli.build_settings(&presets)
+    );
+}
+
+#[test]
+fn test_build_settings_pronounceable() {
+    let cli = Cli::try_parse_from(["xkpasswd", "--pronounceable"]).unwrap();
+    let settings: Settings = cli.build_settings(&PresetRegistry::new()).unwrap();
+    assert_eq!(Settings::default().with_pronounceable(true), settings);
+}
+
+#[test]
+fn test_build_settings_unchecked_word_lengths() {
+    let cli = Cli::try_parse_from(["xkpasswd", "-l", "3", "-u", "12"]).unwrap();
+    assert!(cli.build_settings(&PresetRegistry::new()).is_err());
+
+    let cli = Cli::try_parse_from([
+        "xkpasswd",
+        "-l",
+        "3",
+        "-u",
+        "12",
+        "--unchecked-word-lengths",
+    ])
+    .unwrap();
+    let settings: Settings = cli.build_settings(&PresetRegistry::new()).unwrap();
+    assert_eq!(
+        Settings::default().with_word_lengths_unchecked(Some(3), Some(12)),
+        settings
+    );
+}
+
+#[cfg(feature = "serve")]
+#[test]
+fn test_parse_serve() {
+    let cli = Cli::try_parse_from(["xkpasswd", "serve", "--keys", "keys.txt"]).unwrap();
+    assert!(matches!(
+        cli.subcommand(),
+        Some(Command::Serve {
+            per_minute: 60,
+            burst: 10,
+            ..
+        })
+    ));
+
+    // a bucket never refilled or never filled would stall every request
+    for arg in ["--rate", "--burst"] {
+        let err =
+            Cli::try_parse_from(["xkpasswd", "serve", "--keys", "keys.txt", arg, "0"]).unwrap_err();
+        assert_eq!(
+            clap::error::ErrorKind::ValueValidation,
+            err.kind(),
+            "{}",
+            arg
+        );
+    }
+}
+
+#[cfg(feature = "lang_en")]
+#[test]
+fn test_dice_words() {
+    let cli = Cli::try_parse_from(["xkpasswd", "--dice", "11111 66666,11112"]).unwrap();
+    assert_eq!(Some(vec!["aaaah", "slim", "aaah"]), cli.dice_words());
+
+    assert!(Cli::try_parse_from(["xkpasswd", "--dice", "11111", "--syllables"]).is_err());
+}
+
+#[test]
+fn test_build_settings_word_theme() {
+    let cli = Cli::try_parse_from(["xkpasswd", "--theme", "Animals"]).unwrap();
+    let settings: Settings = cli.build_settings(&PresetRegistry::new()).unwrap();
+    assert_eq!(Settings::default().with_word_theme("animals"), settings);
+}
+
+#[test]
+fn test_build_settings_proper_nouns() {
+    let cli = Cli::try_parse_from(["xkpasswd", "--proper-nouns", "preserve"]).unwrap();
+    let settings: Settings = cli.build_settings(&PresetRegistry::new()).unwrap();
+    assert_eq!(
+        Settings::default().with_proper_nouns(ProperNouns::Preserve),
+        settings
+    );
+}
+
+#[test]
+fn test_build_settings_spelling() {
+    let cli = Cli::try_parse_from(["xkpasswd", "--spelling", "uk"]).unwrap();
+    let settings: Settings = cli.build_settings(&PresetRegistry::new()).unwrap();
+    assert_eq!(
+        Settings::default().with_spelling(SpellingVariant::British),
+        settings
+    );
+}
+
+#[test]
+fn test_build_settings_symbol_set() {
+    let cli = Cli::try_parse_from(["xkpasswd", "--symbol-set", "intl-safe"]).unwrap();
+    let settings: Settings = cli.build_settings(&PresetRegistry::new()).unwrap();
+    assert_eq!(
+        Settings::default().with_symbol_set(SymbolSet::IntlSafe),
+        settings
+    );
+
+    // explicit separators win
+    let cli = Cli::try_parse_from(["xkpasswd", "--symbol-set", "us-shiftless", "-s", "."]).unwrap();
+    let settings: Settings = cli.build_settings(&PresetRegistry::new()).unwrap();
+    assert_eq!(
+        Settings::default()
+            .with_separators(".")
+            .with_padding_symbols(Symbols::US_SHIFTLESS),
+        settings
+    );
+}
+
+#[test]
+fn test_build_settings_checksum() {
+    let cli = Cli::try_parse_from(["xkpasswd", "--checksum"]).unwrap();
+    let settings: Settings = cli.build_settings(&PresetRegistry::new()).unwrap();
+    assert_eq!(Settings::default().with_checksum(true), settings);
+}
+
+#[test]
+fn test_build_settings_avoid_ambiguous() {
+    let cli = Cli::try_parse_from(["xkpasswd", "--avoid-ambiguous", "-s=-|.1"]).unwrap();
+    let settings: Settings = cli.build_settings(&PresetRegistry::new()).unwrap();
+    assert_eq!(
+        Settings::default()
+            .with_separators("-.")
+            .with_avoid_ambiguous(true),
+        settings
+    );
+}
+
+#[test]
+fn test_build_settings_avoid_homophones() {
+    let cli = Cli::try_parse_from(["xkpasswd", "--avoid-homophones"]).unwrap();
+    let settings: Settings = cli.build_settings(&PresetRegistry::new()).unwrap();
+    assert_eq!(Settings::default().with_avoid_homophones(true), settings);
+}
+
+#[cfg(feature = "pass")]
+#[test]
+fn test_pass_insert_subcommand() {
+    let cli = Cli::try_parse_from([
+        "xkpasswd",
+        "pass",
+        "insert",
+        "Mail/gmail",
+        "--preset",
+        "web32",
+    ])
+    .unwrap();
+
+    assert_eq!(
+        Some(&Command::Pass {
+            action: PassCommand::Insert {
+                name: "Mail/gmail".to_string(),
+                pass_bin: "pass".to_string(),
+                force: false,
+            }
+        }),
+        cli.subcommand()
+    );
+    assert_eq!(Some(Preset::Web32), cli.preset());
+
+    let cli = Cli::try_parse_from([
+        "xkpasswd",
+        "pass",
+        "insert",
+        "-f",
+        "--pass-bin",
+        "/opt/bin/pass",
+        "Mail/gmail",
+    ])
+    .unwrap();
+
+    assert_eq!(
+        Some(&Command::Pass {
+            action: PassCommand::Insert {
+                name: "Mail/gmail".to_string(),
+                pass_bin: "/opt/bin/pass".to_string(),
+                force: true,
+            }
+        }),
+        cli.subcommand()
+    );
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn test_hash() {
+    assert_eq!(None, Cli::try_parse_from(["xkpasswd"]).unwrap().hash());
+    assert!(Cli::try_parse_from(["xkpasswd", "--hash", "md5"]).is_err());
+    assert!(Cli::try_parse_from(["xkpasswd", "--hash", "bcrypt", "--export", "csv"]).is_err());
+    assert!(Cli::try_parse_from(["xkpasswd", "--hash-only"]).is_err());
+    assert!(!Cli::try_parse_from(["xkpasswd"]).unwrap().hash_only());
+
+    #[cfg(feature = "hash_argon2")]
+    {
+        use argon2::password_hash::{PasswordHash, PasswordVerifier};
+
+        let cli = Cli::try_parse_from(["xkpasswd", "--hash", "argon2id"]).unwrap();
+        assert_eq!(Some(HashAlgorithm::Argon2id), cli.hash());
+
+        let hash = HashAlgorithm::Argon2id.hash("correct-horse").unwrap();
+        assert!(hash.starts_with("$argon2id$"), "{}", hash);
+
+        let parsed = PasswordHash::new(&hash).unwrap();
+        assert!(argon2::Argon2::default()
+            .verify_password(b"correct-horse", &parsed)
+            .is_ok());
+        assert_ne!(hash, HashAlgorithm::Argon2id.hash("correct-horse").unwrap());
+    }
+
+    #[cfg(feature = "hash_bcrypt")]
+    {
+        let cli = Cli::try_parse_from(["xkpasswd", "--hash", "bcrypt"]).unwrap();
+        assert_eq!(Some(HashAlgorithm::Bcrypt), cli.hash());
+
+        let hash = HashAlgorithm::Bcrypt.hash("correct-horse").unwrap();
+        assert!(hash.starts_with("$2b$12$"), "{}", hash);
+        assert!(bcrypt::verify("correct-horse", &hash).unwrap());
+
+        // never silently truncated
+        assert!(HashAlgorithm::Bcrypt.hash(&"horse".repeat(15)).is_err());
+    }
+
+    #[cfg(feature = "hash_sha512_crypt")]
+    {
+        let cli = Cli::try_parse_from(["xkpasswd", "--hash", "sha512-crypt"]).unwrap();
+        assert_eq!(Some(HashAlgorithm::Sha512Crypt), cli.hash());
+
+        let cli =
+            Cli::try_parse_from(["xkpasswd", "--hash", "sha512-crypt", "--hash-only"]).unwrap();
+        assert!(cli.hash_only());
+
+        let hash = HashAlgorithm::Sha512Crypt.hash("correct-horse").unwrap();
+        assert!(hash.starts_with("$6$"), "{}", hash);
+        assert!(sha_crypt::sha512_check("correct-horse", &hash).is_ok());
+    }
+}
+
+#[cfg(feature = "encrypt")]
+#[test]
+fn test_encrypt_to() {
+    use age::x25519::Identity;
+
+    let alice = Identity::generate();
+    let bob = Identity::generate();
+    let recipients = [alice.to_public().to_string(), bob.to_public().to_string()];
+
+    let cli = Cli::try_parse_from([
+        "xkpasswd",
+        "--encrypt-to",
+        &recipients[0],
+        "--encrypt-to",
+        &recipients[1],
+    ])
+    .unwrap();
+    assert_eq!(&recipients, cli.encrypt_to());
+
+    assert!(Cli::try_parse_from(["xkpasswd", "--encrypt-to", "age1invalid"]).is_err());
+    assert!(Cli::try_parse_from(["xkpasswd", "--encrypt-to", &recipients[0], "--spell"]).is_err());
+    let err = Cli::try_parse_from(["xkpasswd", "--encrypt-to", &recipients[0], "--porcelain"])
+        .unwrap_err();
+    assert_eq!(ErrorKind::ArgumentConflict, err.kind());
+    assert!(parse_age_recipient("bob")
+        .unwrap_err()
+        .starts_with("invalid age recipient 'bob': "));
+
+    let ciphertext = encrypt_to(&recipients, "correct-horse\nbattery-staple\n").unwrap();
+    assert!(ciphertext.starts_with("-----BEGIN AGE ENCRYPTED FILE-----"));
+    assert!(!ciphertext.contains("correct-horse"));
+
+    for identity in [alice, bob] {
+        let plaintext = age::decrypt(&identity, ciphertext.as_bytes()).unwrap();
+        assert_eq!(b"correct-horse\nbattery-staple\n", &plaintext[..]);
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_stdin_jsonl() {
+    assert!(Cli::try_parse_from(["xkpasswd", "--stdin-jsonl"])
+        .unwrap()
+        .stdin_jsonl());
+    assert!(Cli::try_parse_from(["xkpasswd", "--stdin-jsonl", "-n", "3"]).is_err());
+
+    let pass_generator = Xkpasswd::default();
+    let xkcd = r#"{"num_words": 4, "word_length_min": 4, "word_length_max": 8, "case_transform": "LOWER", "separator_character": "-", "padding_digits_before": 0, "padding_digits_after": 0, "padding_type": "NONE"}"#;
+    let input = format!("{{}}\n\n{}\n{}\n", xkcd, xkcd);
+    let mut output = vec![];
+
+    let count = gen_from_jsonl(&pass_generator, input.as_bytes(), &mut output, '\n').unwrap();
+    assert_eq!(3, count);
+
+    let output = String::from_utf8(output).unwrap();
+    let passwds: Vec<&str> = output.lines().collect();
+    assert_eq!(3, passwds.len());
+
+    let xkcd = Settings::from_xkpasswd_json(xkcd).unwrap();
+    assert!(Settings::default().matches(passwds[0], &pass_generator));
+    assert!(xkcd.matches(passwds[1], &pass_generator));
+    assert!(xkcd.matches(passwds[2], &pass_generator));
+
+    // passwords of the lines before the invalid one are still printed
+    let input = "{}\n\n{\"num_words\": 0}\n{}\n";
+    let mut output = vec![];
+    let err = gen_from_jsonl(&pass_generator, input.as_bytes(), &mut output, '\0').unwrap_err();
+    assert!(matches!(err, JsonlError::InvalidSettings(3, _)), "{}", err);
+    assert_eq!(1, output.iter().filter(|&&byte| byte == b'\0').count());
+
+    let err = gen_from_jsonl(&pass_generator, "nope".as_bytes(), &mut vec![], '\n').unwrap_err();
+    assert!(
+        err.to_string().starts_with("line 1: invalid JSON: "),
+        "{}",
+        err
+    );
+
+    // failing to read or write isn't the fault of the settings
+    let err = gen_from_jsonl(&pass_generator, &[0xff, b'\n'][..], &mut vec![], '\n').unwrap_err();
+    assert!(matches!(err, JsonlError::Io(_)), "{}", err);
+
+    let mut full = [0u8; 4];
+    let err =
+        gen_from_jsonl(&pass_generator, "{}".as_bytes(), &mut &mut full[..], '\n').unwrap_err();
+    assert!(matches!(err, JsonlError::Io(_)), "{}", err);
+}
+
+#[cfg(feature = "clipboard")]
+#[test]
+fn test_parse_copy() {
+    let cli = Cli::try_parse_from(["xkpasswd", "--copy", "--clear-after", "30"]).unwrap();
+    assert!(cli.copy());
+    assert_eq!(Some(std::time::Duration::from_secs(30)), cli.clear_after());
+
+    assert!(Cli::try_parse_from(["xkpasswd", "--clear-after", "30"]).is_err());
+    assert!(Cli::try_parse_from(["xkpasswd", "--copy", "--porcelain"]).is_err());
+
+    let cli =
+        Cli::try_parse_from(["xkpasswd", HOLD_CLIPBOARD_COMMAND, "--clear-after", "5"]).unwrap();
+    assert_eq!(
+        Some(&Command::HoldClipboard {
+            clear_after: Some(5)
+        }),
+        cli.subcommand()
+    );
+}
+
+#[cfg(all(windows, feature = "wincred"))]
+#[test]
+fn test_parse_store_wincred() {
+    let cli =
+        Cli::try_parse_from(["xkpasswd", "--store", "wincred", "--name", "git:work"]).unwrap();
+    assert_eq!(
+        Some((
+            Store::Wincred,
+            StoreEntry {
+                name: "git:work".to_string(),
+            }
+        )),
+        cli.store()
+    );
+
+    assert!(Cli::try_parse_from(["xkpasswd", "--store", "wincred"]).is_err());
+    assert!(Cli::try_parse_from(["xkpasswd", "--name", "git:work"]).is_err());
+    assert!(
+        Cli::try_parse_from(["xkpasswd", "--store", "wincred", "--name", "a", "-n", "2"]).is_err()
+    );
+}
+
+#[cfg(all(target_os = "macos", feature = "keychain"))]
+#[test]
+fn test_parse_store_keychain() {
+    let cli = Cli::try_parse_from([
+        "xkpasswd",
+        "--store",
+        "keychain",
+        "--service",
+        "example.com",
+        "--account",
+        "alice",
+    ])
+    .unwrap();
+    assert_eq!(
+        Some((
+            Store::Keychain,
+            StoreEntry {
+                service: "example.com".to_string(),
+                account: "alice".to_string(),
+            }
+        )),
+        cli.store()
+    );
+
+    assert!(
+        Cli::try_parse_from(["xkpasswd", "--store", "keychain", "--account", "alice"]).is_err()
+    );
+    assert!(Cli::try_parse_from(["xkpasswd", "--service", "example.com"]).is_err());
+}
+
+#[cfg(all(target_os = "linux", feature = "secret_service"))]
+#[test]
+fn test_parse_store_secret_service() {
+    let cli = Cli::try_parse_from([
+        "xkpasswd",
+        "--store",
+        "secret-service",
+        "--service",
+        "example.com",
+        "--account",
+        "alice",
+    ])
+    .unwrap();
+    assert_eq!(
+        Some((
+            Store::SecretService,
+            StoreEntry {
+                service: "example.com".to_string(),
+                account: "alice".to_string(),
+            }
+        )),
+        cli.store()
+    );
+
+    let expectations = [
+        vec!["--store", "secret-service", "--service", "example.com"],
+        vec!["--store", "secret-service", "--account", "alice"],
+        vec!["--account", "alice"],
+        vec![
+            "--store",
+            "secret-service",
+            "--service",
+            "a",
+            "--account",
+            "b",
+            "--copy",
+        ],
+    ];
+
+    for args in expectations {
+        let args = [&["xkpasswd"], args.as_slice()].concat();
+        assert!(Cli::try_parse_from(&args).is_err(), "{:?}", args);
+    }
+}
+
+#[cfg(feature = "gui")]
+#[test]
+fn test_gui_controls() {
+    let cli = Cli::try_parse_from(["xkpasswd", "gui", "--preset", "xkcd"]).unwrap();
+    assert_eq!(Some(&Command::Gui), cli.subcommand());
+
+    let settings = Settings::from_preset(Preset::Xkcd);
+    let controls = GuiControls::from_settings(&settings, 0);
+
+    assert_eq!(1, controls.count);
+    assert_eq!(settings, controls.apply(&settings).unwrap());
+
+    let tweaked = GuiControls {
+        count: 3,
+        words_count: 5,
+        word_lengths: (6, 4),
+        padding_digits: (0, 2),
+    };
+    let expected = settings
+        .with_words_count(5)
+        .and_then(|settings| settings.with_word_lengths(Some(4), Some(6)))
+        .unwrap()
+        .with_padding_digits(Some(0), Some(2));
+
+    assert_eq!(expected, tweaked.apply(&settings).unwrap());
+}
+
+#[test]
+fn test_credential_pair() {
+    let passwords = vec!["foo".to_string(), "bar".to_string()];
+    let usernames = ["alice".to_string()];
+
+    assert_eq!(
+        vec![
+            Credential {
+                title: "alice".to_string(),
+                username: "alice".to_string(),
+                password: "foo".to_string(),
+            },
+            Credential {
+                title: "Generated password 2".to_string(),
+                username: "".to_string(),
+                password: "bar".to_string(),
+            },
+        ],
+        Credential::pair(passwords, Some(&usernames))
+    );
+}
+
+#[test]
+fn test_export_keepass() {
+    let credentials = Credential::pair(
+        vec!["a\"b,c".to_string(), "<x&y>".to_string()],
+        Some(&["alice".to_string(), "bob".to_string()]),
+    );
+    let entropy = Entropy {
+        blind_min: 10,
+        blind_max: 20,
+        seen: 5,
+        guess_time: GuessTime::for_entropy(5),
+    };
+
+    let csv = export_credentials(ExportFormat::KeepassCsv, &credentials, &entropy);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(3, lines.len());
+    assert_eq!(r#""Title","Username","Password","URL","Notes""#, lines[0]);
+    assert!(lines[1].starts_with(
+        r#""alice","alice","a""b,c","","Generated by xkpasswd, entropy: between 10 & 20 bits blind"#
+    ));
+    assert!(lines[2].starts_with(r#""bob","bob","<x&y>","","#));
+
+    let xml = export_credentials(ExportFormat::KeepassXml, &credentials, &entropy);
+    assert!(xml.starts_with("<?xml"));
+    assert_eq!(2, xml.matches("<Entry>").count());
+    assert!(xml.contains("<String><Key>UserName</Key><Value>alice</Value></String>"));
+    assert!(xml.contains(
+        r#"<String><Key>Password</Key><Value ProtectInMemory="True">a&quot;b,c</Value></String>"#
+    ));
+    assert!(xml.contains(
+        r#"<String><Key>Password</Key><Value ProtectInMemory="True">&lt;x&amp;y&gt;</Value></String>"#
+    ));
+    assert!(xml.contains("between 10 &amp; 20 bits blind"));
+}
+
+#[test]
+fn test_export_args() {
+    let cli = Cli::try_parse_from([
+        "xkpasswd",
+        "--usernames",
+        "users.txt",
+        "--export",
+        "keepass-csv",
+    ])
+    .unwrap();
+    assert_eq!(Some(ExportFormat::KeepassCsv), cli.export());
+    assert_eq!(Some("users.txt".to_string()), cli.usernames_file);
+
+    assert!(Cli::try_parse_from(["xkpasswd", "--usernames", "users.txt", "-n", "3"]).is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_export_bitwarden() {
+    let credentials = Credential::pair(
+        vec!["a\"b".to_string(), "bar".to_string()],
+        Some(&["alice".to_string()]),
+    );
+    let entropy = Entropy::default();
+
+    let exported = export_credentials(ExportFormat::BitwardenJson, &credentials, &entropy);
+    let export: serde_json::Value = serde_json::from_str(&exported).unwrap();
+
+    assert_eq!(false, export["encrypted"]);
+    assert_eq!(serde_json::json!([]), export["folders"]);
+
+    let items = export["items"].as_array().unwrap();
+    assert_eq!(2, items.len());
+
+    assert_eq!(1, items[0]["type"]);
+    assert_eq!("alice", items[0]["name"]);
+    assert_eq!("alice", items[0]["login"]["username"]);
+    assert_eq!("a\"b", items[0]["login"]["password"]);
+    assert!(items[0]["notes"]
+        .as_str()
+        .unwrap()
+        .starts_with("Generated by xkpasswd, entropy: "));
+
+    assert_eq!("Generated password 2", items[1]["name"]);
+    assert!(items[1]["login"]["username"].is_null());
+    assert_eq!("bar", items[1]["login"]["password"]);
+
+    let cli = Cli::try_parse_from(["xkpasswd", "--export", "bitwarden-json"]).unwrap();
+    assert_eq!(Some(ExportFormat::BitwardenJson), cli.export());
+}
+
+#[test]
+fn test_render_table() {
+    let passwds = ["foo-bar".to_string(), "a,\"b\"".to_string()];
+    let entropy = Entropy {
+        seen: 42,
+        ..Entropy::default()
+    };
+
+    assert_eq!(
+        "index,password,length,entropy_bits\n1,foo-bar,7,42\n2,\"a,\"\"b\"\"\",5,42\n",
+        render_table(TableFormat::Csv, &passwds, &entropy)
+    );
+    assert_eq!(
+        "index\tpassword\tlength\tentropy_bits\n1\tfoo-bar\t7\t42\n2\t\"a,\"\"b\"\"\"\t5\t42\n",
+        render_table(TableFormat::Tsv, &passwds, &entropy)
+    );
+    assert_eq!(
+        "index,password,length,entropy_bits\n",
+        render_table(TableFormat::Csv, &[], &entropy)
+    );
+
+    // never evaluated as formulas by spreadsheets
+    let passwds = [
+        "=1+1".to_string(),
+        "+foo".to_string(),
+        "-bar".to_string(),
+        "@SUM(A1)".to_string(),
+        "=\"a\",b".to_string(),
+    ];
+    assert_eq!(
+        "index,password,length,entropy_bits\n1,'=1+1,4,42\n2,'+foo,4,42\n3,'-bar,4,42\n4,'@SUM(A1),8,42\n5,\"'=\"\"a\"\",b\",6,42\n",
+        render_table(TableFormat::Csv, &passwds, &entropy)
+    );
+
+    let cli = Cli::try_parse_from(["xkpasswd", "-n", "3", "--format", "tsv"]).unwrap();
+    assert_eq!(Some(TableFormat::Tsv), cli.table_format());
+    assert!(
+        Cli::try_parse_from(["xkpasswd", "--format", "csv", "--export", "keepass-csv"]).is_err()
+    );
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(ErrorFormat::Text, DEFAULT_CLI.errors);
+    assert!(Cli::try_parse_from(["xkpasswd", "--errors", "yaml"]).is_err());
+
+    let failures = [
+        Failure::Other,
+        Failure::InvalidSettings,
+        Failure::Dictionary,
+        Failure::Io,
+        Failure::Unsatisfiable,
+    ];
+    let mut exit_codes: Vec<i32> = failures.iter().map(Failure::exit_code).collect();
+    exit_codes.dedup();
+    assert_eq!(vec![1, 3, 4, 5, 6], exit_codes);
+
+    #[cfg(feature = "json")]
+    {
+        let cli = Cli::try_parse_from(["xkpasswd", "pin", "--errors", "json"]).unwrap();
+        assert_eq!(ErrorFormat::Json, cli.errors);
+
+        let error: serde_json::Value =
+            serde_json::from_str(&Failure::Io.to_json("can't read \"words.txt\"")).unwrap();
+        assert_eq!(
+            serde_json::json!({"error": "io", "code": 5, "message": "can't read \"words.txt\""}),
+            error
+        );
+    }
+}
+
+#[test]
+fn test_env_vars() {
+    // setting variables would leak into other tests running in parallel,
+    // so only check that every policy flag has one
+    let command = Cli::command();
+    let env_vars: Vec<(&str, String)> = command
+        .get_arguments()
+        .filter_map(|arg| {
+            let env = arg.get_env()?.to_str()?.to_string();
+            Some((arg.get_long()?, env))
+        })
+        .collect();
+
+    let expected = [
+        ("words", "XKPASSWD_WORDS"),
+        ("word-min", "XKPASSWD_WORD_MIN"),
+        ("word-max", "XKPASSWD_WORD_MAX"),
+        ("unchecked-word-lengths", "XKPASSWD_UNCHECKED_WORD_LENGTHS"),
+        ("transforms", "XKPASSWD_TRANSFORMS"),
+        ("separators", "XKPASSWD_SEPARATORS"),
+        ("digits-before", "XKPASSWD_DIGITS_BEFORE"),
+        ("digits-after", "XKPASSWD_DIGITS_AFTER"),
+        ("symbols", "XKPASSWD_SYMBOLS"),
+        ("symbol-set", "XKPASSWD_SYMBOL_SET"),
+        ("symbols-before", "XKPASSWD_SYMBOLS_BEFORE"),
+        ("symbols-after", "XKPASSWD_SYMBOLS_AFTER"),
+        ("padding", "XKPASSWD_PADDING"),
+        ("join", "XKPASSWD_JOIN"),
+        ("pronounceable", "XKPASSWD_PRONOUNCEABLE"),
+        ("checksum", "XKPASSWD_CHECKSUM"),
+        ("avoid-ambiguous", "XKPASSWD_AVOID_AMBIGUOUS"),
+        ("avoid-homophones", "XKPASSWD_AVOID_HOMOPHONES"),
+        ("theme", "XKPASSWD_THEME"),
+        ("proper-nouns", "XKPASSWD_PROPER_NOUNS"),
+        ("spelling", "XKPASSWD_SPELLING"),
+        ("adaptive-length", "XKPASSWD_ADAPTIVE_LENGTH"),
+        ("preset", "XKPASSWD_PRESET"),
+        ("lang", "XKPASSWD_LANG"),
+        ("config", "XKPASSWD_CONFIG"),
+        ("dict", "XKPASSWD_DICT"),
+        ("user-preset", "XKPASSWD_USER_PRESET"),
+    ];
+
+    assert_eq!(
+        expected
+            .iter()
+            .map(|(long, env)| (*long, env.to_string()))
+            .collect::<Vec<(&str, String)>>(),
+        env_vars
+    );
+
+    // comma separated, so transforms can be given in a single variable
+    let cli = Cli::try_parse_from(["xkpasswd", "-t", "lowercase,titlecase"]).unwrap();
+    assert_eq!(
+        Some(vec![WordTransform::Lowercase, WordTransform::Titlecase]),
+        cli.word_transforms
+    );
 }
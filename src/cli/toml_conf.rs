@@ -18,92 +18,165 @@ pub trait ConfigParser {
 
 impl ConfigParser for Cli {
     fn parse_config_file(&mut self) -> Result<(), ConfigParseError> {
-        let config = match read_config_file(&self.config_file) {
-            Err(err) => return Err(err),
-            Ok(config) => config,
-        };
+        // user preset values come first, so they win over the config file's
+        if let Some(name) = self.user_preset.clone() {
+            let presets = read_user_presets(self.no_user_config)?;
+            let preset = presets.get(&name).ok_or_else(|| {
+                ConfigParseError::InvalidConfig(
+                    "user_preset".to_string(),
+                    format!("no preset named '{}'", name),
+                )
+            })?;
+
+            self.apply_config(preset)?;
+        }
 
-        parse_number_config(
-            self.words_count.is_some(),
-            &config,
-            "words_count",
-            |value| self.words_count = Some(value as u8),
-        );
+        let config = read_config_file(&self.config_file, self.no_user_config)?;
+        self.apply_config(&config)
+    }
+}
+
+impl Cli {
+    fn apply_config(&mut self, config: &toml::Value) -> Result<(), ConfigParseError> {
+        parse_number_config(self.words_count.is_some(), config, "words_count", |value| {
+            self.words_count = Some(value as u8)
+        });
 
         parse_number_config(
             self.word_length_min.is_some(),
-            &config,
+            config,
             "word_min",
             |value| self.word_length_min = Some(value as u8),
         );
 
         parse_number_config(
             self.word_length_max.is_some(),
-            &config,
+            config,
             "word_max",
             |value| self.word_length_max = Some(value as u8),
         );
 
-        parse_transforms(self.word_transforms.is_some(), &config, |transforms| {
+        parse_transforms(self.word_transforms.is_some(), config, |transforms| {
             self.word_transforms = Some(transforms)
         })?;
 
-        parse_str_config(self.separators.is_some(), &config, "separators", |value| {
+        parse_str_config(self.separators.is_some(), config, "separators", |value| {
             self.separators = Some(value)
         });
 
         parse_number_config(
             self.padding_digits_before.is_some(),
-            &config,
+            config,
             "digits_before",
             |value| self.padding_digits_before = Some(value as u8),
         );
 
         parse_number_config(
             self.padding_digits_after.is_some(),
-            &config,
+            config,
             "digits_after",
             |value| self.padding_digits_after = Some(value as u8),
         );
 
-        parse_str_config(
-            self.padding_symbols.is_some(),
-            &config,
-            "symbols",
-            |value| self.padding_symbols = Some(value),
-        );
+        parse_str_config(self.padding_symbols.is_some(), config, "symbols", |value| {
+            self.padding_symbols = Some(value)
+        });
+
+        parse_enum_config(self.symbol_set.is_some(), config, "symbol_set", |value| {
+            self.symbol_set = Some(value)
+        })?;
 
         parse_number_config(
             self.padding_symbols_before.is_some(),
-            &config,
+            config,
             "symbols_before",
             |value| self.padding_symbols_before = Some(value as u8),
         );
 
         parse_number_config(
             self.padding_symbols_after.is_some(),
-            &config,
+            config,
             "symbols_after",
             |value| self.padding_symbols_after = Some(value as u8),
         );
 
-        parse_enum_config(self.padding.is_some(), &config, "padding", |value| {
+        parse_str_config(self.word_theme.is_some(), config, "theme", |value| {
+            self.word_theme = Some(value)
+        });
+
+        parse_enum_config(
+            self.proper_nouns.is_some(),
+            config,
+            "proper_nouns",
+            |value| self.proper_nouns = Some(value),
+        )?;
+
+        parse_enum_config(self.spelling.is_some(), config, "spelling", |value| {
+            self.spelling = Some(value)
+        })?;
+
+        parse_enum_config(self.padding.is_some(), config, "padding", |value| {
             self.padding = Some(value)
         })?;
 
-        parse_enum_config(self.preset.is_some(), &config, "preset", |value| {
-            self.preset = Some(value)
+        parse_enum_config(self.join_style.is_some(), config, "join", |value| {
+            self.join_style = Some(value)
+        })?;
+
+        parse_enum_config(self.preset.is_some(), config, "preset", |value: Preset| {
+            self.preset = Some(value.name().to_string())
         })?;
 
-        parse_enum_config(self.language.is_some(), &config, "lang", |value| {
+        parse_enum_config(self.language.is_some(), config, "lang", |value| {
             self.language = Some(value)
         })?;
 
+        parse_str_config(self.dict_file.is_some(), config, "dict", |value| {
+            self.dict_file = Some(value)
+        });
+
         Ok(())
     }
 }
 
+impl Cli {
+    /// Every table of the user presets file registered under its name,
+    /// none if there's no such file or user config is disabled.
+    pub(super) fn read_preset_registry(&self) -> Result<PresetRegistry, ConfigParseError> {
+        let mut registry = PresetRegistry::new();
+
+        if self.no_user_config || user_dirs::presets_file().is_none() {
+            return Ok(registry);
+        }
+
+        let presets = read_user_presets(false)?;
+        let tables = presets
+            .as_table()
+            .into_iter()
+            .flatten()
+            .filter(|(_, table)| table.is_table());
+
+        for (name, table) in tables {
+            let mut preset_cli = Cli::default();
+            preset_cli.apply_config(table)?;
+
+            preset_cli
+                .build_settings(&PresetRegistry::new())
+                .and_then(|settings| registry.register(name, settings))
+                .map_err(|err| ConfigParseError::InvalidConfig(name.clone(), err))?;
+
+            log::debug!("registered user preset '{}'", name);
+        }
+
+        Ok(registry)
+    }
+}
+
 fn lookup_default_config_path() -> Option<String> {
+    if let Some(path) = user_dirs::config_file() {
+        return path.into_os_string().into_string().ok();
+    }
+
     for mut path in [dirs::preference_dir(), dirs::config_dir(), dirs::home_dir()]
         .into_iter()
         .flatten()
@@ -111,17 +184,38 @@ fn lookup_default_config_path() -> Option<String> {
         path.push(CONFIG_FILE_NAME);
 
         if path.exists() {
-            return match path.into_os_string().into_string() {
-                Ok(config_path) => Some(config_path),
-                Err(_) => None,
-            };
+            return path.into_os_string().into_string().ok();
         }
     }
 
     None
 }
 
-fn read_config_file(config_file: &Option<String>) -> Result<toml::Value, ConfigParseError> {
+fn read_user_presets(no_user_config: bool) -> Result<toml::Value, ConfigParseError> {
+    if no_user_config {
+        return Err(ConfigParseError::InvalidFile(
+            "user presets are disabled by --no-user-config".to_string(),
+        ));
+    }
+
+    let Some(presets_path) = user_dirs::presets_file() else {
+        return Err(ConfigParseError::InvalidFile(
+            "user presets file not found".to_string(),
+        ));
+    };
+
+    log::debug!("found user presets at {}", presets_path.display());
+
+    let data =
+        fs::read(&presets_path).map_err(|err| ConfigParseError::InvalidFile(err.to_string()))?;
+    toml::from_slice::<toml::Value>(&data)
+        .map_err(|err| ConfigParseError::InvalidFile(err.to_string()))
+}
+
+fn read_config_file(
+    config_file: &Option<String>,
+    no_user_config: bool,
+) -> Result<toml::Value, ConfigParseError> {
     let file_data = match config_file {
         Some(config_file) => match fs::read(config_file) {
             Ok(data) => {
@@ -130,6 +224,10 @@ fn read_config_file(config_file: &Option<String>) -> Result<toml::Value, ConfigP
             }
             Err(err) => Err(ConfigParseError::InvalidFile(err.to_string())),
         },
+        None if no_user_config => {
+            log::debug!("user config disabled, ignoring config file at default path");
+            Err(ConfigParseError::Ignore)
+        }
         None => match lookup_default_config_path() {
             None => {
                 log::debug!("config file at default path not found, ignoring");
@@ -323,6 +421,13 @@ mod tests {
             (Preset::Web32, "web32"),
             (Preset::Wifi, "wifi"),
             (Preset::Xkcd, "xkcd"),
+            (Preset::Nist, "nist"),
+            (Preset::Temporary, "temporary"),
+            (Preset::WifiEasyType, "wifi-easy"),
+            (Preset::Mobile, "mobile"),
+            (Preset::SshPassphrase, "ssh"),
+            (Preset::DiskEncryption, "disk"),
+            (Preset::DatabaseSafe, "database"),
         ];
 
         for (_preset, config_value) in table {
@@ -460,4 +565,50 @@ mod tests {
             panic!("shouldn't be invoked")
         });
     }
+
+    #[test]
+    fn test_apply_config_precedence() {
+        let mut cli = Cli::try_parse_from(["xkpasswd", "-w", "5"]).unwrap();
+
+        // user preset first, then the config file, only filling what's still unset
+        let preset: toml::Value = toml::from_str(
+            r#"
+            words_count = 3
+            separators = "-"
+            "#,
+        )
+        .unwrap();
+        let config: toml::Value = toml::from_str(
+            r#"
+            separators = "."
+            digits_after = 2
+            "#,
+        )
+        .unwrap();
+
+        cli.apply_config(&preset).unwrap();
+        cli.apply_config(&config).unwrap();
+
+        assert_eq!(Some(5), cli.words_count);
+        assert_eq!(Some("-".to_string()), cli.separators);
+        assert_eq!(Some(2), cli.padding_digits_after);
+    }
+
+    #[test]
+    fn test_no_user_config() {
+        assert!(matches!(
+            read_config_file(&None, true),
+            Err(ConfigParseError::Ignore)
+        ));
+        assert!(matches!(
+            read_user_presets(true),
+            Err(ConfigParseError::InvalidFile(_))
+        ));
+
+        // explicitly given paths are still read
+        assert!(matches!(
+            read_config_file(&Some("./nope.toml".to_string()), true),
+            Err(ConfigParseError::InvalidFile(_))
+        ));
+    }
 }
@@ -0,0 +1,90 @@
+// Per-user files following the XDG base directory spec:
+//   $XDG_CONFIG_HOME/xkpasswd/config.toml   settings, same format as --config
+//   $XDG_CONFIG_HOME/xkpasswd/presets.toml  named tables of settings for --user-preset
+//   $XDG_DATA_HOME/xkpasswd/dicts/          dictionaries selectable by name with --dict
+// falling back to the platform's config & data directories when the variables aren't set.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+const APP_DIR: &str = "xkpasswd";
+const CONFIG_FILE_NAME: &str = "config.toml";
+const PRESETS_FILE_NAME: &str = "presets.toml";
+const DICTS_DIR: &str = "dicts";
+const DICT_EXTENSIONS: [&str; 2] = ["", ".txt"];
+
+pub fn config_file() -> Option<PathBuf> {
+    existing(config_home()?.join(APP_DIR).join(CONFIG_FILE_NAME))
+}
+
+pub fn presets_file() -> Option<PathBuf> {
+    existing(config_home()?.join(APP_DIR).join(PRESETS_FILE_NAME))
+}
+
+pub fn dict_file(name: &str) -> Option<PathBuf> {
+    find_dict(&data_home()?.join(APP_DIR).join(DICTS_DIR), name)
+}
+
+/// Looks up `name` or `name.txt` in `dicts_dir`, only for plain names, not paths.
+pub fn find_dict(dicts_dir: &Path, name: &str) -> Option<PathBuf> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        return None;
+    }
+
+    DICT_EXTENSIONS
+        .iter()
+        .find_map(|extension| existing(dicts_dir.join(format!("{}{}", name, extension))))
+}
+
+fn config_home() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME").or_else(dirs::config_dir)
+}
+
+fn data_home() -> Option<PathBuf> {
+    xdg_dir("XDG_DATA_HOME").or_else(dirs::data_dir)
+}
+
+// relative paths are invalid per the spec and must be ignored
+fn xdg_dir(var: &str) -> Option<PathBuf> {
+    env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+}
+
+fn existing(path: PathBuf) -> Option<PathBuf> {
+    path.is_file().then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_find_dict() {
+        let dicts_dir = env::temp_dir().join(format!("xkpasswd-dicts-{}", std::process::id()));
+        fs::create_dir_all(&dicts_dir).unwrap();
+        fs::write(dicts_dir.join("pets.txt"), "cat\ndog\n").unwrap();
+        fs::write(dicts_dir.join("birds"), "owl\n").unwrap();
+
+        assert_eq!(
+            Some(dicts_dir.join("pets.txt")),
+            find_dict(&dicts_dir, "pets")
+        );
+        assert_eq!(
+            Some(dicts_dir.join("pets.txt")),
+            find_dict(&dicts_dir, "pets.txt")
+        );
+        assert_eq!(
+            Some(dicts_dir.join("birds")),
+            find_dict(&dicts_dir, "birds")
+        );
+        assert_eq!(None, find_dict(&dicts_dir, "fish"));
+
+        // never escapes the dictionaries directory
+        assert_eq!(None, find_dict(&dicts_dir, "../pets"));
+        assert_eq!(None, find_dict(&dicts_dir, ""));
+
+        fs::remove_dir_all(&dicts_dir).unwrap();
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests;
+
+use crate::dict::{load_word_list, Dict};
+use crate::prelude::{Builder, Xkpasswd};
+use crate::settings::Settings;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const CODENAMES_EN: &str = include_str!("../assets/codenames_en.txt");
+
+/// Themes of the built-in codenames dictionary picked from by default, e.g. `brave-otter`.
+pub const DEFAULT_THEMES: [&str; 2] = ["adjectives", "nouns"];
+
+// DNS labels are limited to 63 chars
+const MAX_LABEL_LENGTH: usize = 63;
+const MIN_WORD_LENGTH: u8 = 3;
+const MAX_WORD_LENGTH: u8 = 12;
+
+/// The built-in dictionary for codenames, with `@adjectives`, `@nouns`, `@animals`
+/// & `@colors` themes.
+pub fn codenames_dict() -> Dict<'static> {
+    load_word_list(CODENAMES_EN.as_bytes())
+}
+
+/// Generates codenames like `brave-otter` for servers & releases, a word from each theme
+/// in turn joined by '-', safe to use as DNS labels.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Codenames {
+    pools: Vec<Vec<String>>,
+}
+
+impl Codenames {
+    /// Picks from the words under each of `themes` in the dictionary of `pass_generator`,
+    /// lowercased & left out unless made of ASCII letters & digits only.
+    pub fn new(pass_generator: &Xkpasswd, themes: &[&str]) -> Result<Self, String> {
+        if themes.is_empty() {
+            return Err("at least 1 theme is required for codenames".to_string());
+        }
+
+        let pools = themes
+            .iter()
+            .map(|theme| {
+                let settings = Settings::default()
+                    .with_word_theme(theme)
+                    .with_word_lengths_unchecked(Some(MIN_WORD_LENGTH), Some(MAX_WORD_LENGTH));
+
+                let mut pool: Vec<String> = pass_generator
+                    .words_pool(&settings)
+                    .iter()
+                    .map(|word| word.to_lowercase())
+                    .filter(|word| word.bytes().all(|byte| byte.is_ascii_alphanumeric()))
+                    .collect();
+                pool.sort();
+                pool.dedup();
+
+                if pool.is_empty() {
+                    Err(format!("no DNS-safe words under theme '{}'", theme))
+                } else {
+                    Ok(pool)
+                }
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let max_length = pools.len() - 1 + pools.len() * MAX_WORD_LENGTH as usize;
+        if max_length > MAX_LABEL_LENGTH {
+            return Err(format!(
+                "codenames of {} words could exceed {} chars",
+                pools.len(),
+                MAX_LABEL_LENGTH
+            ));
+        }
+
+        Ok(Codenames { pools })
+    }
+
+    /// Entropy of each codename in bits, rounded down, with full knowledge of the themes.
+    pub fn entropy(&self) -> usize {
+        self.pools
+            .iter()
+            .map(|pool| (pool.len() as f64).log2())
+            .sum::<f64>()
+            .floor() as usize
+    }
+
+    pub fn gen_codename<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let words: Vec<&str> = self
+            .pools
+            .iter()
+            .filter_map(|pool| pool.choose(rng))
+            .map(String::as_str)
+            .collect();
+
+        words.join("-")
+    }
+}
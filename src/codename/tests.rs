@@ -0,0 +1,68 @@
+use super::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn test_codenames_dict() {
+    let dict = codenames_dict();
+    assert_eq!(
+        vec!["adjectives", "animals", "colors", "nouns"],
+        dict.themes()
+    );
+}
+
+#[test]
+fn test_gen_codename() {
+    let pass_generator = Xkpasswd::from_dict(codenames_dict());
+    let codenames = Codenames::new(&pass_generator, &DEFAULT_THEMES).unwrap();
+    assert_eq!(14, codenames.entropy());
+
+    let mut rng = StdRng::seed_from_u64(42);
+
+    for _ in 0..100 {
+        let codename = codenames.gen_codename(&mut rng);
+        let words: Vec<&str> = codename.split('-').collect();
+        assert_eq!(2, words.len(), "{}", codename);
+        assert!(codename.len() <= MAX_LABEL_LENGTH);
+        assert!(
+            codename
+                .bytes()
+                .all(|byte| byte.is_ascii_lowercase() || byte == b'-'),
+            "{}",
+            codename
+        );
+    }
+
+    let codenames = Codenames::new(&pass_generator, &["colors", "adjectives", "animals"]).unwrap();
+    assert_eq!(3, codenames.gen_codename(&mut rng).split('-').count());
+}
+
+#[test]
+fn test_dns_safe_words() {
+    let dict = load_word_list("@ships\nHMS\nEndeavour\nSão-Paulo\nfast ship\n".as_bytes());
+    let pass_generator = Xkpasswd::from_dict(dict);
+
+    let codenames = Codenames::new(&pass_generator, &["ships", "ships"]).unwrap();
+    let mut rng = StdRng::seed_from_u64(42);
+
+    for _ in 0..10 {
+        let codename = codenames.gen_codename(&mut rng);
+        assert!(
+            ["hms", "endeavour"]
+                .iter()
+                .any(|word| codename.starts_with(word)),
+            "{}",
+            codename
+        );
+    }
+
+    assert_eq!(
+        Err("no DNS-safe words under theme 'planes'".to_string()),
+        Codenames::new(&pass_generator, &["planes"])
+    );
+    assert!(Codenames::new(&pass_generator, &[]).is_err());
+    assert_eq!(
+        Err("codenames of 5 words could exceed 63 chars".to_string()),
+        Codenames::new(&pass_generator, &["ships"; 5])
+    );
+}
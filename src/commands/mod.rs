@@ -0,0 +1,132 @@
+// Plain functions over serializable requests & responses, meant to be registered
+// as Tauri commands as they are, e.g. `generate` behind a `#[tauri::command]` wrapper
+// of the same signature, so desktop wrappers don't re-wrap the builder API.
+// Passwords are generated from the default language, see `Xkpasswd::preloaded`.
+
+#[cfg(test)]
+mod tests;
+
+use crate::prelude::{Builder, Entropy, Preset, Randomizer, Xkpasswd};
+use crate::settings::Settings;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+// same cap as server mode, desktop wrappers have no reason to ask for more
+const MAX_COUNT: usize = 100;
+
+/// Settings to generate with: a query string like the web app's share links,
+/// otherwise a built-in preset by name or alias, otherwise the default settings.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SettingsRequest {
+    pub preset: Option<String>,
+    pub query: Option<String>,
+}
+
+impl SettingsRequest {
+    pub fn settings(&self) -> Result<Settings, String> {
+        match (&self.query, &self.preset) {
+            (Some(query), _) => Settings::from_query_string(query),
+            (None, Some(name)) => Preset::from_str(name).map(Settings::from_preset),
+            (None, None) => Ok(Settings::default()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GenerateRequest {
+    #[serde(flatten)]
+    pub settings: SettingsRequest,
+    pub count: usize,
+}
+
+impl Default for GenerateRequest {
+    fn default() -> Self {
+        GenerateRequest {
+            settings: SettingsRequest::default(),
+            count: 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntropyReport {
+    pub blind_min: usize,
+    pub blind_max: usize,
+    pub seen: usize,
+    /// Human readable, e.g. "3 years 2 months"
+    pub guess_time: String,
+}
+
+impl From<Entropy> for EntropyReport {
+    fn from(entropy: Entropy) -> Self {
+        EntropyReport {
+            blind_min: entropy.blind_min,
+            blind_max: entropy.blind_max,
+            seen: entropy.seen,
+            guess_time: entropy.guess_time.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Generated {
+    pub passwords: Vec<String>,
+    pub entropy: EntropyReport,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresetInfo {
+    pub name: String,
+    pub description: String,
+    pub recommended_use: String,
+    pub example: String,
+    pub entropy: EntropyReport,
+}
+
+pub fn generate(request: GenerateRequest) -> Result<Generated, String> {
+    if request.count == 0 || request.count > MAX_COUNT {
+        return Err(format!("count must be between 1 and {}", MAX_COUNT));
+    }
+
+    let pass_generator = Xkpasswd::preloaded();
+    let settings = request.settings.settings()?;
+    settings.validate_against(pass_generator)?;
+
+    let (passwords, entropy) = pass_generator.gen_passes(&settings, request.count);
+
+    Ok(Generated {
+        passwords,
+        entropy: entropy.into(),
+    })
+}
+
+/// Built-in presets, each with an example password.
+pub fn list_presets() -> Vec<PresetInfo> {
+    let pass_generator = Xkpasswd::preloaded();
+
+    Preset::ALL
+        .into_iter()
+        .map(|preset| {
+            let (example, entropy) = pass_generator.gen_pass(&Settings::from_preset(preset));
+
+            PresetInfo {
+                name: preset.name().to_string(),
+                description: preset.description().to_string(),
+                recommended_use: preset.recommended_use().to_string(),
+                example,
+                entropy: entropy.into(),
+            }
+        })
+        .collect()
+}
+
+/// Entropy of the settings without generating any password, e.g. for a strength meter.
+pub fn entropy_for(request: SettingsRequest) -> Result<EntropyReport, String> {
+    let pass_generator = Xkpasswd::preloaded();
+    let settings = request.settings()?;
+    let pool_size = pass_generator.words_pool(&settings).len();
+
+    Ok(settings.calc_entropy(pool_size).into())
+}
@@ -0,0 +1,114 @@
+use super::*;
+
+#[test]
+fn test_settings_request() {
+    assert_eq!(
+        Settings::default(),
+        SettingsRequest::default().settings().unwrap()
+    );
+
+    let request = SettingsRequest {
+        preset: Some("XKCD".to_string()),
+        query: None,
+    };
+    assert_eq!(
+        Settings::from_preset(Preset::Xkcd),
+        request.settings().unwrap()
+    );
+
+    // the query string wins
+    let request = SettingsRequest {
+        preset: Some("xkcd".to_string()),
+        query: Some("words=5".to_string()),
+    };
+    assert_eq!(5, request.settings().unwrap().words_count());
+
+    let request = SettingsRequest {
+        preset: Some("unknown".to_string()),
+        query: None,
+    };
+    assert_eq!(
+        Err("unknown preset 'unknown'".to_string()),
+        request.settings()
+    );
+}
+
+#[test]
+fn test_generate() {
+    let request = GenerateRequest {
+        settings: SettingsRequest {
+            preset: Some("wifi".to_string()),
+            query: None,
+        },
+        count: 3,
+    };
+    let generated = generate(request).unwrap();
+    let settings = Settings::from_preset(Preset::Wifi);
+
+    assert_eq!(3, generated.passwords.len());
+    for passwd in &generated.passwords {
+        assert!(
+            settings.matches(passwd, Xkpasswd::preloaded()),
+            "{}",
+            passwd
+        );
+    }
+
+    let entropy = entropy_for(SettingsRequest {
+        preset: Some("wifi".to_string()),
+        query: None,
+    })
+    .unwrap();
+    assert_eq!(entropy, generated.entropy);
+
+    for count in [0, MAX_COUNT + 1] {
+        let request = GenerateRequest {
+            count,
+            ..GenerateRequest::default()
+        };
+        assert_eq!(
+            Err("count must be between 1 and 100".to_string()),
+            generate(request)
+        );
+    }
+}
+
+#[test]
+fn test_list_presets() {
+    let presets = list_presets();
+    assert_eq!(Preset::ALL.len(), presets.len());
+
+    let xkcd = presets.iter().find(|info| info.name == "xkcd").unwrap();
+    assert_eq!(Preset::Xkcd.description(), xkcd.description);
+    assert!(Settings::from_preset(Preset::Xkcd).matches(&xkcd.example, Xkpasswd::preloaded()));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_serialization() {
+    let request: GenerateRequest = serde_json::from_str(r#"{"preset": "web32"}"#).unwrap();
+    assert_eq!(
+        GenerateRequest {
+            settings: SettingsRequest {
+                preset: Some("web32".to_string()),
+                query: None,
+            },
+            count: 1,
+        },
+        request
+    );
+
+    let generated = Generated {
+        passwords: vec!["correct.horse".to_string()],
+        entropy: EntropyReport {
+            blind_min: 40,
+            blind_max: 52,
+            seen: 30,
+            guess_time: "less than a day".to_string(),
+        },
+    };
+    assert_eq!(
+        r#"{"passwords":["correct.horse"],"entropy":{"blind_min":40,"blind_max":52,"seen":30,"guess_time":"less than a day"}}"#,
+        serde_json::to_string(&generated).unwrap()
+    );
+}
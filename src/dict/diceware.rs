@@ -0,0 +1,108 @@
+use super::{Dict, WordSource};
+
+pub const DICE_PER_WORD: usize = 5;
+const TABLE_SIZE: usize = 6usize.pow(DICE_PER_WORD as u32);
+
+/// A bundled table with one `<5 dice>\t<word>` line per entry, 7776 entries from "11111" to "66666",
+/// filled with words from the English dictionary. Only the layout is that of the EFF large wordlist,
+/// the same rolls give different words.
+#[cfg(feature = "lang_en")]
+pub const DICEWARE_EN: &str = include_str!("../assets/diceware_en.txt");
+
+/// Diceware table mapping every sequence of 5 dice rolls to exactly one word,
+/// so passwords can be picked with physical dice instead of a machine RNG.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DicewareTable<'a> {
+    // words[i] is the entry whose rolls, read as a base 6 number, equal i
+    words: Vec<&'a str>,
+}
+
+impl<'a> DicewareTable<'a> {
+    /// Parses a table in the EFF format, requiring every roll from "11111" to "66666" exactly once.
+    pub fn parse(table_str: &'a str) -> Result<Self, String> {
+        let mut words: Vec<Option<&'a str>> = vec![None; TABLE_SIZE];
+
+        for line in table_str
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+        {
+            let Some((rolls, word)) = line.split_once(char::is_whitespace) else {
+                return Err(format!("invalid diceware entry '{}'", line));
+            };
+
+            let idx = rolls_to_index(rolls)?;
+            let word = word.trim();
+
+            if word.is_empty() {
+                return Err(format!("missing word for rolls '{}'", rolls));
+            }
+
+            if words[idx].replace(word).is_some() {
+                return Err(format!("duplicated diceware entry for rolls '{}'", rolls));
+            }
+        }
+
+        words
+            .into_iter()
+            .enumerate()
+            .map(|(idx, word)| {
+                word.ok_or_else(|| {
+                    format!("missing diceware entry for rolls '{}'", index_to_rolls(idx))
+                })
+            })
+            .collect::<Result<Vec<&'a str>, String>>()
+            .map(|words| Self { words })
+    }
+
+    /// Word for a single group of 5 rolls, e.g. "16325".
+    pub fn lookup(&self, rolls: &str) -> Result<&'a str, String> {
+        rolls_to_index(rolls).map(|idx| self.words[idx])
+    }
+
+    /// Words for all roll groups in `rolls_str`, separated by whitespace or commas.
+    pub fn lookup_all(&self, rolls_str: &str) -> Result<Vec<&'a str>, String> {
+        let words = rolls_str
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|rolls| !rolls.is_empty())
+            .map(|rolls| self.lookup(rolls))
+            .collect::<Result<Vec<&'a str>, String>>()?;
+
+        if words.is_empty() {
+            return Err("no dice rolls given".to_string());
+        }
+
+        Ok(words)
+    }
+}
+
+impl WordSource for DicewareTable<'_> {
+    fn dict(&self) -> Dict<'_> {
+        Dict::from_words(self.words.iter().copied())
+    }
+}
+
+fn rolls_to_index(rolls: &str) -> Result<usize, String> {
+    if rolls.len() != DICE_PER_WORD {
+        return Err(format!(
+            "expected {} dice rolls but got '{}'",
+            DICE_PER_WORD, rolls
+        ));
+    }
+
+    rolls.chars().try_fold(0, |idx, roll| match roll {
+        '1'..='6' => Ok(idx * 6 + (roll as usize - '1' as usize)),
+        _ => Err(format!("invalid dice roll '{}' in '{}'", roll, rolls)),
+    })
+}
+
+fn index_to_rolls(mut idx: usize) -> String {
+    let mut rolls = ['1'; DICE_PER_WORD];
+
+    for roll in rolls.iter_mut().rev() {
+        *roll = (b'1' + (idx % 6) as u8) as char;
+        idx /= 6;
+    }
+
+    rolls.iter().collect()
+}
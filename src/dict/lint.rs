@@ -0,0 +1,206 @@
+use super::{csv_word, theme_name, DictFormat};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::from_utf8;
+
+/// A problem with one line of a dictionary, found by `lint_dict`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DictIssue {
+    InvalidUtf8,
+    /// A line of the crate's format without a `len:` prefix
+    MissingLength,
+    /// A CSV row with something else than a number after the word, past the header
+    InvalidFrequency,
+    InvalidJson(String),
+    NonAlphabetic(String),
+    /// A word listed under another length than its own, in the crate's format
+    WrongLength {
+        word: String,
+        listed: u8,
+    },
+    /// Same as an earlier word ignoring case, at the given position
+    Duplicate {
+        word: String,
+        first_line: usize,
+    },
+}
+
+impl fmt::Display for DictIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUtf8 => write!(f, "not valid UTF-8"),
+            Self::MissingLength => write!(f, "expected 'len:word,word,...'"),
+            Self::InvalidFrequency => write!(f, "expected 'word,frequency'"),
+            Self::InvalidJson(err) => write!(f, "invalid JSON: {}", err),
+            Self::NonAlphabetic(word) => write!(f, "'{}' has non-alphabetic characters", word),
+            Self::WrongLength { word, listed } => write!(
+                f,
+                "'{}' is listed with length {} but has {} chars",
+                word,
+                listed,
+                word.chars().count()
+            ),
+            Self::Duplicate { word, first_line } => {
+                write!(f, "'{}' duplicates line {}", word, first_line)
+            }
+        }
+    }
+}
+
+/// Issues found in a dictionary along with its words cleaned up.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DictLint {
+    pub format: DictFormat,
+    /// Issues with their positions from 1: line numbers,
+    /// or entry numbers in JSON arrays except for syntax errors
+    pub issues: Vec<(usize, DictIssue)>,
+    /// Words without issues or only listed with the wrong length, in order of appearance
+    pub words: Vec<String>,
+}
+
+impl DictLint {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// The cleaned up words in the crate's format, bucketed by their actual lengths.
+    pub fn cleaned(&self) -> String {
+        let mut buckets: Vec<(usize, Vec<&str>)> = vec![];
+
+        for word in &self.words {
+            let len = word.chars().count();
+
+            match buckets
+                .iter_mut()
+                .find(|(bucket_len, _)| *bucket_len == len)
+            {
+                Some((_, bucket)) => bucket.push(word),
+                None => buckets.push((len, vec![word])),
+            }
+        }
+
+        buckets.sort_by_key(|(len, _)| *len);
+        buckets
+            .iter()
+            .map(|(len, bucket)| format!("{}:{}\n", len, bucket.join(",")))
+            .collect()
+    }
+}
+
+/// Checks a dictionary in any of the supported formats, see `DictFormat::detect`,
+/// for what `load_any_dict` would silently skip, drop or misplace.
+pub fn lint_dict(dict_bytes: &[u8]) -> DictLint {
+    let format = DictFormat::detect(dict_bytes);
+    let mut lint = DictLint {
+        format,
+        ..DictLint::default()
+    };
+
+    // words with their positions & listed lengths, as found before any check
+    let mut entries: Vec<(usize, String, Option<u8>)> = vec![];
+
+    if format == DictFormat::Json {
+        match parse_json_words(dict_bytes) {
+            Ok(words) => entries.extend(
+                words
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, word)| (idx + 1, word, None)),
+            ),
+            Err((line, err)) => lint.issues.push((line, DictIssue::InvalidJson(err))),
+        }
+    }
+
+    let lines = match format {
+        DictFormat::Json => vec![],
+        _ => dict_bytes.split(|&byte| byte == b'\n').collect(),
+    };
+    let mut is_first_row = true;
+
+    for (idx, line) in lines.into_iter().enumerate() {
+        let line_number = idx + 1;
+
+        let Ok(line) = from_utf8(line) else {
+            lint.issues.push((line_number, DictIssue::InvalidUtf8));
+            continue;
+        };
+
+        let line = line.trim();
+        if line.is_empty() || theme_name(line).is_some() {
+            continue;
+        }
+
+        match format {
+            DictFormat::Json | DictFormat::WordList => {
+                entries.push((line_number, line.to_string(), None))
+            }
+            DictFormat::Csv => match csv_word(line) {
+                Some(word) => entries.push((line_number, word.to_string(), None)),
+                None if is_first_row => {}
+                None => lint.issues.push((line_number, DictIssue::InvalidFrequency)),
+            },
+            DictFormat::Buckets => match line
+                .split_once(':')
+                .and_then(|(len_str, words)| Some((len_str.parse::<u8>().ok()?, words)))
+            {
+                Some((len, words)) => entries.extend(
+                    words
+                        .split(',')
+                        .map(|word| (line_number, word.to_string(), Some(len))),
+                ),
+                None => lint.issues.push((line_number, DictIssue::MissingLength)),
+            },
+        }
+
+        is_first_row = false;
+    }
+
+    // lowercased words, the positions they first appeared at & their index in the cleaned words
+    let mut seen: HashMap<String, (usize, usize)> = HashMap::new();
+
+    for (position, word, listed) in entries {
+        if word.is_empty() || !word.chars().all(char::is_alphabetic) {
+            lint.issues.push((position, DictIssue::NonAlphabetic(word)));
+            continue;
+        }
+
+        if let Some(&(first_line, idx)) = seen.get(&word.to_lowercase()) {
+            // like loaded dictionaries, the cleaned one keeps the lowercase spelling
+            if word.chars().all(|char| !char.is_uppercase()) {
+                lint.words[idx] = word.clone();
+            }
+
+            lint.issues
+                .push((position, DictIssue::Duplicate { word, first_line }));
+            continue;
+        }
+
+        if let Some(listed) = listed.filter(|&len| len as usize != word.chars().count()) {
+            lint.issues.push((
+                position,
+                DictIssue::WrongLength {
+                    word: word.clone(),
+                    listed,
+                },
+            ));
+        }
+
+        seen.insert(word.to_lowercase(), (position, lint.words.len()));
+        lint.words.push(word);
+    }
+
+    // stable sort, issues of the same line keep the order they were found in
+    lint.issues.sort_by_key(|(position, _)| *position);
+    lint
+}
+
+// owned, so escaped words are decoded
+#[cfg(feature = "json")]
+fn parse_json_words(dict_bytes: &[u8]) -> Result<Vec<String>, (usize, String)> {
+    serde_json::from_slice(dict_bytes).map_err(|err| (err.line(), err.to_string()))
+}
+
+#[cfg(not(feature = "json"))]
+fn parse_json_words(_dict_bytes: &[u8]) -> Result<Vec<String>, (usize, String)> {
+    Err((1, "requires the 'json' feature".to_string()))
+}
@@ -0,0 +1,35 @@
+use super::{load_word_list, Dict, WordSource};
+use memmap2::Mmap;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+/// A plain word list (one word per line) mapped into memory instead of read into it,
+/// for wordlists too large to be loaded upfront.
+/// Only the index of words is allocated, their content stays in the file.
+#[allow(dead_code)] // library only, unused by the CLI binary
+#[derive(Debug)]
+pub struct MmapWords {
+    mmap: Mmap,
+}
+
+#[allow(dead_code)]
+impl MmapWords {
+    /// Maps the word list at `path` into memory.
+    ///
+    /// The file must not be modified while it's mapped,
+    /// otherwise words read from it may change or become invalid.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the mapping is read-only, and callers are required
+        // not to modify the file for the lifetime of `MmapWords`
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(MmapWords { mmap })
+    }
+}
+
+impl WordSource for MmapWords {
+    fn dict(&self) -> Dict<'_> {
+        load_word_list(&self.mmap)
+    }
+}
@@ -0,0 +1,649 @@
+// the CLI only looks up the built-in English table
+#[cfg_attr(not(feature = "lang_en"), allow(dead_code))]
+pub mod diceware;
+pub mod lint;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+#[cfg(feature = "http")]
+pub mod remote;
+pub mod syllables;
+#[cfg(test)]
+mod tests;
+
+use crate::prelude::SpellingVariant;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+use std::str::*;
+
+const BINARY_DICT_MAGIC: &[u8] = b"XKD1";
+const INVALID_BINARY_DICT_ERR: &str = "invalid binary dictionary";
+const THEME_PREFIX: &str = "@";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DictFormat {
+    /// The crate's own format, one `len:word,word,...` line per length
+    Buckets,
+    /// One word per line, like `/usr/share/dict/words`
+    #[default]
+    WordList,
+    /// An array of words, e.g. `["foo", "bar"]`
+    Json,
+    /// One word per row, optionally followed by a frequency column & a header row,
+    /// like spreadsheet exports
+    Csv,
+}
+
+impl DictFormat {
+    /// Guesses the format from the first non-blank line apart from `@theme` ones,
+    /// without scanning the whole input.
+    pub fn detect(dict_bytes: &[u8]) -> Self {
+        let first_line = dict_bytes
+            .split(|&byte| byte == b'\n')
+            .map(|line| line.trim_ascii())
+            .find(|line| !line.is_empty() && !line.starts_with(THEME_PREFIX.as_bytes()));
+
+        // a length prefix like "5:" only appears in the crate's format
+        let is_buckets = first_line
+            .and_then(|line| {
+                let colon_idx = line.iter().position(|&byte| byte == b':')?;
+                Some(colon_idx > 0 && line[..colon_idx].iter().all(u8::is_ascii_digit))
+            })
+            .unwrap_or(false);
+
+        if is_buckets {
+            DictFormat::Buckets
+        } else if first_line.is_some_and(|line| line.starts_with(b"[")) {
+            DictFormat::Json
+        } else if first_line.is_some_and(|line| line.contains(&b',')) {
+            DictFormat::Csv
+        } else {
+            DictFormat::WordList
+        }
+    }
+}
+
+/// Anything a dictionary can be built from, with words borrowed from the source itself.
+pub trait WordSource {
+    fn dict(&self) -> Dict<'_>;
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Dict<'a> {
+    // all words ordered by length, so any range of lengths maps to a contiguous slice
+    words: Vec<&'a str>,
+    // offsets[len] is the index of the first word whose length is at least len
+    offsets: Vec<usize>,
+    // case-insensitive duplicates dropped at load time
+    duplicates: usize,
+    // words of each `@theme` section by lowercased theme name, also part of the words above
+    themes: BTreeMap<String, Dict<'a>>,
+    // the same words without proper nouns, only when there are any
+    common_words: Option<Box<Dict<'a>>>,
+    // the same words without American & without British spellings, when variants are tagged
+    spellings: Option<Box<(Dict<'a>, Dict<'a>)>>,
+    // lowercased words sounding like others, when listed
+    homophones: HashSet<&'a str>,
+}
+
+/// Counts of a loaded dictionary, e.g. to tell how much a user dictionary was cleaned up.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DictStats {
+    pub words: usize,
+    /// Words of each length, from the shortest
+    pub lengths: Vec<(u8, usize)>,
+    /// Entries dropped for repeating an earlier word, ignoring case
+    pub duplicates: usize,
+    pub proper_nouns: usize,
+}
+
+impl<'a> Dict<'a> {
+    pub fn pool(&self, lengths: Range<u8>) -> &[&'a str] {
+        let Some(last) = self.offsets.len().checked_sub(1) else {
+            return &[];
+        };
+
+        let start = self.offsets[last.min(lengths.start as usize)];
+        let end = self.offsets[last.min(lengths.end as usize)];
+
+        if start >= end {
+            return &[];
+        }
+
+        &self.words[start..end]
+    }
+
+    pub fn bucket(&self, len: u8) -> &[&'a str] {
+        self.pool(len..len.saturating_add(1))
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Words listed under an `@theme` line, e.g. `@animals`, ignoring case.
+    pub fn theme(&self, name: &str) -> Option<&Dict<'a>> {
+        self.themes.get(&name.trim().to_lowercase())
+    }
+
+    /// The same dictionary without proper nouns, see `is_proper_noun`.
+    pub fn without_proper_nouns(&self) -> &Dict<'a> {
+        self.common_words.as_deref().unwrap_or(self)
+    }
+
+    /// The same dictionary without the spellings of the other variant,
+    /// unchanged unless variants are tagged, see `with_spelling_variants`.
+    pub fn spelling(&self, variant: SpellingVariant) -> &Dict<'a> {
+        match (&self.spellings, variant) {
+            (Some(spellings), SpellingVariant::British) => &spellings.0,
+            (Some(spellings), SpellingVariant::American) => &spellings.1,
+            _ => self,
+        }
+    }
+
+    /// Tags spelling variants from lines of `british:american` pairs, e.g. `colour:color`,
+    /// so each variant can leave out the other's spellings.
+    pub fn with_spelling_variants(mut self, variants: &str) -> Self {
+        let pairs: Vec<(&str, &str)> = variants
+            .lines()
+            .filter_map(|line| line.trim().split_once(':'))
+            .collect();
+
+        let british: HashSet<&str> = pairs.iter().map(|(british, _)| *british).collect();
+        let american: HashSet<&str> = pairs.iter().map(|(_, american)| *american).collect();
+
+        self.spellings = Some(Box::new((
+            self.retain_words(|word| !american.contains(word)),
+            self.retain_words(|word| !british.contains(word)),
+        )));
+        self
+    }
+
+    /// Lists words sounding like others from lines of space separated groups, e.g. `their there`,
+    /// to be left out of passwords read aloud. Lines starting with `#` are comments.
+    pub fn with_homophones(mut self, homophones: &'a str) -> Self {
+        self.homophones = homophones
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with('#'))
+            .flat_map(str::split_whitespace)
+            .collect();
+        self
+    }
+
+    /// Tags proper nouns from lines of names in their canonical capitalization, e.g. `Paris`,
+    /// replacing the words they match ignoring case, for dictionaries listing everything
+    /// in lowercase. Lines starting with `#` are comments.
+    pub fn with_proper_nouns(self, names: &'a str) -> Self {
+        let names: HashMap<String, &'a str> = names
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|name| (name.to_lowercase(), name))
+            .collect();
+
+        self.map_words(&|word| {
+            names
+                .get(word.to_lowercase().as_str())
+                .copied()
+                .unwrap_or(word)
+        })
+    }
+
+    /// Whether `word` was listed by `with_homophones`, ignoring case.
+    pub fn is_homophone(&self, word: &str) -> bool {
+        self.homophones.contains(word.to_lowercase().as_str())
+    }
+
+    /// Names of all themes, in alphabetical order.
+    pub fn themes(&self) -> Vec<&str> {
+        self.themes.keys().map(String::as_str).collect()
+    }
+
+    pub fn stats(&self) -> DictStats {
+        let lengths = (0..self.offsets.len().saturating_sub(1))
+            .map(|len| (len as u8, self.offsets[len + 1] - self.offsets[len]))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+
+        DictStats {
+            words: self.words.len(),
+            lengths,
+            duplicates: self.duplicates,
+            proper_nouns: self.words.len() - self.without_proper_nouns().len(),
+        }
+    }
+
+    fn from_buckets(mut buckets: Vec<(u8, Vec<&'a str>)>) -> Self {
+        // stable sort, words keep their original order within a bucket
+        buckets.sort_by_key(|(len, _)| *len);
+
+        // user dictionaries often repeat words or list them in several cases,
+        // making them more likely to be picked, so only one is kept:
+        // the lowercase one if listed, as capitals are left to word transforms, else the first one
+        let mut kept: HashMap<String, &'a str> = HashMap::new();
+
+        for word in buckets.iter().flat_map(|(_, bucket)| bucket) {
+            kept.entry(word.to_lowercase())
+                .and_modify(|kept_word| {
+                    if word.chars().all(|char| !char.is_uppercase()) {
+                        *kept_word = word;
+                    }
+                })
+                .or_insert(word);
+        }
+
+        let mut duplicates = 0;
+
+        for (_, bucket) in buckets.iter_mut() {
+            bucket.retain(|word| {
+                let is_kept = kept.get(&word.to_lowercase()) == Some(word);
+                // the kept spelling itself may be repeated too
+                if is_kept {
+                    kept.remove(&word.to_lowercase());
+                }
+                duplicates += usize::from(!is_kept);
+                is_kept
+            });
+        }
+
+        let max_len = match buckets.last() {
+            Some((len, _)) => *len as usize,
+            None => return Self::default(),
+        };
+
+        let mut offsets: Vec<usize> = Vec::with_capacity(max_len + 2);
+        let mut words: Vec<&'a str> = vec![];
+        let mut buckets = buckets.into_iter().peekable();
+
+        for len in 0..=(max_len + 1) {
+            offsets.push(words.len());
+
+            while let Some((_, bucket)) =
+                buckets.next_if(|(bucket_len, _)| *bucket_len as usize == len)
+            {
+                words.extend(bucket);
+            }
+        }
+
+        let mut dict = Dict {
+            words,
+            offsets,
+            duplicates,
+            themes: BTreeMap::new(),
+            common_words: None,
+            spellings: None,
+            homophones: HashSet::new(),
+        };
+
+        if dict.words.iter().any(|word| is_proper_noun(word)) {
+            let common_words = dict.retain_words(|word| !is_proper_noun(word));
+            dict.common_words = Some(Box::new(common_words));
+        }
+
+        dict
+    }
+
+    // a new dictionary of the words passing `predicate`, each kept under its listed length
+    fn retain_words<F: Fn(&str) -> bool>(&self, predicate: F) -> Dict<'a> {
+        let buckets = (0..self.offsets.len().saturating_sub(1))
+            .map(|len| {
+                let bucket = self.bucket(len as u8).iter().copied();
+                (len as u8, bucket.filter(|word| predicate(word)).collect())
+            })
+            .collect();
+
+        Self::from_buckets(buckets)
+    }
+
+    // the same dictionary with each word replaced by `map`, still under its listed length
+    fn map_words(&self, map: &dyn Fn(&'a str) -> &'a str) -> Dict<'a> {
+        let buckets = (0..self.offsets.len().saturating_sub(1))
+            .map(|len| {
+                let bucket = self.bucket(len as u8).iter().copied();
+                (len as u8, bucket.map(map).collect())
+            })
+            .collect();
+
+        Dict {
+            duplicates: self.duplicates,
+            themes: self
+                .themes
+                .iter()
+                .map(|(name, theme)| (name.clone(), theme.map_words(map)))
+                .collect(),
+            spellings: self.spellings.as_deref().map(|(british, american)| {
+                Box::new((british.map_words(map), american.map_words(map)))
+            }),
+            homophones: self.homophones.clone(),
+            ..Self::from_buckets(buckets)
+        }
+    }
+
+    fn from_words<I: IntoIterator<Item = &'a str>>(words: I) -> Self {
+        Self::from_buckets(bucket_words(words))
+    }
+
+    fn with_themes(mut self, themes: BTreeMap<String, Dict<'a>>) -> Self {
+        self.themes = themes;
+        self
+    }
+}
+
+/// Words starting with an uppercase letter, like `Paris` in `/usr/share/dict/words`,
+/// are taken as proper nouns: dictionaries mark them by their canonical capitalization.
+/// Lowercase dictionaries are tagged by `Dict::with_proper_nouns`, like the bundled English one.
+/// Dictionaries of languages capitalizing all nouns, like German, need lowercasing first.
+pub fn is_proper_noun(word: &str) -> bool {
+    word.chars().next().is_some_and(char::is_uppercase)
+}
+
+fn bucket_words<'a, I: IntoIterator<Item = &'a str>>(words: I) -> Vec<(u8, Vec<&'a str>)> {
+    let mut buckets: Vec<Vec<&'a str>> = vec![];
+
+    for word in words {
+        let len = word.chars().count();
+
+        if len == 0 || len > u8::MAX as usize {
+            continue;
+        }
+
+        if buckets.len() <= len {
+            buckets.resize_with(len + 1, Vec::new);
+        }
+
+        buckets[len].push(word);
+    }
+
+    buckets
+        .into_iter()
+        .enumerate()
+        .filter(|(_, bucket)| !bucket.is_empty())
+        .map(|(len, bucket)| (len as u8, bucket))
+        .collect()
+}
+
+// a line like `@animals` starts a theme section, lasting until the next one,
+// a bare `@` ends it; themed words are loaded as usual & then once more per theme
+fn theme_name(line: &str) -> Option<&str> {
+    line.strip_prefix(THEME_PREFIX).map(str::trim)
+}
+
+fn load_themes<'a, I: IntoIterator<Item = &'a str>>(
+    lines: I,
+    format: DictFormat,
+) -> BTreeMap<String, Dict<'a>> {
+    let mut themed_buckets: BTreeMap<String, Vec<(u8, Vec<&'a str>)>> = BTreeMap::new();
+    let mut theme: Option<String> = None;
+
+    for line in lines.into_iter().map(str::trim) {
+        if let Some(name) = theme_name(line) {
+            theme = Some(name.to_lowercase()).filter(|name| !name.is_empty());
+            continue;
+        }
+
+        let Some(theme) = &theme else {
+            continue;
+        };
+
+        let buckets = match format {
+            DictFormat::Buckets => parse_bucket_line(line).into_iter().collect(),
+            DictFormat::Csv => bucket_words(csv_word(line)),
+            DictFormat::WordList | DictFormat::Json => bucket_words([line]),
+        };
+
+        themed_buckets
+            .entry(theme.clone())
+            .or_default()
+            .extend(buckets);
+    }
+
+    themed_buckets
+        .into_iter()
+        .map(|(theme, buckets)| (theme, Dict::from_buckets(buckets)))
+        .collect()
+}
+
+fn parse_bucket_line(line: &str) -> Option<(u8, Vec<&str>)> {
+    let (len_str, words_csv) = line.split_once(':')?;
+    let len = len_str.parse::<u8>().ok()?;
+
+    let words: Vec<&str> = words_csv
+        .split(',')
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    Some((len, words))
+}
+
+/// Loads a dictionary in the crate's format, one `len:word,word,...` line per length.
+/// Empty entries are skipped, lines without a length prefix are reported with their number.
+///
+/// Like the other line based formats, lines can be grouped into themes by `@theme` lines.
+pub fn load_dict(dict_bytes: &[u8]) -> Result<Dict<'_>, String> {
+    let dict_str = from_utf8(dict_bytes)
+        .map_err(|err| format!("invalid UTF-8 at byte {}", err.valid_up_to()))?;
+    let mut buckets: Vec<(u8, Vec<&str>)> = vec![];
+
+    log::debug!("loaded raw dict with {} lines", dict_str.lines().count());
+
+    for (idx, line) in dict_str.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || theme_name(line).is_some() {
+            continue;
+        }
+
+        let bucket = parse_bucket_line(line)
+            .ok_or_else(|| format!("expected 'len:word,word,...' at line {}", idx + 1))?;
+        buckets.push(bucket);
+    }
+
+    let dict =
+        Dict::from_buckets(buckets).with_themes(load_themes(dict_str.lines(), DictFormat::Buckets));
+    log::debug!(
+        "parsed dict with {:?} entries, {} duplicates dropped",
+        dict.len(),
+        dict.duplicates
+    );
+
+    Ok(dict)
+}
+
+/// Loads a dictionary in any of the supported formats, see `DictFormat::detect`.
+#[cfg_attr(feature = "trace", tracing::instrument(level = "debug", skip_all, fields(bytes = dict_bytes.len()), err))]
+pub fn load_any_dict(dict_bytes: &[u8]) -> Result<Dict<'_>, String> {
+    match DictFormat::detect(dict_bytes) {
+        DictFormat::Buckets => load_dict(dict_bytes),
+        DictFormat::WordList => Ok(load_word_list(dict_bytes)),
+        DictFormat::Json => load_json_dict(dict_bytes),
+        DictFormat::Csv => load_csv_dict(dict_bytes),
+    }
+}
+
+/// Loads a JSON array of words, bucketing them by their length.
+/// Words are borrowed from `dict_bytes` unless any contains escape sequences, e.g. `"caf\u00e9"`:
+/// those dictionaries are decoded & leaked like fetched ones, so they should be loaded once.
+#[cfg(feature = "json")]
+pub fn load_json_dict(dict_bytes: &[u8]) -> Result<Dict<'_>, String> {
+    let words: Vec<&str> = match serde_json::from_slice(dict_bytes) {
+        Ok(words) => words,
+        Err(_) => serde_json::from_slice::<Vec<String>>(dict_bytes)
+            .map_err(|err| format!("invalid JSON dictionary: {}", err))?
+            .into_iter()
+            .map(|word| &*word.leak())
+            .collect(),
+    };
+
+    let dict = Dict::from_words(words.into_iter().map(str::trim));
+    log::debug!("parsed JSON dict with {:?} entries", dict.len());
+
+    Ok(dict)
+}
+
+#[cfg(not(feature = "json"))]
+pub fn load_json_dict(_dict_bytes: &[u8]) -> Result<Dict<'_>, String> {
+    Err("JSON dictionaries require the 'json' feature".to_string())
+}
+
+/// Loads words from the first column of CSV rows, bucketing them by their length.
+/// A second column must be a number, e.g. a frequency, but is otherwise ignored
+/// so every word stays equally likely, as entropy estimates assume.
+/// A first row without a number there is taken as a header.
+pub fn load_csv_dict(dict_bytes: &[u8]) -> Result<Dict<'_>, String> {
+    let dict_str = from_utf8(dict_bytes)
+        .map_err(|err| format!("invalid UTF-8 at byte {}", err.valid_up_to()))?;
+    let mut words: Vec<&str> = vec![];
+    let mut is_first_row = true;
+
+    for (idx, row) in dict_str.lines().enumerate() {
+        let row = row.trim();
+        if row.is_empty() || theme_name(row).is_some() {
+            continue;
+        }
+
+        match csv_word(row) {
+            Some(word) => words.push(word),
+            None if is_first_row => log::debug!("skipping CSV header '{}'", row),
+            None => return Err(format!("expected 'word,frequency' at line {}", idx + 1)),
+        }
+
+        is_first_row = false;
+    }
+
+    let dict = Dict::from_words(words).with_themes(load_themes(dict_str.lines(), DictFormat::Csv));
+    log::debug!("parsed CSV dict with {:?} entries", dict.len());
+
+    Ok(dict)
+}
+
+// the unquoted first field of a row, unless the second one is set but isn't a number
+pub(super) fn csv_word(row: &str) -> Option<&str> {
+    let (word, rest) = split_csv_field(row)?;
+    let frequency = match rest {
+        Some(rest) => split_csv_field(rest)?.0,
+        None => "",
+    };
+
+    (frequency.is_empty() || frequency.parse::<f64>().is_ok()).then_some(word)
+}
+
+// the first field of a row, trimmed & unquoted, along with the rest after its comma if any;
+// quoted fields may hold commas, but not escaped quotes, which can't be borrowed unescaped
+fn split_csv_field(row: &str) -> Option<(&str, Option<&str>)> {
+    let row = row.trim_start();
+
+    let Some(quoted) = row.strip_prefix('"') else {
+        return Some(match row.split_once(',') {
+            Some((field, rest)) => (field.trim(), Some(rest)),
+            None => (row.trim(), None),
+        });
+    };
+
+    let (field, rest) = quoted.split_once('"')?;
+    let rest = rest.trim_start();
+
+    match rest.strip_prefix(',') {
+        Some(rest) => Some((field, Some(rest))),
+        None if rest.is_empty() => Some((field, None)),
+        None => None,
+    }
+}
+
+/// Loads a plain list of one word per line, bucketing words by their length.
+/// Blank lines, lines that aren't valid UTF-8 and words longer than 255 chars are skipped.
+pub fn load_word_list(dict_bytes: &[u8]) -> Dict<'_> {
+    let words = dict_bytes
+        .split(|&byte| byte == b'\n')
+        .filter_map(|line| from_utf8(line).ok())
+        .map(str::trim)
+        .filter(|line| theme_name(line).is_none());
+
+    let dict = Dict::from_words(words).with_themes(load_themes(
+        dict_bytes
+            .split(|&byte| byte == b'\n')
+            .filter_map(|line| from_utf8(line).ok()),
+        DictFormat::WordList,
+    ));
+    log::debug!(
+        "parsed word list with {:?} entries, {} duplicates dropped",
+        dict.len(),
+        dict.duplicates
+    );
+
+    dict
+}
+
+/// Loads a dictionary in the binary format generated by `build.rs`,
+/// borrowing all words straight from `dict_bytes`.
+#[cfg_attr(feature = "trace", tracing::instrument(level = "debug", skip_all, fields(bytes = dict_bytes.len()), err))]
+pub fn load_binary_dict(dict_bytes: &[u8]) -> Result<Dict<'_>, String> {
+    let data = dict_bytes
+        .strip_prefix(BINARY_DICT_MAGIC)
+        .ok_or(INVALID_BINARY_DICT_ERR)?;
+    let (&buckets_count, data) = data.split_first().ok_or(INVALID_BINARY_DICT_ERR)?;
+
+    let (buckets_header, data) = data
+        .split_at_checked(buckets_count as usize * 3)
+        .ok_or(INVALID_BINARY_DICT_ERR)?;
+    let buckets: Vec<(u8, usize)> = buckets_header
+        .chunks_exact(3)
+        .map(|chunk| (chunk[0], u16::from_le_bytes([chunk[1], chunk[2]]) as usize))
+        .collect();
+
+    let words_count = buckets.iter().map(|(_, count)| count).sum();
+    let (word_lengths, data) = data
+        .split_at_checked(words_count)
+        .ok_or(INVALID_BINARY_DICT_ERR)?;
+    let blob = from_utf8(data).map_err(|_| INVALID_BINARY_DICT_ERR)?;
+
+    let mut words: Vec<&str> = Vec::with_capacity(words_count);
+    let mut start = 0;
+
+    for &word_len in word_lengths {
+        let end = start + word_len as usize;
+        words.push(blob.get(start..end).ok_or(INVALID_BINARY_DICT_ERR)?);
+        start = end;
+    }
+
+    if start != blob.len() {
+        return Err(INVALID_BINARY_DICT_ERR.to_string());
+    }
+
+    let max_len = match buckets.last() {
+        Some((len, _)) => *len as usize,
+        None => return Ok(Dict::default()),
+    };
+
+    let mut offsets: Vec<usize> = Vec::with_capacity(max_len + 2);
+    let mut buckets = buckets.into_iter().peekable();
+    let mut offset = 0;
+
+    for len in 0..=(max_len + 1) {
+        offsets.push(offset);
+
+        while let Some((_, count)) = buckets.next_if(|(bucket_len, _)| *bucket_len as usize == len)
+        {
+            offset += count;
+        }
+    }
+
+    // buckets must be sorted by length for offsets to cover every word
+    if offset != words.len() {
+        return Err(INVALID_BINARY_DICT_ERR.to_string());
+    }
+
+    log::debug!("parsed binary dict with {:?} entries", words.len());
+
+    Ok(Dict {
+        words,
+        offsets,
+        duplicates: 0,
+        themes: BTreeMap::new(),
+        common_words: None,
+        spellings: None,
+        homophones: HashSet::new(),
+    })
+}
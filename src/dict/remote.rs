@@ -0,0 +1,56 @@
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::time::Duration;
+
+// well above the largest wordlists around, only meant to stop runaway responses
+const MAX_DICT_BYTES: u64 = 64 * 1024 * 1024;
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Downloads a dictionary in any supported format, failing unless its SHA-256 digest
+/// matches `sha256` (hex encoded, case-insensitive).
+pub fn fetch_dict(url: &str, sha256: &str) -> Result<Vec<u8>, String> {
+    let response = ureq::get(url)
+        .timeout(FETCH_TIMEOUT)
+        .call()
+        .map_err(|err| format!("failed to fetch {}: {}", url, err))?;
+
+    let mut dict_bytes: Vec<u8> = vec![];
+    response
+        .into_reader()
+        .take(MAX_DICT_BYTES + 1)
+        .read_to_end(&mut dict_bytes)
+        .map_err(|err| format!("failed to fetch {}: {}", url, err))?;
+
+    if dict_bytes.len() as u64 > MAX_DICT_BYTES {
+        return Err(format!(
+            "dictionary at {} is larger than {} bytes",
+            url, MAX_DICT_BYTES
+        ));
+    }
+
+    verify_sha256(&dict_bytes, sha256)?;
+    log::debug!(
+        "fetched {} bytes of dictionary from {}",
+        dict_bytes.len(),
+        url
+    );
+
+    Ok(dict_bytes)
+}
+
+pub fn verify_sha256(dict_bytes: &[u8], sha256: &str) -> Result<(), String> {
+    let digest: String = Sha256::digest(dict_bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    if digest.eq_ignore_ascii_case(sha256.trim()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "dictionary checksum mismatch, expected {} but got {}",
+            sha256.trim(),
+            digest
+        ))
+    }
+}
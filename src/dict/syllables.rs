@@ -0,0 +1,69 @@
+use super::{Dict, WordSource};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+const CONSONANTS: [char; 16] = [
+    'b', 'd', 'f', 'g', 'h', 'j', 'k', 'l', 'm', 'n', 'p', 'r', 's', 't', 'v', 'z',
+];
+const VOWELS: [char; 5] = ['a', 'e', 'i', 'o', 'u'];
+
+/// Pronounceable nonsense words made of alternating consonants & vowels,
+/// e.g. "bakito" or "olumes", for when real dictionary words aren't allowed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyllableWords {
+    words: Vec<String>,
+}
+
+impl SyllableWords {
+    // every word length allowed by settings
+    const DEFAULT_LENGTHS: RangeInclusive<u8> = 4..=10;
+    const DEFAULT_WORDS_PER_LENGTH: usize = 2000;
+
+    /// Generates up to `words_per_length` distinct words for each length in `lengths`.
+    pub fn generate(lengths: RangeInclusive<u8>, words_per_length: usize) -> Self {
+        let mut rng = crate::rng::rng();
+        let mut words: Vec<String> = vec![];
+
+        for len in lengths {
+            let mut seen: HashSet<String> = HashSet::new();
+            // bail out when the pool of possible words is nearly exhausted
+            let mut attempts = words_per_length * 4;
+
+            while seen.len() < words_per_length && attempts > 0 {
+                attempts -= 1;
+                seen.insert(gen_word(&mut rng, len as usize));
+            }
+
+            words.extend(seen);
+        }
+
+        SyllableWords { words }
+    }
+}
+
+impl Default for SyllableWords {
+    fn default() -> Self {
+        Self::generate(Self::DEFAULT_LENGTHS, Self::DEFAULT_WORDS_PER_LENGTH)
+    }
+}
+
+impl WordSource for SyllableWords {
+    fn dict(&self) -> Dict<'_> {
+        Dict::from_words(self.words.iter().map(String::as_str))
+    }
+}
+
+fn gen_word<R: Rng>(rng: &mut R, len: usize) -> String {
+    // words may start with either a consonant or a vowel, but always alternate after
+    let mut is_vowel = rng.gen_bool(0.25);
+
+    (0..len)
+        .map(|_| {
+            let pool: &[char] = if is_vowel { &VOWELS } else { &CONSONANTS };
+            is_vowel = !is_vowel;
+            *pool.choose(rng).unwrap()
+        })
+        .collect()
+}
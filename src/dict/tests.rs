@@ -0,0 +1,681 @@
+#[cfg(feature = "lang_en")]
+use super::diceware::*;
+use super::syllables::SyllableWords;
+use super::*;
+use std::collections::HashSet;
+
+#[test]
+fn test_load_dict_blank() {
+    let dict = load_dict(&[]).unwrap();
+    assert!(dict.is_empty());
+
+    let dict_bytes = "".as_bytes();
+    let dict = load_dict(dict_bytes).unwrap();
+    assert!(dict.is_empty());
+    assert!(dict.pool(0..u8::MAX).is_empty());
+}
+
+#[test]
+fn test_load_dict_valid_data() {
+    let table = [
+        "2:an,do\n3:foo,bar",
+        r#"
+        2:an,do
+        3:foo,bar
+        "#,
+        // buckets out of order
+        "3:foo,bar\n2:an,do",
+    ];
+
+    for dict_str in table {
+        let dict_bytes = dict_str.as_bytes();
+        let dict = load_dict(dict_bytes).unwrap();
+
+        assert_eq!(4, dict.len());
+        assert_eq!(vec!["an", "do"], dict.bucket(2));
+        assert_eq!(vec!["foo", "bar"], dict.bucket(3));
+        assert!(dict.bucket(4).is_empty());
+    }
+}
+
+#[test]
+fn test_load_dict_invalid_data() {
+    let table: [(&[u8], &str); 4] = [
+        (b"foo:3", "expected 'len:word,word,...' at line 1"),
+        (b"3:foo\n\nbar", "expected 'len:word,word,...' at line 3"),
+        (b"3:foo\n300:bar", "expected 'len:word,word,...' at line 2"),
+        (&[b'3', b':', 0xff], "invalid UTF-8 at byte 2"),
+    ];
+
+    for (dict_bytes, err) in table {
+        assert_eq!(Err(err.to_string()), load_dict(dict_bytes));
+    }
+
+    // empty entries are skipped
+    let dict = load_dict(b"3:foo,,bar,").unwrap();
+    assert_eq!(vec!["foo", "bar"], dict.bucket(3));
+}
+
+#[test]
+fn test_dict_pool() {
+    let dict = load_dict("2:an,do\n3:foo,bar\n5:hello,world\n6:foobar".as_bytes()).unwrap();
+
+    let table: [(Range<u8>, Vec<&str>); 8] = [
+        (0..2, vec![]),
+        (2..3, vec!["an", "do"]),
+        (2..4, vec!["an", "do", "foo", "bar"]),
+        (3..6, vec!["foo", "bar", "hello", "world"]),
+        (4..5, vec![]),
+        (4..7, vec!["hello", "world", "foobar"]),
+        (6..u8::MAX, vec!["foobar"]),
+        (7..10, vec![]),
+    ];
+
+    for (lengths, expected) in table {
+        assert_eq!(expected, dict.pool(lengths));
+    }
+
+    // empty or inverted ranges
+    assert!(dict.pool(3..3).is_empty());
+    #[allow(clippy::reversed_empty_ranges)]
+    let inverted = 5..2;
+    assert!(dict.pool(inverted).is_empty());
+}
+
+#[test]
+fn test_load_binary_dict_embedded() {
+    let table: [(&[u8], &[u8]); 5] = [
+        (
+            include_bytes!("../assets/dict_de.txt"),
+            include_bytes!(concat!(env!("OUT_DIR"), "/dict_de.bin")),
+        ),
+        (
+            include_bytes!("../assets/dict_en.txt"),
+            include_bytes!(concat!(env!("OUT_DIR"), "/dict_en.bin")),
+        ),
+        (
+            include_bytes!("../assets/dict_es.txt"),
+            include_bytes!(concat!(env!("OUT_DIR"), "/dict_es.bin")),
+        ),
+        (
+            include_bytes!("../assets/dict_fr.txt"),
+            include_bytes!(concat!(env!("OUT_DIR"), "/dict_fr.bin")),
+        ),
+        (
+            include_bytes!("../assets/dict_pt.txt"),
+            include_bytes!(concat!(env!("OUT_DIR"), "/dict_pt.bin")),
+        ),
+    ];
+
+    for (text_bytes, binary_bytes) in table {
+        assert_eq!(
+            load_dict(text_bytes).unwrap(),
+            load_binary_dict(binary_bytes).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_load_binary_dict_valid_data() {
+    let dict_bytes = [
+        b"XKD1".as_slice(),
+        &[2],                // buckets count
+        &[2, 2, 0, 3, 1, 0], // 2 words of length 2, 1 word of length 3
+        &[2, 2, 3],          // byte length of each word
+        b"andofoo",
+    ]
+    .concat();
+
+    let dict = load_binary_dict(&dict_bytes).unwrap();
+    assert_eq!(load_dict("2:an,do\n3:foo".as_bytes()).unwrap(), dict);
+
+    let dict_bytes = [b"XKD1".as_slice(), &[0]].concat();
+    assert!(load_binary_dict(&dict_bytes).unwrap().is_empty());
+}
+
+#[test]
+fn test_load_binary_dict_invalid_data() {
+    let table = [
+        // blank
+        vec![],
+        // wrong magic
+        [b"XKD0".as_slice(), &[0]].concat(),
+        // missing buckets count
+        b"XKD1".to_vec(),
+        // truncated buckets header
+        [b"XKD1".as_slice(), &[1, 2]].concat(),
+        // truncated word lengths
+        [b"XKD1".as_slice(), &[1, 2, 2, 0, 2]].concat(),
+        // truncated words
+        [b"XKD1".as_slice(), &[1, 2, 2, 0, 2, 2], b"and"].concat(),
+        // trailing bytes
+        [b"XKD1".as_slice(), &[1, 2, 2, 0, 2, 2], b"andoo"].concat(),
+        // invalid UTF-8
+        [b"XKD1".as_slice(), &[1, 2, 1, 0, 2], &[0xff, 0xfe]].concat(),
+        // word split inside a multi-byte char
+        [b"XKD1".as_slice(), &[1, 1, 2, 0, 1, 2], "éa".as_bytes()].concat(),
+        // unsorted buckets
+        [b"XKD1".as_slice(), &[2, 3, 1, 0, 2, 1, 0, 3, 2], b"fooan"].concat(),
+    ];
+
+    for dict_bytes in table {
+        assert_eq!(
+            Err("invalid binary dictionary".to_string()),
+            load_binary_dict(&dict_bytes)
+        );
+    }
+}
+
+#[test]
+fn test_load_word_list() {
+    let dict_bytes = [
+        "foo\n".as_bytes(),
+        "bar\r\n".as_bytes(),
+        "\n".as_bytes(),
+        "  hello  \n".as_bytes(),
+        &[0xff, 0xfe, b'\n'],
+        "été\n".as_bytes(),
+        "an".as_bytes(),
+    ]
+    .concat();
+
+    let dict = load_word_list(&dict_bytes);
+    assert_eq!(5, dict.len());
+    assert_eq!(vec!["an"], dict.bucket(2));
+    assert_eq!(vec!["foo", "bar", "été"], dict.bucket(3));
+    assert_eq!(vec!["hello"], dict.bucket(5));
+
+    // same content as the crate's format
+    assert_eq!(
+        load_dict("2:an\n3:foo,bar,été\n5:hello".as_bytes()).unwrap(),
+        dict
+    );
+
+    // too long words are skipped
+    let long_word = "a".repeat(256);
+    let dict = load_word_list(long_word.as_bytes());
+    assert!(dict.is_empty());
+
+    assert!(load_word_list(&[]).is_empty());
+}
+
+#[test]
+fn test_dedup_words() {
+    let dict = load_word_list("Haus\nfoo\nhaus\nFOO\nbar\nfoo\n".as_bytes());
+
+    // the lowercase spelling is kept, capitals being up to word transforms
+    assert_eq!(vec!["foo", "bar"], dict.bucket(3));
+    assert_eq!(vec!["haus"], dict.bucket(4));
+    assert_eq!(
+        DictStats {
+            words: 3,
+            lengths: vec![(3, 2), (4, 1)],
+            duplicates: 3,
+            proper_nouns: 0,
+        },
+        dict.stats()
+    );
+
+    let dict = load_dict("3:Foo,bar,foo\n4:haus".as_bytes()).unwrap();
+    assert_eq!(vec!["bar", "foo"], dict.bucket(3));
+    assert_eq!(1, dict.stats().duplicates);
+
+    // otherwise the first one
+    let dict = load_word_list("Paris\nPARIS\n".as_bytes());
+    assert_eq!(vec!["Paris"], dict.bucket(5));
+
+    assert_eq!(DictStats::default(), Dict::default().stats());
+}
+
+#[test]
+fn test_without_proper_nouns() {
+    let dict = load_word_list("Paris\nhorse\nÉvian\nNASA\nzebra\n".as_bytes());
+    assert!(is_proper_noun("Paris"));
+    assert!(is_proper_noun("Évian"));
+    assert!(!is_proper_noun("horse"));
+
+    assert_eq!(5, dict.len());
+    assert_eq!(3, dict.stats().proper_nouns);
+    assert_eq!(&["horse", "zebra"], dict.without_proper_nouns().bucket(5));
+    assert!(dict.without_proper_nouns().bucket(4).is_empty());
+
+    // nothing to leave out
+    let dict = load_word_list(b"horse\nzebra\n");
+    assert_eq!(&dict, dict.without_proper_nouns());
+}
+
+#[test]
+fn test_with_proper_nouns() {
+    let dict = load_word_list(b"@places\nparis\nhorse\n@animals\nzebra\nmccoy\n")
+        .with_proper_nouns("# comment\nParis\nMcCoy\n\nLondon\n");
+
+    assert_eq!(&["Paris", "horse", "zebra", "McCoy"], dict.bucket(5));
+    assert_eq!(2, dict.stats().proper_nouns);
+    assert_eq!(&["horse", "zebra"], dict.without_proper_nouns().bucket(5));
+    assert_eq!(&["Paris", "horse"], dict.theme("places").unwrap().bucket(5));
+}
+
+#[test]
+fn test_spelling_variants() {
+    let dict = load_word_list(b"colour\ncolor\ncentre\nhorse\n")
+        .with_spelling_variants("colour:color\ncentre:center\n");
+
+    assert_eq!(&dict, dict.spelling(SpellingVariant::Any));
+
+    let british = dict.spelling(SpellingVariant::British);
+    assert_eq!(&["horse"], british.bucket(5));
+    assert_eq!(&["colour", "centre"], british.bucket(6));
+
+    let american = dict.spelling(SpellingVariant::American);
+    assert_eq!(&["color", "horse"], american.bucket(5));
+    assert!(american.bucket(6).is_empty());
+}
+
+#[test]
+fn test_lint_dict() {
+    use super::lint::*;
+
+    let dict_bytes = [
+        "3:foo,bar,hello\n".as_bytes(),
+        "x:nope\n".as_bytes(),
+        "4:it's,,BAR\n".as_bytes(),
+        &[b'4', b':', 0xff, 0xfe, b'\n'],
+        "5:world\n".as_bytes(),
+    ]
+    .concat();
+
+    let lint = lint_dict(&dict_bytes);
+    assert!(!lint.is_clean());
+    assert_eq!(
+        vec![
+            (
+                1,
+                DictIssue::WrongLength {
+                    word: "hello".to_string(),
+                    listed: 3,
+                }
+            ),
+            (2, DictIssue::MissingLength),
+            (3, DictIssue::NonAlphabetic("it's".to_string())),
+            (3, DictIssue::NonAlphabetic("".to_string())),
+            (
+                3,
+                DictIssue::Duplicate {
+                    word: "BAR".to_string(),
+                    first_line: 1,
+                }
+            ),
+            (4, DictIssue::InvalidUtf8),
+        ],
+        lint.issues
+    );
+
+    // misplaced words moved to their own length
+    assert_eq!("3:foo,bar\n5:hello,world\n", lint.cleaned());
+    assert!(lint_dict(lint.cleaned().as_bytes()).is_clean());
+
+    let lint = lint_dict("foo\nhello\n\nwo rld\nFoo\n".as_bytes());
+    assert_eq!(
+        vec![
+            (4, DictIssue::NonAlphabetic("wo rld".to_string())),
+            (
+                5,
+                DictIssue::Duplicate {
+                    word: "Foo".to_string(),
+                    first_line: 1,
+                }
+            ),
+        ],
+        lint.issues
+    );
+    assert_eq!(vec!["foo", "hello"], lint.words);
+
+    // the lowercase spelling is kept, wherever it's listed
+    let lint = lint_dict("Foo\nhello\nfoo\n".as_bytes());
+    assert_eq!(vec!["foo", "hello"], lint.words);
+
+    let lint = lint_dict("word,frequency\nfoo,1\nb4r,2\nhello,often\n".as_bytes());
+    assert_eq!(DictFormat::Csv, lint.format);
+    assert_eq!(
+        vec![
+            (3, DictIssue::NonAlphabetic("b4r".to_string())),
+            (4, DictIssue::InvalidFrequency),
+        ],
+        lint.issues
+    );
+    assert_eq!(vec!["foo"], lint.words);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_lint_json_dict() {
+    use super::lint::*;
+
+    let lint = lint_dict(r#"["foo", "caf\u00e9", "Foo"]"#.as_bytes());
+    assert_eq!(DictFormat::Json, lint.format);
+    assert_eq!(
+        vec![(
+            3,
+            DictIssue::Duplicate {
+                word: "Foo".to_string(),
+                first_line: 1,
+            }
+        )],
+        lint.issues
+    );
+    assert_eq!(vec!["foo", "café"], lint.words);
+
+    let lint = lint_dict("[\n\"foo\",\n1\n]".as_bytes());
+    assert!(matches!(lint.issues[..], [(3, DictIssue::InvalidJson(_))]));
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_mmap_words() {
+    use crate::dict::mmap::MmapWords;
+    use std::fs;
+
+    let path = std::env::temp_dir().join(format!("xkpasswd-mmap-{}.txt", std::process::id()));
+    fs::write(&path, "foo\nbar\nhello\nan\n").unwrap();
+
+    let source = MmapWords::open(&path).unwrap();
+    let dict = source.dict();
+    assert_eq!(vec!["an"], dict.bucket(2));
+    assert_eq!(vec!["foo", "bar"], dict.bucket(3));
+    assert_eq!(vec!["hello"], dict.pool(4..10));
+
+    drop(dict);
+    drop(source);
+    fs::remove_file(&path).unwrap();
+
+    assert!(MmapWords::open(&path).is_err());
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn test_fetch_dict() {
+    use super::remote::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    const WORDS: &str = "foo\nbar\nhello\n";
+    const SHA256: &str = "f734d834efd79968d58f4a2f627cf4e07152224901895ac707be401f05e1278a";
+
+    assert_eq!(Ok(()), verify_sha256(WORDS.as_bytes(), SHA256));
+    assert_eq!(
+        Ok(()),
+        verify_sha256(WORDS.as_bytes(), &SHA256.to_uppercase())
+    );
+    assert!(verify_sha256(b"foo", SHA256)
+        .unwrap_err()
+        .starts_with("dictionary checksum mismatch"));
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/words.txt", listener.local_addr().unwrap());
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming().take(2) {
+            let mut stream = stream.unwrap();
+            // the request ends with a blank line, its content doesn't matter
+            BufReader::new(&stream)
+                .lines()
+                .map_while(Result::ok)
+                .find(|line| line.is_empty());
+
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                WORDS.len(),
+                WORDS
+            )
+            .unwrap();
+        }
+    });
+
+    assert_eq!(WORDS.as_bytes(), fetch_dict(&url, SHA256).unwrap());
+    assert!(fetch_dict(&url, &"0".repeat(64)).is_err());
+}
+
+#[test]
+fn test_dict_themes() {
+    let table = [
+        "@Animals\nhorse\nzebra\n@colors\namber\nolive\n@\nball\n",
+        "@animals\n5:horse,zebra\n@ colors \n5:amber,olive\n@\n4:ball\n",
+        "word,frequency\n@animals\nhorse,2\nzebra,1\n@colors\namber,3\nolive,\n@\nball,4\n",
+    ];
+
+    for dict_str in table {
+        let dict = load_any_dict(dict_str.as_bytes()).unwrap();
+        assert_eq!(5, dict.len(), "{}", dict_str);
+        assert_eq!(vec!["animals", "colors"], dict.themes());
+        assert_eq!(
+            &["horse", "zebra"],
+            dict.theme("ANIMALS").unwrap().bucket(5)
+        );
+        assert_eq!(&["amber", "olive"], dict.theme("colors").unwrap().bucket(5));
+        assert_eq!(None, dict.theme("foods"));
+    }
+
+    // themed words count once in the whole dictionary
+    let dict = load_word_list(b"@animals\nhorse\n@pets\nhorse\ncat\n");
+    assert_eq!(&["cat", "horse"], dict.pool(0..u8::MAX));
+    assert_eq!(1, dict.stats().duplicates);
+    assert_eq!(
+        &["cat", "horse"],
+        dict.theme("pets").unwrap().pool(0..u8::MAX)
+    );
+
+    let lint = super::lint::lint_dict(b"@animals\n5:horse,zebra\n");
+    assert!(lint.is_clean());
+    assert_eq!(vec!["horse", "zebra"], lint.words);
+}
+
+#[test]
+fn test_dict_format_detect() {
+    let table = [
+        ("2:an,do\n3:foo,bar", DictFormat::Buckets),
+        ("\n\n  10:abcdefghij\n", DictFormat::Buckets),
+        ("foo\nbar\nbaz", DictFormat::WordList),
+        ("aardvark's\nabacus\n", DictFormat::WordList),
+        ("foo:bar", DictFormat::WordList),
+        ("@animals\n5:horse,zebra", DictFormat::Buckets),
+        ("\n  [\"foo\", \"bar\"]", DictFormat::Json),
+        ("word,frequency\nfoo,12", DictFormat::Csv),
+        (":foo", DictFormat::WordList),
+        ("12", DictFormat::WordList),
+        ("", DictFormat::WordList),
+    ];
+
+    for (dict_str, expected) in table {
+        assert_eq!(expected, DictFormat::detect(dict_str.as_bytes()));
+    }
+}
+
+#[test]
+fn test_load_any_dict() {
+    let expected = load_dict("3:foo,bar\n5:hello".as_bytes()).unwrap();
+    assert_eq!(
+        expected,
+        load_any_dict("3:foo,bar\n5:hello".as_bytes()).unwrap()
+    );
+    assert_eq!(
+        expected,
+        load_any_dict("foo\nhello\nbar\n".as_bytes()).unwrap()
+    );
+
+    // like /usr/share/dict/words, bucketed by computed lengths
+    let dict = load_any_dict("A\nA's\nAachen\naardvark\n".as_bytes()).unwrap();
+    assert_eq!(vec!["A's"], dict.bucket(3));
+    assert_eq!(vec!["aardvark"], dict.bucket(8));
+
+    assert_eq!(
+        Err("expected 'len:word,word,...' at line 2".to_string()),
+        load_any_dict("3:foo\nhello".as_bytes())
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_load_json_dict() {
+    let dict = load_any_dict(r#"["foo", " bar ", "hello", ""]"#.as_bytes()).unwrap();
+    assert_eq!(load_dict("3:foo,bar\n5:hello".as_bytes()).unwrap(), dict);
+
+    assert!(load_json_dict(br#"{"words": ["foo"]}"#).is_err());
+    assert!(load_json_dict(br#"["foo", 42]"#).is_err());
+
+    // escaped words are decoded
+    let dict = load_json_dict(br#"["caf\u00e9", "\"", "it\u2019s", "foo"]"#).unwrap();
+    assert_eq!(&["\""], dict.bucket(1));
+    assert_eq!(&["foo"], dict.bucket(3));
+    assert_eq!(&["café", "it’s"], dict.bucket(4));
+}
+
+#[test]
+fn test_load_csv_dict() {
+    let expected = load_dict("3:foo,bar\n5:hello".as_bytes()).unwrap();
+
+    let table = [
+        "foo,1\nbar,2\nhello,0.5\n",
+        "word,frequency\nfoo,1\n\"bar\",2\n hello , 3 \n",
+        "foo,\nbar\nhello,1,extra\n",
+    ];
+
+    for dict_str in table {
+        assert_eq!(
+            expected,
+            load_csv_dict(dict_str.as_bytes()).unwrap(),
+            "{}",
+            dict_str
+        );
+    }
+
+    assert_eq!(
+        Err("expected 'word,frequency' at line 3".to_string()),
+        load_any_dict("foo,1\nbar,2\nhello,often".as_bytes())
+    );
+
+    // commas in quoted fields are part of them
+    let dict = load_csv_dict("\"word, quoted\",frequency\n\"foo, bar\",12\nhello,\"1\"".as_bytes())
+        .unwrap();
+    assert_eq!(&["foo, bar"], dict.bucket(8));
+    assert_eq!(&["hello"], dict.bucket(5));
+
+    // unterminated or escaped quotes are rejected rather than split at commas
+    for dict_str in ["foo,1\n\"foo, bar,12", "foo,1\n\"foo\"\"s, bar\",12"] {
+        assert_eq!(
+            Err("expected 'word,frequency' at line 2".to_string()),
+            load_csv_dict(dict_str.as_bytes()),
+            "{}",
+            dict_str
+        );
+    }
+}
+
+#[test]
+fn test_syllable_words() {
+    let source = SyllableWords::generate(4..=6, 50);
+    let dict = source.dict();
+
+    assert_eq!(150, dict.len());
+
+    for len in 4..=6 {
+        let bucket = dict.bucket(len);
+        assert_eq!(50, bucket.len());
+
+        let unique: HashSet<&&str> = bucket.iter().collect();
+        assert_eq!(50, unique.len());
+
+        for word in bucket {
+            let is_vowels: Vec<bool> = word.chars().map(|char| "aeiou".contains(char)).collect();
+            // consonants & vowels alternate
+            assert!(
+                is_vowels.windows(2).all(|pair| pair[0] != pair[1]),
+                "{}",
+                word
+            );
+        }
+    }
+
+    assert!(dict.bucket(7).is_empty());
+
+    // only 21 possible single letter words, more can't be generated
+    assert_eq!(21, SyllableWords::generate(1..=1, 100).dict().len());
+}
+
+#[cfg(feature = "lang_en")]
+#[test]
+fn test_diceware_table() {
+    let table = DicewareTable::parse(DICEWARE_EN).unwrap();
+
+    assert_eq!(Ok("aaaah"), table.lookup("11111"));
+    assert_eq!(Ok("aaah"), table.lookup("11112"));
+    assert_eq!(Ok("aaron"), table.lookup("11113"));
+    assert_eq!(Ok("slim"), table.lookup("66666"));
+    assert_eq!(7776, table.dict().len());
+
+    assert_eq!(
+        Ok(vec!["aaaah", "slim", "aaah"]),
+        table.lookup_all(" 11111\n66666, 11112 ")
+    );
+
+    let expectations = [
+        ("", "no dice rolls given"),
+        ("1111", "expected 5 dice rolls but got '1111'"),
+        ("111111", "expected 5 dice rolls but got '111111'"),
+        ("11171", "invalid dice roll '7' in '11171'"),
+        ("11111 1a111", "invalid dice roll 'a' in '1a111'"),
+    ];
+
+    for (rolls, err) in expectations {
+        assert_eq!(Err(err.to_string()), table.lookup_all(rolls), "{}", rolls);
+    }
+}
+
+#[cfg(feature = "lang_en")]
+#[test]
+fn test_diceware_table_parse_errors() {
+    let entries: Vec<String> = DICEWARE_EN.lines().map(String::from).collect();
+
+    let missing = entries[..7775].join("\n");
+    assert_eq!(
+        Err("missing diceware entry for rolls '66666'".to_string()),
+        DicewareTable::parse(&missing)
+    );
+
+    let duplicated = [&entries[..], &["11111\tfoo".to_string()]]
+        .concat()
+        .join("\n");
+    assert_eq!(
+        Err("duplicated diceware entry for rolls '11111'".to_string()),
+        DicewareTable::parse(&duplicated)
+    );
+
+    assert_eq!(
+        Err("invalid diceware entry 'abacus'".to_string()),
+        DicewareTable::parse("abacus")
+    );
+    assert_eq!(
+        Err("invalid dice roll '0' in '01111'".to_string()),
+        DicewareTable::parse("01111 abacus")
+    );
+}
+
+#[test]
+fn test_homophones() {
+    let dict = load_word_list(b"there\nmoon\n").with_homophones(
+        "# homophones
+         their there
+         two too to
+
+         # near-homophones
+         accept except",
+    );
+
+    assert!(dict.is_homophone("there"));
+    assert!(dict.is_homophone("Their"));
+    assert!(dict.is_homophone("two"));
+    assert!(dict.is_homophone("except"));
+    assert!(!dict.is_homophone("moon"));
+    assert!(!dict.is_homophone("#"));
+    assert!(!dict.is_homophone("near-homophones"));
+}
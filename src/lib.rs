@@ -1,6 +1,23 @@
+pub mod analyzer;
 pub mod bit_flags;
+pub mod codename;
+#[cfg(feature = "commands")]
+pub mod commands;
+pub mod dict;
+#[cfg(feature = "lua")]
+pub mod lua;
+pub mod pin;
 pub mod prelude;
+pub mod recovery;
+pub mod rng;
+#[cfg(feature = "ruby")]
+mod ruby;
 pub mod settings;
+#[cfg(feature = "tasks")]
+pub mod tasks;
+#[cfg(feature = "test_utils")]
+pub mod test_utils;
+pub mod typing;
 mod wasm;
 
 #[cfg(test)]
@@ -25,4 +42,26 @@ mod tests {
             .with_fixed_padding();
         assert_eq!(4, pass.gen_pass(&settings).passwd().split('.').count());
     }
+
+    #[cfg(feature = "wasm_objects")]
+    #[wasm_bindgen_test]
+    fn test_settings_from_object() {
+        use serde::Serialize;
+        use std::collections::HashMap;
+        use wasm_bindgen::JsCast;
+
+        let object = HashMap::from([("wordsCount", 4), ("paddingDigitsAfter", 0)])
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .unwrap();
+        let settings = WasmSettings::from_object(object.unchecked_into()).unwrap();
+        let query = settings.to_query_string();
+
+        assert!(query.contains("words=4&"), "{}", query);
+        assert!(query.contains("digits_after=0&"), "{}", query);
+
+        let object = HashMap::from([("wordCount", 4)])
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .unwrap();
+        assert!(WasmSettings::from_object(object.unchecked_into()).is_err());
+    }
 }
@@ -0,0 +1,62 @@
+// Lua bindings over `commands`, for hosts embedding Lua like editors & automation tools:
+//
+//   local xkpasswd = require("xkpasswd")
+//   local passwords, entropy = xkpasswd.generate({ preset = "wifi", count = 3 })
+//   local entropy = xkpasswd.entropy({ query = "words=4&separators=-" })
+//   local presets = xkpasswd.presets()
+//
+// Options are the fields of `GenerateRequest` & `SettingsRequest`, all optional.
+
+#[cfg(test)]
+mod tests;
+
+use crate::commands::{self, GenerateRequest, SettingsRequest};
+use mlua::{Error, Lua, LuaSerdeExt, Result, Table, Value};
+use serde::de::DeserializeOwned;
+
+// a missing or nil options table means the defaults
+fn options<T: DeserializeOwned + Default>(lua: &Lua, options: Option<Value>) -> Result<T> {
+    match options {
+        None | Some(Value::Nil) => Ok(T::default()),
+        Some(options) => lua.from_value(options),
+    }
+}
+
+/// Table of `generate`, `entropy` & `presets` functions.
+pub fn module(lua: &Lua) -> Result<Table> {
+    let module = lua.create_table()?;
+
+    module.set(
+        "generate",
+        lua.create_function(|lua, request: Option<Value>| {
+            let request: GenerateRequest = options(lua, request)?;
+            let generated = commands::generate(request).map_err(Error::runtime)?;
+            Ok((
+                lua.to_value(&generated.passwords)?,
+                lua.to_value(&generated.entropy)?,
+            ))
+        })?,
+    )?;
+
+    module.set(
+        "entropy",
+        lua.create_function(|lua, request: Option<Value>| {
+            let request: SettingsRequest = options(lua, request)?;
+            let entropy = commands::entropy_for(request).map_err(Error::runtime)?;
+            lua.to_value(&entropy)
+        })?,
+    )?;
+
+    module.set(
+        "presets",
+        lua.create_function(|lua, ()| lua.to_value(&commands::list_presets()))?,
+    )?;
+
+    Ok(module)
+}
+
+/// Makes `require("xkpasswd")` return `module`.
+pub fn preload(lua: &Lua) -> Result<()> {
+    let preload: Table = lua.globals().get::<Table>("package")?.get("preload")?;
+    preload.set("xkpasswd", lua.create_function(|lua, ()| module(lua))?)
+}
@@ -0,0 +1,85 @@
+use super::*;
+use crate::prelude::{Builder, Preset, Xkpasswd};
+use crate::settings::Settings;
+
+#[test]
+fn test_generate() {
+    let lua = Lua::new();
+    preload(&lua).unwrap();
+
+    let (passwords, seen): (Vec<String>, usize) = lua
+        .load(
+            r#"
+            local xkpasswd = require("xkpasswd")
+            local passwords, entropy = xkpasswd.generate({ preset = "wifi", count = 3 })
+            return passwords, entropy.seen
+            "#,
+        )
+        .eval()
+        .unwrap();
+
+    let settings = Settings::from_preset(Preset::Wifi);
+    assert_eq!(3, passwords.len());
+    for passwd in &passwords {
+        assert!(
+            settings.matches(passwd, Xkpasswd::preloaded()),
+            "{}",
+            passwd
+        );
+    }
+
+    let entropy = commands::entropy_for(SettingsRequest {
+        preset: Some("wifi".to_string()),
+        query: None,
+    })
+    .unwrap();
+    assert_eq!(entropy.seen, seen);
+
+    // defaults without options
+    let passwords: Vec<String> = lua
+        .load(r#"return require("xkpasswd").generate()"#)
+        .eval()
+        .unwrap();
+    assert_eq!(1, passwords.len());
+}
+
+#[test]
+fn test_entropy_and_presets() {
+    let lua = Lua::new();
+    lua.globals()
+        .set("xkpasswd", module(&lua).unwrap())
+        .unwrap();
+
+    let seen: usize = lua
+        .load(r#"return xkpasswd.entropy({ query = "words=4" }).seen"#)
+        .eval()
+        .unwrap();
+    let expected = commands::entropy_for(SettingsRequest {
+        preset: None,
+        query: Some("words=4".to_string()),
+    })
+    .unwrap();
+    assert_eq!(expected.seen, seen);
+
+    let (count, name): (usize, String) = lua
+        .load(
+            r#"
+            local presets = xkpasswd.presets()
+            return #presets, presets[1].name
+            "#,
+        )
+        .eval()
+        .unwrap();
+    assert_eq!(Preset::ALL.len(), count);
+    assert_eq!(Preset::Default.name(), name);
+
+    let err = lua
+        .load(r#"return xkpasswd.generate({ preset = "unknown" })"#)
+        .eval::<Vec<String>>()
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("unknown preset 'unknown'"),
+        "{}",
+        err
+    );
+}
@@ -1,21 +1,568 @@
+mod analyzer;
 mod bit_flags;
 mod cli;
+mod codename;
+mod dict;
+mod pin;
 mod prelude;
+mod recovery;
+mod rng;
 mod settings;
+mod typing;
 
 use cli::*;
+use dict::syllables::SyllableWords;
 use prelude::*;
 use settings::*;
+use std::io;
+use std::process;
+
+// WASI has no processes to hold the clipboard or run pass & ssh-keygen, no display
+// & no listening sockets
+#[cfg(all(
+    target_os = "wasi",
+    any(
+        feature = "clipboard",
+        feature = "gui",
+        feature = "pass",
+        feature = "serve",
+        feature = "ssh_keygen"
+    )
+))]
+compile_error!("the clipboard, gui, pass, serve & ssh_keygen features aren't supported on WASI");
 
 fn main() {
+    // run by ssh-keygen with the prompt as the only argument, not meant for clap
+    #[cfg(all(unix, feature = "ssh_keygen"))]
+    if std::env::var_os(SSH_ASKPASS_ENV).is_some() {
+        if ssh_askpass().is_err() {
+            process::exit(Failure::Other.exit_code());
+        }
+
+        return;
+    }
+
     let mut cli = Cli::init();
-    let settings: Settings = cli.parse_settings();
+    log::debug!("using the {} random number generator", rng::backend_name());
+
+    #[cfg(feature = "clipboard")]
+    if let Some(Command::HoldClipboard { clear_after }) = cli.subcommand() {
+        // the parent only reads whether the clipboard is ready, errors are already reported
+        let clear_after = clear_after.map(std::time::Duration::from_secs);
+        if hold_clipboard(clear_after).is_err() {
+            process::exit(Failure::Other.exit_code());
+        }
+
+        return;
+    }
+
+    if let Some(Command::Dict {
+        action: DictCommand::Lint { file, output },
+    }) = cli.subcommand()
+    {
+        let dict_bytes = read_dict_file(file).unwrap_or_else(|err| {
+            cli.fail(
+                Failure::Io,
+                &tr!("error-read-dict", file = file, error = err),
+            );
+        });
+
+        let lint = dict::lint::lint_dict(&dict_bytes);
+        print!("{}", render_dict_lint(&lint));
+
+        if let Some(output) = output {
+            if let Err(err) = std::fs::write(output, lint.cleaned()) {
+                cli.fail(
+                    Failure::Io,
+                    &tr!("error-write-dict", file = output, error = err),
+                );
+            }
+        }
+
+        if !lint.is_clean() {
+            process::exit(Failure::Dictionary.exit_code());
+        }
+
+        return;
+    }
+
+    if let Some(Command::Pin { digits, count }) = cli.subcommand() {
+        for _ in 0..*count {
+            match pin::gen_pin(*digits) {
+                Ok(pin) => println!("{}", pin),
+                Err(err) => {
+                    cli.fail(Failure::InvalidSettings, &tr!("error-pin", error = err));
+                }
+            }
+        }
+
+        return;
+    }
+
+    if let Some(Command::Recovery { count, .. }) = cli.subcommand() {
+        let format = cli
+            .code_format()
+            .expect("recovery subcommand")
+            .unwrap_or_else(|err| {
+                cli.fail(
+                    Failure::InvalidSettings,
+                    &tr!("error-recovery", error = err),
+                );
+            });
+        log::info!("{} bits of entropy per code", format.entropy());
+
+        let codes = recovery::gen_recovery_codes(*count, &format).unwrap_or_else(|err| {
+            cli.fail(Failure::Unsatisfiable, &tr!("error-recovery", error = err));
+        });
+
+        for code in codes {
+            println!("{}", code);
+        }
+
+        return;
+    }
+
     let language = cli.language();
-    log::info!("generating password in {:?} with {}", language, settings);
+    let dict_bytes = cli.read_dict();
+    let syllable_words = cli.syllables().then(SyllableWords::default);
+
+    let pass_generator = match (&dict_bytes, &syllable_words) {
+        (Some(dict_bytes), _) => {
+            let dict = dict::load_any_dict(dict_bytes).unwrap_or_else(|err| {
+                cli.fail(Failure::Dictionary, &tr!("error-load-dict", error = err));
+            });
+            let stats = dict.stats();
+            log::info!(
+                "using custom dictionary of {} words ({} proper nouns), {} duplicates dropped",
+                stats.words,
+                stats.proper_nouns,
+                stats.duplicates
+            );
+            Xkpasswd::from_dict(dict)
+        }
+        (None, Some(syllable_words)) => {
+            log::info!("using generated syllable words");
+            Xkpasswd::from_source(syllable_words)
+        }
+        (None, None) => {
+            log::info!("using built-in dictionary in {:?}", language);
+            Xkpasswd::for_language(language)
+        }
+    };
+
+    if let Some(Command::Codename { themes, count }) = cli.subcommand() {
+        // built-in dictionaries have no themes, codenames come with their own
+        let codenames_generator = Xkpasswd::from_dict(codename::codenames_dict());
+        let generator = match dict_bytes {
+            Some(_) => &pass_generator,
+            None => &codenames_generator,
+        };
+
+        let themes: Vec<&str> = themes.iter().map(String::as_str).collect();
+        let codenames = codename::Codenames::new(generator, &themes).unwrap_or_else(|err| {
+            cli.fail(Failure::Dictionary, &tr!("error-codename", error = err));
+        });
+        log::info!("{} bits of entropy per codename", codenames.entropy());
+
+        let mut rng = rng::rng();
+        for _ in 0..*count {
+            println!("{}", codenames.gen_codename(&mut rng));
+        }
+
+        return;
+    }
+
+    if let Some(Command::Presets) = cli.subcommand() {
+        print!("{}", render_presets(&pass_generator));
+        return;
+    }
+
+    if let Some(Command::Compare) = cli.subcommand() {
+        print!("{}", render_comparison(&pass_generator));
+        return;
+    }
+
+    if let Some(Command::Analyze) = cli.subcommand() {
+        match read_password(&tr!("password-prompt")) {
+            Ok(password) => println!("{}", pass_generator.analyze(&password)),
+            Err(err) => cli.fail(Failure::Io, &tr!("error-read-password", error = err)),
+        }
+
+        return;
+    }
+
+    #[cfg(feature = "json")]
+    if cli.stdin_jsonl() {
+        let mut output = io::BufWriter::new(io::stdout().lock());
+
+        match gen_from_jsonl(
+            &pass_generator,
+            io::stdin().lock(),
+            &mut output,
+            cli.terminator(),
+        ) {
+            Ok(count) => log::info!("generated {} passwords from stdin", count),
+            Err(err) => {
+                // whatever was generated before the failing line still goes out
+                drop(output);
+                let failure = match err {
+                    JsonlError::Io(_) => Failure::Io,
+                    JsonlError::InvalidSettings(..) => Failure::InvalidSettings,
+                };
+                cli.fail(failure, &tr!("error-stdin-jsonl", error = err));
+            }
+        }
+
+        return;
+    }
+
+    if let Some(words) = cli.dice_words() {
+        print!("{}{}", words.join(" "), cli.terminator());
+        return;
+    }
+
+    let mut settings: Settings = cli.parse_settings();
+
+    if let Some(Command::Suggest {
+        max_length,
+        no_symbols,
+        no_digits,
+        limit,
+    }) = cli.subcommand()
+    {
+        let mut solver = SettingsSolver::new(settings)
+            .with_min_entropy(
+                cli.min_entropy()
+                    .unwrap_or(SettingsSolver::DEFAULT_MIN_ENTROPY),
+            )
+            .with_symbols(!no_symbols)
+            .with_digits(!no_digits);
+
+        if let Some(max_length) = max_length {
+            solver = solver.with_max_length(*max_length);
+        }
+
+        match solver.solve(&pass_generator, *limit) {
+            Ok(suggestions) => print!("{}", render_suggestions(&pass_generator, &suggestions)),
+            Err(err) => {
+                cli.fail(Failure::Unsatisfiable, &tr!("error-suggest", error = err));
+            }
+        }
+
+        return;
+    }
+
+    if let Some(bits) = cli.min_entropy() {
+        let pool_size = pass_generator.words_pool(&settings).len();
+        settings = settings
+            .for_target_entropy_in_pool(bits, pool_size)
+            .unwrap_or_else(|err| {
+                cli.fail(
+                    Failure::Unsatisfiable,
+                    &tr!("error-invalid-settings", error = err),
+                );
+            });
+
+        let message = tr!(
+            "min-entropy-settings",
+            words = settings.words_count(),
+            bits = bits,
+            settings = settings.compact(),
+        );
+        eprintln!("{}", message);
+    }
+
+    for warning in settings.lint(&pass_generator) {
+        eprintln!("{}", messages().warning(&warning));
+    }
+
+    if let Some(preset) = cli.preset() {
+        for warning in settings.lint_against_preset(preset) {
+            eprintln!("{}", messages().warning(&warning));
+        }
+
+        let deltas = Settings::from_preset(preset).diff(&settings);
+
+        if !deltas.is_empty() {
+            let deltas: Vec<String> = deltas.iter().map(|delta| delta.to_string()).collect();
+            log::info!("differs from preset {} in: {}", preset, deltas.join(", "));
+        }
+    }
 
-    let pass_generator = Xkpasswd::for_language(language);
-    let (passwd, entropy) = pass_generator.gen_pass(&settings);
+    // settings are validated before each generation, so they can be fixed in the session
+    if let Some(Command::Repl) = cli.subcommand() {
+        let mut repl = Repl::new(&pass_generator, settings);
+
+        if let Err(err) = repl.run(io::stdin().lock(), &mut io::stdout()) {
+            cli.fail(Failure::Io, &tr!("error-repl", error = err));
+        }
+
+        return;
+    }
+
+    if let Some(Command::Verify) = cli.subcommand() {
+        let password = read_password(&tr!("password-prompt"))
+            .unwrap_or_else(|err| cli.fail(Failure::Io, &tr!("error-read-password", error = err)));
+        log::info!("verifying password against {}", settings);
+
+        if settings.matches(&password, &pass_generator) {
+            println!("{}", tr!("verify-match"));
+        } else {
+            println!("{}", tr!("verify-mismatch"));
+            process::exit(Failure::Other.exit_code());
+        }
+
+        return;
+    }
+
+    // only matters when generating, a password can still be verified against the settings
+    if let Err(err) = settings.validate_against(&pass_generator) {
+        cli.fail(
+            Failure::InvalidSettings,
+            &tr!("error-invalid-settings", error = err),
+        );
+    }
+
+    #[cfg(feature = "gui")]
+    if let Some(Command::Gui) = cli.subcommand() {
+        if let Err(err) = run_gui(&pass_generator, &settings, cli.count()) {
+            cli.fail(Failure::Other, &tr!("error-gui", error = err));
+        }
+
+        return;
+    }
+
+    #[cfg(feature = "pass")]
+    if let Some(Command::Pass {
+        action:
+            PassCommand::Insert {
+                name,
+                pass_bin,
+                force,
+            },
+    }) = cli.subcommand()
+    {
+        log::info!("generating password for pass with {}", settings);
+        let (passwd, _) = pass_generator.gen_pass(&settings);
+
+        if let Err(err) = insert_into_pass(pass_bin, name, &passwd, *force) {
+            cli.fail(
+                Failure::Other,
+                &tr!("error-pass-insert", name = name, error = err),
+            );
+        }
+
+        return;
+    }
+
+    #[cfg(feature = "serve")]
+    if let Some(Command::Serve {
+        listen,
+        keys_file,
+        per_minute,
+        burst,
+    }) = cli.subcommand()
+    {
+        let api_keys = ApiKeys::load(keys_file).unwrap_or_else(|err| {
+            cli.fail(
+                Failure::Io,
+                &tr!("error-api-keys", file = keys_file, error = err),
+            );
+        });
+
+        let presets = cli.preset_registry();
+        log::info!("serving passwords with {}", settings);
+        let server = Server::new(
+            &pass_generator,
+            &settings,
+            &presets,
+            api_keys,
+            *burst,
+            *per_minute,
+        );
+
+        if let Err(err) = server.listen(listen) {
+            cli.fail(
+                Failure::Io,
+                &tr!("error-serve", address = listen, error = err),
+            );
+        }
+
+        return;
+    }
+
+    #[cfg(all(unix, feature = "daemon"))]
+    if let Some(Command::Daemon { socket }) = cli.subcommand() {
+        let presets = cli.preset_registry();
+        log::info!("answering requests with {}", settings);
+
+        if let Err(err) = run_daemon(socket, &pass_generator, &settings, &presets) {
+            cli.fail(
+                Failure::Io,
+                &tr!("error-daemon", socket = socket, error = err),
+            );
+        }
+
+        return;
+    }
+
+    log::info!("generating password with {}", settings);
+    log::debug!("compact settings: {}", settings.compact());
+
+    let usernames = cli.read_usernames();
+    let count = usernames.as_ref().map_or(cli.count(), Vec::len);
+
+    let (passwds, entropy) = match (cli.unique(), cli.minimize_typing()) {
+        (_, Some((layout, candidates))) => {
+            let mut entropy = Entropy::default();
+            let passwds = (0..count)
+                .map(|_| {
+                    let (passwd, pass_entropy) =
+                        pass_generator.gen_easiest_pass(&settings, layout, candidates);
+                    entropy = pass_entropy;
+                    passwd
+                })
+                .collect();
+            (passwds, entropy)
+        }
+        (Some(uniqueness), None) => pass_generator
+            .gen_unique_passes(&settings, count, uniqueness)
+            .unwrap_or_else(|err| {
+                cli.fail(Failure::Unsatisfiable, &tr!("error-unique", error = err));
+            }),
+        (None, None) => pass_generator.gen_passes(&settings, count),
+    };
     log::info!("calculated entropy: {}", entropy);
 
-    println!("{}", passwd);
+    // the passphrase is then output as usual, e.g. printed once or saved with --store
+    #[cfg(all(unix, feature = "ssh_keygen"))]
+    if let Some(key_file) = cli.ssh_keygen() {
+        let created = std::env::current_exe().and_then(|askpass_bin| {
+            ssh_keygen(SSH_KEYGEN_BIN, &askpass_bin, key_file, &passwds[0])
+        });
+        if let Err(err) = created {
+            cli.fail(
+                Failure::Other,
+                &tr!("error-ssh-keygen", file = key_file, error = err),
+            );
+        }
+
+        eprintln!("{}", tr!("ssh-keygen-created", file = key_file));
+    }
+
+    #[cfg(credential_store)]
+    if let Some((store, entry)) = cli.store() {
+        if let Err(err) = store.save(&entry, &passwds[0]) {
+            cli.fail(Failure::Other, &tr!("error-store", error = err));
+        }
+
+        eprintln!("{}", tr!("store-saved"));
+        return;
+    }
+
+    #[cfg(feature = "clipboard")]
+    if cli.copy() {
+        if let Err(err) = copy_detached(&passwds.join("\n"), cli.clear_after()) {
+            cli.fail(Failure::Other, &tr!("error-clipboard", error = err));
+        }
+
+        let message = match cli.clear_after() {
+            Some(clear_after) => tr!("clipboard-copied-clearing", seconds = clear_after.as_secs()),
+            None => tr!("clipboard-copied"),
+        };
+        eprintln!("{}", message);
+
+        return;
+    }
+
+    #[cfg(feature = "encrypt")]
+    if !cli.encrypt_to().is_empty() {
+        // whatever would have been printed, without ever writing it in plaintext
+        let plaintext = if let Some(format) = cli.export() {
+            let credentials = Credential::pair(passwds, usernames.as_deref());
+            export_credentials(format, &credentials, &entropy)
+        } else if let Some(format) = cli.table_format() {
+            render_table(format, &passwds, &entropy)
+        } else {
+            let terminator = cli.terminator().to_string();
+            passwds
+                .iter()
+                .map(|passwd| passwd.clone() + &terminator)
+                .collect()
+        };
+
+        match encrypt_to(cli.encrypt_to(), &plaintext) {
+            Ok(ciphertext) => print!("{}", ciphertext),
+            Err(err) => {
+                cli.fail(Failure::Other, &tr!("error-encrypt", error = err));
+            }
+        }
+
+        return;
+    }
+
+    if let Some(format) = cli.export() {
+        let credentials = Credential::pair(passwds, usernames.as_deref());
+        print!("{}", export_credentials(format, &credentials, &entropy));
+        return;
+    }
+
+    if let Some(format) = cli.table_format() {
+        print!("{}", render_table(format, &passwds, &entropy));
+        return;
+    }
+
+    let color = cli.color();
+    let terminator = cli.terminator();
+
+    #[cfg(feature = "hash")]
+    if let Some(algorithm) = cli.hash() {
+        for passwd in passwds {
+            match algorithm.hash(&passwd) {
+                // the passwords are never printed anywhere, not even on stderr
+                Ok(hash) if cli.hash_only() => print!("{}{}", hash, terminator),
+                Ok(hash) => print!("{}\t{}{}", passwd, hash, terminator),
+                Err(err) => {
+                    cli.fail(Failure::Other, &tr!("error-hash", error = err));
+                }
+            }
+        }
+
+        return;
+    }
+
+    if cli.conceal() {
+        let lines: Vec<String> = passwds
+            .iter()
+            .map(|passwd| {
+                if color {
+                    colorize(&pass_generator.analyze(passwd))
+                } else {
+                    passwd.clone()
+                }
+            })
+            .collect();
+
+        if let Err(err) = conceal(&lines, io::stdin().lock(), &mut io::stdout()) {
+            cli.fail(Failure::Io, &tr!("error-conceal", error = err));
+        }
+
+        return;
+    }
+
+    for passwd in passwds {
+        if color {
+            print!(
+                "{}{}",
+                colorize(&pass_generator.analyze(&passwd)),
+                terminator
+            );
+        } else {
+            print!("{}{}", passwd, terminator);
+        }
+
+        if let Some(mode) = cli.spell() {
+            println!("{}", spell_out(&passwd, mode));
+        }
+    }
 }
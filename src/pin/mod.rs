@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests;
+
+use rand::Rng;
+use std::fmt;
+
+const COMMON_PINS: &str = include_str!("../assets/common_pins.txt");
+
+/// Why a PIN is easily guessed, see `weakness`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Weakness {
+    /// A single digit or a block of digits repeated, e.g. `0000` or `123123`
+    Repeated,
+    /// Digits going up or down by one, e.g. `3456` or `987654`
+    Straight,
+    /// A year people are likely to pick, e.g. `1987`
+    Year,
+    /// One of the PINs most often chosen by people, e.g. `2580` down the keypad
+    Common,
+}
+
+impl fmt::Display for Weakness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Repeated => write!(f, "repeated digits"),
+            Self::Straight => write!(f, "a straight of digits"),
+            Self::Year => write!(f, "a year"),
+            Self::Common => write!(f, "a commonly used PIN"),
+        }
+    }
+}
+
+pub const MIN_PIN_DIGITS: usize = 4;
+pub const MAX_PIN_DIGITS: usize = 16;
+
+/// Why `pin` is easily guessed, if it is, assuming it's made of ASCII digits only.
+pub fn weakness(pin: &str) -> Option<Weakness> {
+    let digits = pin.as_bytes();
+
+    if is_repeated(digits) {
+        return Some(Weakness::Repeated);
+    }
+
+    if is_straight(digits) {
+        return Some(Weakness::Straight);
+    }
+
+    if digits.len() == 4 && ("1900".."2100").contains(&pin) {
+        return Some(Weakness::Year);
+    }
+
+    if COMMON_PINS
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .any(|line| line.trim() == pin)
+    {
+        return Some(Weakness::Common);
+    }
+
+    None
+}
+
+/// A PIN of `digits` digits picked uniformly among those without any weakness,
+/// which leaves out a few hundred of the 10,000 4-digit PINs.
+pub fn gen_pin(digits: usize) -> Result<String, String> {
+    gen_pin_with(&mut crate::rng::rng(), digits)
+}
+
+/// Same as `gen_pin`, drawing from `rng`, e.g. a `test_utils::FixedRng` in tests.
+pub fn gen_pin_with<R: Rng + ?Sized>(rng: &mut R, digits: usize) -> Result<String, String> {
+    if !(MIN_PIN_DIGITS..=MAX_PIN_DIGITS).contains(&digits) {
+        return Err(format!(
+            "PINs must have {} to {} digits",
+            MIN_PIN_DIGITS, MAX_PIN_DIGITS
+        ));
+    }
+
+    // rejection sampling keeps the remaining PINs equally likely
+    loop {
+        let pin: String = (0..digits)
+            .map(|_| char::from(b'0' + rng.gen_range(0..10)))
+            .collect();
+
+        match weakness(&pin) {
+            Some(weakness) => log::debug!("rejected PIN: {}", weakness),
+            None => return Ok(pin),
+        }
+    }
+}
+
+// a block repeated over the whole PIN, down to a single digit
+fn is_repeated(digits: &[u8]) -> bool {
+    (1..=digits.len() / 2)
+        .filter(|&block| digits.len().is_multiple_of(block))
+        .any(|block| digits.chunks(block).all(|chunk| chunk == &digits[..block]))
+}
+
+fn is_straight(digits: &[u8]) -> bool {
+    let steps: Vec<i8> = digits
+        .windows(2)
+        .map(|pair| pair[1] as i8 - pair[0] as i8)
+        .collect();
+
+    steps.iter().all(|&step| step == 1) || steps.iter().all(|&step| step == -1)
+}
@@ -0,0 +1,51 @@
+use super::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn test_weakness() {
+    let expectations = [
+        ("0000", Some(Weakness::Repeated)),
+        ("1212", Some(Weakness::Repeated)),
+        ("123123", Some(Weakness::Repeated)),
+        ("777777", Some(Weakness::Repeated)),
+        ("3456", Some(Weakness::Straight)),
+        ("987654", Some(Weakness::Straight)),
+        ("1987", Some(Weakness::Year)),
+        ("2024", Some(Weakness::Year)),
+        ("2580", Some(Weakness::Common)),
+        ("147258", Some(Weakness::Common)),
+        ("12345678", Some(Weakness::Straight)),
+        ("4817", None),
+        ("2100", None),
+        ("8901", None),
+        ("121213", None),
+        ("503917", None),
+        // years only make up whole 4-digit PINs
+        ("198700", None),
+    ];
+
+    for (pin, expected) in expectations {
+        assert_eq!(expected, weakness(pin), "{}", pin);
+    }
+}
+
+#[test]
+fn test_gen_pin() {
+    let mut rng = StdRng::seed_from_u64(42);
+
+    for digits in [4, 6, 8] {
+        for _ in 0..100 {
+            let pin = gen_pin_with(&mut rng, digits).unwrap();
+            assert_eq!(digits, pin.len());
+            assert!(pin.chars().all(|char| char.is_ascii_digit()), "{}", pin);
+            assert_eq!(None, weakness(&pin), "{}", pin);
+        }
+    }
+
+    assert_eq!(6, gen_pin(6).unwrap().len());
+
+    let err = Err("PINs must have 4 to 16 digits".to_string());
+    assert_eq!(err, gen_pin(3));
+    assert_eq!(err, gen_pin(17));
+}
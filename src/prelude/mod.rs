@@ -1,10 +1,17 @@
 #[cfg(test)]
 mod tests;
 
-use std::collections::HashMap;
-use std::fmt;
+use crate::dict::{load_binary_dict, Dict, WordSource};
+use rand::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt::{self, Write};
 use std::ops::Range;
-use std::str::*;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+use std::time::SystemTime;
 use wasm_bindgen::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -13,6 +20,73 @@ pub enum PaddingStrategy {
     Adaptive(usize),
 }
 
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JoinStyle {
+    #[default]
+    Separated,
+    /// Titlecase every word regardless of word transforms,
+    /// combine with empty separators for `CorrectHorseBatteryStaple` style
+    Camel,
+}
+
+/// What to do with proper nouns of the dictionary, see `dict::is_proper_noun`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProperNouns {
+    /// Picked & transformed like any other word
+    #[default]
+    Include,
+    Exclude,
+    /// Picked but kept in their canonical capitalization, e.g. `Paris` whatever the transforms
+    Preserve,
+}
+
+/// Which spellings to pick words in, for dictionaries tagging variants like English.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpellingVariant {
+    /// Either spelling, as listed in the dictionary
+    #[default]
+    Any,
+    /// e.g. `colour`, `centre` & `organise`
+    British,
+    /// e.g. `color`, `center` & `organize`
+    American,
+}
+
+/// Curated symbols for separators & padding, see `SymbolSet`.
+pub struct Symbols;
+
+impl Symbols {
+    /// Typed without Shift on a US keyboard, e.g. at BIOS prompts assuming a US layout
+    pub const US_SHIFTLESS: &'static str = "`-=[]\\;',./";
+    /// Typed without AltGr or dead keys on US, UK, German, French & Spanish layouts
+    pub const INTL_SAFE: &'static str = "!$%&()*+,-./:;=?_";
+    /// Printable ASCII without quotes nor backslash, which often need escaping
+    pub const ASCII_ONLY_NO_QUOTES: &'static str = "!#$%&()*+,-./:;<=>?@[]^_{|}~";
+}
+
+/// Which curated symbols to use as both separators & padding symbols,
+/// for passwords typed on foreign keyboard layouts.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymbolSet {
+    UsShiftless,
+    IntlSafe,
+    AsciiOnlyNoQuotes,
+}
+
+impl SymbolSet {
+    pub fn symbols(&self) -> &'static str {
+        match self {
+            Self::UsShiftless => Symbols::US_SHIFTLESS,
+            Self::IntlSafe => Symbols::INTL_SAFE,
+            Self::AsciiOnlyNoQuotes => Symbols::ASCII_ONLY_NO_QUOTES,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum PaddingResult {
     Unchanged,
@@ -21,7 +95,7 @@ pub enum PaddingResult {
 }
 
 #[wasm_bindgen]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Preset {
     Default,
     AppleID,
@@ -31,6 +105,141 @@ pub enum Preset {
     Web32,
     Wifi,
     Xkcd,
+    Nist,
+    Temporary,
+    WifiEasyType,
+    Mobile,
+    SshPassphrase,
+    DiskEncryption,
+    DatabaseSafe,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 15] = [
+        Self::Default,
+        Self::AppleID,
+        Self::WindowsNtlmV1,
+        Self::SecurityQuestions,
+        Self::Web16,
+        Self::Web32,
+        Self::Wifi,
+        Self::Xkcd,
+        Self::Nist,
+        Self::Temporary,
+        Self::WifiEasyType,
+        Self::Mobile,
+        Self::SshPassphrase,
+        Self::DiskEncryption,
+        Self::DatabaseSafe,
+    ];
+
+    /// Canonical kebab-case name, as used by the CLI & config files.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::AppleID => "apple-id",
+            Self::WindowsNtlmV1 => "ntlm",
+            Self::SecurityQuestions => "secq",
+            Self::Web16 => "web16",
+            Self::Web32 => "web32",
+            Self::Wifi => "wifi",
+            Self::Xkcd => "xkcd",
+            Self::Nist => "nist",
+            Self::Temporary => "temporary",
+            Self::WifiEasyType => "wifi-easy",
+            Self::Mobile => "mobile",
+            Self::SshPassphrase => "ssh",
+            Self::DiskEncryption => "disk",
+            Self::DatabaseSafe => "database",
+        }
+    }
+
+    /// Other names still accepted for the preset, e.g. spelled after the variant.
+    pub fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            Self::AppleID => &["appleid"],
+            Self::WindowsNtlmV1 => &["windows-ntlm-v1", "windowsntlmv1", "ntlm-v1", "ntlmv1"],
+            Self::SecurityQuestions => &["security-questions", "securityquestions"],
+            Self::WifiEasyType => &["wifi-easy-type", "wifieasytype"],
+            Self::SshPassphrase => &["ssh-passphrase", "sshpassphrase"],
+            Self::DiskEncryption => &["disk-encryption", "diskencryption"],
+            Self::DatabaseSafe => &["database-safe", "databasesafe", "db"],
+            _ => &[],
+        }
+    }
+
+    /// Any of the presets, e.g. to generate varied test data.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::ALL[rng.gen_range(0..Self::ALL.len())]
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Default => "Some sensible default values",
+            Self::AppleID => "Apple ID passwords",
+            Self::WindowsNtlmV1 => "Windows NTLM v1",
+            Self::SecurityQuestions => "Security questions",
+            Self::Web16 => "Maximum 16 characters for older websites",
+            Self::Web32 => "Maximum 32 characters for modern websites",
+            Self::Wifi => "Fixed 63 characters for Wifi WPA2 keys",
+            Self::Xkcd => "As described in the original XKCD comic",
+            Self::Nist => "Long passphrases following NIST SP 800-63B guidance",
+            Self::Temporary => "Short temporary passwords, easy to read over the phone",
+            Self::WifiEasyType => "Wifi keys that are easy to type on TV remotes & game consoles",
+            Self::Mobile => "Easy to type on mobile keyboards",
+            Self::SshPassphrase => "Long SSH key passphrases, safe to paste into shells",
+            Self::DiskEncryption => {
+                "Lowercase words & digits only, no symbols to hunt for at pre-boot prompts"
+            }
+            Self::DatabaseSafe => "Without quotes, backslashes, semicolons or @",
+        }
+    }
+
+    pub fn recommended_use(&self) -> &'static str {
+        match self {
+            Self::Default => "General purpose passwords without specific requirements",
+            Self::AppleID => "Accounts requiring digits, symbols and mixed case letters",
+            Self::WindowsNtlmV1 => "Legacy systems limiting passwords to 14 characters",
+            Self::SecurityQuestions => "Answers to security questions, readable as a sentence",
+            Self::Web16 => "Websites limiting passwords to 16 characters",
+            Self::Web32 => "Websites limiting passwords to 32 characters",
+            Self::Wifi => "WPA2 pre-shared keys, which allow at most 63 characters",
+            Self::Xkcd => "Memorable passwords that are easy to type",
+            Self::Nist => "Services following NIST guidance, without composition rules",
+            Self::Temporary => "One-time passwords to be changed on first login",
+            Self::WifiEasyType => "WPA2/WPA3 keys entered with on-screen keyboards",
+            Self::Mobile => "Passwords typed on phones & tablets with few keyboard switches",
+            Self::SshPassphrase => "Passphrases protecting SSH private keys, typed at every unlock",
+            Self::DiskEncryption => {
+                "Full disk encryption unlocked at pre-boot prompts, often forcing a US layout"
+            }
+            Self::DatabaseSafe => {
+                "Database users embedded in connection strings, URLs & YAML without escaping"
+            }
+        }
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Parses canonical names & aliases, ignoring case and with '_' in place of '-'.
+impl FromStr for Preset {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let normalized = name.trim().to_lowercase().replace('_', "-");
+
+        Self::ALL
+            .into_iter()
+            .find(|preset| {
+                preset.name() == normalized || preset.aliases().contains(&normalized.as_str())
+            })
+            .ok_or_else(|| format!("unknown preset '{}'", name))
+    }
 }
 
 #[wasm_bindgen]
@@ -161,8 +370,6 @@ pub enum Language {
     Spanish,
 }
 
-type Dict<'a> = HashMap<u8, Vec<&'a str>>;
-
 pub trait L10n {
     fn for_language(language: Language) -> Self;
 }
@@ -174,31 +381,129 @@ pub trait Builder: Default + fmt::Display + Sized {
         min_length: Option<u8>,
         max_length: Option<u8>,
     ) -> Result<Self, String>;
+    /// Like `with_word_lengths` without the 4 to 10 bounds, flagged by `Settings::lint` instead.
+    fn with_word_lengths_unchecked(&self, min_length: Option<u8>, max_length: Option<u8>) -> Self;
     fn with_separators(&self, separators: &str) -> Self;
     fn with_padding_digits(&self, prefix: Option<u8>, suffix: Option<u8>) -> Self;
     fn with_padding_symbols(&self, symbols: &str) -> Self;
+    /// Uses the symbols of `symbol_set` as both separators & padding symbols.
+    fn with_symbol_set(&self, symbol_set: SymbolSet) -> Self;
     fn with_padding_symbol_lengths(&self, prefix: Option<u8>, suffix: Option<u8>) -> Self;
     fn with_padding_strategy(&self, strategy: PaddingStrategy) -> Result<Self, String>;
     fn with_word_transforms(&self, transform: u8) -> Result<Self, String>;
+    fn with_join_style(&self, style: JoinStyle) -> Self;
+    fn with_pronounceable(&self, pronounceable: bool) -> Self;
+    #[cfg(feature = "extra_entropy")]
+    fn with_extra_entropy(&self, entropy: &[u8]) -> Self;
+    fn with_checksum(&self, checksum: bool) -> Self;
+    fn with_word_theme(&self, theme: &str) -> Self;
+    fn with_proper_nouns(&self, proper_nouns: ProperNouns) -> Self;
+    fn with_spelling(&self, spelling: SpellingVariant) -> Self;
+    /// Strips confusable characters like `l`, `1`, `I`, `O`, `0` & `|` from separators,
+    /// symbols & digits, and skips words that would show them once transformed.
+    fn with_avoid_ambiguous(&self, avoid_ambiguous: bool) -> Self;
+    /// Skips words sounding like others, like `their` & `there`, for passwords read aloud.
+    /// Only dictionaries listing homophones have any, like the built-in English one.
+    fn with_avoid_homophones(&self, avoid_homophones: bool) -> Self;
     fn from_preset(preset: Preset) -> Self;
 }
 
+/// The choices behind each password, implemented by `Settings` and by any other strategy
+/// generated from with `Xkpasswd::gen_pass` & co., e.g. weighted transforms or themed picks.
+/// Trait objects work too, e.g. `pass_generator.gen_pass(boxed.as_ref())`.
+/// Only the pool & padding filters have defaults, leaving the whole dictionary in the pool,
+/// along with the entropy, unknown & reported as none rather than overstated.
 pub trait Randomizer {
+    /// Lengths of the words in the pool.
     fn word_lengths(&self) -> Range<u8>;
+
+    /// Theme the pool is restricted to, for dictionaries tagging their words.
+    fn word_theme(&self) -> Option<&str> {
+        None
+    }
+
+    fn proper_nouns(&self) -> ProperNouns {
+        ProperNouns::default()
+    }
+
+    fn spelling(&self) -> SpellingVariant {
+        SpellingVariant::default()
+    }
+
+    fn avoid_homophones(&self) -> bool {
+        false
+    }
+
+    /// Whether `word` is left out of the pool, as any of its transforms would be ambiguous to read.
+    fn is_ambiguous(&self, _word: &str) -> bool {
+        false
+    }
+
+    /// The words of a password, picked from `pool` & already transformed.
     fn rand_words(&self, pool: &[&str]) -> Vec<String>;
+    /// What goes between words, digits included, the same for a whole password.
     fn rand_separator(&self) -> String;
+    /// Symbols & digits before the words.
     fn rand_prefix(&self) -> (String, String);
+    /// Digits & symbols after the words.
     fn rand_suffix(&self) -> (String, String);
-    fn adjust_padding(&self, pass_length: usize) -> PaddingResult;
-    fn calc_entropy(&self, pool_size: usize) -> Entropy;
+
+    /// How to fit a password of `pass_length` characters to a length.
+    fn adjust_padding(&self, _pass_length: usize) -> PaddingResult {
+        PaddingResult::Unchanged
+    }
+
+    fn appends_checksum(&self) -> bool {
+        false
+    }
+
+    /// The built-in preset these choices are identical to, reported to observers.
+    fn preset(&self) -> Option<Preset> {
+        None
+    }
+
+    /// Entropy of the passwords when words are picked from `pool_size` ones.
+    fn calc_entropy(&self, _pool_size: usize) -> Entropy {
+        Entropy::default()
+    }
 }
 
+/// A generator configured once & used as `Box<dyn PasswordGenerator>`, e.g. by plugin systems
+/// or app containers picking the dictionary & settings at runtime,
+/// like a `ConfiguredGenerator`.
+#[allow(dead_code)] // library only, unused by the CLI binary
+pub trait PasswordGenerator {
+    fn generate(&self) -> Result<String, String>;
+}
+
+/// What observers learn about each generation: how strong and when, never the passwords.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenerationMeta {
+    /// The built-in preset the settings are identical to, if any
+    pub preset: Option<Preset>,
+    pub entropy: Entropy,
+    pub count: usize,
+    pub timestamp: SystemTime,
+}
+
+type Observer = Arc<dyn Fn(&GenerationMeta) + Send + Sync>;
+
 #[derive(Debug)]
-pub struct Xkpasswd {
-    dict: Dict<'static>,
+pub struct Xkpasswd<'a> {
+    dict: Arc<Dict<'a>>,
+    observers: Observers,
 }
 
-impl Default for Xkpasswd {
+#[derive(Clone, Default)]
+struct Observers(Vec<Observer>);
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} observer(s)", self.0.len())
+    }
+}
+
+impl Default for Xkpasswd<'static> {
     fn default() -> Self {
         if cfg!(feature = "lang_en") {
             Xkpasswd::for_language(Language::English)
@@ -216,93 +521,505 @@ impl Default for Xkpasswd {
     }
 }
 
-impl L10n for Xkpasswd {
+impl L10n for Xkpasswd<'static> {
     fn for_language(language: Language) -> Self {
-        let dict_bytes: &[u8] = match language {
-            #[cfg(feature = "lang_en")]
-            Language::English => include_bytes!("../assets/dict_en.txt"),
-            #[cfg(feature = "lang_de")]
-            Language::German => include_bytes!("../assets/dict_de.txt"),
-            #[cfg(feature = "lang_es")]
-            Language::Spanish => include_bytes!("../assets/dict_es.txt"),
-            #[cfg(feature = "lang_fr")]
-            Language::French => include_bytes!("../assets/dict_fr.txt"),
-            #[cfg(feature = "lang_pt")]
-            Language::Portuguese => include_bytes!("../assets/dict_pt.txt"),
-            #[allow(unreachable_patterns)]
-            _ => panic!("no language bundled"),
-        };
+        // embedded dictionaries are parsed once and shared by all instances
+        static DICTS: [OnceLock<Arc<Dict<'static>>>; 5] = [const { OnceLock::new() }; 5];
+
+        let dict = DICTS[language as usize]
+            .get_or_init(|| {
+                let dict = load_binary_dict(embedded_dict_bytes(language))
+                    .expect("invalid embedded dictionary");
+
+                #[cfg(feature = "lang_en")]
+                let dict = match language {
+                    Language::English => dict
+                        .with_proper_nouns(include_str!("../assets/proper_nouns_en.txt"))
+                        .with_spelling_variants(include_str!("../assets/spelling_en.txt"))
+                        .with_homophones(include_str!("../assets/homophones_en.txt")),
+                    _ => dict,
+                };
 
-        let dict = load_dict(dict_bytes);
-        Xkpasswd { dict }
+                Arc::new(dict)
+            })
+            .clone();
+
+        Xkpasswd {
+            dict,
+            observers: Observers::default(),
+        }
     }
 }
 
-impl Xkpasswd {
-    pub fn gen_pass<S: Randomizer>(&self, settings: &S) -> (String, Entropy) {
-        let mut all_words: Vec<&str> = vec![];
+impl Xkpasswd<'static> {
+    /// Shared instance for the default language, initialized on first use,
+    /// so hot paths don't pay for constructing a generator per call.
+    pub fn preloaded() -> &'static Self {
+        static PRELOADED: OnceLock<Xkpasswd<'static>> = OnceLock::new();
+        PRELOADED.get_or_init(Xkpasswd::default)
+    }
 
-        settings.word_lengths().for_each(|len| {
-            if let Some(words) = self.dict.get(&len) {
-                all_words.extend(words);
-            };
-        });
+    /// Downloads a dictionary in any supported format, e.g. a centrally managed wordlist
+    /// pulled at startup, refusing it unless its SHA-256 digest matches `sha256`.
+    ///
+    /// The downloaded bytes are kept for the rest of the program,
+    /// so this is meant to be called once rather than per request.
+    #[cfg(feature = "http")]
+    #[cfg_attr(feature = "trace", tracing::instrument(level = "debug", skip_all, err))]
+    pub fn from_url(url: &str, sha256: &str) -> Result<Self, String> {
+        let dict_bytes: &'static [u8] = crate::dict::remote::fetch_dict(url, sha256)?.leak();
+        let dict = crate::dict::load_any_dict(dict_bytes)?;
+
+        if dict.is_empty() {
+            return Err(format!("no words in the dictionary at {}", url));
+        }
 
-        let separator = &settings.rand_separator();
-        let mut words: Vec<String> = vec![];
+        Ok(Self::from_dict(dict))
+    }
+}
 
-        let (prefix_symbols, prefix_digits) = settings.rand_prefix();
-        if !prefix_digits.is_empty() {
-            words.push(prefix_digits);
+impl<'a> Xkpasswd<'a> {
+    pub fn from_dict(dict: Dict<'a>) -> Self {
+        Xkpasswd {
+            dict: Arc::new(dict),
+            observers: Observers::default(),
         }
+    }
 
-        words.extend(settings.rand_words(&all_words));
+    /// Builds a generator borrowing its words from `source`, e.g. a memory-mapped word list.
+    pub fn from_source<W: WordSource + ?Sized>(source: &'a W) -> Self {
+        Self::from_dict(source.dict())
+    }
+
+    pub fn dict(&self) -> &Dict<'a> {
+        &self.dict
+    }
+
+    /// Words the settings pick from: those of their lengths & spelling variant,
+    /// within their theme if any, without proper nouns if excluded
+    /// and without ambiguous words nor homophones if avoided.
+    /// Empty when the dictionary has no such theme.
+    #[cfg_attr(feature = "trace", tracing::instrument(level = "debug", skip_all))]
+    pub fn words_pool<S: Randomizer + ?Sized>(&self, settings: &S) -> Cow<'_, [&'a str]> {
+        let dict = self.dict.spelling(settings.spelling());
+
+        let dict = match settings.word_theme() {
+            Some(theme) => match dict.theme(theme) {
+                Some(dict) => dict,
+                None => return Cow::Borrowed(&[]),
+            },
+            None => dict,
+        };
 
-        let (suffix_digits, suffix_symbols) = settings.rand_suffix();
-        if !suffix_digits.is_empty() {
-            words.push(suffix_digits);
+        let dict = match settings.proper_nouns() {
+            ProperNouns::Exclude => dict.without_proper_nouns(),
+            ProperNouns::Include | ProperNouns::Preserve => dict,
+        };
+
+        let pool = dict.pool(settings.word_lengths());
+
+        // homophones are listed once for the whole dictionary, not for each theme or variant
+        let is_excluded = |word: &str| {
+            settings.is_ambiguous(word)
+                || (settings.avoid_homophones() && self.dict.is_homophone(word))
+        };
+
+        if pool.iter().any(|word| is_excluded(word)) {
+            let words = pool.iter().copied();
+            Cow::Owned(words.filter(|word| !is_excluded(word)).collect())
+        } else {
+            Cow::Borrowed(pool)
         }
+    }
+
+    /// Registers `observer` to be called after every generation, e.g. to write audit records.
+    /// It receives the preset, entropy & time of generation, but never the passwords.
+    pub fn on_generate<F: Fn(&GenerationMeta) + Send + Sync + 'static>(&mut self, observer: F) {
+        self.observers.0.push(Arc::new(observer));
+    }
 
-        let passwd = format!(
-            "{}{}{}",
-            prefix_symbols,
-            words.join(separator),
-            suffix_symbols
-        );
-
-        let passwd = match settings.adjust_padding(passwd.len()) {
-            PaddingResult::Unchanged => passwd,
-            PaddingResult::TrimTo(len) => passwd[..len].to_string(),
-            PaddingResult::Pad(padded_symbols) => passwd + &padded_symbols,
+    fn notify<S: Randomizer + ?Sized>(&self, settings: &S, entropy: &Entropy, count: usize) {
+        if self.observers.0.is_empty() {
+            return;
+        }
+
+        let meta = GenerationMeta {
+            preset: settings.preset(),
+            entropy: *entropy,
+            count,
+            timestamp: SystemTime::now(),
         };
 
+        for observer in &self.observers.0 {
+            observer(&meta);
+        }
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument(level = "debug", skip_all))]
+    pub fn gen_pass<S: Randomizer + ?Sized>(&self, settings: &S) -> (String, Entropy) {
+        let all_words = self.words_pool(settings);
+        let passwd = build_pass(settings, &all_words);
+        let entropy = settings.calc_entropy(all_words.len());
+        self.notify(settings, &entropy, 1);
+
+        (passwd, entropy)
+    }
+
+    /// Generates `candidates` passwords & returns the one with the lowest `key`, e.g. the easiest
+    /// to type. Being picked by a known criteria, it loses up to log2(candidates) bits,
+    /// taken off the seen entropy.
+    pub fn gen_pass_min_by_key<S: Randomizer + ?Sized, K: Ord, F: Fn(&str) -> K>(
+        &self,
+        settings: &S,
+        candidates: usize,
+        key: F,
+    ) -> (String, Entropy) {
+        let all_words = self.words_pool(settings);
+        let passwd = (0..candidates.max(1))
+            .map(|_| build_pass(settings, &all_words))
+            .min_by_key(|passwd| key(passwd))
+            .unwrap_or_default();
+
         let entropy = settings.calc_entropy(all_words.len());
+        let lost_bits = (candidates.max(1) as f64).log2().ceil() as usize;
+        let seen = entropy.seen.saturating_sub(lost_bits);
+        let entropy = Entropy {
+            seen,
+            guess_time: GuessTime::for_entropy(seen),
+            ..entropy
+        };
+        self.notify(settings, &entropy, 1);
 
         (passwd, entropy)
     }
+
+    /// Appends a generated password to `buffer`, so callers can reuse a pre-sized buffer
+    /// (or one they zeroize afterwards) instead of allocating a new string per password.
+    pub fn gen_pass_into<S: Randomizer + ?Sized>(
+        &self,
+        settings: &S,
+        buffer: &mut String,
+    ) -> Entropy {
+        self.gen_pass_write(settings, buffer)
+            .expect("writing to a String never fails")
+    }
+
+    /// Writes a generated password to `out` without building an intermediate string.
+    pub fn gen_pass_write<S: Randomizer + ?Sized, W: fmt::Write>(
+        &self,
+        settings: &S,
+        mut out: W,
+    ) -> Result<Entropy, fmt::Error> {
+        let all_words = self.words_pool(settings);
+        write_pass(settings, &all_words, &mut out)?;
+
+        let entropy = settings.calc_entropy(all_words.len());
+        self.notify(settings, &entropy, 1);
+        Ok(entropy)
+    }
+
+    /// Generates `count` passwords sharing the same settings, hence the same entropy.
+    /// With the `parallel` feature, passwords are generated across threads,
+    /// each with its own RNG, and still returned in a stable order.
+    #[cfg_attr(feature = "trace", tracing::instrument(level = "debug", skip_all, fields(count = count)))]
+    pub fn gen_passes<S: Randomizer + Sync + ?Sized>(
+        &self,
+        settings: &S,
+        count: usize,
+    ) -> (Vec<String>, Entropy) {
+        let all_words = self.words_pool(settings);
+
+        #[cfg(feature = "parallel")]
+        let passwds: Vec<String> = (0..count)
+            .into_par_iter()
+            .map(|_| build_pass(settings, &all_words))
+            .collect();
+
+        #[cfg(not(feature = "parallel"))]
+        let passwds: Vec<String> = (0..count)
+            .map(|_| build_pass(settings, &all_words))
+            .collect();
+
+        let entropy = settings.calc_entropy(all_words.len());
+        self.notify(settings, &entropy, count);
+
+        (passwds, entropy)
+    }
+}
+
+/// What no 2 passwords of a batch have in common.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Uniqueness {
+    Passwords,
+    /// e.g. for provisioning accounts whose owners sit next to each other
+    Words,
+}
+
+// per password, before concluding the settings allow too few distinct ones
+const UNIQUE_PASS_ATTEMPTS: usize = 100;
+
+impl Xkpasswd<'_> {
+    /// Generates `count` passwords like `gen_passes`, one at a time, guaranteeing `uniqueness`.
+    /// Without shared words, later passwords are picked from fewer words,
+    /// so the returned entropy is that of the last, weakest one.
+    #[cfg_attr(feature = "trace", tracing::instrument(level = "debug", skip_all, fields(count = count), err))]
+    pub fn gen_unique_passes<S: Randomizer + ?Sized>(
+        &self,
+        settings: &S,
+        count: usize,
+        uniqueness: Uniqueness,
+    ) -> Result<(Vec<String>, Entropy), String> {
+        let all_words = self.words_pool(settings);
+
+        let (passwds, pool_size) = match uniqueness {
+            Uniqueness::Passwords => (
+                gen_distinct_passes(settings, &all_words, count)?,
+                all_words.len(),
+            ),
+            Uniqueness::Words => gen_passes_without_shared_words(settings, &all_words, count)?,
+        };
+
+        let entropy = settings.calc_entropy(pool_size);
+        self.notify(settings, &entropy, count);
+
+        Ok((passwds, entropy))
+    }
+}
+
+fn gen_distinct_passes<S: Randomizer + ?Sized>(
+    settings: &S,
+    all_words: &[&str],
+    count: usize,
+) -> Result<Vec<String>, String> {
+    let mut seen: HashSet<String> = HashSet::with_capacity(count);
+    let mut passwds: Vec<String> = Vec::with_capacity(count);
+
+    while passwds.len() < count {
+        let passwd = (0..UNIQUE_PASS_ATTEMPTS)
+            .map(|_| build_pass(settings, all_words))
+            .find(|passwd| !seen.contains(passwd))
+            .ok_or_else(|| {
+                format!(
+                    "the settings allow too few distinct passwords for {}",
+                    count
+                )
+            })?;
+
+        seen.insert(passwd.clone());
+        passwds.push(passwd);
+    }
+
+    Ok(passwds)
+}
+
+// returns the size of the last pool picked from, along with the passwords
+fn gen_passes_without_shared_words<S: Randomizer + ?Sized>(
+    settings: &S,
+    all_words: &[&str],
+    count: usize,
+) -> Result<(Vec<String>, usize), String> {
+    let mut remaining: Vec<&str> = all_words.to_vec();
+    let mut pool_size = remaining.len();
+    let mut passwds: Vec<String> = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        pool_size = remaining.len();
+        let words = settings.rand_words(&remaining);
+
+        // fewer left than needed, so some would be repeated, or none at all
+        if remaining.len() < words.len() || (remaining.is_empty() && !all_words.is_empty()) {
+            return Err(format!(
+                "not enough words in the dictionary for {} passwords without shared words",
+                count
+            ));
+        }
+
+        // transforms only change letter cases
+        let used: HashSet<String> = words.iter().map(|word| word.to_lowercase()).collect();
+        remaining.retain(|word| !used.contains(&word.to_lowercase()));
+
+        let mut passwd = String::new();
+        write_pass_with_words(settings, words, &mut passwd)
+            .expect("writing to a String never fails");
+        passwds.push(passwd);
+    }
+
+    Ok((passwds, pool_size))
+}
+
+fn build_pass<S: Randomizer + ?Sized>(settings: &S, all_words: &[&str]) -> String {
+    let mut passwd = String::new();
+    write_pass(settings, all_words, &mut passwd).expect("writing to a String never fails");
+    passwd
+}
+
+fn write_pass<S: Randomizer + ?Sized, W: fmt::Write>(
+    settings: &S,
+    all_words: &[&str],
+    out: &mut W,
+) -> fmt::Result {
+    write_pass_with_words(settings, settings.rand_words(all_words), out)
+}
+
+fn write_pass_with_words<S: Randomizer + ?Sized, W: fmt::Write>(
+    settings: &S,
+    rand_words: Vec<String>,
+    out: &mut W,
+) -> fmt::Result {
+    if !settings.appends_checksum() {
+        return write_body(settings, rand_words, out);
+    }
+
+    // the checksum covers the whole password, so it has to be built first
+    let mut body = String::new();
+    write_body(settings, rand_words, &mut body)?;
+    out.write_str(&body)?;
+    out.write_str(&checksum_digits(&body))
 }
 
-fn load_dict(dict_bytes: &[u8]) -> Dict {
-    let dict_str = from_utf8(dict_bytes).unwrap_or("").trim();
-    let mut dict: Dict = HashMap::new();
+fn write_body<S: Randomizer + ?Sized, W: fmt::Write>(
+    settings: &S,
+    rand_words: Vec<String>,
+    out: &mut W,
+) -> fmt::Result {
+    let separator = settings.rand_separator();
+    let mut words: Vec<String> = vec![];
 
-    log::debug!("loaded raw dict with {} lines", dict_str.lines().count());
+    let (prefix_symbols, prefix_digits) = settings.rand_prefix();
+    if !prefix_digits.is_empty() {
+        words.push(prefix_digits);
+    }
+
+    words.extend(rand_words);
+
+    let (suffix_digits, suffix_symbols) = settings.rand_suffix();
+    if !suffix_digits.is_empty() {
+        words.push(suffix_digits);
+    }
 
-    dict_str.lines().for_each(|line| {
-        let mut comps = line.trim().split(':');
+    // measure upfront, so the password can be written straight to `out`
+    // instead of being joined and trimmed in an intermediate string
+    let pass_length = prefix_symbols.chars().count()
+        + words.iter().map(|word| word.chars().count()).sum::<usize>()
+        + separator.chars().count() * words.len().saturating_sub(1)
+        + suffix_symbols.chars().count();
 
-        if let Some(len_str) = comps.next() {
-            let len = len_str.parse::<u8>().unwrap();
-            let words_csv = comps.next().unwrap_or("");
-            let words: Vec<&str> = words_csv.split(',').collect();
-            dict.insert(len, words);
+    let (max_length, padded_symbols) = match settings.adjust_padding(pass_length) {
+        PaddingResult::Unchanged => (pass_length, None),
+        PaddingResult::TrimTo(len) => (len, None),
+        PaddingResult::Pad(padded_symbols) => (pass_length, Some(padded_symbols)),
+    };
+
+    let mut writer = TruncatingWriter {
+        out,
+        remaining: max_length,
+    };
+
+    writer.write_str(&prefix_symbols)?;
+
+    for (idx, word) in words.iter().enumerate() {
+        if idx > 0 {
+            writer.write_str(&separator)?;
         }
+
+        writer.write_str(word)?;
+    }
+
+    writer.write_str(&suffix_symbols)?;
+
+    if let Some(padded_symbols) = padded_symbols {
+        writer.out.write_str(&padded_symbols)?;
+    }
+
+    Ok(())
+}
+
+pub const CHECKSUM_LENGTH: usize = 2;
+
+/// Checksum digits appended to passwords, a position weighted sum of all chars modulo 97,
+/// so any single mistyped char or swapped pair of adjacent chars changes it.
+pub fn checksum_digits(body: &str) -> String {
+    let sum = body.chars().enumerate().fold(0u64, |sum, (idx, char)| {
+        (sum + (idx as u64 % 96 + 1) * char as u64) % 97
     });
 
-    log::debug!(
-        "parsed dict with {:?} entries",
-        dict.iter().fold(0, |acc, cur| acc + cur.1.len())
-    );
+    format!("{:0width$}", sum, width = CHECKSUM_LENGTH)
+}
+
+/// Whether the last digits of `passwd` are the checksum of the rest,
+/// to catch typos before submitting it anywhere.
+pub fn verify_checksum(passwd: &str) -> bool {
+    match passwd.char_indices().rev().nth(CHECKSUM_LENGTH - 1) {
+        Some((idx, _)) => checksum_digits(&passwd[..idx]) == passwd[idx..],
+        None => false,
+    }
+}
+
+// writes at most `remaining` chars to `out`, silently dropping the rest
+struct TruncatingWriter<'w, W: fmt::Write> {
+    out: &'w mut W,
+    remaining: usize,
+}
+
+impl<W: fmt::Write> fmt::Write for TruncatingWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.remaining == 0 {
+            return Ok(());
+        }
+
+        match s.char_indices().nth(self.remaining) {
+            Some((idx, _)) => {
+                self.remaining = 0;
+                self.out.write_str(&s[..idx])
+            }
+            None => {
+                self.remaining -= s.chars().count();
+                self.out.write_str(s)
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "compressed_dicts"))]
+macro_rules! embedded_dict {
+    ($lang:literal) => {
+        include_bytes!(concat!(env!("OUT_DIR"), "/dict_", $lang, ".bin"))
+    };
+}
+
+#[cfg(feature = "compressed_dicts")]
+macro_rules! embedded_dict {
+    ($lang:literal) => {
+        include_bytes!(concat!(env!("OUT_DIR"), "/dict_", $lang, ".bin.deflate"))
+    };
+}
+
+fn embedded_dict_bytes(language: Language) -> &'static [u8] {
+    let dict_bytes: &'static [u8] = match language {
+        #[cfg(feature = "lang_en")]
+        Language::English => embedded_dict!("en"),
+        #[cfg(feature = "lang_de")]
+        Language::German => embedded_dict!("de"),
+        #[cfg(feature = "lang_es")]
+        Language::Spanish => embedded_dict!("es"),
+        #[cfg(feature = "lang_fr")]
+        Language::French => embedded_dict!("fr"),
+        #[cfg(feature = "lang_pt")]
+        Language::Portuguese => embedded_dict!("pt"),
+        #[allow(unreachable_patterns)]
+        _ => panic!("no language bundled"),
+    };
+
+    // decompressed lazily, only for the languages actually used
+    #[cfg(feature = "compressed_dicts")]
+    let dict_bytes = {
+        static DECOMPRESSED: [OnceLock<Vec<u8>>; 5] = [const { OnceLock::new() }; 5];
+
+        DECOMPRESSED[language as usize].get_or_init(|| {
+            miniz_oxide::inflate::decompress_to_vec(dict_bytes)
+                .expect("invalid compressed embedded dictionary")
+        })
+    };
 
-    dict
+    dict_bytes
 }
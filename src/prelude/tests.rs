@@ -1,4 +1,5 @@
 use super::*;
+use crate::settings::Settings;
 
 struct MockSettings {
     padding_digits: (usize, usize),
@@ -38,148 +39,163 @@ impl Randomizer for MockSettings {
             PaddingResult::Pad(str) => PaddingResult::Pad(str.clone()),
         }
     }
-
-    fn calc_entropy(&self, _: usize) -> Entropy {
-        Entropy::default()
-    }
-}
-
-#[test]
-fn test_load_dict_blank() {
-    let dict = load_dict(&[]);
-    assert!(dict.is_empty());
-
-    let dict_bytes = "".as_bytes();
-    let dict = load_dict(dict_bytes);
-    assert!(dict.is_empty());
 }
 
 #[test]
-fn test_load_dict_valid_data() {
-    let table = [
-        "2:an,do\n3:foo,bar",
-        r#"
-        2:an,do
-        3:foo,bar
-        "#,
-    ];
-
-    for dict_str in table {
-        let dict_bytes = dict_str.as_bytes();
-        let dict = load_dict(dict_bytes);
+fn test_xkpasswd_shared_dict() {
+    let pass = Xkpasswd::default();
+    let other_pass = Xkpasswd::default();
+    assert!(Arc::ptr_eq(&pass.dict, &other_pass.dict));
 
-        assert_eq!(2, dict.len());
-        assert_eq!(vec!["an", "do"], *dict.get(&2).unwrap());
-        assert_eq!(vec!["foo", "bar"], *dict.get(&3).unwrap());
-        assert!(dict.get(&4).is_none());
-    }
+    let preloaded = Xkpasswd::preloaded();
+    assert!(Arc::ptr_eq(&pass.dict, &preloaded.dict));
+    assert!(std::ptr::eq(preloaded, Xkpasswd::preloaded()));
 }
 
+#[cfg(all(feature = "compressed_dicts", feature = "lang_en"))]
 #[test]
-#[should_panic]
-fn test_load_dict_invalid_data() {
-    let dict_bytes = "foo:3".as_bytes();
-    load_dict(dict_bytes);
+fn test_embedded_dict_bytes_compressed() {
+    let raw_bytes: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/dict_en.bin"));
+    let compressed_bytes: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/dict_en.bin.deflate"));
+    assert!(compressed_bytes.len() < raw_bytes.len());
+
+    let dict_bytes = embedded_dict_bytes(Language::English);
+    assert_eq!(raw_bytes, dict_bytes);
+    // decompressed only once
+    assert!(std::ptr::eq(
+        dict_bytes,
+        embedded_dict_bytes(Language::English)
+    ));
 }
 
 #[cfg(feature = "lang_en")]
 #[test]
 fn test_xkpasswd_for_en() {
     let pass = Xkpasswd::for_language(Language::English);
-    assert_eq!(false, pass.dict.is_empty());
+    assert!(!pass.dict.is_empty());
 
-    assert!(pass.dict.get(&2).is_none());
-    assert!(pass.dict.get(&3).is_none());
+    assert!(pass.dict.bucket(2).is_empty());
+    assert!(pass.dict.bucket(3).is_empty());
 
-    assert_eq!(1500, pass.dict.get(&4).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&5).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&6).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&7).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&8).unwrap().len());
-    assert_eq!(1338, pass.dict.get(&9).unwrap().len());
-    assert_eq!(807, pass.dict.get(&10).unwrap().len());
+    assert_eq!(1500, pass.dict.bucket(4).len());
+    assert_eq!(1500, pass.dict.bucket(5).len());
+    assert_eq!(1500, pass.dict.bucket(6).len());
+    assert_eq!(1500, pass.dict.bucket(7).len());
+    assert_eq!(1500, pass.dict.bucket(8).len());
+    assert_eq!(1338, pass.dict.bucket(9).len());
+    assert_eq!(807, pass.dict.bucket(10).len());
 
-    assert!(pass.dict.get(&11).is_none());
+    assert!(pass.dict.bucket(11).is_empty());
+}
+
+#[cfg(feature = "lang_en")]
+#[test]
+fn test_xkpasswd_proper_nouns() {
+    // the English dictionary is all lowercase, names are tagged by a bundled list
+    let pass = Xkpasswd::for_language(Language::English);
+    let names = pass.dict.bucket(5);
+    assert!(names.contains(&"Aaron"));
+    assert!(names.contains(&"Agnes"));
+    assert!(!names.contains(&"aaron"));
+    assert!(names.contains(&"grace"));
+
+    let common_words = pass.dict.without_proper_nouns().bucket(5);
+    assert!(!common_words.contains(&"Aaron"));
+    assert!(common_words.contains(&"horse"));
+    assert!(pass.dict.stats().proper_nouns > 1000);
 }
 
 #[cfg(feature = "lang_de")]
 #[test]
 fn test_xkpasswd_for_de() {
     let pass = Xkpasswd::for_language(Language::German);
-    assert_eq!(false, pass.dict.is_empty());
+    assert!(!pass.dict.is_empty());
 
-    assert!(pass.dict.get(&2).is_none());
-    assert!(pass.dict.get(&3).is_none());
+    assert!(pass.dict.bucket(2).is_empty());
+    assert!(pass.dict.bucket(3).is_empty());
 
-    assert_eq!(1277, pass.dict.get(&4).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&5).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&6).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&7).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&8).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&9).unwrap().len());
-    assert_eq!(1185, pass.dict.get(&10).unwrap().len());
+    assert_eq!(1277, pass.dict.bucket(4).len());
+    assert_eq!(1500, pass.dict.bucket(5).len());
+    assert_eq!(1500, pass.dict.bucket(6).len());
+    assert_eq!(1500, pass.dict.bucket(7).len());
+    assert_eq!(1500, pass.dict.bucket(8).len());
+    assert_eq!(1500, pass.dict.bucket(9).len());
+    assert_eq!(1185, pass.dict.bucket(10).len());
 
-    assert!(pass.dict.get(&11).is_none());
+    assert!(pass.dict.bucket(11).is_empty());
 }
 
 #[cfg(feature = "lang_es")]
 #[test]
 fn test_xkpasswd_for_es() {
     let pass = Xkpasswd::for_language(Language::Spanish);
-    assert_eq!(false, pass.dict.is_empty());
+    assert!(!pass.dict.is_empty());
 
-    assert!(pass.dict.get(&2).is_none());
-    assert!(pass.dict.get(&3).is_none());
+    assert!(pass.dict.bucket(2).is_empty());
+    assert!(pass.dict.bucket(3).is_empty());
 
-    assert_eq!(1111, pass.dict.get(&4).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&5).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&6).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&7).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&8).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&9).unwrap().len());
-    assert_eq!(1129, pass.dict.get(&10).unwrap().len());
+    assert_eq!(1111, pass.dict.bucket(4).len());
+    assert_eq!(1500, pass.dict.bucket(5).len());
+    assert_eq!(1500, pass.dict.bucket(6).len());
+    assert_eq!(1500, pass.dict.bucket(7).len());
+    assert_eq!(1500, pass.dict.bucket(8).len());
+    assert_eq!(1500, pass.dict.bucket(9).len());
+    assert_eq!(1129, pass.dict.bucket(10).len());
 
-    assert!(pass.dict.get(&11).is_none());
+    assert!(pass.dict.bucket(11).is_empty());
 }
 
 #[cfg(feature = "lang_fr")]
 #[test]
 fn test_xkpasswd_for_fr() {
     let pass = Xkpasswd::for_language(Language::French);
-    assert_eq!(false, pass.dict.is_empty());
+    assert!(!pass.dict.is_empty());
 
-    assert!(pass.dict.get(&2).is_none());
-    assert!(pass.dict.get(&3).is_none());
+    assert!(pass.dict.bucket(2).is_empty());
+    assert!(pass.dict.bucket(3).is_empty());
 
-    assert_eq!(1212, pass.dict.get(&4).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&5).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&6).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&7).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&8).unwrap().len());
-    assert_eq!(1438, pass.dict.get(&9).unwrap().len());
-    assert_eq!(902, pass.dict.get(&10).unwrap().len());
+    assert_eq!(1212, pass.dict.bucket(4).len());
+    assert_eq!(1500, pass.dict.bucket(5).len());
+    assert_eq!(1500, pass.dict.bucket(6).len());
+    assert_eq!(1500, pass.dict.bucket(7).len());
+    assert_eq!(1500, pass.dict.bucket(8).len());
+    assert_eq!(1438, pass.dict.bucket(9).len());
+    assert_eq!(902, pass.dict.bucket(10).len());
 
-    assert!(pass.dict.get(&11).is_none());
+    assert!(pass.dict.bucket(11).is_empty());
 }
 
 #[cfg(feature = "lang_pt")]
 #[test]
 fn test_xkpasswd_for_pt() {
     let pass = Xkpasswd::for_language(Language::Portuguese);
-    assert_eq!(false, pass.dict.is_empty());
+    assert!(!pass.dict.is_empty());
 
-    assert!(pass.dict.get(&2).is_none());
-    assert!(pass.dict.get(&3).is_none());
+    assert!(pass.dict.bucket(2).is_empty());
+    assert!(pass.dict.bucket(3).is_empty());
 
-    assert_eq!(1130, pass.dict.get(&4).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&5).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&6).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&7).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&8).unwrap().len());
-    assert_eq!(1397, pass.dict.get(&9).unwrap().len());
-    assert_eq!(925, pass.dict.get(&10).unwrap().len());
+    assert_eq!(1130, pass.dict.bucket(4).len());
+    assert_eq!(1500, pass.dict.bucket(5).len());
+    assert_eq!(1500, pass.dict.bucket(6).len());
+    assert_eq!(1500, pass.dict.bucket(7).len());
+    assert_eq!(1500, pass.dict.bucket(8).len());
+    assert_eq!(1397, pass.dict.bucket(9).len());
+    assert_eq!(925, pass.dict.bucket(10).len());
 
-    assert!(pass.dict.get(&11).is_none());
+    assert!(pass.dict.bucket(11).is_empty());
+}
+
+#[cfg(all(feature = "lang_en", feature = "lang_fr"))]
+#[test]
+fn test_xkpasswd_homophones() {
+    // only the English dictionary ships a homophone list
+    let pass = Xkpasswd::for_language(Language::English);
+    assert!(pass.dict.is_homophone("there"));
+    assert!(pass.dict.is_homophone("Weather"));
+    assert!(!pass.dict.is_homophone("zebra"));
+
+    let pass = Xkpasswd::for_language(Language::French);
+    assert!(!pass.dict.is_homophone("there"));
 }
 
 #[test]
@@ -253,7 +269,493 @@ fn test_xkpasswd_gen_pass() {
     ];
 
     for (expected, settings) in table {
-        let (passwd, _) = pass.gen_pass(&settings);
+        let (passwd, entropy) = pass.gen_pass(&settings);
         assert_eq!(expected, passwd);
+        // only the required methods are implemented, leaving the entropy unknown
+        assert_eq!(Entropy::default(), entropy);
+    }
+}
+
+#[test]
+fn test_xkpasswd_gen_pass_multi_byte_trim() {
+    struct MultiByteSettings;
+
+    impl Randomizer for MultiByteSettings {
+        fn word_lengths(&self) -> Range<u8> {
+            3..4
+        }
+
+        fn word_theme(&self) -> Option<&str> {
+            None
+        }
+
+        fn proper_nouns(&self) -> ProperNouns {
+            ProperNouns::Include
+        }
+
+        fn spelling(&self) -> SpellingVariant {
+            SpellingVariant::Any
+        }
+
+        fn avoid_homophones(&self) -> bool {
+            false
+        }
+
+        fn is_ambiguous(&self, _word: &str) -> bool {
+            false
+        }
+
+        fn rand_words(&self, _: &[&str]) -> Vec<String> {
+            vec!["été".to_string(), "über".to_string()]
+        }
+
+        fn rand_separator(&self) -> String {
+            "🔑".to_string()
+        }
+
+        fn rand_prefix(&self) -> (String, String) {
+            ("✨".to_string(), "".to_string())
+        }
+
+        fn rand_suffix(&self) -> (String, String) {
+            ("".to_string(), "✨✨".to_string())
+        }
+
+        fn adjust_padding(&self, pass_length: usize) -> PaddingResult {
+            // length must be counted in chars, not bytes
+            assert_eq!(11, pass_length);
+            PaddingResult::TrimTo(6)
+        }
+
+        fn appends_checksum(&self) -> bool {
+            false
+        }
+
+        fn preset(&self) -> Option<Preset> {
+            None
+        }
+    }
+
+    let pass = Xkpasswd::default();
+    let (passwd, _) = pass.gen_pass(&MultiByteSettings);
+    assert_eq!("✨été🔑ü", passwd);
+}
+
+#[test]
+fn test_xkpasswd_from_source() {
+    struct Words(String);
+
+    impl WordSource for Words {
+        fn dict(&self) -> Dict<'_> {
+            crate::dict::load_word_list(self.0.as_bytes())
+        }
+    }
+
+    let source = Words("foo\nbar\nbaz".to_string());
+    let pass = Xkpasswd::from_source(&source);
+    assert_eq!(vec!["foo", "bar", "baz"], pass.dict.bucket(3));
+
+    let settings = MockSettings {
+        padding_digits: (0, 0),
+        padding_symbols: (0, 0),
+        padding_result: PaddingResult::Unchanged,
+    };
+    let (passwd, _) = pass.gen_pass(&settings);
+    assert_eq!("foo.bar.baz", passwd);
+}
+
+// a strategy implemented on top of settings, as it would be outside of the crate
+struct Shouting(Settings);
+
+impl Randomizer for Shouting {
+    fn word_lengths(&self) -> Range<u8> {
+        self.0.word_lengths()
+    }
+
+    fn rand_words(&self, pool: &[&str]) -> Vec<String> {
+        let words = self.0.rand_words(pool);
+        words.iter().map(|word| word.to_uppercase()).collect()
+    }
+
+    fn rand_separator(&self) -> String {
+        self.0.rand_separator()
+    }
+
+    fn rand_prefix(&self) -> (String, String) {
+        self.0.rand_prefix()
+    }
+
+    fn rand_suffix(&self) -> (String, String) {
+        self.0.rand_suffix()
+    }
+
+    fn calc_entropy(&self, pool_size: usize) -> Entropy {
+        self.0.calc_entropy(pool_size)
+    }
+}
+
+#[test]
+fn test_xkpasswd_dyn_randomizer() {
+    let pass = Xkpasswd::default();
+    let strategies: Vec<Box<dyn Randomizer + Sync>> = vec![
+        Box::new(MockSettings {
+            padding_digits: (1, 2),
+            padding_symbols: (1, 2),
+            padding_result: PaddingResult::Unchanged,
+        }),
+        Box::new(Shouting(Settings::default())),
+    ];
+
+    let (passwd, _) = pass.gen_pass(strategies[0].as_ref());
+    assert_eq!("?1.foo.bar.baz.67!!", passwd);
+
+    let settings = Settings::default();
+    let (passwds, entropy) = pass.gen_passes(strategies[1].as_ref(), 10);
+    assert_eq!(
+        settings.calc_entropy(pass.words_pool(&settings).len()),
+        entropy
+    );
+
+    for passwd in passwds {
+        assert!(
+            !passwd.chars().any(char::is_lowercase),
+            "{} has lowercase words",
+            passwd
+        );
+    }
+}
+
+#[test]
+fn test_xkpasswd_gen_passes() {
+    let pass = Xkpasswd::default();
+    let settings = MockSettings {
+        padding_digits: (1, 2),
+        padding_symbols: (1, 2),
+        padding_result: PaddingResult::Unchanged,
+    };
+
+    let (passwds, entropy) = pass.gen_passes(&settings, 100);
+    assert_eq!(100, passwds.len());
+    assert!(passwds.iter().all(|passwd| passwd == "?1.foo.bar.baz.67!!"));
+    assert_eq!(Entropy::default(), entropy);
+
+    let (passwds, _) = pass.gen_passes(&settings, 0);
+    assert!(passwds.is_empty());
+}
+
+#[test]
+fn test_xkpasswd_gen_unique_passes() {
+    let source = "foo\nbar\nbaz\nqux".to_string();
+    let pass = Xkpasswd::from_dict(crate::dict::load_word_list(source.as_bytes()));
+    let settings = MockSettings {
+        padding_digits: (0, 0),
+        padding_symbols: (0, 0),
+        padding_result: PaddingResult::Unchanged,
+    };
+
+    let (passwds, _) = pass
+        .gen_unique_passes(&settings, 1, Uniqueness::Passwords)
+        .unwrap();
+    assert_eq!(vec!["foo.bar.baz"], passwds);
+
+    // always the same password
+    assert_eq!(
+        Err("the settings allow too few distinct passwords for 2".to_string()),
+        pass.gen_unique_passes(&settings, 2, Uniqueness::Passwords)
+    );
+
+    // 3 words per password, only 1 left after the first
+    assert_eq!(
+        Err("not enough words in the dictionary for 2 passwords without shared words".to_string()),
+        pass.gen_unique_passes(&settings, 2, Uniqueness::Words)
+    );
+}
+
+#[test]
+fn test_xkpasswd_gen_pass_into() {
+    let pass = Xkpasswd::default();
+    let settings = MockSettings {
+        padding_digits: (5, 5),
+        padding_symbols: (5, 5),
+        padding_result: PaddingResult::TrimTo(22),
+    };
+
+    let mut buffer = String::with_capacity(64);
+    let entropy = pass.gen_pass_into(&settings, &mut buffer);
+    assert_eq!("?????12345.foo.bar.baz", buffer);
+    assert_eq!(Entropy::default(), entropy);
+
+    // appended to existing content
+    let settings = MockSettings {
+        padding_digits: (2, 0),
+        padding_symbols: (2, 0),
+        padding_result: PaddingResult::Pad("$$$$$".to_string()),
+    };
+    buffer.push('\n');
+    pass.gen_pass_into(&settings, &mut buffer);
+    assert_eq!("?????12345.foo.bar.baz\n??12.foo.bar.baz$$$$$", buffer);
+}
+
+#[test]
+fn test_xkpasswd_gen_pass_write() {
+    struct FailingWriter;
+
+    impl fmt::Write for FailingWriter {
+        fn write_str(&mut self, _: &str) -> fmt::Result {
+            Err(fmt::Error)
+        }
+    }
+
+    let pass = Xkpasswd::default();
+    let settings = MockSettings {
+        padding_digits: (1, 2),
+        padding_symbols: (1, 2),
+        padding_result: PaddingResult::Unchanged,
+    };
+
+    let mut passwd = String::new();
+    let result = pass.gen_pass_write(&settings, &mut passwd);
+    assert_eq!(Ok(Entropy::default()), result);
+    assert_eq!("?1.foo.bar.baz.67!!", passwd);
+
+    assert_eq!(
+        Err(fmt::Error),
+        pass.gen_pass_write(&settings, FailingWriter)
+    );
+}
+
+#[test]
+fn test_truncating_writer() {
+    let table = [
+        (0, vec!["foo", "bar"], ""),
+        (2, vec!["foo", "bar"], "fo"),
+        (3, vec!["foo", "bar"], "foo"),
+        (4, vec!["foo", "bar"], "foob"),
+        (10, vec!["foo", "bar"], "foobar"),
+        (3, vec!["✨é", "🔑ü"], "✨é🔑"),
+    ];
+
+    for (remaining, parts, expected) in table {
+        let mut out = String::new();
+        let mut writer = TruncatingWriter {
+            out: &mut out,
+            remaining,
+        };
+
+        for part in parts {
+            writer.write_str(part).unwrap();
+        }
+
+        assert_eq!(expected, out);
+    }
+}
+
+#[test]
+fn test_random_preset() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashSet;
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let picked: HashSet<Preset> = (0..1000).map(|_| Preset::random(&mut rng)).collect();
+    assert_eq!(Preset::ALL.len(), picked.len());
+}
+
+#[test]
+fn test_preset_names() {
+    for preset in Preset::ALL {
+        assert_eq!(Ok(preset), preset.to_string().parse());
+
+        for alias in preset.aliases() {
+            assert_eq!(Ok(preset), alias.parse());
+        }
+    }
+
+    assert_eq!("ntlm", Preset::WindowsNtlmV1.to_string());
+    assert_eq!("wifi-easy", Preset::WifiEasyType.to_string());
+
+    let table = [
+        ("WindowsNTLMv1", Preset::WindowsNtlmV1),
+        ("windows_ntlm_v1", Preset::WindowsNtlmV1),
+        ("AppleID", Preset::AppleID),
+        ("apple_id", Preset::AppleID),
+        ("SecurityQuestions", Preset::SecurityQuestions),
+        (" Web32 ", Preset::Web32),
+        ("wifi_easy_type", Preset::WifiEasyType),
+    ];
+
+    for (name, preset) in table {
+        assert_eq!(Ok(preset), name.parse(), "{}", name);
+    }
+
+    assert_eq!(
+        Err("unknown preset 'web64'".to_string()),
+        "web64".parse::<Preset>()
+    );
+}
+
+#[test]
+fn test_preset_metadata() {
+    let presets = [
+        Preset::Default,
+        Preset::AppleID,
+        Preset::WindowsNtlmV1,
+        Preset::SecurityQuestions,
+        Preset::Web16,
+        Preset::Web32,
+        Preset::Wifi,
+        Preset::Xkcd,
+        Preset::Nist,
+        Preset::Temporary,
+        Preset::WifiEasyType,
+        Preset::Mobile,
+        Preset::SshPassphrase,
+        Preset::DiskEncryption,
+        Preset::DatabaseSafe,
+    ];
+
+    for preset in presets {
+        assert!(!preset.description().is_empty());
+        assert!(!preset.recommended_use().is_empty());
+    }
+
+    assert_eq!(
+        "As described in the original XKCD comic",
+        Preset::Xkcd.description()
+    );
+}
+
+#[test]
+fn test_checksum_digits() {
+    assert_eq!("00", checksum_digits(""));
+    assert_eq!(
+        "97".len(),
+        checksum_digits("correct-horse-battery-staple").len()
+    );
+
+    let passwd = "correct-horse-battery-staple";
+    let checksum = checksum_digits(passwd);
+    assert!(checksum.chars().all(|char| char.is_ascii_digit()));
+    assert!(verify_checksum(&format!("{}{}", passwd, checksum)));
+
+    let typos = [
+        "correct-horse-battery-stapel",
+        "correct-horse-batery-staple",
+        "correct_horse-battery-staple",
+        "Correct-horse-battery-staple",
+        "ocrrect-horse-battery-staple",
+    ];
+
+    for typo in typos {
+        assert!(
+            !verify_checksum(&format!("{}{}", typo, checksum)),
+            "{}",
+            typo
+        );
+    }
+
+    assert!(verify_checksum(&format!(
+        "été🔑{}",
+        checksum_digits("été🔑")
+    )));
+    assert!(!verify_checksum("1"));
+    assert!(!verify_checksum(""));
+}
+
+#[test]
+fn test_on_generate() {
+    use std::sync::Mutex;
+
+    let events: Arc<Mutex<Vec<GenerationMeta>>> = Arc::new(Mutex::new(vec![]));
+    let mut pass_generator = Xkpasswd::default();
+
+    // no observers, nothing to notify
+    pass_generator.gen_pass(&MockSettings {
+        padding_digits: (0, 0),
+        padding_symbols: (0, 0),
+        padding_result: PaddingResult::Unchanged,
+    });
+
+    let recorded = events.clone();
+    pass_generator.on_generate(move |meta| recorded.lock().unwrap().push(meta.clone()));
+
+    let settings = MockSettings {
+        padding_digits: (0, 0),
+        padding_symbols: (0, 0),
+        padding_result: PaddingResult::Unchanged,
+    };
+
+    let before = SystemTime::now();
+    pass_generator.gen_pass(&settings);
+    pass_generator.gen_passes(&settings, 3);
+    pass_generator.gen_pass_into(&settings, &mut String::new());
+
+    let events = events.lock().unwrap();
+    assert_eq!(
+        vec![1, 3, 1],
+        events.iter().map(|meta| meta.count).collect::<Vec<usize>>()
+    );
+
+    for meta in events.iter() {
+        assert_eq!(None, meta.preset);
+        assert_eq!(Entropy::default(), meta.entropy);
+        assert!(meta.timestamp >= before);
+    }
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn test_xkpasswd_from_url() {
+    // nothing listens on port 1
+    let err = Xkpasswd::from_url("http://127.0.0.1:1/words.txt", &"0".repeat(64)).unwrap_err();
+    assert!(
+        err.starts_with("failed to fetch http://127.0.0.1:1/words.txt"),
+        "{}",
+        err
+    );
+}
+
+#[cfg(feature = "trace")]
+#[test]
+fn test_tracing_spans() {
+    use std::sync::Mutex;
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let captured = Captured::default();
+    let writer = captured.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(move || writer.clone())
+        .with_max_level(tracing::Level::DEBUG)
+        .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
+        .finish();
+
+    let dict = crate::dict::load_any_dict(b"ball\ncake\ndoor\nlamp\n").unwrap();
+    let pass_generator = Xkpasswd::from_dict(dict);
+    let passwds = tracing::subscriber::with_default(subscriber, || {
+        pass_generator.gen_passes(&Settings::default(), 2).0
+    });
+
+    let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("gen_passes{count=2}"), "{}", output);
+    assert!(output.contains("words_pool"), "{}", output);
+
+    for passwd in passwds {
+        assert!(!output.contains(&passwd), "{}", output);
+    }
+    for word in ["ball", "cake", "door", "lamp"] {
+        assert!(!output.to_lowercase().contains(word), "{}", output);
     }
 }
@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Characters recovery codes are drawn from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CodeAlphabet {
+    /// Crockford's base 32, uppercase letters & digits without I, L, O & U
+    #[default]
+    Base32,
+    /// Digits only, e.g. for codes typed on a phone keypad
+    Digits,
+    /// Lowercase hexadecimal digits
+    Hex,
+    /// Letters & digits without those easily mistaken for one another
+    Unambiguous,
+}
+
+impl CodeAlphabet {
+    pub fn chars(&self) -> &'static str {
+        match self {
+            Self::Base32 => "0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+            Self::Digits => "0123456789",
+            Self::Hex => "0123456789abcdef",
+            Self::Unambiguous => "23456789ABCDEFGHJKMNPQRSTUVWXYZabcdefghijkmnpqrstuvwxyz",
+        }
+    }
+}
+
+/// Layout of recovery codes, 3 groups of 4 base 32 characters by default,
+/// e.g. `7KQ2-M9XD-F3TB`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeFormat {
+    chars: Vec<char>,
+    groups: usize,
+    group_length: usize,
+    separator: String,
+}
+
+impl Default for CodeFormat {
+    fn default() -> Self {
+        CodeFormat {
+            chars: CodeAlphabet::default().chars().chars().collect(),
+            groups: 3,
+            group_length: 4,
+            separator: "-".to_string(),
+        }
+    }
+}
+
+impl CodeFormat {
+    pub fn with_alphabet(self, alphabet: CodeAlphabet) -> Self {
+        self.with_chars(alphabet.chars())
+            .expect("built-in alphabets have distinct chars")
+    }
+
+    /// A custom alphabet, of at least 2 distinct chars, e.g. only uppercase letters.
+    pub fn with_chars(mut self, chars: &str) -> Result<Self, String> {
+        let mut distinct: Vec<char> = vec![];
+
+        for char in chars.chars() {
+            if distinct.contains(&char) {
+                return Err(format!("duplicated char '{}' in alphabet", char));
+            }
+
+            distinct.push(char);
+        }
+
+        if distinct.len() < 2 {
+            return Err("alphabet must have at least 2 chars".to_string());
+        }
+
+        self.chars = distinct;
+        Ok(self)
+    }
+
+    pub fn with_groups(mut self, groups: usize, group_length: usize) -> Result<Self, String> {
+        if groups == 0 || group_length == 0 {
+            return Err("only positive numbers of groups & group length are allowed".to_string());
+        }
+
+        self.groups = groups;
+        self.group_length = group_length;
+        Ok(self)
+    }
+
+    pub fn with_separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Entropy of each code in bits, rounded down.
+    pub fn entropy(&self) -> usize {
+        let length = (self.groups * self.group_length) as f64;
+        (length * (self.chars.len() as f64).log2()).floor() as usize
+    }
+
+    /// A single code, each char picked uniformly & independently from the alphabet.
+    pub fn gen_code<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let groups: Vec<String> = (0..self.groups)
+            .map(|_| {
+                (0..self.group_length)
+                    .filter_map(|_| self.chars.choose(rng))
+                    .collect()
+            })
+            .collect();
+
+        groups.join(&self.separator)
+    }
+
+    // as a float, as it overflows integers with only a few groups
+    fn distinct_codes(&self) -> f64 {
+        (self.chars.len() as f64).powf((self.groups * self.group_length) as f64)
+    }
+}
+
+/// `count` distinct recovery codes, e.g. to print once & keep offline in case a second factor
+/// is lost. Fails when the format can't make that many distinct codes.
+pub fn gen_recovery_codes(count: usize, format: &CodeFormat) -> Result<Vec<String>, String> {
+    gen_recovery_codes_with(&mut crate::rng::rng(), count, format)
+}
+
+/// Same as `gen_recovery_codes`, drawing from `rng`, e.g. a `test_utils::FixedRng` in tests.
+pub fn gen_recovery_codes_with<R: Rng + ?Sized>(
+    rng: &mut R,
+    count: usize,
+    format: &CodeFormat,
+) -> Result<Vec<String>, String> {
+    if count as f64 > format.distinct_codes() {
+        return Err(format!(
+            "can't make {} distinct codes of {} chars from {} chars",
+            count,
+            format.groups * format.group_length,
+            format.chars.len()
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    let mut codes = vec![];
+
+    while codes.len() < count {
+        let code = format.gen_code(rng);
+
+        if seen.insert(code.clone()) {
+            codes.push(code);
+        }
+    }
+
+    Ok(codes)
+}
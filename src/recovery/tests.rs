@@ -0,0 +1,79 @@
+use super::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn test_default_format() {
+    let format = CodeFormat::default();
+    assert_eq!(60, format.entropy());
+
+    let codes = gen_recovery_codes(10, &format).unwrap();
+    assert_eq!(10, codes.len());
+    assert_eq!(10, codes.iter().collect::<HashSet<_>>().len());
+
+    for code in codes {
+        let groups: Vec<&str> = code.split('-').collect();
+        assert_eq!(3, groups.len(), "{}", code);
+
+        for group in groups {
+            assert_eq!(4, group.len(), "{}", code);
+            assert!(
+                group
+                    .chars()
+                    .all(|char| CodeAlphabet::Base32.chars().contains(char)),
+                "{}",
+                code
+            );
+        }
+    }
+}
+
+#[test]
+fn test_custom_format() {
+    let format = CodeFormat::default()
+        .with_alphabet(CodeAlphabet::Digits)
+        .with_groups(2, 5)
+        .unwrap()
+        .with_separator(" ");
+    assert_eq!(33, format.entropy());
+
+    let code = format.gen_code(&mut StdRng::seed_from_u64(42));
+    assert_eq!(11, code.len());
+    assert_eq!(Some(5), code.find(' '));
+    assert!(code
+        .replace(' ', "")
+        .chars()
+        .all(|char| char.is_ascii_digit()));
+
+    let format = CodeFormat::default()
+        .with_chars("ab")
+        .unwrap()
+        .with_groups(1, 2)
+        .unwrap();
+    let mut codes = gen_recovery_codes_with(&mut StdRng::seed_from_u64(42), 4, &format).unwrap();
+    codes.sort();
+    assert_eq!(vec!["aa", "ab", "ba", "bb"], codes);
+
+    assert_eq!(
+        Err("can't make 5 distinct codes of 2 chars from 2 chars".to_string()),
+        gen_recovery_codes(5, &format)
+    );
+}
+
+#[test]
+fn test_invalid_format() {
+    assert_eq!(
+        Err("duplicated char 'a' in alphabet".to_string()),
+        CodeFormat::default().with_chars("abca")
+    );
+    assert_eq!(
+        Err("alphabet must have at least 2 chars".to_string()),
+        CodeFormat::default().with_chars("a")
+    );
+    assert!(CodeFormat::default().with_groups(0, 4).is_err());
+    assert!(CodeFormat::default().with_groups(3, 0).is_err());
+
+    // multi-byte chars count once
+    let format = CodeFormat::default().with_chars("αβγδ").unwrap();
+    assert_eq!(24, format.entropy());
+}
@@ -0,0 +1,90 @@
+// The random number generator behind every password, code & salt, picked at build time:
+// - by default, the thread-local RNG of `rand`, a ChaCha12 reseeded from the OS
+// - rng_os: the OS generator for every single number, slowest but with no state in memory
+// - rng_chacha: a ChaCha20 freshly seeded from the OS for each use
+// - rng_insecure: a small, fast & predictable generator, only meant for tests & benchmarks
+// When several are enabled, e.g. by --all-features, the most conservative one wins.
+
+#[cfg(all(
+    feature = "rng_insecure",
+    not(any(feature = "rng_os", feature = "rng_chacha")),
+    not(debug_assertions),
+    not(feature = "rng_insecure_release")
+))]
+compile_error!(
+    "rng_insecure makes passwords predictable, enable rng_insecure_release to build it in release mode anyway"
+);
+
+use rand::RngCore;
+#[cfg(all(
+    any(feature = "rng_chacha", feature = "rng_insecure"),
+    not(feature = "rng_os")
+))]
+use rand::SeedableRng;
+
+/// A generator from the backend selected at build time, e.g. to pass to `gen_pin_with`.
+pub fn rng() -> impl RngCore {
+    backend_rng()
+}
+
+#[cfg(feature = "rng_os")]
+fn backend_rng() -> rand::rngs::OsRng {
+    rand::rngs::OsRng
+}
+
+#[cfg(all(feature = "rng_chacha", not(feature = "rng_os")))]
+fn backend_rng() -> rand_chacha::ChaCha20Rng {
+    rand_chacha::ChaCha20Rng::from_entropy()
+}
+
+#[cfg(all(
+    feature = "rng_insecure",
+    not(any(feature = "rng_os", feature = "rng_chacha"))
+))]
+fn backend_rng() -> rand::rngs::SmallRng {
+    rand::rngs::SmallRng::from_entropy()
+}
+
+#[cfg(not(any(feature = "rng_os", feature = "rng_chacha", feature = "rng_insecure")))]
+fn backend_rng() -> rand::rngs::ThreadRng {
+    rand::thread_rng()
+}
+
+/// Name of the selected backend, for logs & diagnostics.
+pub fn backend_name() -> &'static str {
+    if cfg!(feature = "rng_os") {
+        "os"
+    } else if cfg!(feature = "rng_chacha") {
+        "chacha20"
+    } else if cfg!(feature = "rng_insecure") {
+        "insecure"
+    } else {
+        "thread"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let expected = if cfg!(feature = "rng_os") {
+            "os"
+        } else if cfg!(feature = "rng_chacha") {
+            "chacha20"
+        } else if cfg!(feature = "rng_insecure") {
+            "insecure"
+        } else {
+            "thread"
+        };
+        assert_eq!(expected, backend_name());
+
+        // every use is seeded on its own, never replaying the same numbers
+        let mut first = [0u8; 32];
+        let mut second = [0u8; 32];
+        rng().fill_bytes(&mut first);
+        rng().fill_bytes(&mut second);
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,90 @@
+// Ruby extension, built as the cdylib with the `ruby` feature & required as `xkpasswd`:
+//
+//   Xkpasswd.generate(preset: "wifi", count: 3) # => ["...", "...", "..."]
+//   Xkpasswd.entropy(query: "words=4&separators=-") # => { seen: 52, ... }
+//   Xkpasswd.presets # => [{ name: "default", ... }, ...]
+//
+// Settings are given like in `commands`, invalid ones raise ArgumentError.
+
+// linked to libruby with `ruby_dev`, which the extension itself must not be
+#[cfg(all(test, feature = "ruby_dev"))]
+mod tests;
+
+use crate::commands::{self, EntropyReport, GenerateRequest, PresetInfo, SettingsRequest};
+use magnus::scan_args::{get_kwargs, scan_args};
+use magnus::{function, prelude::*, Error, RArray, RHash, Ruby, Value};
+
+// keywords of both `generate` & `entropy`, the latter ignoring `count`
+fn scan_request(args: &[Value]) -> Result<(SettingsRequest, Option<usize>), Error> {
+    let args = scan_args::<(), (), (), (), RHash, ()>(args)?;
+    let kwargs = get_kwargs::<_, (), (Option<String>, Option<String>, Option<usize>), ()>(
+        args.keywords,
+        &[],
+        &["preset", "query", "count"],
+    )?;
+    let (preset, query, count) = kwargs.optional;
+
+    Ok((SettingsRequest { preset, query }, count))
+}
+
+fn entropy_hash(ruby: &Ruby, entropy: EntropyReport) -> Result<RHash, Error> {
+    let hash = ruby.hash_new();
+    hash.aset(ruby.to_symbol("blind_min"), entropy.blind_min)?;
+    hash.aset(ruby.to_symbol("blind_max"), entropy.blind_max)?;
+    hash.aset(ruby.to_symbol("seen"), entropy.seen)?;
+    hash.aset(ruby.to_symbol("guess_time"), entropy.guess_time)?;
+    Ok(hash)
+}
+
+fn preset_hash(ruby: &Ruby, preset: PresetInfo) -> Result<RHash, Error> {
+    let hash = ruby.hash_new();
+    hash.aset(ruby.to_symbol("name"), preset.name)?;
+    hash.aset(ruby.to_symbol("description"), preset.description)?;
+    hash.aset(ruby.to_symbol("recommended_use"), preset.recommended_use)?;
+    hash.aset(ruby.to_symbol("example"), preset.example)?;
+    hash.aset(
+        ruby.to_symbol("entropy"),
+        entropy_hash(ruby, preset.entropy)?,
+    )?;
+    Ok(hash)
+}
+
+fn generate(ruby: &Ruby, args: &[Value]) -> Result<Vec<String>, Error> {
+    let (settings, count) = scan_request(args)?;
+    let request = GenerateRequest {
+        settings,
+        count: count.unwrap_or(1),
+    };
+
+    commands::generate(request)
+        .map(|generated| generated.passwords)
+        .map_err(|err| Error::new(ruby.exception_arg_error(), err))
+}
+
+fn entropy(ruby: &Ruby, args: &[Value]) -> Result<RHash, Error> {
+    let (settings, _) = scan_request(args)?;
+    let entropy = commands::entropy_for(settings)
+        .map_err(|err| Error::new(ruby.exception_arg_error(), err))?;
+
+    entropy_hash(ruby, entropy)
+}
+
+// built in place rather than collected, Ruby objects must stay on the stack for the GC
+fn presets(ruby: &Ruby) -> Result<RArray, Error> {
+    let array = ruby.ary_new();
+
+    for preset in commands::list_presets() {
+        array.push(preset_hash(ruby, preset)?)?;
+    }
+
+    Ok(array)
+}
+
+#[magnus::init(name = "xkpasswd")]
+fn init(ruby: &Ruby) -> Result<(), Error> {
+    let module = ruby.define_module("Xkpasswd")?;
+    module.define_module_function("generate", function!(generate, -1))?;
+    module.define_module_function("entropy", function!(entropy, -1))?;
+    module.define_module_function("presets", function!(presets, 0))?;
+    Ok(())
+}
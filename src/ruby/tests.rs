@@ -0,0 +1,53 @@
+use super::*;
+use crate::prelude::{Builder, Preset, Xkpasswd};
+use crate::settings::Settings;
+
+// Ruby can only be started once per process, hence a single test
+#[test]
+fn test_extension() {
+    Ruby::init(|ruby| {
+        init(ruby)?;
+
+        let passwords: Vec<String> = ruby.eval(r#"Xkpasswd.generate(preset: "wifi", count: 3)"#)?;
+        let settings = Settings::from_preset(Preset::Wifi);
+        assert_eq!(3, passwords.len());
+        for passwd in &passwords {
+            assert!(
+                settings.matches(passwd, Xkpasswd::preloaded()),
+                "{}",
+                passwd
+            );
+        }
+
+        // defaults without keywords
+        let passwords: Vec<String> = ruby.eval("Xkpasswd.generate")?;
+        assert_eq!(1, passwords.len());
+
+        let seen: usize = ruby.eval(r#"Xkpasswd.entropy(query: "words=4")[:seen]"#)?;
+        let expected = commands::entropy_for(SettingsRequest {
+            preset: None,
+            query: Some("words=4".to_string()),
+        })
+        .unwrap();
+        assert_eq!(expected.seen, seen);
+
+        let names: Vec<String> = ruby.eval("Xkpasswd.presets.map { |preset| preset[:name] }")?;
+        let expected: Vec<String> = commands::list_presets()
+            .into_iter()
+            .map(|preset| preset.name)
+            .collect();
+        assert_eq!(expected, names);
+
+        for script in [
+            r#"Xkpasswd.generate(preset: "nope")"#,
+            r#"Xkpasswd.entropy(query: "words=x")"#,
+            r#"Xkpasswd.generate(typo: 1)"#,
+        ] {
+            let err = ruby.eval::<Value>(script).unwrap_err();
+            assert!(err.is_kind_of(ruby.exception_arg_error()), "{}", script);
+        }
+
+        Ok(())
+    })
+    .unwrap();
+}
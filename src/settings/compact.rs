@@ -0,0 +1,288 @@
+// Compact single line form of settings, e.g. `w3:l4-8:t=LU:s=.-:d0,2:p=!@#:pl0,2:fixed`,
+// short enough to fit in one environment variable or a chat message.
+// Fields are separated by ':', with ':' & '\' escaped by '\' in separators & symbols.
+// Extra entropy is never encoded, as it's meant to stay private.
+
+use super::Settings;
+use crate::bit_flags::{BitFlags, FieldSize, WordTransform};
+use crate::prelude::{JoinStyle, PaddingStrategy, ProperNouns, SpellingVariant};
+use std::fmt;
+use std::str::FromStr;
+
+const TRANSFORM_CODES: [(char, WordTransform); 6] = [
+    ('L', WordTransform::Lowercase),
+    ('T', WordTransform::Titlecase),
+    ('U', WordTransform::Uppercase),
+    ('I', WordTransform::InversedTitlecase),
+    ('a', WordTransform::AltercaseLowerFirst),
+    ('A', WordTransform::AltercaseUpperFirst),
+];
+
+const CAMEL_FLAG: &str = "camel";
+const PRONOUNCEABLE_FLAG: &str = "pron";
+const CHECKSUM_FLAG: &str = "cs";
+const NO_PROPER_NOUNS_FLAG: &str = "nopn";
+const PRESERVE_PROPER_NOUNS_FLAG: &str = "keeppn";
+const BRITISH_SPELLING_FLAG: &str = "gb";
+const AMERICAN_SPELLING_FLAG: &str = "us";
+const AVOID_AMBIGUOUS_FLAG: &str = "noamb";
+const AVOID_HOMOPHONES_FLAG: &str = "nohph";
+
+/// Displays settings in their compact form, parsed back by `Settings::from_str`.
+pub struct CompactSettings<'a>(&'a Settings);
+
+impl Settings {
+    /// The compact single line form, e.g. `w3:l4-8:t=LU:s=.-:d0,2:p=!@#:pl0,2:fixed`,
+    /// as opposed to the human readable description of `Display`.
+    pub fn compact(&self) -> CompactSettings<'_> {
+        CompactSettings(self)
+    }
+}
+
+impl fmt::Display for CompactSettings<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let settings = self.0;
+        let (word_min, word_max) = settings.word_lengths;
+        let (digits_before, digits_after) = settings.padding_digits;
+        let (symbols_before, symbols_after) = settings.padding_symbol_lengths;
+
+        let padding = match settings.padding_strategy {
+            PaddingStrategy::Fixed => "fixed".to_string(),
+            PaddingStrategy::Adaptive(len) => format!("adaptive{}", len),
+        };
+
+        let mut fields = vec![
+            format!("w{}", settings.words_count),
+            format!("l{}-{}", word_min, word_max),
+            format!("t={}", format_transforms(settings.word_transforms)),
+            format!("s={}", escape(&settings.separators)),
+            format!("d{},{}", digits_before, digits_after),
+            format!("p={}", escape(&settings.padding_symbols)),
+            format!("pl{},{}", symbols_before, symbols_after),
+            padding,
+        ];
+
+        if settings.join_style == JoinStyle::Camel {
+            fields.push(CAMEL_FLAG.to_string());
+        }
+
+        if settings.pronounceable {
+            fields.push(PRONOUNCEABLE_FLAG.to_string());
+        }
+
+        if settings.checksum {
+            fields.push(CHECKSUM_FLAG.to_string());
+        }
+
+        if let Some(theme) = &settings.word_theme {
+            fields.push(format!("th={}", escape(theme)));
+        }
+
+        match settings.proper_nouns {
+            ProperNouns::Include => {}
+            ProperNouns::Exclude => fields.push(NO_PROPER_NOUNS_FLAG.to_string()),
+            ProperNouns::Preserve => fields.push(PRESERVE_PROPER_NOUNS_FLAG.to_string()),
+        }
+
+        match settings.spelling {
+            SpellingVariant::Any => {}
+            SpellingVariant::British => fields.push(BRITISH_SPELLING_FLAG.to_string()),
+            SpellingVariant::American => fields.push(AMERICAN_SPELLING_FLAG.to_string()),
+        }
+
+        if settings.avoid_ambiguous {
+            fields.push(AVOID_AMBIGUOUS_FLAG.to_string());
+        }
+
+        if settings.avoid_homophones {
+            fields.push(AVOID_HOMOPHONES_FLAG.to_string());
+        }
+
+        write!(f, "{}", fields.join(":"))
+    }
+}
+
+/// Parses the compact form, fields can come in any order
+/// and missing ones keep their default values.
+impl FromStr for Settings {
+    type Err = String;
+
+    fn from_str(compact: &str) -> Result<Self, Self::Err> {
+        let mut words_count: Option<u8> = None;
+        let mut word_lengths: Option<(u8, u8)> = None;
+        let mut transforms: Option<FieldSize> = None;
+        let mut separators: Option<String> = None;
+        let mut digits: Option<(u8, u8)> = None;
+        let mut symbols: Option<String> = None;
+        let mut symbol_lengths: Option<(u8, u8)> = None;
+        let mut strategy: Option<PaddingStrategy> = None;
+        let mut join_style: Option<JoinStyle> = None;
+        let mut pronounceable: Option<bool> = None;
+        let mut checksum: Option<bool> = None;
+        let mut theme: Option<String> = None;
+        let mut proper_nouns: Option<ProperNouns> = None;
+        let mut spelling: Option<SpellingVariant> = None;
+        let mut avoid_ambiguous: Option<bool> = None;
+        let mut avoid_homophones: Option<bool> = None;
+
+        for field in split_fields(compact)? {
+            // checked from the longest prefixes, "pl" before "p="
+            let duplicated = if let Some(value) = field.strip_prefix("pl") {
+                symbol_lengths
+                    .replace(parse_pair(&field, value, ',')?)
+                    .is_some()
+            } else if let Some(value) = field.strip_prefix("p=") {
+                symbols.replace(value.to_string()).is_some()
+            } else if let Some(value) = field.strip_prefix("s=") {
+                separators.replace(value.to_string()).is_some()
+            } else if let Some(value) = field.strip_prefix("th=") {
+                theme.replace(value.to_string()).is_some()
+            } else if let Some(value) = field.strip_prefix("t=") {
+                transforms.replace(parse_transforms(value)?).is_some()
+            } else if let Some(value) = field.strip_prefix('w') {
+                words_count.replace(parse_number(&field, value)?).is_some()
+            } else if let Some(value) = field.strip_prefix('l') {
+                word_lengths
+                    .replace(parse_pair(&field, value, '-')?)
+                    .is_some()
+            } else if let Some(value) = field.strip_prefix('d') {
+                digits.replace(parse_pair(&field, value, ',')?).is_some()
+            } else if field == "fixed" {
+                strategy.replace(PaddingStrategy::Fixed).is_some()
+            } else if let Some(value) = field.strip_prefix("adaptive") {
+                strategy
+                    .replace(PaddingStrategy::Adaptive(parse_number(&field, value)?))
+                    .is_some()
+            } else if field == CAMEL_FLAG {
+                join_style.replace(JoinStyle::Camel).is_some()
+            } else if field == PRONOUNCEABLE_FLAG {
+                pronounceable.replace(true).is_some()
+            } else if field == CHECKSUM_FLAG {
+                checksum.replace(true).is_some()
+            } else if field == NO_PROPER_NOUNS_FLAG {
+                proper_nouns.replace(ProperNouns::Exclude).is_some()
+            } else if field == PRESERVE_PROPER_NOUNS_FLAG {
+                proper_nouns.replace(ProperNouns::Preserve).is_some()
+            } else if field == BRITISH_SPELLING_FLAG {
+                spelling.replace(SpellingVariant::British).is_some()
+            } else if field == AMERICAN_SPELLING_FLAG {
+                spelling.replace(SpellingVariant::American).is_some()
+            } else if field == AVOID_AMBIGUOUS_FLAG {
+                avoid_ambiguous.replace(true).is_some()
+            } else if field == AVOID_HOMOPHONES_FLAG {
+                avoid_homophones.replace(true).is_some()
+            } else {
+                return Err(format!("unknown field '{}'", field));
+            };
+
+            if duplicated {
+                return Err(format!("duplicated field '{}'", field));
+            }
+        }
+
+        let mut settings = Settings::default();
+
+        if let Some(words_count) = words_count {
+            settings.set_words_count(words_count)?;
+        }
+
+        if let Some((min, max)) = word_lengths {
+            settings.set_word_lengths(Some(min), Some(max))?;
+        }
+
+        if let Some(transforms) = transforms {
+            settings.set_word_transforms(transforms)?;
+        }
+
+        if let Some(separators) = separators {
+            settings.set_separators(&separators);
+        }
+
+        if let Some((before, after)) = digits {
+            settings.set_padding_digits(Some(before), Some(after));
+        }
+
+        if let Some(symbols) = symbols {
+            settings.set_padding_symbols(&symbols);
+        }
+
+        // lengths first, as adaptive padding resets them
+        if let Some((before, after)) = symbol_lengths {
+            settings.set_padding_symbol_lengths(Some(before), Some(after));
+        }
+
+        if let Some(strategy) = strategy {
+            settings.set_padding_strategy(strategy)?;
+        }
+
+        settings.set_join_style(join_style.unwrap_or_default());
+        settings.set_pronounceable(pronounceable.unwrap_or_default());
+        settings.set_checksum(checksum.unwrap_or_default());
+        settings.set_word_theme(&theme.unwrap_or_default());
+        settings.set_proper_nouns(proper_nouns.unwrap_or_default());
+        settings.set_spelling(spelling.unwrap_or_default());
+        settings.set_avoid_ambiguous(avoid_ambiguous.unwrap_or_default());
+        settings.set_avoid_homophones(avoid_homophones.unwrap_or_default());
+
+        Ok(settings)
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(':', "\\:")
+}
+
+fn split_fields(compact: &str) -> Result<Vec<String>, String> {
+    let mut fields: Vec<String> = vec![];
+    let mut field = String::new();
+    let mut chars = compact.trim().chars();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => match chars.next() {
+                Some(escaped) => field.push(escaped),
+                None => return Err("dangling escape character at the end".to_string()),
+            },
+            ':' => fields.push(std::mem::take(&mut field)),
+            _ => field.push(char),
+        }
+    }
+
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+    }
+
+    Ok(fields)
+}
+
+fn parse_number<T: FromStr>(field: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid number in '{}'", field))
+}
+
+fn parse_pair<T: FromStr>(field: &str, value: &str, delimiter: char) -> Result<(T, T), String> {
+    let (first, second) = value
+        .split_once(delimiter)
+        .ok_or_else(|| format!("expected 2 numbers in '{}'", field))?;
+
+    Ok((parse_number(field, first)?, parse_number(field, second)?))
+}
+
+pub(super) fn format_transforms(transforms: FieldSize) -> String {
+    TRANSFORM_CODES
+        .iter()
+        .filter(|(_, transform)| transforms.has_flag(*transform))
+        .map(|(code, _)| code)
+        .collect()
+}
+
+pub(super) fn parse_transforms(codes: &str) -> Result<FieldSize, String> {
+    codes.chars().try_fold(0, |transforms, code| {
+        TRANSFORM_CODES
+            .iter()
+            .find(|(transform_code, _)| *transform_code == code)
+            .map(|(_, transform)| transforms | *transform)
+            .ok_or_else(|| format!("unknown word transform '{}'", code))
+    })
+}
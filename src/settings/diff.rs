@@ -0,0 +1,185 @@
+use super::Settings;
+use crate::bit_flags::{BitFlags, FieldSize};
+use crate::prelude::{JoinStyle, PaddingStrategy, ProperNouns, SpellingVariant};
+use std::fmt;
+
+/// A single field differing between 2 settings, with both values formatted for display.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SettingDelta {
+    pub field: &'static str,
+    pub from: String,
+    pub to: String,
+}
+
+impl fmt::Display for SettingDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}→{})", self.field, self.from, self.to)
+    }
+}
+
+impl Settings {
+    /// Fields of `other` differing from these settings, in declaration order.
+    pub fn diff(&self, other: &Settings) -> Vec<SettingDelta> {
+        let fields = [
+            (
+                "words_count",
+                self.words_count != other.words_count,
+                self.words_count.to_string(),
+                other.words_count.to_string(),
+            ),
+            (
+                "word_lengths",
+                self.word_lengths != other.word_lengths,
+                format_pair(self.word_lengths, '-'),
+                format_pair(other.word_lengths, '-'),
+            ),
+            (
+                "word_transforms",
+                self.word_transforms != other.word_transforms,
+                format_transforms(self.word_transforms),
+                format_transforms(other.word_transforms),
+            ),
+            (
+                "separators",
+                self.separators != other.separators,
+                format_chars(&self.separators),
+                format_chars(&other.separators),
+            ),
+            (
+                "padding_digits",
+                self.padding_digits != other.padding_digits,
+                format_pair(self.padding_digits, ','),
+                format_pair(other.padding_digits, ','),
+            ),
+            (
+                "padding_symbols",
+                self.padding_symbols != other.padding_symbols,
+                format_chars(&self.padding_symbols),
+                format_chars(&other.padding_symbols),
+            ),
+            (
+                "padding_symbol_lengths",
+                self.padding_symbol_lengths != other.padding_symbol_lengths,
+                format_pair(self.padding_symbol_lengths, ','),
+                format_pair(other.padding_symbol_lengths, ','),
+            ),
+            (
+                "padding_strategy",
+                self.padding_strategy != other.padding_strategy,
+                format_strategy(&self.padding_strategy),
+                format_strategy(&other.padding_strategy),
+            ),
+            (
+                "join_style",
+                self.join_style != other.join_style,
+                format_join_style(self.join_style),
+                format_join_style(other.join_style),
+            ),
+            (
+                "pronounceable",
+                self.pronounceable != other.pronounceable,
+                self.pronounceable.to_string(),
+                other.pronounceable.to_string(),
+            ),
+            // only sizes are shown, the bytes themselves are meant to stay private
+            (
+                "extra_entropy",
+                self.extra_entropy != other.extra_entropy,
+                format_entropy_size(&self.extra_entropy),
+                format_entropy_size(&other.extra_entropy),
+            ),
+            (
+                "checksum",
+                self.checksum != other.checksum,
+                self.checksum.to_string(),
+                other.checksum.to_string(),
+            ),
+            (
+                "word_theme",
+                self.word_theme != other.word_theme,
+                format_theme(&self.word_theme),
+                format_theme(&other.word_theme),
+            ),
+            (
+                "proper_nouns",
+                self.proper_nouns != other.proper_nouns,
+                format_proper_nouns(self.proper_nouns),
+                format_proper_nouns(other.proper_nouns),
+            ),
+            (
+                "spelling",
+                self.spelling != other.spelling,
+                format_spelling(self.spelling),
+                format_spelling(other.spelling),
+            ),
+            (
+                "avoid_ambiguous",
+                self.avoid_ambiguous != other.avoid_ambiguous,
+                self.avoid_ambiguous.to_string(),
+                other.avoid_ambiguous.to_string(),
+            ),
+            (
+                "avoid_homophones",
+                self.avoid_homophones != other.avoid_homophones,
+                self.avoid_homophones.to_string(),
+                other.avoid_homophones.to_string(),
+            ),
+        ];
+
+        fields
+            .into_iter()
+            .filter(|(_, changed, _, _)| *changed)
+            .map(|(field, _, from, to)| SettingDelta { field, from, to })
+            .collect()
+    }
+}
+
+fn format_pair((first, second): (u8, u8), delimiter: char) -> String {
+    format!("{}{}{}", first, delimiter, second)
+}
+
+fn format_transforms(transforms: FieldSize) -> String {
+    transforms.to_strings().join(" & ")
+}
+
+fn format_chars(chars: &str) -> String {
+    format!("⟪{}⟫", chars)
+}
+
+fn format_strategy(strategy: &PaddingStrategy) -> String {
+    match strategy {
+        PaddingStrategy::Fixed => "fixed".to_string(),
+        PaddingStrategy::Adaptive(len) => format!("adaptive to {}", len),
+    }
+}
+
+fn format_join_style(style: JoinStyle) -> String {
+    match style {
+        JoinStyle::Separated => "separated".to_string(),
+        JoinStyle::Camel => "camel".to_string(),
+    }
+}
+
+fn format_theme(theme: &Option<String>) -> String {
+    theme.as_deref().unwrap_or("none").to_string()
+}
+
+fn format_proper_nouns(proper_nouns: ProperNouns) -> String {
+    match proper_nouns {
+        ProperNouns::Include => "include".to_string(),
+        ProperNouns::Exclude => "exclude".to_string(),
+        ProperNouns::Preserve => "preserve".to_string(),
+    }
+}
+
+fn format_spelling(spelling: SpellingVariant) -> String {
+    match spelling {
+        SpellingVariant::Any => "any".to_string(),
+        SpellingVariant::British => "british".to_string(),
+        SpellingVariant::American => "american".to_string(),
+    }
+}
+
+fn format_entropy_size(entropy: &[u8]) -> String {
+    format!("{} bytes", entropy.len())
+}
@@ -0,0 +1,156 @@
+use super::Settings;
+use crate::prelude::{PaddingStrategy, Preset, Randomizer, Xkpasswd};
+use std::fmt;
+
+pub const MIN_RECOMMENDED_ENTROPY: usize = 40;
+pub const MAX_RECOMMENDED_SYMBOLS: u8 = 10;
+
+/// A legal but risky configuration, worth telling users about without refusing it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Warning {
+    SingleWord,
+    NoSeparation,
+    LowEntropy(usize),
+    TooManySymbols(u8),
+    UncheckedWordLengths(u8, u8),
+    /// Symbols typed differently when a pre-boot prompt forces the US layout
+    LayoutDependentSymbols(String),
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SingleWord => write!(f, "a single word is easily guessed by dictionary attacks"),
+            Self::NoSeparation => write!(
+                f,
+                "without separators nor padding, passwords are only dictionary words"
+            ),
+            Self::LowEntropy(entropy) => write!(
+                f,
+                "entropy of {} bits with full knowledge is below the recommended {} bits",
+                entropy, MIN_RECOMMENDED_ENTROPY
+            ),
+            Self::TooManySymbols(count) => write!(
+                f,
+                "{} padding symbols add length but little entropy, more than {} are hard to type",
+                count, MAX_RECOMMENDED_SYMBOLS
+            ),
+            Self::UncheckedWordLengths(min, max) => write!(
+                f,
+                "word lengths of {} to {} are outside the usual {} to {}, the dictionary may have few such words",
+                min, max, Settings::MIN_WORD_LENGTH, Settings::MAX_WORD_LENGTH
+            ),
+            Self::LayoutDependentSymbols(symbols) => write!(
+                f,
+                "symbols {} may not be where expected at pre-boot prompts forcing a US keyboard layout",
+                symbols
+            ),
+        }
+    }
+}
+
+impl Settings {
+    /// Fails when the words of `pass_generator` within the word lengths & theme
+    /// are too few to pick `words_count` distinct ones, which would be repeated otherwise.
+    pub fn validate_against(&self, pass_generator: &Xkpasswd) -> Result<(), String> {
+        let (word_min, word_max) = self.word_lengths;
+        let mut lengths = if word_min == word_max {
+            format!("{} letters", word_min)
+        } else {
+            format!("{} to {} letters", word_min, word_max)
+        };
+
+        if let Some(theme) = &self.word_theme {
+            lengths.push_str(&format!(" themed '{}'", theme));
+        }
+
+        let pool_size = pass_generator.words_pool(self).len();
+
+        if pool_size == 0 {
+            return Err(format!("no words of {} in the dictionary", lengths));
+        }
+
+        if pool_size < self.words_count as usize {
+            return Err(format!(
+                "only {} distinct word(s) of {} in the dictionary for {} words",
+                pool_size, lengths, self.words_count
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Risky configurations, with entropy estimated against the words of `pass_generator`.
+    pub fn lint(&self, pass_generator: &Xkpasswd) -> Vec<Warning> {
+        let mut warnings: Vec<Warning> = vec![];
+
+        if self.words_count == 1 {
+            warnings.push(Warning::SingleWord);
+        }
+
+        let has_padding = self.padding_digits != (0, 0)
+            || (!self.padding_symbols.is_empty()
+                && (self.padding_symbol_lengths != (0, 0)
+                    || matches!(self.padding_strategy, PaddingStrategy::Adaptive(_))));
+
+        if self.separators.is_empty() && !has_padding {
+            warnings.push(Warning::NoSeparation);
+        }
+
+        let pool_size = pass_generator.words_pool(self).len();
+        let entropy = self.calc_entropy(pool_size);
+
+        if entropy.seen < MIN_RECOMMENDED_ENTROPY {
+            warnings.push(Warning::LowEntropy(entropy.seen));
+        }
+
+        let (symbols_before, symbols_after) = self.padding_symbol_lengths;
+        let symbols_count = symbols_before.saturating_add(symbols_after);
+
+        if symbols_count > MAX_RECOMMENDED_SYMBOLS {
+            warnings.push(Warning::TooManySymbols(symbols_count));
+        }
+
+        let (word_min, word_max) = self.word_lengths;
+
+        if word_min < Self::MIN_WORD_LENGTH || word_max > Self::MAX_WORD_LENGTH {
+            warnings.push(Warning::UncheckedWordLengths(word_min, word_max));
+        }
+
+        warnings
+    }
+
+    /// Changes defeating the purpose of the preset the settings were derived from,
+    /// e.g. symbols added to `Preset::DiskEncryption`. Only symbols are checked there,
+    /// letters & digits moved by non-US layouts like AZERTY are left to the user.
+    pub fn lint_against_preset(&self, preset: Preset) -> Vec<Warning> {
+        let mut warnings: Vec<Warning> = vec![];
+
+        if preset == Preset::DiskEncryption {
+            let has_padding_symbols = self.padding_symbol_lengths != (0, 0)
+                || matches!(self.padding_strategy, PaddingStrategy::Adaptive(_));
+            let padding_symbols = if has_padding_symbols {
+                self.padding_symbols.as_str()
+            } else {
+                ""
+            };
+
+            let mut symbols: Vec<char> = self
+                .separators
+                .chars()
+                .chain(padding_symbols.chars())
+                .filter(|c| !c.is_ascii_alphanumeric())
+                .collect();
+            symbols.sort_unstable();
+            symbols.dedup();
+
+            if !symbols.is_empty() {
+                warnings.push(Warning::LayoutDependentSymbols(
+                    symbols.into_iter().collect(),
+                ));
+            }
+        }
+
+        warnings
+    }
+}
@@ -0,0 +1,223 @@
+use super::Settings;
+use crate::bit_flags::{BitFlags, WordTransform};
+use crate::prelude::{verify_checksum, PaddingStrategy, Xkpasswd, CHECKSUM_LENGTH};
+use std::cmp;
+
+enum Token {
+    Digits(usize),
+    // indices of the word variants allowed at this position
+    Word(Vec<usize>),
+}
+
+// matches a password body (without padding symbols) against the structural template:
+// [digits] word (separator word)* [digits], joined by a single repeated separator
+struct Matcher<'s> {
+    separator: &'s str,
+    tokens: &'s [Token],
+    variants: &'s [Vec<String>],
+    // the body may be cut short by adaptive padding
+    truncated: bool,
+}
+
+impl Settings {
+    /// Checks whether `candidate` could have been generated with these settings,
+    /// using words from the dictionary of `pass_generator`.
+    pub fn matches(&self, candidate: &str, pass_generator: &Xkpasswd) -> bool {
+        let pool = pass_generator.words_pool(self);
+
+        let transforms = self.allowed_transforms();
+        let variants: Vec<Vec<String>> = transforms
+            .iter()
+            .map(|&transform| {
+                pool.iter()
+                    .map(|word| self.transform_pool_word(word, transform))
+                    .collect()
+            })
+            .collect();
+
+        let tokens = self.template_tokens(&transforms);
+
+        let mut separators: Vec<String> = self.separators.chars().map(String::from).collect();
+        separators.sort();
+        separators.dedup();
+        if separators.is_empty() {
+            separators.push("".to_string());
+        }
+
+        let matches_body = |body: &str, truncated: bool| {
+            separators.iter().any(|separator| {
+                let matcher = Matcher {
+                    separator,
+                    tokens: &tokens,
+                    variants: &variants,
+                    truncated,
+                };
+                matcher.matches(body, 0)
+            })
+        };
+
+        let candidate = if self.checksum {
+            if !verify_checksum(candidate) {
+                return false;
+            }
+
+            let (body, _) = split_at_char(candidate, candidate.chars().count() - CHECKSUM_LENGTH);
+            body
+        } else {
+            candidate
+        };
+
+        let chars_count = candidate.chars().count();
+
+        match self.padding_strategy {
+            PaddingStrategy::Fixed => {
+                let (prefix_len, suffix_len) = if self.padding_symbols.is_empty() {
+                    (0, 0)
+                } else {
+                    let (prefix, suffix) = self.padding_symbol_lengths;
+                    (prefix as usize, suffix as usize)
+                };
+
+                if chars_count < prefix_len + suffix_len {
+                    return false;
+                }
+
+                let (prefix, rest) = split_at_char(candidate, prefix_len);
+                let (body, suffix) = split_at_char(rest, chars_count - prefix_len - suffix_len);
+
+                self.is_padding(prefix) && self.is_padding(suffix) && matches_body(body, false)
+            }
+            PaddingStrategy::Adaptive(len) => match chars_count.cmp(&self.adaptive_body_len(len)) {
+                cmp::Ordering::Greater => false,
+                // nothing to pad with
+                cmp::Ordering::Less => {
+                    self.padding_symbols.is_empty() && matches_body(candidate, false)
+                }
+                cmp::Ordering::Equal => {
+                    if matches_body(candidate, true) {
+                        return true;
+                    }
+
+                    // strip the padded symbols, a single repeated char at the end
+                    (1..=chars_count).any(|padded_len| {
+                        let (body, padding) = split_at_char(candidate, chars_count - padded_len);
+                        self.is_padding(padding) && matches_body(body, false)
+                    })
+                }
+            },
+        }
+    }
+
+    pub(super) fn allowed_transforms(&self) -> Vec<WordTransform> {
+        if self
+            .applied_transforms()
+            .has_flag(WordTransform::AltercaseLowerFirst)
+            || self
+                .applied_transforms()
+                .has_flag(WordTransform::AltercaseUpperFirst)
+        {
+            return vec![WordTransform::Lowercase, WordTransform::Uppercase];
+        }
+
+        Self::ALL_SINGLE_WORD_TRANSFORMS
+            .into_iter()
+            .filter(|&transform| self.applied_transforms() & transform)
+            .collect()
+    }
+
+    fn template_tokens(&self, transforms: &[WordTransform]) -> Vec<Token> {
+        let (prefix_digits, suffix_digits) = self.padding_digits;
+        let lower_first = self
+            .applied_transforms()
+            .has_flag(WordTransform::AltercaseLowerFirst);
+        let upper_first = self
+            .applied_transforms()
+            .has_flag(WordTransform::AltercaseUpperFirst);
+
+        let mut tokens: Vec<Token> = vec![];
+
+        if prefix_digits > 0 {
+            tokens.push(Token::Digits(prefix_digits as usize));
+        }
+
+        for idx in 0..self.words_count {
+            // alternating transforms are fixed per position, see `build_transforms_list`
+            let allowed = if lower_first || upper_first {
+                let is_lower = (idx % 2 == 0) == lower_first;
+                vec![if is_lower { 0 } else { 1 }]
+            } else {
+                (0..transforms.len()).collect()
+            };
+
+            tokens.push(Token::Word(allowed));
+        }
+
+        if suffix_digits > 0 {
+            tokens.push(Token::Digits(suffix_digits as usize));
+        }
+
+        tokens
+    }
+
+    fn is_padding(&self, symbols: &str) -> bool {
+        let mut chars = symbols.chars();
+
+        match chars.next() {
+            None => true,
+            Some(first) => self.padding_symbols.contains(first) && chars.all(|char| char == first),
+        }
+    }
+}
+
+impl Matcher<'_> {
+    fn matches(&self, rest: &str, idx: usize) -> bool {
+        if idx == self.tokens.len() {
+            return rest.is_empty();
+        }
+
+        if rest.is_empty() {
+            return self.truncated;
+        }
+
+        let rest = if idx == 0 {
+            rest
+        } else {
+            match rest.strip_prefix(self.separator) {
+                Some("") => return self.truncated,
+                Some(rest) => rest,
+                None => return self.truncated && self.separator.starts_with(rest),
+            }
+        };
+
+        match &self.tokens[idx] {
+            Token::Digits(count) => {
+                let digits_len = rest
+                    .chars()
+                    .take(*count)
+                    .take_while(|char| char.is_ascii_digit())
+                    .count();
+
+                if digits_len == *count {
+                    self.matches(&rest[digits_len..], idx + 1)
+                } else {
+                    self.truncated && digits_len == rest.len()
+                }
+            }
+            Token::Word(allowed) => allowed.iter().any(|&variant_idx| {
+                self.variants[variant_idx].iter().any(|word| {
+                    match rest.strip_prefix(word.as_str()) {
+                        Some(next) => self.matches(next, idx + 1),
+                        None => self.truncated && word.starts_with(rest),
+                    }
+                })
+            }),
+        }
+    }
+}
+
+fn split_at_char(text: &str, chars_count: usize) -> (&str, &str) {
+    match text.char_indices().nth(chars_count) {
+        Some((idx, _)) => text.split_at(idx),
+        None => (text, ""),
+    }
+}
@@ -1,14 +1,40 @@
+pub mod compact;
+pub mod diff;
+pub mod lint;
+mod matcher;
+mod patch;
+mod query_string;
+pub mod registry;
+mod solver;
+mod target;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "json")]
+mod xkpasswd_json;
 
 use crate::bit_flags::{BitFlags, FieldSize, WordTransform};
+use crate::dict::is_proper_noun;
 use crate::prelude::{
-    Builder, Entropy, GuessTime, PaddingResult, PaddingStrategy, Preset, Randomizer,
+    Builder, Entropy, GuessTime, JoinStyle, PaddingResult, PaddingStrategy, PasswordGenerator,
+    Preset, ProperNouns, Randomizer, SpellingVariant, SymbolSet, Xkpasswd, CHECKSUM_LENGTH,
 };
+#[cfg(feature = "extra_entropy")]
+use hkdf::Hkdf;
+pub use patch::SettingsPatch;
 use rand::distributions::{Distribution, Uniform};
-use rand::Rng;
+#[cfg(feature = "extra_entropy")]
+use rand::rngs::StdRng;
+use rand::seq::index;
+#[cfg(feature = "extra_entropy")]
+use rand::SeedableRng;
+use rand::{Rng, RngCore};
+#[cfg(feature = "extra_entropy")]
+use sha2::Sha256;
+pub use solver::{SettingsSolver, Suggestion};
+#[cfg(feature = "extra_entropy")]
+use std::cell::RefCell;
 use std::cmp;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fmt;
 use std::ops::Range;
 use std::result::Result;
@@ -16,6 +42,13 @@ use std::result::Result;
 const MIN_WORD_LENGTH_ERR: &str = "min word length must be 4 or higher";
 const MAX_WORD_LENGTH_ERR: &str = "max word length must be 10 or lower";
 
+#[cfg(feature = "extra_entropy")]
+thread_local! {
+    // the RNG of the password being generated on this thread, along with the extra entropy
+    // it was derived from, so the key is derived once per password rather than per pick
+    static PASS_RNG: RefCell<Option<(Vec<u8>, StdRng)>> = const { RefCell::new(None) };
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Settings {
     words_count: u8,
@@ -26,6 +59,15 @@ pub struct Settings {
     padding_symbols: String,
     padding_symbol_lengths: (u8, u8),
     padding_strategy: PaddingStrategy,
+    join_style: JoinStyle,
+    pronounceable: bool,
+    extra_entropy: Vec<u8>,
+    checksum: bool,
+    word_theme: Option<String>,
+    proper_nouns: ProperNouns,
+    spelling: SpellingVariant,
+    avoid_ambiguous: bool,
+    avoid_homophones: bool,
 }
 
 impl Default for Settings {
@@ -39,6 +81,15 @@ impl Default for Settings {
             padding_symbols: Self::DEFAULT_SYMBOLS.to_string(),
             padding_symbol_lengths: (0, Self::DEFAULT_PADDING_LENGTH),
             padding_strategy: Self::DEFAULT_PADDING_STRATEGY,
+            join_style: JoinStyle::Separated,
+            pronounceable: false,
+            extra_entropy: vec![],
+            checksum: false,
+            word_theme: None,
+            proper_nouns: ProperNouns::Include,
+            spelling: SpellingVariant::Any,
+            avoid_ambiguous: false,
+            avoid_homophones: false,
         }
     }
 }
@@ -55,19 +106,61 @@ impl fmt::Display for Settings {
 
         let mut desc = vec![format!("{} word(s)", self.words_count), word_lengths];
 
-        let word_transforms = self.word_transforms.to_strings();
+        if let Some(theme) = &self.word_theme {
+            desc.push(format!("words themed '{}'", theme));
+        }
+
+        match self.spelling {
+            SpellingVariant::Any => {}
+            SpellingVariant::British => desc.push("British spelling".to_string()),
+            SpellingVariant::American => desc.push("American spelling".to_string()),
+        }
+
+        let word_transforms = self.applied_transforms().to_strings();
         if word_transforms.len() > 1 {
             desc.push(format!("mixed of {}", word_transforms.join(" & ")));
         } else {
             desc.push(format!("{} only", word_transforms[0]))
         }
 
-        if self.separators.len() > 1 {
+        if self.separators.chars().count() > 1 {
             desc.push(format!("a separator from ⟪{}⟫", self.separators));
         } else {
             desc.push(format!("'{}' as separator", self.separators));
         }
 
+        if self.join_style == JoinStyle::Camel {
+            desc.push("words joined in CamelCase".to_string());
+        }
+
+        if self.pronounceable {
+            desc.push("easy to say aloud word combinations".to_string());
+        }
+
+        if self.avoid_ambiguous {
+            desc.push("no ambiguous characters like l, 1 & O".to_string());
+        }
+
+        if self.avoid_homophones {
+            desc.push("no words sounding like others".to_string());
+        }
+
+        match self.proper_nouns {
+            ProperNouns::Include => {}
+            ProperNouns::Exclude => desc.push("no proper nouns".to_string()),
+            ProperNouns::Preserve => {
+                desc.push("proper nouns in their canonical capitalization".to_string())
+            }
+        }
+
+        if !self.extra_entropy.is_empty() {
+            desc.push("extra user supplied entropy mixed into randomness".to_string());
+        }
+
+        if self.checksum {
+            desc.push(format!("{} checksum digits at the end", CHECKSUM_LENGTH));
+        }
+
         let (prefix, suffix) = self.padding_digits;
 
         if prefix > 0 && suffix > 0 {
@@ -81,7 +174,7 @@ impl fmt::Display for Settings {
             desc.push(format!("{} digit(s) after", suffix));
         }
 
-        let padding_symbols = if self.padding_symbols.len() > 1 {
+        let padding_symbols = if self.padding_symbols.chars().count() > 1 {
             format!("from ⟪{}⟫", self.padding_symbols)
         } else {
             format!("of '{}'", self.padding_symbols)
@@ -131,12 +224,8 @@ impl fmt::Display for Settings {
 
 impl Builder for Settings {
     fn with_words_count(&self, words_count: u8) -> Result<Self, String> {
-        if words_count == 0 {
-            return Err("only positive integer is allowed for words count".to_string());
-        }
-
         let mut cloned = self.clone();
-        cloned.words_count = words_count;
+        cloned.set_words_count(words_count)?;
         Ok(cloned)
     }
 
@@ -145,109 +234,113 @@ impl Builder for Settings {
         min_length: Option<u8>,
         max_length: Option<u8>,
     ) -> Result<Self, String> {
-        let min_length = min_length.unwrap_or(self.word_lengths.0);
-        let max_length = max_length.unwrap_or(self.word_lengths.1);
-
-        let min = cmp::min(min_length, max_length);
-        let max = cmp::max(min_length, max_length);
-
-        if min < Self::MIN_WORD_LENGTH {
-            return Err(MIN_WORD_LENGTH_ERR.to_string());
-        }
-
-        if max > Self::MAX_WORD_LENGTH {
-            return Err(MAX_WORD_LENGTH_ERR.to_string());
-        }
-
         let mut cloned = self.clone();
-        cloned.word_lengths = (min, max);
+        cloned.set_word_lengths(min_length, max_length)?;
         Ok(cloned)
     }
 
+    fn with_word_lengths_unchecked(&self, min_length: Option<u8>, max_length: Option<u8>) -> Self {
+        let mut cloned = self.clone();
+        cloned.set_word_lengths_unchecked(min_length, max_length);
+        cloned
+    }
+
     fn with_separators(&self, separators: &str) -> Self {
         let mut cloned = self.clone();
-        cloned.separators = separators.to_string();
+        cloned.set_separators(separators);
         cloned
     }
 
     fn with_padding_digits(&self, prefix: Option<u8>, suffix: Option<u8>) -> Self {
-        if prefix.is_none() && suffix.is_none() {
-            return self.clone();
-        }
-
         let mut cloned = self.clone();
-        cloned.padding_digits = (
-            prefix.unwrap_or(self.padding_digits.0),
-            suffix.unwrap_or(self.padding_digits.1),
-        );
+        cloned.set_padding_digits(prefix, suffix);
         cloned
     }
 
     fn with_padding_symbols(&self, symbols: &str) -> Self {
         let mut cloned = self.clone();
-        cloned.padding_symbols = symbols.to_string();
+        cloned.set_padding_symbols(symbols);
         cloned
     }
 
-    fn with_padding_symbol_lengths(&self, prefix: Option<u8>, suffix: Option<u8>) -> Self {
-        if prefix.is_none() && suffix.is_none() {
-            return self.clone();
-        }
+    fn with_symbol_set(&self, symbol_set: SymbolSet) -> Self {
+        let mut cloned = self.clone();
+        cloned.set_separators(symbol_set.symbols());
+        cloned.set_padding_symbols(symbol_set.symbols());
+        cloned
+    }
 
+    fn with_padding_symbol_lengths(&self, prefix: Option<u8>, suffix: Option<u8>) -> Self {
         let mut cloned = self.clone();
-        cloned.padding_symbol_lengths = (
-            prefix.unwrap_or(self.padding_symbol_lengths.0),
-            suffix.unwrap_or(self.padding_symbol_lengths.1),
-        );
-        cloned.padding_strategy = PaddingStrategy::Fixed;
+        cloned.set_padding_symbol_lengths(prefix, suffix);
         cloned
     }
 
     fn with_padding_strategy(&self, strategy: PaddingStrategy) -> Result<Self, String> {
         let mut cloned = self.clone();
-
-        match strategy {
-            PaddingStrategy::Adaptive(0) => {
-                return Err("invalid adaptive padding number".to_string())
-            }
-            PaddingStrategy::Adaptive(_) => {
-                cloned.padding_strategy = strategy;
-                cloned.padding_symbol_lengths = (0, 0);
-            }
-            PaddingStrategy::Fixed => {
-                cloned.padding_strategy = strategy;
-            }
-        }
-
+        cloned.set_padding_strategy(strategy)?;
         Ok(cloned)
     }
 
     fn with_word_transforms(&self, transforms: FieldSize) -> Result<Self, String> {
         let mut cloned = self.clone();
+        cloned.set_word_transforms(transforms)?;
+        Ok(cloned)
+    }
 
-        // handle group transforms first
-        if transforms.has_flag(WordTransform::AltercaseLowerFirst) {
-            cloned.word_transforms = FieldSize::from_flag(WordTransform::AltercaseLowerFirst);
-            return Ok(cloned);
-        }
+    fn with_join_style(&self, style: JoinStyle) -> Self {
+        let mut cloned = self.clone();
+        cloned.set_join_style(style);
+        cloned
+    }
 
-        if transforms.has_flag(WordTransform::AltercaseUpperFirst) {
-            cloned.word_transforms = FieldSize::from_flag(WordTransform::AltercaseUpperFirst);
-            return Ok(cloned);
-        }
+    fn with_pronounceable(&self, pronounceable: bool) -> Self {
+        let mut cloned = self.clone();
+        cloned.set_pronounceable(pronounceable);
+        cloned
+    }
 
-        // no transform matched
-        if !transforms.has_flag(WordTransform::Lowercase)
-            && !transforms.has_flag(WordTransform::Titlecase)
-            && !transforms.has_flag(WordTransform::Uppercase)
-            && !transforms.has_flag(WordTransform::InversedTitlecase)
-        {
-            return Err("invalid transform".to_string());
-        }
+    #[cfg(feature = "extra_entropy")]
+    fn with_extra_entropy(&self, entropy: &[u8]) -> Self {
+        let mut cloned = self.clone();
+        cloned.set_extra_entropy(entropy);
+        cloned
+    }
 
+    fn with_checksum(&self, checksum: bool) -> Self {
         let mut cloned = self.clone();
-        cloned.word_transforms = transforms;
-        Ok(cloned)
+        cloned.set_checksum(checksum);
+        cloned
+    }
+
+    fn with_word_theme(&self, theme: &str) -> Self {
+        let mut cloned = self.clone();
+        cloned.set_word_theme(theme);
+        cloned
+    }
+
+    fn with_proper_nouns(&self, proper_nouns: ProperNouns) -> Self {
+        let mut cloned = self.clone();
+        cloned.set_proper_nouns(proper_nouns);
+        cloned
+    }
+
+    fn with_spelling(&self, spelling: SpellingVariant) -> Self {
+        let mut cloned = self.clone();
+        cloned.set_spelling(spelling);
+        cloned
+    }
+
+    fn with_avoid_ambiguous(&self, avoid_ambiguous: bool) -> Self {
+        let mut cloned = self.clone();
+        cloned.set_avoid_ambiguous(avoid_ambiguous);
+        cloned
+    }
+
+    fn with_avoid_homophones(&self, avoid_homophones: bool) -> Self {
+        let mut cloned = self.clone();
+        cloned.set_avoid_homophones(avoid_homophones);
+        cloned
     }
 
     fn from_preset(preset: Preset) -> Self {
@@ -261,6 +354,15 @@ impl Builder for Settings {
                 padding_symbols: "!?@&".to_string(),
                 padding_symbol_lengths: (1, 1),
                 padding_strategy: PaddingStrategy::Fixed,
+                join_style: JoinStyle::Separated,
+                pronounceable: false,
+                extra_entropy: vec![],
+                checksum: false,
+                word_theme: None,
+                proper_nouns: ProperNouns::Include,
+                spelling: SpellingVariant::Any,
+                avoid_ambiguous: false,
+                avoid_homophones: false,
             },
             Preset::WindowsNtlmV1 => Settings {
                 words_count: 2,
@@ -271,6 +373,15 @@ impl Builder for Settings {
                 padding_symbols: "!@$%^&*+=:|~?".to_string(),
                 padding_symbol_lengths: (0, 1),
                 padding_strategy: PaddingStrategy::Fixed,
+                join_style: JoinStyle::Separated,
+                pronounceable: false,
+                extra_entropy: vec![],
+                checksum: false,
+                word_theme: None,
+                proper_nouns: ProperNouns::Include,
+                spelling: SpellingVariant::Any,
+                avoid_ambiguous: false,
+                avoid_homophones: false,
             },
             Preset::SecurityQuestions => Settings {
                 words_count: 6,
@@ -281,6 +392,15 @@ impl Builder for Settings {
                 padding_symbols: ".!?".to_string(),
                 padding_symbol_lengths: (0, 1),
                 padding_strategy: PaddingStrategy::Fixed,
+                join_style: JoinStyle::Separated,
+                pronounceable: false,
+                extra_entropy: vec![],
+                checksum: false,
+                word_theme: None,
+                proper_nouns: ProperNouns::Include,
+                spelling: SpellingVariant::Any,
+                avoid_ambiguous: false,
+                avoid_homophones: false,
             },
             Preset::Web16 => Settings {
                 words_count: 3,
@@ -291,6 +411,15 @@ impl Builder for Settings {
                 padding_symbols: "!@$%^&*+=:|~?".to_string(),
                 padding_symbol_lengths: (1, 1),
                 padding_strategy: PaddingStrategy::Fixed,
+                join_style: JoinStyle::Separated,
+                pronounceable: false,
+                extra_entropy: vec![],
+                checksum: false,
+                word_theme: None,
+                proper_nouns: ProperNouns::Include,
+                spelling: SpellingVariant::Any,
+                avoid_ambiguous: false,
+                avoid_homophones: false,
             },
             Preset::Web32 => Settings {
                 words_count: 4,
@@ -301,6 +430,15 @@ impl Builder for Settings {
                 padding_symbols: "!@$%^&*+=:|~?".to_string(),
                 padding_symbol_lengths: (1, 1),
                 padding_strategy: PaddingStrategy::Fixed,
+                join_style: JoinStyle::Separated,
+                pronounceable: false,
+                extra_entropy: vec![],
+                checksum: false,
+                word_theme: None,
+                proper_nouns: ProperNouns::Include,
+                spelling: SpellingVariant::Any,
+                avoid_ambiguous: false,
+                avoid_homophones: false,
             },
             Preset::Wifi => Settings {
                 words_count: 6,
@@ -311,6 +449,15 @@ impl Builder for Settings {
                 padding_symbols: "!@$%^&*+=:|~?".to_string(),
                 padding_symbol_lengths: (0, 0),
                 padding_strategy: PaddingStrategy::Adaptive(63),
+                join_style: JoinStyle::Separated,
+                pronounceable: false,
+                extra_entropy: vec![],
+                checksum: false,
+                word_theme: None,
+                proper_nouns: ProperNouns::Include,
+                spelling: SpellingVariant::Any,
+                avoid_ambiguous: false,
+                avoid_homophones: false,
             },
             Preset::Xkcd => Settings {
                 words_count: 4,
@@ -321,20 +468,245 @@ impl Builder for Settings {
                 padding_symbols: "".to_string(),
                 padding_symbol_lengths: (0, 0),
                 padding_strategy: PaddingStrategy::Fixed,
+                join_style: JoinStyle::Separated,
+                pronounceable: false,
+                extra_entropy: vec![],
+                checksum: false,
+                word_theme: None,
+                proper_nouns: ProperNouns::Include,
+                spelling: SpellingVariant::Any,
+                avoid_ambiguous: false,
+                avoid_homophones: false,
+            },
+            Preset::Nist => Settings {
+                words_count: 5,
+                word_lengths: (4, 8),
+                word_transforms: WordTransform::Lowercase | WordTransform::Titlecase,
+                separators: "-".to_string(),
+                padding_digits: (0, 0),
+                padding_symbols: "".to_string(),
+                padding_symbol_lengths: (0, 0),
+                padding_strategy: PaddingStrategy::Fixed,
+                join_style: JoinStyle::Separated,
+                pronounceable: false,
+                extra_entropy: vec![],
+                checksum: false,
+                word_theme: None,
+                proper_nouns: ProperNouns::Include,
+                spelling: SpellingVariant::Any,
+                avoid_ambiguous: false,
+                avoid_homophones: false,
+            },
+            // not one of the HSXKPasswd presets, the values are this crate's own
+            Preset::Temporary => Settings {
+                words_count: 3,
+                word_lengths: (4, 6),
+                word_transforms: FieldSize::from_flag(WordTransform::Lowercase),
+                separators: "-".to_string(),
+                padding_digits: (0, 2),
+                padding_symbols: "".to_string(),
+                padding_symbol_lengths: (0, 0),
+                padding_strategy: PaddingStrategy::Fixed,
+                join_style: JoinStyle::Separated,
+                pronounceable: false,
+                extra_entropy: vec![],
+                checksum: false,
+                word_theme: None,
+                proper_nouns: ProperNouns::Include,
+                spelling: SpellingVariant::Any,
+                avoid_ambiguous: false,
+                avoid_homophones: false,
+            },
+            Preset::WifiEasyType => Settings {
+                words_count: 5,
+                word_lengths: (4, 8),
+                word_transforms: FieldSize::from_flag(WordTransform::Lowercase),
+                separators: "-".to_string(),
+                padding_digits: (0, 4),
+                padding_symbols: "".to_string(),
+                padding_symbol_lengths: (0, 0),
+                padding_strategy: PaddingStrategy::Fixed,
+                join_style: JoinStyle::Separated,
+                pronounceable: false,
+                extra_entropy: vec![],
+                checksum: false,
+                word_theme: None,
+                proper_nouns: ProperNouns::Include,
+                spelling: SpellingVariant::Any,
+                avoid_ambiguous: false,
+                avoid_homophones: false,
+            },
+            Preset::Mobile => Settings {
+                words_count: 4,
+                word_lengths: (4, 8),
+                word_transforms: FieldSize::from_flag(WordTransform::Lowercase),
+                separators: " ".to_string(),
+                padding_digits: (0, 3),
+                padding_symbols: Self::EASY_SYMBOLS.to_string(),
+                padding_symbol_lengths: (0, 1),
+                padding_strategy: PaddingStrategy::Fixed,
+                join_style: JoinStyle::Separated,
+                pronounceable: false,
+                extra_entropy: vec![],
+                checksum: false,
+                word_theme: None,
+                proper_nouns: ProperNouns::Include,
+                spelling: SpellingVariant::Any,
+                avoid_ambiguous: false,
+                avoid_homophones: false,
+            },
+            // no quotes, spaces, `$`, `!` or globs, so it survives `-N '...'`, scripts & heredocs
+            Preset::SshPassphrase => Settings {
+                words_count: 6,
+                word_lengths: (4, 8),
+                word_transforms: WordTransform::Lowercase | WordTransform::Titlecase,
+                separators: Self::SHELL_SAFE_SYMBOLS.to_string(),
+                padding_digits: (0, 2),
+                padding_symbols: "".to_string(),
+                padding_symbol_lengths: (0, 0),
+                padding_strategy: PaddingStrategy::Fixed,
+                join_style: JoinStyle::Separated,
+                pronounceable: false,
+                extra_entropy: vec![],
+                checksum: false,
+                word_theme: None,
+                proper_nouns: ProperNouns::Include,
+                spelling: SpellingVariant::Any,
+                avoid_ambiguous: false,
+                avoid_homophones: false,
+            },
+            // symbols move the most between keyboard layouts, though letters like y & z on QWERTZ
+            // or a & q on AZERTY still do, so the passphrase is best set up on the layout it's typed on
+            Preset::DiskEncryption => Settings {
+                words_count: 6,
+                word_lengths: (4, 8),
+                word_transforms: FieldSize::from_flag(WordTransform::Lowercase),
+                separators: Self::DIGITS.to_string(),
+                padding_digits: (0, 2),
+                padding_symbols: "".to_string(),
+                padding_symbol_lengths: (0, 0),
+                padding_strategy: PaddingStrategy::Fixed,
+                join_style: JoinStyle::Separated,
+                pronounceable: false,
+                extra_entropy: vec![],
+                checksum: false,
+                word_theme: None,
+                proper_nouns: ProperNouns::Include,
+                spelling: SpellingVariant::Any,
+                avoid_ambiguous: false,
+                avoid_homophones: false,
+            },
+            // besides `DATABASE_UNSAFE_CHARS`, also leaving out `:/?#%&` reserved in URLs,
+            // `$` interpolated by shells & `.env` files, and `|` or `~` special to YAML
+            Preset::DatabaseSafe => Settings {
+                words_count: 4,
+                word_lengths: (4, 8),
+                word_transforms: WordTransform::Lowercase | WordTransform::Uppercase,
+                separators: "-_.".to_string(),
+                padding_digits: (0, 2),
+                padding_symbols: "!*+=^".to_string(),
+                padding_symbol_lengths: (0, 2),
+                padding_strategy: PaddingStrategy::Fixed,
+                join_style: JoinStyle::Separated,
+                pronounceable: false,
+                extra_entropy: vec![],
+                checksum: false,
+                word_theme: None,
+                proper_nouns: ProperNouns::Include,
+                spelling: SpellingVariant::Any,
+                avoid_ambiguous: false,
+                avoid_homophones: false,
             },
             _ => Self::default(),
         }
     }
 }
 
+impl Preset {
+    pub fn example_settings(&self) -> Settings {
+        Settings::from_preset(*self)
+    }
+}
+
+impl Xkpasswd<'_> {
+    /// One example password for every preset, e.g. for previews & comparisons.
+    pub fn gen_all_presets(&self) -> BTreeMap<Preset, String> {
+        Preset::ALL
+            .into_iter()
+            .map(|preset| (preset, self.gen_pass(&preset.example_settings()).0))
+            .collect()
+    }
+}
+
+/// Settings checked once against the dictionary they generate from, as a `PasswordGenerator`.
+#[allow(dead_code)] // library only, unused by the CLI binary
+#[derive(Debug)]
+pub struct ConfiguredGenerator<'a> {
+    pass_generator: Xkpasswd<'a>,
+    settings: Settings,
+}
+
+#[allow(dead_code)]
+impl<'a> ConfiguredGenerator<'a> {
+    /// Fails when the settings can't be met by the dictionary, e.g. too few words of some length.
+    pub fn new(pass_generator: Xkpasswd<'a>, settings: Settings) -> Result<Self, String> {
+        settings.validate_against(&pass_generator)?;
+        Ok(ConfiguredGenerator {
+            pass_generator,
+            settings,
+        })
+    }
+}
+
+impl PasswordGenerator for ConfiguredGenerator<'_> {
+    fn generate(&self) -> Result<String, String> {
+        Ok(self.pass_generator.gen_pass(&self.settings).0)
+    }
+}
+
 impl Randomizer for Settings {
     fn word_lengths(&self) -> Range<u8> {
         let (min, max) = self.word_lengths;
-        min..(max + 1)
+        // capped by the setters, but deserialized settings may still hold u8::MAX
+        min..max.saturating_add(1)
+    }
+
+    fn word_theme(&self) -> Option<&str> {
+        self.word_theme.as_deref()
+    }
+
+    fn proper_nouns(&self) -> ProperNouns {
+        self.proper_nouns
+    }
+
+    fn spelling(&self) -> SpellingVariant {
+        self.spelling
+    }
+
+    fn avoid_homophones(&self) -> bool {
+        self.avoid_homophones
+    }
+
+    fn is_ambiguous(&self, word: &str) -> bool {
+        self.avoid_ambiguous
+            && self.allowed_transforms().into_iter().any(|transform| {
+                self.transform_pool_word(word, transform)
+                    .contains(|char| Self::AMBIGUOUS_CHARS.contains(char))
+            })
     }
 
     fn rand_words(&self, pool: &[&str]) -> Vec<String> {
-        let words_list = self.build_words_list(pool);
+        // words are picked first, so each password gets its own derived RNG
+        #[cfg(feature = "extra_entropy")]
+        if !self.extra_entropy.is_empty() {
+            PASS_RNG.set(Some((self.extra_entropy.clone(), self.derive_rng())));
+        }
+
+        let words_list = if self.pronounceable {
+            self.build_pronounceable_words_list(pool)
+        } else {
+            self.build_words_list(pool)
+        };
 
         log::debug!(
             "randomizing {} words from a pool of {} entries",
@@ -352,20 +724,24 @@ impl Randomizer for Settings {
         words_list
             .iter()
             .zip(transforms_list.iter())
-            .map(|(word, &transform)| transform_word(word, transform))
+            .map(|(word, &transform)| self.transform_pool_word(word, transform))
             .collect()
     }
 
     fn rand_separator(&self) -> String {
-        rand_chars(&self.separators, 1)
+        rand_chars(&mut self.rng(), &self.separators, 1)
     }
 
     fn rand_prefix(&self) -> (String, String) {
         let (prefix_digits, _) = self.padding_digits;
         let (prefix_symbols, _) = self.padding_symbol_lengths;
         (
-            rand_chars(&self.padding_symbols, prefix_symbols as usize),
-            rand_digits(prefix_digits),
+            rand_chars(
+                &mut self.rng(),
+                &self.padding_symbols,
+                prefix_symbols as usize,
+            ),
+            self.rand_padding_digits(prefix_digits),
         )
     }
 
@@ -373,30 +749,39 @@ impl Randomizer for Settings {
         let (_, suffix_digits) = self.padding_digits;
         let (_, suffix_symbols) = self.padding_symbol_lengths;
         (
-            rand_digits(suffix_digits),
-            rand_chars(&self.padding_symbols, suffix_symbols as usize),
+            self.rand_padding_digits(suffix_digits),
+            rand_chars(
+                &mut self.rng(),
+                &self.padding_symbols,
+                suffix_symbols as usize,
+            ),
         )
     }
 
     fn adjust_padding(&self, pass_length: usize) -> PaddingResult {
         match self.padding_strategy {
             PaddingStrategy::Fixed => PaddingResult::Unchanged,
-            PaddingStrategy::Adaptive(len) => match len.cmp(&pass_length) {
+            PaddingStrategy::Adaptive(len) => match self.adaptive_body_len(len).cmp(&pass_length) {
                 cmp::Ordering::Equal => PaddingResult::Unchanged,
                 cmp::Ordering::Less => {
+                    let body_len = self.adaptive_body_len(len);
                     log::debug!(
                         "trimmed {} characters to fit padding strategy",
-                        pass_length - len
+                        pass_length - body_len
                     );
 
-                    PaddingResult::TrimTo(len)
+                    PaddingResult::TrimTo(body_len)
                 }
                 cmp::Ordering::Greater => {
-                    let padded_symbols = rand_chars(&self.padding_symbols, len - pass_length);
+                    let padded_symbols = rand_chars(
+                        &mut self.rng(),
+                        &self.padding_symbols,
+                        self.adaptive_body_len(len) - pass_length,
+                    );
 
                     log::debug!(
                         "padded {} symbols to fit padding strategy",
-                        padded_symbols.len()
+                        padded_symbols.chars().count()
                     );
 
                     PaddingResult::Pad(padded_symbols)
@@ -405,37 +790,23 @@ impl Randomizer for Settings {
         }
     }
 
-    fn calc_entropy(&self, pool_size: usize) -> Entropy {
-        let (min_total_len, max_total_len) = match self.padding_strategy {
-            PaddingStrategy::Adaptive(len) => (len, len),
-            PaddingStrategy::Fixed => {
-                let prefix_digits = if self.padding_digits.0 > 0 {
-                    self.padding_digits.0 + 1
-                } else {
-                    self.padding_digits.0
-                };
-
-                let suffix_digits = if self.padding_digits.1 > 0 {
-                    self.padding_digits.1 + 1
-                } else {
-                    self.padding_digits.1
-                };
+    fn appends_checksum(&self) -> bool {
+        self.checksum
+    }
 
-                let non_alpha_len = self.padding_symbol_lengths.0
-                    + self.padding_symbol_lengths.1
-                    + prefix_digits
-                    + suffix_digits
-                    + self.words_count
-                    - 1;
+    fn preset(&self) -> Option<Preset> {
+        Preset::ALL
+            .into_iter()
+            .find(|&preset| Settings::from_preset(preset) == *self)
+    }
 
-                let count = self.words_count as usize;
-                let (min, max) = self.word_lengths;
-                (
-                    count * (min as usize) + (non_alpha_len as usize),
-                    count * (max as usize) + (non_alpha_len as usize),
-                )
-            }
-        };
+    fn calc_entropy(&self, pool_size: usize) -> Entropy {
+        // checksum digits are derived from the rest of the password,
+        // they make it longer without making it any harder to guess
+        let checksum_len = self.checksum_len();
+        let (min_total_len, max_total_len) = self.length_range();
+        let (min_total_len, max_total_len) =
+            (min_total_len - checksum_len, max_total_len - checksum_len);
 
         log::debug!(
             "entropy: blind length of {} ~ {}",
@@ -443,9 +814,9 @@ impl Randomizer for Settings {
             max_total_len
         );
 
-        let single_word_transform = self.word_transforms
+        let single_word_transform = self.applied_transforms()
             == FieldSize::from_flag(WordTransform::Lowercase)
-            || self.word_transforms == FieldSize::from_flag(WordTransform::Uppercase);
+            || self.applied_transforms() == FieldSize::from_flag(WordTransform::Uppercase);
 
         let mut blind_pool_size = if single_word_transform { 26 } else { 52 };
 
@@ -465,7 +836,7 @@ impl Randomizer for Settings {
         let blind_min = (min_total_len as f64) * blind_pool;
         let blind_max = (max_total_len as f64) * blind_pool;
 
-        let seen_words_pool_size = pool_size * self.word_transforms.to_flags().len();
+        let seen_words_pool_size = pool_size * self.applied_transforms().to_flags().len();
         log::debug!("entropy: seen words pool size of {}", seen_words_pool_size);
 
         let seen_words_entropy = (self.words_count as f64) * (seen_words_pool_size as f64).log2();
@@ -473,19 +844,25 @@ impl Randomizer for Settings {
         let seen_separator_entropy = if self.separators.is_empty() {
             0.0
         } else {
-            (self.separators.len() as f64).log2()
+            (self.separators.chars().count() as f64).log2()
         };
 
         let seen_digits_entropy = if self.padding_digits == (0, 0) {
             0.0
         } else {
-            f64::from(self.padding_digits.0 + self.padding_digits.1) * 10f64.log2()
+            let digits_count = if self.avoid_ambiguous {
+                Self::UNAMBIGUOUS_DIGITS.len()
+            } else {
+                10
+            };
+
+            f64::from(self.padding_digits.0 + self.padding_digits.1) * (digits_count as f64).log2()
         };
 
         let seen_symbols_entropy = if self.padding_symbols.is_empty() {
             0.0
         } else {
-            (self.padding_symbols.len() as f64).log2()
+            (self.padding_symbols.chars().count() as f64).log2()
         };
 
         let seen = (seen_words_entropy
@@ -494,6 +871,14 @@ impl Randomizer for Settings {
             + seen_symbols_entropy)
             .round() as usize;
 
+        // the least awkward of a few word lists is picked, just like `gen_pass_min_by_key`
+        let seen = if self.pronounceable {
+            let lost_bits = (Self::PRONOUNCEABLE_ATTEMPTS as f64).log2().ceil() as usize;
+            seen.saturating_sub(lost_bits)
+        } else {
+            seen
+        };
+
         Entropy {
             blind_min: blind_min.round() as usize,
             blind_max: blind_max.round() as usize,
@@ -504,14 +889,28 @@ impl Randomizer for Settings {
 }
 
 impl Settings {
-    const MIN_WORD_LENGTH: u8 = 4;
-    const MAX_WORD_LENGTH: u8 = 10;
+    pub(crate) const MIN_WORD_LENGTH: u8 = 4;
+    pub(crate) const MAX_WORD_LENGTH: u8 = 10;
     const DEFAULT_PADDING_LENGTH: u8 = 2;
     const DEFAULT_PADDING_STRATEGY: PaddingStrategy = PaddingStrategy::Fixed;
     const DEFAULT_SEPARATORS: &str = ".-_~";
     const DEFAULT_SYMBOLS: &str = "~@$%^&*-_+=:|?/.;";
+    // available on the first symbols page of both iOS & Android default keyboards,
+    // leaving out quotes & brackets which are easily mistyped or mangled
+    pub const EASY_SYMBOLS: &str = "-/:;$&@.,?!";
+    /// Never special to POSIX shells, whether quoted or not
+    pub const SHELL_SAFE_SYMBOLS: &str = "-_.,:+";
+    /// Easily mistaken for one another, especially on paper
+    pub const AMBIGUOUS_CHARS: &str = "lI1|O0`'";
+    /// Breaking out of quoted strings, `key=value;` connection strings or `user@host` URLs
+    pub const DATABASE_UNSAFE_CHARS: &str = "'\"`\\;@";
+    const DIGITS: &str = "0123456789";
+    const UNAMBIGUOUS_DIGITS: &str = "23456789";
     const DEFAULT_WORDS_COUNT: u8 = 3;
     const DEFAULT_WORD_LENGTHS: (u8, u8) = (Self::MIN_WORD_LENGTH, Self::MAX_WORD_LENGTH);
+    const PRONOUNCEABLE_ATTEMPTS: usize = 32;
+    #[cfg(feature = "extra_entropy")]
+    const RNG_SEED_INFO: &[u8] = b"xkpasswd rng seed";
     const DEFAULT_WORD_TRANSFORMS: FieldSize = 0b00000101; // WordTransform::Lowercase | WordTransform::Uppercase
 
     const ALL_SINGLE_WORD_TRANSFORMS: [WordTransform; 4] = [
@@ -521,16 +920,315 @@ impl Settings {
         WordTransform::InversedTitlecase,
     ];
 
+    pub fn words_count(&self) -> u8 {
+        self.words_count
+    }
+
+    /// Digits before & after the words.
+    pub fn padding_digits(&self) -> (u8, u8) {
+        self.padding_digits
+    }
+
+    /// Shortest & longest passwords, in characters.
+    pub fn length_range(&self) -> (usize, usize) {
+        let (min_len, max_len) = match self.padding_strategy {
+            PaddingStrategy::Adaptive(len) => {
+                let body_len = self.adaptive_body_len(len);
+                (body_len, body_len)
+            }
+            PaddingStrategy::Fixed => {
+                // words are joined without anything in between when there's no separator
+                let separator_len = if self.separators.is_empty() { 0 } else { 1 };
+
+                let prefix_digits = if self.padding_digits.0 > 0 {
+                    self.padding_digits.0 as usize + separator_len
+                } else {
+                    0
+                };
+
+                let suffix_digits = if self.padding_digits.1 > 0 {
+                    self.padding_digits.1 as usize + separator_len
+                } else {
+                    0
+                };
+
+                let non_alpha_len = self.padding_symbol_lengths.0 as usize
+                    + self.padding_symbol_lengths.1 as usize
+                    + prefix_digits
+                    + suffix_digits
+                    + (self.words_count as usize - 1) * separator_len;
+
+                let count = self.words_count as usize;
+                let (min, max) = self.word_lengths;
+                (
+                    count * (min as usize) + non_alpha_len,
+                    count * (max as usize) + non_alpha_len,
+                )
+            }
+        };
+
+        let checksum_len = self.checksum_len();
+        (min_len + checksum_len, max_len + checksum_len)
+    }
+
+    fn set_words_count(&mut self, words_count: u8) -> Result<(), String> {
+        if words_count == 0 {
+            return Err("only positive integer is allowed for words count".to_string());
+        }
+
+        self.words_count = words_count;
+        Ok(())
+    }
+
+    fn set_word_lengths(
+        &mut self,
+        min_length: Option<u8>,
+        max_length: Option<u8>,
+    ) -> Result<(), String> {
+        let (min, max) = self.ordered_word_lengths(min_length, max_length);
+
+        if min < Self::MIN_WORD_LENGTH {
+            return Err(MIN_WORD_LENGTH_ERR.to_string());
+        }
+
+        if max > Self::MAX_WORD_LENGTH {
+            return Err(MAX_WORD_LENGTH_ERR.to_string());
+        }
+
+        self.word_lengths = (min, max);
+        Ok(())
+    }
+
+    // opted in, e.g. for lists of 3 letter words or long compounds,
+    // it's up to the dictionary to have enough words of these lengths;
+    // still below u8::MAX, keeping the exclusive end of `word_lengths()` in range
+    fn set_word_lengths_unchecked(&mut self, min_length: Option<u8>, max_length: Option<u8>) {
+        let (min, max) = self.ordered_word_lengths(min_length, max_length);
+        self.word_lengths = (min.clamp(1, u8::MAX - 1), max.clamp(1, u8::MAX - 1));
+    }
+
+    fn ordered_word_lengths(&self, min_length: Option<u8>, max_length: Option<u8>) -> (u8, u8) {
+        let min_length = min_length.unwrap_or(self.word_lengths.0);
+        let max_length = max_length.unwrap_or(self.word_lengths.1);
+
+        (
+            cmp::min(min_length, max_length),
+            cmp::max(min_length, max_length),
+        )
+    }
+
+    fn set_separators(&mut self, separators: &str) {
+        self.separators = self.without_ambiguous_chars(separators);
+    }
+
+    fn set_padding_digits(&mut self, prefix: Option<u8>, suffix: Option<u8>) {
+        if prefix.is_none() && suffix.is_none() {
+            return;
+        }
+
+        self.padding_digits = (
+            prefix.unwrap_or(self.padding_digits.0),
+            suffix.unwrap_or(self.padding_digits.1),
+        );
+    }
+
+    fn set_padding_symbols(&mut self, symbols: &str) {
+        self.padding_symbols = self.without_ambiguous_chars(symbols);
+    }
+
+    fn set_padding_symbol_lengths(&mut self, prefix: Option<u8>, suffix: Option<u8>) {
+        if prefix.is_none() && suffix.is_none() {
+            return;
+        }
+
+        self.padding_symbol_lengths = (
+            prefix.unwrap_or(self.padding_symbol_lengths.0),
+            suffix.unwrap_or(self.padding_symbol_lengths.1),
+        );
+        self.padding_strategy = PaddingStrategy::Fixed;
+    }
+
+    fn set_padding_strategy(&mut self, strategy: PaddingStrategy) -> Result<(), String> {
+        match strategy {
+            PaddingStrategy::Adaptive(0) => {
+                return Err("invalid adaptive padding number".to_string())
+            }
+            PaddingStrategy::Adaptive(_) => {
+                self.padding_strategy = strategy;
+                self.padding_symbol_lengths = (0, 0);
+            }
+            PaddingStrategy::Fixed => {
+                self.padding_strategy = strategy;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn set_word_transforms(&mut self, transforms: FieldSize) -> Result<(), String> {
+        // handle group transforms first
+        if transforms.has_flag(WordTransform::AltercaseLowerFirst) {
+            self.word_transforms = FieldSize::from_flag(WordTransform::AltercaseLowerFirst);
+            return Ok(());
+        }
+
+        if transforms.has_flag(WordTransform::AltercaseUpperFirst) {
+            self.word_transforms = FieldSize::from_flag(WordTransform::AltercaseUpperFirst);
+            return Ok(());
+        }
+
+        // no transform matched
+        if !transforms.has_flag(WordTransform::Lowercase)
+            && !transforms.has_flag(WordTransform::Titlecase)
+            && !transforms.has_flag(WordTransform::Uppercase)
+            && !transforms.has_flag(WordTransform::InversedTitlecase)
+        {
+            return Err("invalid transform".to_string());
+        }
+
+        self.word_transforms = transforms;
+        Ok(())
+    }
+
+    fn set_join_style(&mut self, style: JoinStyle) {
+        self.join_style = style;
+    }
+
+    fn set_pronounceable(&mut self, pronounceable: bool) {
+        self.pronounceable = pronounceable;
+    }
+
+    #[cfg(feature = "extra_entropy")]
+    fn set_extra_entropy(&mut self, entropy: &[u8]) {
+        self.extra_entropy = entropy.to_vec();
+    }
+
+    fn set_checksum(&mut self, checksum: bool) {
+        self.checksum = checksum;
+    }
+
+    // theme names are matched ignoring case, an empty one means no theme
+    fn set_word_theme(&mut self, theme: &str) {
+        let theme = theme.trim().to_lowercase();
+        self.word_theme = Some(theme).filter(|theme| !theme.is_empty());
+    }
+
+    fn set_proper_nouns(&mut self, proper_nouns: ProperNouns) {
+        self.proper_nouns = proper_nouns;
+    }
+
+    fn set_spelling(&mut self, spelling: SpellingVariant) {
+        self.spelling = spelling;
+    }
+
+    // strips ambiguous chars from the current separators & symbols as well as later ones,
+    // turning it off again doesn't bring them back
+    fn set_avoid_ambiguous(&mut self, avoid_ambiguous: bool) {
+        self.avoid_ambiguous = avoid_ambiguous;
+        self.separators = self.without_ambiguous_chars(&self.separators);
+        self.padding_symbols = self.without_ambiguous_chars(&self.padding_symbols);
+    }
+
+    fn set_avoid_homophones(&mut self, avoid_homophones: bool) {
+        self.avoid_homophones = avoid_homophones;
+    }
+
+    fn without_ambiguous_chars(&self, chars: &str) -> String {
+        if self.avoid_ambiguous {
+            chars.replace(|char| Self::AMBIGUOUS_CHARS.contains(char), "")
+        } else {
+            chars.to_string()
+        }
+    }
+
+    fn rand_padding_digits(&self, count: u8) -> String {
+        if self.avoid_ambiguous {
+            let mut rng = self.rng();
+            (0..count)
+                .map(|_| rand_chars(&mut rng, Self::UNAMBIGUOUS_DIGITS, 1))
+                .collect()
+        } else {
+            rand_digits(&mut self.rng(), count)
+        }
+    }
+
+    // preserved proper nouns skip transforms, so their transform entropy is slightly overestimated
+    fn transform_pool_word(&self, word: &str, transform: WordTransform) -> String {
+        if self.proper_nouns == ProperNouns::Preserve && is_proper_noun(word) {
+            word.to_string()
+        } else {
+            transform_word(word, transform)
+        }
+    }
+
+    fn checksum_len(&self) -> usize {
+        if self.checksum {
+            CHECKSUM_LENGTH
+        } else {
+            0
+        }
+    }
+
+    // checksum digits come on top of the body, which is fitted so the whole password is `len` long
+    fn adaptive_body_len(&self, len: usize) -> usize {
+        len.saturating_sub(self.checksum_len())
+    }
+
+    // the RNG of the build's backend alone by default, or one drawn from the password's RNG
+    // derived from extra entropy, see `derive_rng`
+    fn rng(&self) -> Box<dyn RngCore> {
+        #[cfg(feature = "extra_entropy")]
+        if !self.extra_entropy.is_empty() {
+            return PASS_RNG.with_borrow_mut(|pass_rng| {
+                let rng = match pass_rng {
+                    Some((extra_entropy, rng)) if *extra_entropy == self.extra_entropy => rng,
+                    _ => {
+                        &mut pass_rng
+                            .insert((self.extra_entropy.clone(), self.derive_rng()))
+                            .1
+                    }
+                };
+
+                Box::new(StdRng::from_rng(rng).expect("StdRng never fails")) as Box<dyn RngCore>
+            });
+        }
+
+        Box::new(crate::rng::rng())
+    }
+
+    // seeded from both the backend's output & extra entropy via HKDF-SHA256,
+    // so the extra bytes can only add unpredictability, never remove it
+    #[cfg(feature = "extra_entropy")]
+    fn derive_rng(&self) -> StdRng {
+        let mut salt = [0u8; 32];
+        crate::rng::rng().fill(&mut salt);
+
+        let mut seed = <StdRng as SeedableRng>::Seed::default();
+        Hkdf::<Sha256>::new(Some(&salt), &self.extra_entropy)
+            .expand(Self::RNG_SEED_INFO, &mut seed)
+            .expect("a seed is far shorter than HKDF's limit");
+
+        StdRng::from_seed(seed)
+    }
+
+    // transforms actually applied to words, camel case joining overrides the configured ones
+    fn applied_transforms(&self) -> FieldSize {
+        match self.join_style {
+            JoinStyle::Separated => self.word_transforms,
+            JoinStyle::Camel => FieldSize::from_flag(WordTransform::Titlecase),
+        }
+    }
+
     fn build_words_list<'a>(&self, pool: &[&'a str]) -> Vec<&'a str> {
         if pool.is_empty() {
             return vec![];
         }
 
-        let mut rng = rand::thread_rng();
-        let word_indices = Uniform::from(0..pool.len());
+        let mut rng = self.rng();
 
         // not enough words to distinguishably randomize
         if pool.len() < self.words_count as usize {
+            let word_indices = Uniform::from(0..pool.len());
             return (0..self.words_count)
                 .map(|_| {
                     let index: usize = word_indices.sample(&mut rng);
@@ -539,24 +1237,40 @@ impl Settings {
                 .collect();
         }
 
-        // enough words, ensure no duplicates
-        let mut index_marker: HashMap<usize, bool> = HashMap::new();
-        (0..self.words_count)
-            .map(|_| loop {
-                let index: usize = word_indices.sample(&mut rng);
-                let word = pool[index];
-
-                if index_marker.get(&index).is_none() {
-                    index_marker.insert(index, true);
-                    break word;
-                }
-            })
+        // enough words, ensure no duplicates with a partial Fisher-Yates shuffle
+        // so the cost stays bounded even when words count approaches pool size
+        index::sample(&mut rng, pool.len(), self.words_count as usize)
+            .into_iter()
+            .map(|index| pool[index])
             .collect()
     }
 
+    // resamples words a few times to avoid combinations awkward to say aloud,
+    // falling back to the least awkward one found for small pools
+    fn build_pronounceable_words_list<'a>(&self, pool: &[&'a str]) -> Vec<&'a str> {
+        let mut best_words = self.build_words_list(pool);
+        let mut best_score = awkward_transitions(&best_words);
+
+        for _ in 1..Self::PRONOUNCEABLE_ATTEMPTS {
+            if best_score == 0 {
+                break;
+            }
+
+            let words = self.build_words_list(pool);
+            let score = awkward_transitions(&words);
+
+            if score < best_score {
+                best_words = words;
+                best_score = score;
+            }
+        }
+
+        best_words
+    }
+
     fn build_transforms_list(&self) -> Vec<WordTransform> {
         if self
-            .word_transforms
+            .applied_transforms()
             .has_flag(WordTransform::AltercaseLowerFirst)
         {
             return (0..self.words_count)
@@ -571,7 +1285,7 @@ impl Settings {
         }
 
         if self
-            .word_transforms
+            .applied_transforms()
             .has_flag(WordTransform::AltercaseUpperFirst)
         {
             return (0..self.words_count)
@@ -587,10 +1301,10 @@ impl Settings {
 
         let whitelisted_transforms: Vec<&WordTransform> = Self::ALL_SINGLE_WORD_TRANSFORMS
             .iter()
-            .filter(|&&transform| self.word_transforms & transform)
+            .filter(|&&transform| self.applied_transforms() & transform)
             .collect();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = self.rng();
         let transform_indices = Uniform::from(0..whitelisted_transforms.len());
 
         (0..self.words_count)
@@ -602,7 +1316,145 @@ impl Settings {
     }
 }
 
-fn rand_digits(count: u8) -> String {
+/// Accumulates settings by value, so long chains don't clone on every step
+/// the way `Builder` methods do. The first invalid value is reported by `build`.
+#[allow(dead_code)] // library only, unused by the CLI binary
+#[derive(Clone, Debug, Default)]
+pub struct SettingsBuilder {
+    settings: Settings,
+    error: Option<String>,
+}
+
+#[allow(dead_code)]
+impl SettingsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_preset(preset: Preset) -> Self {
+        SettingsBuilder {
+            settings: Settings::from_preset(preset),
+            error: None,
+        }
+    }
+
+    pub fn with_words_count(self, words_count: u8) -> Self {
+        self.try_apply(|settings| settings.set_words_count(words_count))
+    }
+
+    pub fn with_word_lengths(self, min_length: Option<u8>, max_length: Option<u8>) -> Self {
+        self.try_apply(|settings| settings.set_word_lengths(min_length, max_length))
+    }
+
+    pub fn with_word_lengths_unchecked(
+        mut self,
+        min_length: Option<u8>,
+        max_length: Option<u8>,
+    ) -> Self {
+        self.settings
+            .set_word_lengths_unchecked(min_length, max_length);
+        self
+    }
+
+    pub fn with_separators(mut self, separators: &str) -> Self {
+        self.settings.set_separators(separators);
+        self
+    }
+
+    pub fn with_padding_digits(mut self, prefix: Option<u8>, suffix: Option<u8>) -> Self {
+        self.settings.set_padding_digits(prefix, suffix);
+        self
+    }
+
+    pub fn with_padding_symbols(mut self, symbols: &str) -> Self {
+        self.settings.set_padding_symbols(symbols);
+        self
+    }
+
+    pub fn with_symbol_set(mut self, symbol_set: SymbolSet) -> Self {
+        self.settings.set_separators(symbol_set.symbols());
+        self.settings.set_padding_symbols(symbol_set.symbols());
+        self
+    }
+
+    pub fn with_padding_symbol_lengths(mut self, prefix: Option<u8>, suffix: Option<u8>) -> Self {
+        self.settings.set_padding_symbol_lengths(prefix, suffix);
+        self
+    }
+
+    pub fn with_padding_strategy(self, strategy: PaddingStrategy) -> Self {
+        self.try_apply(|settings| settings.set_padding_strategy(strategy))
+    }
+
+    pub fn with_word_transforms(self, transforms: FieldSize) -> Self {
+        self.try_apply(|settings| settings.set_word_transforms(transforms))
+    }
+
+    pub fn with_join_style(mut self, style: JoinStyle) -> Self {
+        self.settings.set_join_style(style);
+        self
+    }
+
+    pub fn with_pronounceable(mut self, pronounceable: bool) -> Self {
+        self.settings.set_pronounceable(pronounceable);
+        self
+    }
+
+    #[cfg(feature = "extra_entropy")]
+    pub fn with_extra_entropy(mut self, entropy: &[u8]) -> Self {
+        self.settings.set_extra_entropy(entropy);
+        self
+    }
+
+    pub fn with_checksum(mut self, checksum: bool) -> Self {
+        self.settings.set_checksum(checksum);
+        self
+    }
+
+    pub fn with_word_theme(mut self, theme: &str) -> Self {
+        self.settings.set_word_theme(theme);
+        self
+    }
+
+    pub fn with_proper_nouns(mut self, proper_nouns: ProperNouns) -> Self {
+        self.settings.set_proper_nouns(proper_nouns);
+        self
+    }
+
+    pub fn with_spelling(mut self, spelling: SpellingVariant) -> Self {
+        self.settings.set_spelling(spelling);
+        self
+    }
+
+    pub fn with_avoid_ambiguous(mut self, avoid_ambiguous: bool) -> Self {
+        self.settings.set_avoid_ambiguous(avoid_ambiguous);
+        self
+    }
+
+    pub fn with_avoid_homophones(mut self, avoid_homophones: bool) -> Self {
+        self.settings.set_avoid_homophones(avoid_homophones);
+        self
+    }
+
+    pub fn build(self) -> Result<Settings, String> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.settings),
+        }
+    }
+
+    fn try_apply<F: FnOnce(&mut Settings) -> Result<(), String>>(mut self, setter: F) -> Self {
+        if self.error.is_none() {
+            if let Err(err) = setter(&mut self.settings) {
+                self.error = Some(err);
+            }
+        }
+
+        self
+    }
+}
+
+fn rand_digits<R: Rng + ?Sized>(rng: &mut R, count: u8) -> String {
     if count == 0 {
         return "".to_string();
     }
@@ -621,30 +1473,63 @@ fn rand_digits(count: u8) -> String {
         u64::MAX
     };
 
-    let mut rng = rand::thread_rng();
-    let padding_digits: u64 = Uniform::from(lower_bound..upper_bound).sample(&mut rng);
+    let padding_digits: u64 = Uniform::from(lower_bound..upper_bound).sample(rng);
     padding_digits.to_string()
 }
 
-fn rand_chars(pool: &str, count: usize) -> String {
-    if pool.is_empty() {
+/// Counts transitions between consecutive words that are awkward to say aloud:
+/// a word ending with the letter the next one starts with ("bus stop"),
+/// or both words starting with the same letter ("big bad").
+pub fn awkward_transitions(words: &[&str]) -> usize {
+    words
+        .windows(2)
+        .filter(|pair| {
+            let prev_first = first_char_lowercase(pair[0]);
+            let prev_last = pair[0]
+                .chars()
+                .last()
+                .and_then(|char| char.to_lowercase().next());
+            let next_first = first_char_lowercase(pair[1]);
+
+            next_first.is_some() && (next_first == prev_last || next_first == prev_first)
+        })
+        .count()
+}
+
+fn first_char_lowercase(word: &str) -> Option<char> {
+    word.chars()
+        .next()
+        .and_then(|char| char.to_lowercase().next())
+}
+
+fn rand_chars<R: Rng + ?Sized>(rng: &mut R, pool: &str, count: usize) -> String {
+    // pick from chars rather than bytes so multi-byte symbols (emoji, accents)
+    // are neither miscounted nor split
+    let chars: Vec<char> = pool.chars().collect();
+
+    if chars.is_empty() {
         return "".to_string();
     }
 
-    let mut rng = rand::thread_rng();
-    let idx = rng.gen_range(0..pool.len());
-    pool.chars()
-        .nth(idx)
-        .unwrap()
-        .to_string()
-        .repeat(count as _)
+    let idx = rng.gen_range(0..chars.len());
+    chars[idx].to_string().repeat(count)
 }
 
 fn transform_word(word: &str, transform: WordTransform) -> String {
+    // split on the first char instead of the first byte,
+    // so words starting with multi-byte characters don't panic
+    let mut chars = word.chars();
+    let (first, rest) = match chars.next() {
+        Some(first) => (first, chars.as_str()),
+        None => return "".to_string(),
+    };
+
     match transform {
-        WordTransform::Titlecase => word[..1].to_uppercase() + &word[1..],
+        WordTransform::Titlecase => first.to_uppercase().collect::<String>() + rest,
         WordTransform::Uppercase => word.to_uppercase(),
-        WordTransform::InversedTitlecase => word[..1].to_lowercase() + &word[1..].to_uppercase(),
+        WordTransform::InversedTitlecase => {
+            first.to_lowercase().collect::<String>() + &rest.to_uppercase()
+        }
         // lowercase by default
         _ => word.to_lowercase(),
     }
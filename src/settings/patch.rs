@@ -0,0 +1,141 @@
+use super::Settings;
+use crate::bit_flags::FieldSize;
+use crate::prelude::{Builder, JoinStyle, PaddingStrategy, ProperNouns, SpellingVariant};
+
+/// Partial settings where every unset field keeps the value of whatever it's applied to,
+/// so layers like preset < config file < environment < command line
+/// can each be expressed as a patch & applied in order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SettingsPatch {
+    pub words_count: Option<u8>,
+    pub word_length_min: Option<u8>,
+    pub word_length_max: Option<u8>,
+    pub unchecked_word_lengths: Option<bool>,
+    pub word_transforms: Option<FieldSize>,
+    pub separators: Option<String>,
+    pub padding_digits_before: Option<u8>,
+    pub padding_digits_after: Option<u8>,
+    pub padding_symbols: Option<String>,
+    pub padding_symbols_before: Option<u8>,
+    pub padding_symbols_after: Option<u8>,
+    pub padding_strategy: Option<PaddingStrategy>,
+    pub join_style: Option<JoinStyle>,
+    pub pronounceable: Option<bool>,
+    #[cfg(feature = "extra_entropy")]
+    pub extra_entropy: Option<Vec<u8>>,
+    pub checksum: Option<bool>,
+    pub word_theme: Option<String>,
+    pub proper_nouns: Option<ProperNouns>,
+    pub spelling: Option<SpellingVariant>,
+    pub avoid_ambiguous: Option<bool>,
+    pub avoid_homophones: Option<bool>,
+}
+
+impl SettingsPatch {
+    /// Combines with a patch of a higher layer, whose set fields win.
+    pub fn overlay(self, upper: SettingsPatch) -> SettingsPatch {
+        SettingsPatch {
+            words_count: upper.words_count.or(self.words_count),
+            word_length_min: upper.word_length_min.or(self.word_length_min),
+            word_length_max: upper.word_length_max.or(self.word_length_max),
+            unchecked_word_lengths: upper.unchecked_word_lengths.or(self.unchecked_word_lengths),
+            word_transforms: upper.word_transforms.or(self.word_transforms),
+            separators: upper.separators.or(self.separators),
+            padding_digits_before: upper.padding_digits_before.or(self.padding_digits_before),
+            padding_digits_after: upper.padding_digits_after.or(self.padding_digits_after),
+            padding_symbols: upper.padding_symbols.or(self.padding_symbols),
+            padding_symbols_before: upper.padding_symbols_before.or(self.padding_symbols_before),
+            padding_symbols_after: upper.padding_symbols_after.or(self.padding_symbols_after),
+            padding_strategy: upper.padding_strategy.or(self.padding_strategy),
+            join_style: upper.join_style.or(self.join_style),
+            pronounceable: upper.pronounceable.or(self.pronounceable),
+            #[cfg(feature = "extra_entropy")]
+            extra_entropy: upper.extra_entropy.or(self.extra_entropy),
+            checksum: upper.checksum.or(self.checksum),
+            word_theme: upper.word_theme.or(self.word_theme),
+            proper_nouns: upper.proper_nouns.or(self.proper_nouns),
+            spelling: upper.spelling.or(self.spelling),
+            avoid_ambiguous: upper.avoid_ambiguous.or(self.avoid_ambiguous),
+            avoid_homophones: upper.avoid_homophones.or(self.avoid_homophones),
+        }
+    }
+
+    /// Applies set fields on top of `base`, with the same validations as the builder methods.
+    pub fn apply<B: Builder>(&self, base: &B) -> Result<B, String> {
+        let settings = if self.unchecked_word_lengths == Some(true) {
+            base.with_word_lengths_unchecked(self.word_length_min, self.word_length_max)
+        } else {
+            base.with_word_lengths(self.word_length_min, self.word_length_max)?
+        };
+
+        let mut settings = settings
+            .with_padding_digits(self.padding_digits_before, self.padding_digits_after)
+            .with_padding_symbol_lengths(self.padding_symbols_before, self.padding_symbols_after);
+
+        if let Some(words_count) = self.words_count {
+            settings = settings.with_words_count(words_count)?;
+        }
+
+        if let Some(word_transforms) = self.word_transforms {
+            settings = settings.with_word_transforms(word_transforms)?;
+        }
+
+        if let Some(separators) = &self.separators {
+            settings = settings.with_separators(separators);
+        }
+
+        if let Some(padding_symbols) = &self.padding_symbols {
+            settings = settings.with_padding_symbols(padding_symbols);
+        }
+
+        if let Some(join_style) = self.join_style {
+            settings = settings.with_join_style(join_style);
+        }
+
+        if let Some(pronounceable) = self.pronounceable {
+            settings = settings.with_pronounceable(pronounceable);
+        }
+
+        #[cfg(feature = "extra_entropy")]
+        if let Some(extra_entropy) = &self.extra_entropy {
+            settings = settings.with_extra_entropy(extra_entropy);
+        }
+
+        if let Some(checksum) = self.checksum {
+            settings = settings.with_checksum(checksum);
+        }
+
+        if let Some(word_theme) = &self.word_theme {
+            settings = settings.with_word_theme(word_theme);
+        }
+
+        if let Some(proper_nouns) = self.proper_nouns {
+            settings = settings.with_proper_nouns(proper_nouns);
+        }
+
+        if let Some(spelling) = self.spelling {
+            settings = settings.with_spelling(spelling);
+        }
+
+        if let Some(avoid_ambiguous) = self.avoid_ambiguous {
+            settings = settings.with_avoid_ambiguous(avoid_ambiguous);
+        }
+
+        if let Some(avoid_homophones) = self.avoid_homophones {
+            settings = settings.with_avoid_homophones(avoid_homophones);
+        }
+
+        // last, as adaptive padding resets symbol lengths
+        if let Some(padding_strategy) = &self.padding_strategy {
+            settings = settings.with_padding_strategy(padding_strategy.clone())?;
+        }
+
+        Ok(settings)
+    }
+}
+
+impl Settings {
+    pub fn merge(&self, patch: SettingsPatch) -> Result<Settings, String> {
+        patch.apply(self)
+    }
+}
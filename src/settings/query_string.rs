@@ -0,0 +1,291 @@
+// URL query string form of settings, e.g. `words=3&word_min=4&word_max=8&transforms=LU&separators=.-`,
+// keyed after the CLI options, to share a whole config in a link.
+// Word transforms use the single letter codes of the compact form.
+
+use super::compact::{format_transforms, parse_transforms};
+use super::Settings;
+use crate::prelude::{JoinStyle, PaddingStrategy, ProperNouns, SpellingVariant};
+use std::str::FromStr;
+
+const PARAMS: [&str; 20] = [
+    "words",
+    "word_min",
+    "word_max",
+    "transforms",
+    "separators",
+    "digits_before",
+    "digits_after",
+    "symbols",
+    "symbols_before",
+    "symbols_after",
+    "padding",
+    "adaptive_length",
+    "join",
+    "pronounceable",
+    "checksum",
+    "theme",
+    "proper_nouns",
+    "spelling",
+    "avoid_ambiguous",
+    "avoid_homophones",
+];
+
+impl Settings {
+    pub fn to_query_string(&self) -> String {
+        let (word_min, word_max) = self.word_lengths;
+        let (digits_before, digits_after) = self.padding_digits;
+        let (symbols_before, symbols_after) = self.padding_symbol_lengths;
+
+        let mut params = vec![
+            ("words", self.words_count.to_string()),
+            ("word_min", word_min.to_string()),
+            ("word_max", word_max.to_string()),
+            ("transforms", format_transforms(self.word_transforms)),
+            ("separators", self.separators.clone()),
+            ("digits_before", digits_before.to_string()),
+            ("digits_after", digits_after.to_string()),
+            ("symbols", self.padding_symbols.clone()),
+            ("symbols_before", symbols_before.to_string()),
+            ("symbols_after", symbols_after.to_string()),
+        ];
+
+        match self.padding_strategy {
+            PaddingStrategy::Fixed => params.push(("padding", "fixed".to_string())),
+            PaddingStrategy::Adaptive(len) => {
+                params.push(("padding", "adaptive".to_string()));
+                params.push(("adaptive_length", len.to_string()));
+            }
+        }
+
+        if self.join_style == JoinStyle::Camel {
+            params.push(("join", "camel".to_string()));
+        }
+
+        if self.pronounceable {
+            params.push(("pronounceable", "true".to_string()));
+        }
+
+        if self.checksum {
+            params.push(("checksum", "true".to_string()));
+        }
+
+        if let Some(theme) = &self.word_theme {
+            params.push(("theme", theme.clone()));
+        }
+
+        match self.proper_nouns {
+            ProperNouns::Include => {}
+            ProperNouns::Exclude => params.push(("proper_nouns", "exclude".to_string())),
+            ProperNouns::Preserve => params.push(("proper_nouns", "preserve".to_string())),
+        }
+
+        match self.spelling {
+            SpellingVariant::Any => {}
+            SpellingVariant::British => params.push(("spelling", "british".to_string())),
+            SpellingVariant::American => params.push(("spelling", "american".to_string())),
+        }
+
+        if self.avoid_ambiguous {
+            params.push(("avoid_ambiguous", "true".to_string()));
+        }
+
+        if self.avoid_homophones {
+            params.push(("avoid_homophones", "true".to_string()));
+        }
+
+        params
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, percent_encode(value)))
+            .collect::<Vec<String>>()
+            .join("&")
+    }
+
+    /// Parses a query string with or without the leading '?' or '#',
+    /// missing keys keep their default values, unknown ones are ignored
+    /// so links can carry other parameters.
+    pub fn from_query_string(query: &str) -> Result<Self, String> {
+        let query = query.trim_start_matches(['?', '#']);
+        let mut params: Vec<(String, String)> = vec![];
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            params.push((percent_decode(key)?, percent_decode(value)?));
+        }
+
+        let get = |key: &str| {
+            params
+                .iter()
+                .rev()
+                .find(|(param, _)| param == key)
+                .map(|(_, value)| value.as_str())
+        };
+
+        let mut settings = Settings::default();
+
+        if let Some(words_count) = get("words") {
+            settings.set_words_count(parse_param("words", words_count)?)?;
+        }
+
+        settings.set_word_lengths(
+            get("word_min")
+                .map(|value| parse_param("word_min", value))
+                .transpose()?,
+            get("word_max")
+                .map(|value| parse_param("word_max", value))
+                .transpose()?,
+        )?;
+
+        if let Some(transforms) = get("transforms") {
+            settings.set_word_transforms(parse_transforms(transforms)?)?;
+        }
+
+        if let Some(separators) = get("separators") {
+            settings.set_separators(separators);
+        }
+
+        settings.set_padding_digits(
+            get("digits_before")
+                .map(|value| parse_param("digits_before", value))
+                .transpose()?,
+            get("digits_after")
+                .map(|value| parse_param("digits_after", value))
+                .transpose()?,
+        );
+
+        if let Some(symbols) = get("symbols") {
+            settings.set_padding_symbols(symbols);
+        }
+
+        // lengths first, as adaptive padding resets them
+        settings.set_padding_symbol_lengths(
+            get("symbols_before")
+                .map(|value| parse_param("symbols_before", value))
+                .transpose()?,
+            get("symbols_after")
+                .map(|value| parse_param("symbols_after", value))
+                .transpose()?,
+        );
+
+        match get("padding") {
+            None => {}
+            Some("fixed") => settings.set_padding_strategy(PaddingStrategy::Fixed)?,
+            Some("adaptive") => {
+                let len = get("adaptive_length")
+                    .ok_or("'adaptive_length' is required for adaptive padding")?;
+                settings.set_padding_strategy(PaddingStrategy::Adaptive(parse_param(
+                    "adaptive_length",
+                    len,
+                )?))?;
+            }
+            Some(padding) => return Err(format!("invalid padding '{}'", padding)),
+        }
+
+        match get("join") {
+            None | Some("separated") => settings.set_join_style(JoinStyle::Separated),
+            Some("camel") => settings.set_join_style(JoinStyle::Camel),
+            Some(join) => return Err(format!("invalid join style '{}'", join)),
+        }
+
+        if let Some(pronounceable) = get("pronounceable") {
+            settings.set_pronounceable(parse_param("pronounceable", pronounceable)?);
+        }
+
+        if let Some(checksum) = get("checksum") {
+            settings.set_checksum(parse_param("checksum", checksum)?);
+        }
+
+        if let Some(theme) = get("theme") {
+            settings.set_word_theme(theme);
+        }
+
+        match get("proper_nouns") {
+            None | Some("include") => settings.set_proper_nouns(ProperNouns::Include),
+            Some("exclude") => settings.set_proper_nouns(ProperNouns::Exclude),
+            Some("preserve") => settings.set_proper_nouns(ProperNouns::Preserve),
+            Some(proper_nouns) => {
+                return Err(format!("invalid proper nouns option '{}'", proper_nouns))
+            }
+        }
+
+        match get("spelling") {
+            None | Some("any") => settings.set_spelling(SpellingVariant::Any),
+            Some("british") => settings.set_spelling(SpellingVariant::British),
+            Some("american") => settings.set_spelling(SpellingVariant::American),
+            Some(spelling) => return Err(format!("invalid spelling variant '{}'", spelling)),
+        }
+
+        if let Some(avoid_ambiguous) = get("avoid_ambiguous") {
+            settings.set_avoid_ambiguous(parse_param("avoid_ambiguous", avoid_ambiguous)?);
+        }
+
+        if let Some(avoid_homophones) = get("avoid_homophones") {
+            settings.set_avoid_homophones(parse_param("avoid_homophones", avoid_homophones)?);
+        }
+
+        Ok(settings)
+    }
+
+    /// Changes a single parameter of the query string form, e.g. `words` to `5`,
+    /// unlike `from_query_string` unknown keys are errors.
+    pub fn with_query_param(&self, key: &str, value: &str) -> Result<Self, String> {
+        if !PARAMS.contains(&key) {
+            return Err(format!("unknown parameter '{}'", key));
+        }
+
+        let query = format!(
+            "{}&{}={}",
+            self.to_query_string(),
+            key,
+            percent_encode(value)
+        );
+        Self::from_query_string(&query)
+    }
+}
+
+fn parse_param<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value '{}' for '{}'", value, key))
+}
+
+// everything but unreserved characters (RFC 3986), so values survive as a fragment too
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn percent_decode(value: &str) -> Result<String, String> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(value.len());
+    let mut input = value.bytes();
+
+    while let Some(byte) = input.next() {
+        match byte {
+            b'%' => {
+                let hex = [input.next(), input.next()];
+                let decoded = match hex {
+                    [Some(high), Some(low)]
+                        if high.is_ascii_hexdigit() && low.is_ascii_hexdigit() =>
+                    {
+                        std::str::from_utf8(&[high, low])
+                            .ok()
+                            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    }
+                    _ => None,
+                };
+
+                bytes.push(decoded.ok_or_else(|| format!("invalid escape in '{}'", value))?);
+            }
+            b'+' => bytes.push(b' '),
+            _ => bytes.push(byte),
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|_| format!("invalid UTF-8 in '{}'", value))
+}
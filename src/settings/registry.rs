@@ -0,0 +1,43 @@
+use super::Settings;
+use crate::prelude::{Builder, Preset};
+use std::collections::BTreeMap;
+
+/// Named settings registered at runtime, e.g. a company wide "corp-standard",
+/// resolved before the built-in presets so they can also be overridden.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PresetRegistry {
+    presets: BTreeMap<String, Settings>,
+}
+
+impl PresetRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers settings under a name, replacing any registered before with the same name.
+    pub fn register(&mut self, name: &str, settings: Settings) -> Result<(), String> {
+        let name = name.trim();
+
+        if name.is_empty() {
+            return Err("preset name must not be empty".to_string());
+        }
+
+        self.presets.insert(name.to_string(), settings);
+        Ok(())
+    }
+
+    /// Settings registered under `name`, otherwise those of the built-in preset
+    /// with this name or alias.
+    pub fn resolve(&self, name: &str) -> Result<Settings, String> {
+        if let Some(settings) = self.presets.get(name.trim()) {
+            return Ok(settings.clone());
+        }
+
+        name.parse::<Preset>().map(Settings::from_preset)
+    }
+
+    /// Names of registered presets, in alphabetical order.
+    pub fn names(&self) -> Vec<&str> {
+        self.presets.keys().map(String::as_str).collect()
+    }
+}
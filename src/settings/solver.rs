@@ -0,0 +1,203 @@
+use super::Settings;
+use crate::prelude::{Entropy, PaddingStrategy, Randomizer, Xkpasswd};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+
+// separators, padding digits & padding symbol lengths, suggested once each
+type Shape = (String, (u8, u8), (u8, u8));
+
+/// Settings meeting the constraints of a `SettingsSolver`, with the lengths of their passwords.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suggestion {
+    pub settings: Settings,
+    pub entropy: Entropy,
+    pub length_range: (usize, usize),
+}
+
+/// Searches words counts, word lengths, separators & padding for settings meeting constraints,
+/// e.g. at most 20 characters with 64 bits of entropy and no symbols. Everything else,
+/// like transforms, theme or spelling, is kept from the base settings.
+#[derive(Clone, Debug)]
+pub struct SettingsSolver {
+    base: Settings,
+    max_length: Option<usize>,
+    min_entropy: usize,
+    symbols: bool,
+    digits: bool,
+}
+
+impl Default for SettingsSolver {
+    fn default() -> Self {
+        Self::new(Settings::default())
+    }
+}
+
+impl SettingsSolver {
+    pub const DEFAULT_MIN_ENTROPY: usize = 64;
+    const MAX_WORDS_COUNT: u8 = 10;
+    const PADDING_DIGITS: [(u8, u8); 3] = [(0, 0), (0, 2), (2, 2)];
+    const PADDING_SYMBOL_LENGTHS: [(u8, u8); 3] = [(0, 0), (0, 1), (1, 1)];
+
+    pub fn new(base: Settings) -> Self {
+        SettingsSolver {
+            base,
+            max_length: None,
+            min_entropy: Self::DEFAULT_MIN_ENTROPY,
+            symbols: true,
+            digits: true,
+        }
+    }
+
+    /// Longest passwords allowed, counting checksum digits.
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Entropy required with full knowledge of the settings & dictionary.
+    pub fn with_min_entropy(mut self, bits: usize) -> Self {
+        self.min_entropy = bits;
+        self
+    }
+
+    /// Whether separators & padding symbols are allowed at all.
+    pub fn with_symbols(mut self, symbols: bool) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    /// Whether padding digits are allowed.
+    pub fn with_digits(mut self, digits: bool) -> Self {
+        self.digits = digits;
+        self
+    }
+
+    /// Up to `limit` viable settings for the words of `pass_generator`, shortest first,
+    /// then strongest. Each combination of separators & padding is suggested once,
+    /// with the fewest characters it takes to reach the entropy.
+    pub fn solve(
+        &self,
+        pass_generator: &Xkpasswd,
+        limit: usize,
+    ) -> Result<Vec<Suggestion>, String> {
+        let mut pool_sizes: HashMap<(u8, u8), usize> = HashMap::new();
+        let mut best: BTreeMap<Shape, Suggestion> = BTreeMap::new();
+
+        for settings in self.candidates() {
+            let pool_size = *pool_sizes
+                .entry(settings.word_lengths)
+                .or_insert_with(|| pass_generator.words_pool(&settings).len());
+
+            // words are picked without repetition
+            if pool_size < settings.words_count as usize {
+                continue;
+            }
+
+            let length_range = settings.length_range();
+            let entropy = settings.calc_entropy(pool_size);
+
+            if entropy.seen < self.min_entropy
+                || self.max_length.is_some_and(|max| length_range.1 > max)
+            {
+                continue;
+            }
+
+            let suggestion = Suggestion {
+                settings,
+                entropy,
+                length_range,
+            };
+
+            let shape: Shape = (
+                suggestion.settings.separators.clone(),
+                suggestion.settings.padding_digits,
+                suggestion.settings.padding_symbol_lengths,
+            );
+
+            match best.get(&shape) {
+                Some(current) if rank(current) <= rank(&suggestion) => {}
+                _ => {
+                    best.insert(shape, suggestion);
+                }
+            }
+        }
+
+        if best.is_empty() {
+            return Err(match self.max_length {
+                Some(max_length) => format!(
+                    "no settings reach {} bits of entropy within {} characters",
+                    self.min_entropy, max_length
+                ),
+                None => format!("no settings reach {} bits of entropy", self.min_entropy),
+            });
+        }
+
+        let mut suggestions: Vec<Suggestion> = best.into_values().collect();
+        suggestions.sort_by_key(rank);
+        suggestions.truncate(limit);
+
+        Ok(suggestions)
+    }
+
+    fn candidates(&self) -> Vec<Settings> {
+        let separators: &[&str] = if self.symbols {
+            &["", "-", Settings::DEFAULT_SEPARATORS]
+        } else {
+            &[""]
+        };
+
+        let padding_digits: &[(u8, u8)] = if self.digits {
+            &Self::PADDING_DIGITS
+        } else {
+            &Self::PADDING_DIGITS[..1]
+        };
+
+        let padding_symbol_lengths: &[(u8, u8)] = if self.symbols {
+            &Self::PADDING_SYMBOL_LENGTHS
+        } else {
+            &Self::PADDING_SYMBOL_LENGTHS[..1]
+        };
+
+        let mut base = self.base.clone();
+        base.padding_strategy = PaddingStrategy::Fixed;
+
+        let mut candidates = vec![];
+
+        for words_count in 2..=Self::MAX_WORDS_COUNT {
+            for min in Settings::MIN_WORD_LENGTH..=Settings::MAX_WORD_LENGTH {
+                for max in min..=Settings::MAX_WORD_LENGTH {
+                    for separator in separators {
+                        for &digits in padding_digits {
+                            for &symbol_lengths in padding_symbol_lengths {
+                                let mut settings = base.clone();
+                                settings.words_count = words_count;
+                                settings.word_lengths = (min, max);
+                                settings.set_separators(separator);
+                                settings.padding_digits = digits;
+                                settings.padding_symbol_lengths = symbol_lengths;
+                                // unused symbols would still count towards the entropy
+                                settings.set_padding_symbols(if symbol_lengths == (0, 0) {
+                                    ""
+                                } else {
+                                    Settings::DEFAULT_SYMBOLS
+                                });
+                                candidates.push(settings);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        candidates
+    }
+}
+
+// shortest first, then strongest, then with the fewest words to type
+fn rank(suggestion: &Suggestion) -> (usize, Reverse<usize>, u8) {
+    (
+        suggestion.length_range.1,
+        Reverse(suggestion.entropy.seen),
+        suggestion.settings.words_count,
+    )
+}
@@ -0,0 +1,44 @@
+use super::Settings;
+use crate::dict::DictStats;
+use crate::prelude::{Builder, Randomizer};
+
+impl Settings {
+    /// The same settings with the fewest words, but no fewer than already set,
+    /// reaching `bits` of entropy with full knowledge, picking from all words
+    /// of `dict_stats` within the word lengths.
+    pub fn for_target_entropy(&self, bits: usize, dict_stats: &DictStats) -> Result<Self, String> {
+        let word_lengths = self.word_lengths();
+        let pool_size = dict_stats
+            .lengths
+            .iter()
+            .filter(|(len, _)| word_lengths.contains(len))
+            .map(|(_, count)| count)
+            .sum();
+
+        self.for_target_entropy_in_pool(bits, pool_size)
+    }
+
+    /// Like `for_target_entropy`, with the exact number of words to pick from,
+    /// e.g. `Xkpasswd::words_pool` which also accounts for themes & proper nouns.
+    pub fn for_target_entropy_in_pool(
+        &self,
+        bits: usize,
+        pool_size: usize,
+    ) -> Result<Self, String> {
+        let mut settings = self.clone();
+
+        // words are picked without repetition, so there can't be more than the pool has
+        while settings.calc_entropy(pool_size).seen < bits {
+            if settings.words_count as usize >= pool_size || settings.words_count == u8::MAX {
+                return Err(format!(
+                    "can't reach {} bits of entropy with {} words to pick from",
+                    bits, pool_size
+                ));
+            }
+
+            settings = settings.with_words_count(settings.words_count + 1)?;
+        }
+
+        Ok(settings)
+    }
+}
@@ -1,4 +1,7 @@
 use super::*;
+use crate::dict::DictStats;
+use crate::prelude::{verify_checksum, PasswordGenerator, Symbols, Uniqueness, Xkpasswd};
+use proptest::prelude::*;
 use std::collections::HashSet;
 
 #[test]
@@ -606,6 +609,29 @@ fn test_calc_entropy() {
         ((Preset::Web32, 2493), (177, 203, 65), (1_000_000_001, 0, 0)),
         ((Preset::Wifi, 6631), (413, 413, 116), (1_000_000_001, 0, 0)),
         ((Preset::Xkcd, 6631), (121, 224, 55), (1_000_001, 0, 0)),
+        ((Preset::Nist, 6631), (153, 281, 68), (1_000_000_001, 0, 0)),
+        ((Preset::Temporary, 4500), (103, 140, 43), (278, 11, 6)),
+        (
+            (Preset::WifiEasyType, 6631),
+            (177, 298, 77),
+            (1_000_000_001, 0, 0),
+        ),
+        ((Preset::Mobile, 6631), (146, 243, 64), (1_000_001, 0, 0)),
+        (
+            (Preset::SshPassphrase, 6631),
+            (210, 367, 91),
+            (1_000_000_001, 0, 0),
+        ),
+        (
+            (Preset::DiskEncryption, 6631),
+            (195, 341, 86),
+            (1_000_000_001, 0, 0),
+        ),
+        (
+            (Preset::DatabaseSafe, 6631),
+            (157, 262, 65),
+            (1_000_000_001, 0, 0),
+        ),
     ];
 
     for ((preset, pool_size), (blind_min, blind_max, seen), (years, months, days)) in table {
@@ -653,6 +679,23 @@ fn test_build_words_list() {
         let unique_words: HashSet<String> = words.iter().map(|word| word.to_lowercase()).collect();
         assert_eq!(3, unique_words.len());
     }
+    // pool size equal to words count, every word picked exactly once
+    let pool = &["foo", "bar", "fooz"];
+
+    for _ in 0..10 {
+        let words = settings.build_words_list(pool);
+        assert_eq!(
+            HashSet::from(["foo", "bar", "fooz"]),
+            words.into_iter().collect::<HashSet<&str>>()
+        );
+    }
+
+    // words count close to a large pool size still yields distinct words
+    let settings = Settings::default().with_words_count(255).unwrap();
+    let pool: Vec<String> = (0..256).map(|idx| format!("word{}", idx)).collect();
+    let pool: Vec<&str> = pool.iter().map(|word| word.as_str()).collect();
+    let words = settings.build_words_list(&pool);
+    assert_eq!(255, words.into_iter().collect::<HashSet<&str>>().len());
 }
 
 #[test]
@@ -703,18 +746,18 @@ fn test_build_transforms_list() {
 
 #[test]
 fn test_rand_digits() {
-    assert_eq!("", rand_digits(0));
+    assert_eq!("", rand_digits(&mut rand::thread_rng(), 0));
 
     for count in 1..21 {
         for _ in 0..100 {
-            let digits = rand_digits(count);
+            let digits = rand_digits(&mut rand::thread_rng(), count);
             assert_eq!(count as usize, digits.len());
         }
     }
 
     for count in 21..100 {
         for _ in 0..100 {
-            let digits = rand_digits(count);
+            let digits = rand_digits(&mut rand::thread_rng(), count);
             assert_eq!(20, digits.len());
         }
     }
@@ -722,32 +765,158 @@ fn test_rand_digits() {
 
 #[test]
 fn test_rand_chars() {
-    assert_eq!("".to_string(), rand_chars("", 1));
+    assert_eq!("".to_string(), rand_chars(&mut rand::thread_rng(), "", 1));
 
     // single char randomize
     for _ in 0..10 {
-        let result = rand_chars(Settings::DEFAULT_SYMBOLS, 1);
+        let result = rand_chars(&mut rand::thread_rng(), Settings::DEFAULT_SYMBOLS, 1);
         assert!(Settings::DEFAULT_SYMBOLS.contains(&result));
     }
 
     // multi char randomize
     for _ in 0..10 {
         for count in 2..5 {
-            let result = rand_chars(Settings::DEFAULT_SYMBOLS, count);
-            assert_eq!(count as usize, result.len());
+            let result = rand_chars(&mut rand::thread_rng(), Settings::DEFAULT_SYMBOLS, count);
+            assert_eq!(count, result.len());
             assert_eq!(
-                result
-                    .chars()
-                    .nth(0)
-                    .unwrap()
-                    .to_string()
-                    .repeat(count as usize),
+                result.chars().next().unwrap().to_string().repeat(count),
                 result
             );
         }
     }
 }
 
+#[test]
+fn test_rand_chars_multi_byte() {
+    let pool = "🔑✨€ß";
+    let pool_chars: Vec<char> = pool.chars().collect();
+
+    for _ in 0..100 {
+        let result = rand_chars(&mut rand::thread_rng(), pool, 1);
+        assert_eq!(1, result.chars().count());
+        assert!(pool_chars.contains(&result.chars().next().unwrap()));
+    }
+
+    for count in 2..5 {
+        let result = rand_chars(&mut rand::thread_rng(), pool, count);
+        assert_eq!(count, result.chars().count());
+        assert_eq!(
+            result.chars().next().unwrap().to_string().repeat(count),
+            result
+        );
+    }
+}
+
+#[test]
+fn test_adjust_padding_multi_byte() {
+    let settings = Settings::default()
+        .with_padding_symbols("🔑")
+        .with_padding_strategy(PaddingStrategy::Adaptive(15))
+        .unwrap();
+
+    match settings.adjust_padding(12) {
+        PaddingResult::Pad(padded_symbols) => assert_eq!("🔑🔑🔑", padded_symbols),
+        _ => panic!("invalid padding result"),
+    }
+}
+
+#[test]
+fn test_for_target_entropy() {
+    let settings = Settings::default();
+    let stats = DictStats {
+        words: 7000,
+        lengths: vec![(3, 369), (4, 1000), (10, 5631), (11, 1000)],
+        ..Default::default()
+    };
+
+    let scaled = settings.for_target_entropy(80, &stats).unwrap();
+    assert_eq!(5, scaled.words_count);
+    assert!(scaled.calc_entropy(6631).seen >= 80);
+    assert!(
+        settings
+            .with_words_count(4)
+            .unwrap()
+            .calc_entropy(6631)
+            .seen
+            < 80
+    );
+    assert_eq!(settings.with_words_count(5).unwrap(), scaled);
+
+    // never fewer words than already set
+    assert_eq!(settings, settings.for_target_entropy(1, &stats).unwrap());
+
+    assert_eq!(
+        Err("can't reach 80 bits of entropy with 4 words to pick from".to_string()),
+        settings.for_target_entropy_in_pool(80, 4)
+    );
+    assert!(settings.for_target_entropy_in_pool(80, 0).is_err());
+}
+
+#[test]
+fn test_settings_solver() {
+    let pass_generator = Xkpasswd::default();
+    let suggestions = SettingsSolver::default()
+        .with_max_length(28)
+        .with_symbols(false)
+        .solve(&pass_generator, 3)
+        .unwrap();
+
+    assert_eq!(3, suggestions.len());
+
+    for suggestion in &suggestions {
+        let settings = &suggestion.settings;
+        assert!(suggestion.entropy.seen >= SettingsSolver::DEFAULT_MIN_ENTROPY);
+        assert!(suggestion.length_range.1 <= 28);
+        assert_eq!(settings.length_range(), suggestion.length_range);
+        assert_eq!("", settings.separators);
+        assert_eq!("", settings.padding_symbols);
+        assert!(settings.validate_against(&pass_generator).is_ok());
+    }
+
+    // shortest first
+    assert!(suggestions
+        .windows(2)
+        .all(|pair| pair[0].length_range.1 <= pair[1].length_range.1));
+
+    // other settings are kept from the base
+    let suggestions = SettingsSolver::new(Settings::default().with_checksum(true))
+        .with_min_entropy(40)
+        .with_digits(false)
+        .solve(&pass_generator, 10)
+        .unwrap();
+
+    for suggestion in &suggestions {
+        assert!(suggestion.settings.checksum);
+        assert_eq!((0, 0), suggestion.settings.padding_digits);
+    }
+
+    assert_eq!(
+        Err("no settings reach 64 bits of entropy within 12 characters".to_string()),
+        SettingsSolver::default()
+            .with_max_length(12)
+            .solve(&pass_generator, 5)
+    );
+    assert_eq!(
+        Err("no settings reach 1000 bits of entropy".to_string()),
+        SettingsSolver::default()
+            .with_min_entropy(1000)
+            .solve(&pass_generator, 5)
+    );
+}
+
+#[test]
+fn test_calc_entropy_multi_byte() {
+    // entropy depends on the number of chars to pick from, not their byte length
+    let ascii = Settings::default()
+        .with_separators(".-")
+        .with_padding_symbols("!?");
+    let multi_byte = Settings::default()
+        .with_separators("🔑✨")
+        .with_padding_symbols("€ß");
+
+    assert_eq!(ascii.calc_entropy(1000), multi_byte.calc_entropy(1000));
+}
+
 #[test]
 fn test_transform_word() {
     let table = [
@@ -795,3 +964,1447 @@ fn test_transform_word() {
         }
     }
 }
+
+#[test]
+fn test_transform_word_multi_byte() {
+    let table = [
+        (
+            WordTransform::Lowercase,
+            [
+                ("Ébène", "ébène"),
+                ("Über", "über"),
+                ("ñandú", "ñandú"),
+                ("", ""),
+            ],
+        ),
+        (
+            WordTransform::Titlecase,
+            [
+                ("ébène", "Ébène"),
+                ("über", "Über"),
+                ("ñandú", "Ñandú"),
+                ("", ""),
+            ],
+        ),
+        (
+            WordTransform::Uppercase,
+            [
+                ("ébène", "ÉBÈNE"),
+                ("über", "ÜBER"),
+                ("ñandú", "ÑANDÚ"),
+                ("", ""),
+            ],
+        ),
+        (
+            WordTransform::InversedTitlecase,
+            [
+                ("Ébène", "éBÈNE"),
+                ("über", "üBER"),
+                ("Ñandú", "ñANDÚ"),
+                ("", ""),
+            ],
+        ),
+    ];
+
+    for (transform, cases) in table {
+        for (word, expected) in cases {
+            assert_eq!(expected, transform_word(word, transform));
+        }
+    }
+}
+
+#[test]
+fn test_settings_builder() {
+    // same result as chaining Builder methods
+    let expected = Settings::default()
+        .with_words_count(5)
+        .unwrap()
+        .with_word_lengths(Some(5), Some(6))
+        .unwrap()
+        .with_word_transforms(WordTransform::Lowercase | WordTransform::InversedTitlecase)
+        .unwrap()
+        .with_separators("~@#")
+        .with_padding_digits(Some(1), Some(3))
+        .with_padding_symbols("$%^")
+        .with_padding_symbol_lengths(Some(3), Some(1))
+        .with_padding_strategy(PaddingStrategy::Adaptive(17))
+        .unwrap();
+
+    let settings = SettingsBuilder::new()
+        .with_words_count(5)
+        .with_word_lengths(Some(5), Some(6))
+        .with_word_transforms(WordTransform::Lowercase | WordTransform::InversedTitlecase)
+        .with_separators("~@#")
+        .with_padding_digits(Some(1), Some(3))
+        .with_padding_symbols("$%^")
+        .with_padding_symbol_lengths(Some(3), Some(1))
+        .with_padding_strategy(PaddingStrategy::Adaptive(17))
+        .build();
+    assert_eq!(Ok(expected), settings);
+
+    // defaults & presets
+    assert_eq!(Ok(Settings::default()), SettingsBuilder::new().build());
+    assert_eq!(
+        Ok(Settings::from_preset(Preset::Xkcd)),
+        SettingsBuilder::from_preset(Preset::Xkcd).build()
+    );
+}
+
+#[test]
+fn test_with_word_lengths_unchecked() {
+    let settings = Settings::default().with_word_lengths_unchecked(Some(12), Some(3));
+    assert_eq!((3, 12), settings.word_lengths);
+    assert_eq!(3..13, settings.word_lengths());
+
+    // words have at least 1 letter
+    let settings = Settings::default().with_word_lengths_unchecked(Some(0), None);
+    assert_eq!((1, Settings::MAX_WORD_LENGTH), settings.word_lengths);
+
+    // and fewer than u8::MAX, with the range end still fitting in a u8
+    let long_settings = Settings::default().with_word_lengths_unchecked(Some(250), Some(255));
+    assert_eq!((250, 254), long_settings.word_lengths);
+    assert_eq!(250..255, long_settings.word_lengths());
+
+    let mut long_settings = long_settings;
+    long_settings.word_lengths = (250, 255);
+    assert_eq!(250..255, long_settings.word_lengths());
+
+    assert_eq!(
+        Ok(settings),
+        SettingsBuilder::new()
+            .with_word_lengths_unchecked(Some(0), None)
+            .build()
+    );
+}
+
+#[test]
+fn test_settings_builder_errors() {
+    // the first error is reported, later steps don't override it
+    let settings = SettingsBuilder::new()
+        .with_words_count(0)
+        .with_word_lengths(Some(1), None)
+        .with_separators("-")
+        .build();
+    assert_eq!(
+        Err("only positive integer is allowed for words count".to_string()),
+        settings
+    );
+
+    let settings = SettingsBuilder::new()
+        .with_words_count(3)
+        .with_word_lengths(None, Some(Settings::MAX_WORD_LENGTH + 1))
+        .with_padding_strategy(PaddingStrategy::Adaptive(0))
+        .build();
+    assert_eq!(Err(MAX_WORD_LENGTH_ERR.to_string()), settings);
+
+    let settings = SettingsBuilder::new().with_word_transforms(0).build();
+    assert_eq!(Err("invalid transform".to_string()), settings);
+}
+
+#[test]
+fn test_preset_example_settings() {
+    for preset in [Preset::Default, Preset::Web32, Preset::Wifi, Preset::Xkcd] {
+        assert_eq!(Settings::from_preset(preset), preset.example_settings());
+    }
+}
+
+#[test]
+fn test_mobile_preset_symbols() {
+    let settings = Settings::from_preset(Preset::Mobile);
+
+    for _ in 0..10 {
+        let (digits, symbols) = settings.rand_suffix();
+        assert_eq!(3, digits.len());
+        assert_eq!(1, symbols.chars().count());
+        assert!(Settings::EASY_SYMBOLS.contains(&symbols));
+    }
+}
+
+#[test]
+fn test_ssh_passphrase_preset() {
+    let pass_generator = Xkpasswd::default();
+    let settings = Settings::from_preset(Preset::SshPassphrase);
+
+    for _ in 0..10 {
+        let (passwd, entropy) = pass_generator.gen_pass(&settings);
+        assert!(
+            passwd
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || Settings::SHELL_SAFE_SYMBOLS.contains(c)),
+            "{}",
+            passwd
+        );
+        assert!(entropy.seen >= 80, "{}", entropy);
+    }
+}
+
+#[test]
+fn test_database_safe_preset() {
+    let pass_generator = Xkpasswd::default();
+    let settings = Settings::from_preset(Preset::DatabaseSafe);
+
+    for _ in 0..10 {
+        let passwd = pass_generator.gen_pass(&settings).0;
+        assert!(
+            !passwd.contains(|c| Settings::DATABASE_UNSAFE_CHARS.contains(c)),
+            "{}",
+            passwd
+        );
+        assert!(passwd.chars().all(|c| c.is_ascii_graphic()), "{}", passwd);
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_from_xkpasswd_json() {
+    // as exported by xkpasswd.net for its default preset
+    let config = r#"{
+        "num_words": 3,
+        "word_length_min": 4,
+        "word_length_max": 8,
+        "case_transform": "ALTERNATE",
+        "separator_character": "RANDOM",
+        "separator_alphabet": ["!", "@", "$", "%", "^", "&", "*", "-", "_", "+", "=", ":", "|", "~", "?", "/", ".", ";"],
+        "padding_digits_before": 2,
+        "padding_digits_after": 2,
+        "padding_type": "FIXED",
+        "padding_character": "RANDOM",
+        "symbol_alphabet": ["!", "@", "$", "%", "^", "&", "*", "-", "_", "+", "=", ":", "|", "~", "?", "/", ".", ";"],
+        "padding_characters_before": 2,
+        "padding_characters_after": 2,
+        "random_increment": "AUTO"
+    }"#;
+
+    let expected = Settings::default()
+        .with_words_count(3)
+        .unwrap()
+        .with_word_lengths(Some(4), Some(8))
+        .unwrap()
+        .with_word_transforms(FieldSize::from_flag(WordTransform::AltercaseLowerFirst))
+        .unwrap()
+        .with_separators("!@$%^&*-_+=:|~?/.;")
+        .with_padding_digits(Some(2), Some(2))
+        .with_padding_symbols("!@$%^&*-_+=:|~?/.;")
+        .with_padding_symbol_lengths(Some(2), Some(2));
+    assert_eq!(Ok(expected), Settings::from_xkpasswd_json(config));
+
+    let config = r#"{
+        "num_words": 4,
+        "case_transform": "NONE",
+        "separator_character": "NONE",
+        "padding_type": "ADAPTIVE",
+        "padding_character": "SEPARATOR",
+        "pad_to_length": 32
+    }"#;
+    let settings = Settings::from_xkpasswd_json(config).unwrap();
+    assert_eq!(4, settings.words_count);
+    assert_eq!(
+        FieldSize::from_flag(WordTransform::Lowercase),
+        settings.word_transforms
+    );
+    assert_eq!("", settings.separators);
+    assert_eq!("", settings.padding_symbols);
+    assert_eq!(PaddingStrategy::Adaptive(32), settings.padding_strategy);
+
+    let table = [
+        ("[]", "config must be a JSON object"),
+        (r#"{"num_words": -1}"#, "invalid number for 'num_words'"),
+        (
+            r#"{"num_words": 0}"#,
+            "only positive integer is allowed for words count",
+        ),
+        (r#"{"word_length_max": 12}"#, MAX_WORD_LENGTH_ERR),
+        (
+            r#"{"case_transform": 1}"#,
+            "invalid string for 'case_transform'",
+        ),
+        (
+            r#"{"case_transform": "FOO"}"#,
+            "unsupported case transform 'FOO'",
+        ),
+        (
+            r#"{"separator_character": "RANDOM"}"#,
+            "invalid alphabet for 'separator_alphabet'",
+        ),
+        (
+            r#"{"padding_type": "ADAPTIVE"}"#,
+            "'pad_to_length' is required for adaptive padding",
+        ),
+        (
+            r#"{"padding_type": "FOO"}"#,
+            "unsupported padding type 'FOO'",
+        ),
+    ];
+
+    for (config, expected) in table {
+        assert_eq!(
+            Err(expected.to_string()),
+            Settings::from_xkpasswd_json(config)
+        );
+    }
+
+    assert!(Settings::from_xkpasswd_json("{")
+        .unwrap_err()
+        .starts_with("invalid JSON"));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_to_xkpasswd_json() {
+    for preset in Preset::ALL {
+        let settings = Settings::from_preset(preset);
+
+        // exported faithfully or not at all
+        match settings.to_xkpasswd_json() {
+            Ok(config) => assert_eq!(
+                Ok(settings),
+                Settings::from_xkpasswd_json(&config),
+                "{:?}",
+                preset
+            ),
+            Err(err) => assert!(err.contains("xkpasswd"), "{:?}: {}", preset, err),
+        }
+    }
+
+    let config = Settings::from_preset(Preset::Xkcd)
+        .to_xkpasswd_json()
+        .unwrap();
+    let config: serde_json::Value = serde_json::from_str(&config).unwrap();
+    assert_eq!("RANDOM", config["case_transform"]);
+    assert_eq!("-", config["separator_character"]);
+    assert_eq!("NONE", config["padding_type"]);
+
+    let settings = Settings::default()
+        .with_word_transforms(WordTransform::Lowercase | WordTransform::Titlecase)
+        .unwrap();
+    assert_eq!(
+        Err("word transforms lowercase & Titlecase have no xkpasswd equivalent".to_string()),
+        settings.to_xkpasswd_json()
+    );
+
+    // xkpasswd alternates starting with a lowercase word only
+    let settings = Settings::from_preset(Preset::Web32);
+    assert_eq!(
+        Err("word transforms ALTERCASE upper FIRST have no xkpasswd equivalent".to_string()),
+        settings.to_xkpasswd_json()
+    );
+
+    let settings = Settings::default().with_checksum(true);
+    assert_eq!(
+        Err("xkpasswd configs have no notion of checksum digits".to_string()),
+        settings.to_xkpasswd_json()
+    );
+}
+
+#[test]
+fn test_matches_generated() {
+    let pass_generator = Xkpasswd::default();
+
+    for preset in [
+        Preset::Default,
+        Preset::AppleID,
+        Preset::WindowsNtlmV1,
+        Preset::SecurityQuestions,
+        Preset::Web16,
+        Preset::Web32,
+        Preset::Wifi,
+        Preset::Xkcd,
+        Preset::Nist,
+        Preset::Temporary,
+        Preset::WifiEasyType,
+        Preset::Mobile,
+        Preset::SshPassphrase,
+        Preset::DiskEncryption,
+        Preset::DatabaseSafe,
+    ] {
+        let settings = Settings::from_preset(preset);
+
+        for _ in 0..5 {
+            let (passwd, _) = pass_generator.gen_pass(&settings);
+            assert!(
+                settings.matches(&passwd, &pass_generator),
+                "{:?} doesn't match {}",
+                preset,
+                passwd
+            );
+        }
+    }
+}
+
+#[test]
+fn test_matches() {
+    let dict = crate::dict::load_word_list(b"ball\ncake\ndoor\nwhale\nzebra\n");
+    let pass_generator = Xkpasswd::from_dict(dict);
+
+    let settings = Settings::default()
+        .with_words_count(2)
+        .unwrap()
+        .with_word_transforms(WordTransform::Lowercase | WordTransform::Titlecase)
+        .unwrap()
+        .with_separators("-.")
+        .with_padding_digits(Some(0), Some(2))
+        .with_padding_symbols("!?")
+        .with_padding_symbol_lengths(Some(1), Some(2));
+
+    let table = [
+        ("!Ball-whale-42??", true),
+        ("?cake.Door.00!!", true),
+        // mixed separators
+        ("!Ball-whale.42??", false),
+        // mixed padding symbols
+        ("!Ball-whale-42?!", false),
+        // word not in dictionary
+        ("!Ball-horse-42??", false),
+        // transform not allowed
+        ("!BALL-whale-42??", false),
+        // too few words & digits
+        ("!Ball-42??", false),
+        ("!Ball-whale-4??", false),
+        ("!Ball-whale-42", false),
+        ("", false),
+    ];
+
+    for (candidate, expected) in table {
+        assert_eq!(
+            expected,
+            settings.matches(candidate, &pass_generator),
+            "{}",
+            candidate
+        );
+    }
+
+    // alternating transforms are bound to word positions
+    let settings = settings
+        .with_word_transforms(FieldSize::from_flag(WordTransform::AltercaseLowerFirst))
+        .unwrap();
+    assert!(settings.matches("!ball-WHALE-42??", &pass_generator));
+    assert!(!settings.matches("!BALL-whale-42??", &pass_generator));
+
+    // adaptive padding: both padded & trimmed passwords
+    let settings = Settings::default()
+        .with_words_count(2)
+        .unwrap()
+        .with_word_transforms(FieldSize::from_flag(WordTransform::Lowercase))
+        .unwrap()
+        .with_separators("-")
+        .with_padding_digits(Some(0), Some(0))
+        .with_padding_symbols("*")
+        .with_padding_strategy(PaddingStrategy::Adaptive(12))
+        .unwrap();
+
+    assert!(settings.matches("ball-cake***", &pass_generator));
+    assert!(settings.matches("whale-zebra*", &pass_generator));
+    assert!(settings.matches("zebra-whale*", &pass_generator));
+    assert!(!settings.matches("ball-cake**", &pass_generator));
+    assert!(!settings.matches("ball-cake*+*", &pass_generator));
+
+    let settings = settings
+        .with_padding_strategy(PaddingStrategy::Adaptive(8))
+        .unwrap();
+    assert!(settings.matches("whale-ze", &pass_generator));
+    assert!(settings.matches("ball-cak", &pass_generator));
+    assert!(!settings.matches("ball-hor", &pass_generator));
+}
+
+#[test]
+fn test_length_range() {
+    let table = [
+        (Preset::Default, (19, 37)),
+        (Preset::WindowsNtlmV1, (14, 14)),
+        (Preset::Web16, (16, 16)),
+        (Preset::Wifi, (63, 63)),
+        (Preset::Xkcd, (19, 35)),
+    ];
+
+    for (preset, expected) in table {
+        assert_eq!(expected, Settings::from_preset(preset).length_range());
+    }
+}
+
+#[test]
+fn test_camel_join_style() {
+    let settings = Settings::default()
+        .with_words_count(4)
+        .unwrap()
+        .with_separators("")
+        .with_padding_digits(Some(0), Some(0))
+        .with_padding_symbols("")
+        .with_padding_symbol_lengths(Some(0), Some(0))
+        .with_join_style(JoinStyle::Camel);
+
+    let words = settings.rand_words(&["correct", "horse", "battery", "staple"]);
+    assert_eq!(4, words.len());
+
+    for word in &words {
+        let mut chars = word.chars();
+        assert!(chars.next().unwrap().is_uppercase());
+        assert!(chars.all(|char| char.is_lowercase()));
+    }
+
+    // titlecase only, so no entropy from word transforms
+    assert_eq!(
+        FieldSize::from_flag(WordTransform::Titlecase),
+        settings.applied_transforms()
+    );
+    assert_eq!(16, settings.calc_entropy(16).seen);
+    assert_eq!((16, 40), settings.length_range());
+
+    let pass_generator = Xkpasswd::default();
+    let (passwd, _) = pass_generator.gen_pass(&settings);
+    assert!(passwd.chars().all(char::is_alphabetic));
+    assert!(settings.matches(&passwd, &pass_generator));
+
+    // separators still apply when set
+    let settings = settings.with_separators("-");
+    assert_eq!((19, 43), settings.length_range());
+
+    // back to configured transforms
+    let settings = settings.with_join_style(JoinStyle::Separated);
+    assert_eq!(
+        Settings::DEFAULT_WORD_TRANSFORMS,
+        settings.applied_transforms()
+    );
+
+    assert_eq!(
+        Ok(Settings::default().with_join_style(JoinStyle::Camel)),
+        SettingsBuilder::new()
+            .with_join_style(JoinStyle::Camel)
+            .build()
+    );
+}
+
+#[test]
+fn test_awkward_transitions() {
+    let table: [(&[&str], usize); 7] = [
+        (&[], 0),
+        (&["bus"], 0),
+        (&["correct", "horse", "battery"], 0),
+        (&["bus", "stop"], 1),
+        (&["big", "bad", "dog"], 2),
+        (&["Bus", "Stop"], 1),
+        (&["été", "Élan"], 1),
+    ];
+
+    for (words, expected) in table {
+        assert_eq!(expected, awkward_transitions(words), "{:?}", words);
+    }
+}
+
+#[test]
+fn test_pronounceable_words() {
+    let settings = Settings::default()
+        .with_words_count(3)
+        .unwrap()
+        .with_pronounceable(true);
+    assert!(settings.to_string().contains("easy to say aloud"));
+
+    // "bus stop" & "stop pan" are the awkward ones, "pan bus stop" is not
+    let pool = &["bus", "stop", "pan"];
+
+    for _ in 0..10 {
+        let words = settings.build_pronounceable_words_list(pool);
+        assert_eq!(0, awkward_transitions(&words), "{:?}", words);
+    }
+
+    // no way around it, still returns enough words
+    let pool = &["bus", "bass", "boss"];
+    assert_eq!(3, settings.build_pronounceable_words_list(pool).len());
+
+    assert!(!Settings::default().pronounceable);
+
+    // picking the least awkward of a few word lists costs up to log2(attempts) bits
+    let plain = Settings::default().with_words_count(3).unwrap();
+    assert_eq!(
+        plain.calc_entropy(pool.len()).seen - 5,
+        settings.calc_entropy(pool.len()).seen
+    );
+}
+
+#[cfg(feature = "extra_entropy")]
+#[test]
+fn test_extra_entropy() {
+    let settings = Settings::default().with_extra_entropy(b"asdfjkl;qwerty");
+    assert_eq!(b"asdfjkl;qwerty".to_vec(), settings.extra_entropy);
+    assert_ne!(Settings::default(), settings);
+    assert!(settings.to_string().contains("extra user supplied entropy"));
+    assert!(!Settings::default().to_string().contains("entropy"));
+
+    // derived once per password, with fresh thread RNG output mixed in,
+    // so the same extra entropy never repeats
+    settings.rand_words(&["horse"]);
+    let first_pick = PASS_RNG.with_borrow(|pass_rng| pass_rng.as_ref().unwrap().1.clone());
+    assert_ne!(settings.rng().next_u64(), settings.rng().next_u64());
+
+    settings.rand_words(&["horse"]);
+    let next_pick = PASS_RNG.with_borrow(|pass_rng| pass_rng.as_ref().unwrap().1.clone());
+    assert_ne!(first_pick, next_pick);
+
+    // never drawn from the RNG derived from other extra entropy
+    let other = Settings::default().with_extra_entropy(b"zxcvbnm");
+    other.rng();
+    PASS_RNG.with_borrow(|pass_rng| assert_eq!(b"zxcvbnm", &pass_rng.as_ref().unwrap().0[..]));
+
+    let pass_generator = Xkpasswd::default();
+    let (passwd, entropy) = pass_generator.gen_pass(&settings);
+    assert!(settings.matches(&passwd, &pass_generator), "{}", passwd);
+    assert_eq!(pass_generator.gen_pass(&Settings::default()).1, entropy);
+
+    let built = SettingsBuilder::new()
+        .with_extra_entropy(b"asdfjkl;qwerty")
+        .build();
+    assert_eq!(Ok(settings), built);
+}
+
+#[test]
+fn test_checksum() {
+    for preset in [Preset::Default, Preset::Xkcd] {
+        let plain_settings = Settings::from_preset(preset);
+        let settings = plain_settings.with_checksum(true);
+        assert!(settings
+            .to_string()
+            .contains("2 checksum digits at the end"));
+
+        let (min_len, max_len) = plain_settings.length_range();
+        assert_eq!((min_len + 2, max_len + 2), settings.length_range());
+
+        // derived from the rest of the password, checksum adds no entropy
+        assert_eq!(
+            plain_settings.calc_entropy(1000),
+            settings.calc_entropy(1000)
+        );
+
+        let pass_generator = Xkpasswd::default();
+        let (passwd, _) = pass_generator.gen_pass(&settings);
+        assert!(verify_checksum(&passwd), "{}", passwd);
+        assert!(settings.matches(&passwd, &pass_generator), "{}", passwd);
+
+        let (body, checksum) = passwd.split_at(passwd.len() - 2);
+        assert!(plain_settings.matches(body, &pass_generator), "{}", body);
+
+        let wrong_checksum = format!("{}{}", body, if checksum == "00" { "01" } else { "00" });
+        assert!(!settings.matches(&wrong_checksum, &pass_generator));
+    }
+
+    // still fitting the 63 characters of WPA2 keys, the body is shortened instead
+    let settings = Settings::from_preset(Preset::Wifi).with_checksum(true);
+    assert_eq!((63, 63), settings.length_range());
+
+    let pass_generator = Xkpasswd::default();
+    for _ in 0..10 {
+        let (passwd, _) = pass_generator.gen_pass(&settings);
+        assert_eq!(63, passwd.chars().count(), "{}", passwd);
+        assert!(verify_checksum(&passwd), "{}", passwd);
+        assert!(settings.matches(&passwd, &pass_generator), "{}", passwd);
+    }
+
+    let built = SettingsBuilder::new().with_checksum(true).build();
+    assert_eq!(Ok(Settings::default().with_checksum(true)), built);
+}
+
+#[test]
+fn test_preset() {
+    for preset in Preset::ALL {
+        assert_eq!(Some(preset), Settings::from_preset(preset).preset());
+    }
+
+    assert_eq!(Some(Preset::Default), Settings::default().preset());
+
+    let customized = Settings::from_preset(Preset::Xkcd).with_separators("_");
+    assert_eq!(None, customized.preset());
+
+    let events: std::sync::Arc<std::sync::Mutex<Vec<Option<Preset>>>> = Default::default();
+    let recorded = events.clone();
+    let mut pass_generator = Xkpasswd::default();
+    pass_generator.on_generate(move |meta| recorded.lock().unwrap().push(meta.preset));
+
+    pass_generator.gen_pass(&Settings::from_preset(Preset::Web32));
+    pass_generator.gen_pass(&customized);
+    assert_eq!(vec![Some(Preset::Web32), None], *events.lock().unwrap());
+}
+
+#[test]
+fn test_compact() {
+    assert_eq!(
+        "w3:l4-10:t=LU:s=.-_~:d0,2:p=~@$%^&*-_+=\\:|?/.;:pl0,2:fixed",
+        Settings::default().compact().to_string()
+    );
+
+    let settings = Settings::default()
+        .with_words_count(4)
+        .unwrap()
+        .with_word_lengths(Some(4), Some(8))
+        .unwrap()
+        .with_word_transforms(FieldSize::from_flag(WordTransform::AltercaseUpperFirst))
+        .unwrap()
+        .with_separators("")
+        .with_padding_symbols("\\!")
+        .with_padding_strategy(PaddingStrategy::Adaptive(32))
+        .unwrap()
+        .with_join_style(JoinStyle::Camel)
+        .with_pronounceable(true)
+        .with_checksum(true)
+        .with_word_theme("animals")
+        .with_proper_nouns(ProperNouns::Preserve)
+        .with_spelling(SpellingVariant::British)
+        .with_avoid_ambiguous(true)
+        .with_avoid_homophones(true);
+    assert_eq!(
+        "w4:l4-8:t=A:s=:d0,2:p=\\\\!:pl0,0:adaptive32:camel:pron:cs:th=animals:keeppn:gb:noamb:nohph",
+        settings.compact().to_string()
+    );
+
+    for settings in Preset::ALL
+        .map(Settings::from_preset)
+        .into_iter()
+        .chain([settings])
+    {
+        assert_eq!(Ok(settings.clone()), settings.compact().to_string().parse());
+    }
+
+    // any order, missing fields keep their defaults
+    assert_eq!(
+        Ok(Settings::default()
+            .with_separators(":")
+            .with_words_count(5)
+            .unwrap()),
+        "s=\\::w5".parse()
+    );
+    assert_eq!(Ok(Settings::default()), "".parse());
+
+    let expectations = [
+        ("w0", "only positive integer is allowed for words count"),
+        ("l2-8", MIN_WORD_LENGTH_ERR),
+        ("wx", "invalid number in 'wx'"),
+        ("d1", "expected 2 numbers in 'd1'"),
+        ("t=LX", "unknown word transform 'X'"),
+        ("adaptive0", "invalid adaptive padding number"),
+        ("w3:w4", "duplicated field 'w4'"),
+        ("nope", "unknown field 'nope'"),
+        ("s=\\", "dangling escape character at the end"),
+    ];
+
+    for (compact, err) in expectations {
+        assert_eq!(
+            Err(err.to_string()),
+            compact.parse::<Settings>(),
+            "{}",
+            compact
+        );
+    }
+}
+
+#[test]
+fn test_query_string() {
+    assert_eq!(
+        "words=3&word_min=4&word_max=10&transforms=LU&separators=.-_~&digits_before=0&digits_after=2&symbols=~%40%24%25%5E%26%2A-_%2B%3D%3A%7C%3F%2F.%3B&symbols_before=0&symbols_after=2&padding=fixed",
+        Settings::default().to_query_string()
+    );
+
+    let settings = Settings::from_preset(Preset::SecurityQuestions)
+        .with_padding_strategy(PaddingStrategy::Adaptive(24))
+        .unwrap()
+        .with_join_style(JoinStyle::Camel)
+        .with_pronounceable(true)
+        .with_checksum(true)
+        .with_padding_symbols("é&=#")
+        .with_word_theme("Big Cats")
+        .with_proper_nouns(ProperNouns::Exclude)
+        .with_spelling(SpellingVariant::American)
+        .with_avoid_ambiguous(true)
+        .with_avoid_homophones(true);
+    let query = settings.to_query_string();
+    assert!(query.contains("separators=%20"), "{}", query);
+    assert!(query.ends_with(
+        "&padding=adaptive&adaptive_length=24&join=camel&pronounceable=true&checksum=true&theme=big%20cats&proper_nouns=exclude&spelling=american&avoid_ambiguous=true&avoid_homophones=true"
+    ));
+
+    for settings in Preset::ALL
+        .map(Settings::from_preset)
+        .into_iter()
+        .chain([settings])
+    {
+        let query = settings.to_query_string();
+        assert_eq!(Ok(settings.clone()), Settings::from_query_string(&query));
+        assert_eq!(
+            Ok(settings),
+            Settings::from_query_string(&format!("#{}", query))
+        );
+    }
+
+    // missing keys keep their defaults, unknown ones are ignored
+    assert_eq!(
+        Ok(Settings::default()
+            .with_separators(" +")
+            .with_words_count(5)
+            .unwrap()),
+        Settings::from_query_string("?lang=en&words=5&separators=+%2B")
+    );
+    assert_eq!(Ok(Settings::default()), Settings::from_query_string(""));
+
+    let expectations = [
+        (
+            "words=0",
+            "only positive integer is allowed for words count",
+        ),
+        ("words=x", "invalid value 'x' for 'words'"),
+        ("word_max=12", MAX_WORD_LENGTH_ERR),
+        (
+            "padding=adaptive",
+            "'adaptive_length' is required for adaptive padding",
+        ),
+        ("padding=nope", "invalid padding 'nope'"),
+        ("join=nope", "invalid join style 'nope'"),
+        ("proper_nouns=nope", "invalid proper nouns option 'nope'"),
+        ("spelling=canadian", "invalid spelling variant 'canadian'"),
+        ("checksum=1", "invalid value '1' for 'checksum'"),
+        ("separators=%2", "invalid escape in '%2'"),
+        ("separators=%+1", "invalid escape in '%+1'"),
+        ("separators=%FF", "invalid UTF-8 in '%FF'"),
+    ];
+
+    for (query, err) in expectations {
+        assert_eq!(
+            Err(err.to_string()),
+            Settings::from_query_string(query),
+            "{}",
+            query
+        );
+    }
+}
+
+#[test]
+fn test_with_query_param() {
+    let settings = Settings::from_preset(Preset::Xkcd);
+
+    assert_eq!(
+        settings.with_words_count(5),
+        settings.with_query_param("words", "5")
+    );
+    assert_eq!(
+        Ok(settings.with_separators("&=")),
+        settings.with_query_param("separators", "&=")
+    );
+    assert_eq!(
+        Err("unknown parameter 'lang'".to_string()),
+        settings.with_query_param("lang", "en")
+    );
+    assert_eq!(
+        Err("invalid value 'many' for 'words'".to_string()),
+        settings.with_query_param("words", "many")
+    );
+}
+
+#[test]
+fn test_word_theme() {
+    let dict = crate::dict::load_word_list(
+        b"ball\ncake\ndoor\n@animals\nhorse\nwhale\nzebra\n@colors\namber\nolive\n",
+    );
+    let pass_generator = Xkpasswd::from_dict(dict);
+
+    let settings = Settings::default()
+        .with_words_count(2)
+        .unwrap()
+        .with_word_theme(" Animals ");
+    assert_eq!(Some("animals"), settings.word_theme());
+    assert!(settings.to_string().contains("words themed 'animals'"));
+    assert_eq!(Ok(()), settings.validate_against(&pass_generator));
+
+    let animals = ["horse", "whale", "zebra"];
+    assert_eq!(&animals, &*pass_generator.words_pool(&settings));
+
+    let settings = settings.with_separators("-");
+    let (passwd, entropy) = pass_generator.gen_pass(&settings);
+    for word in passwd.split('-').take(2) {
+        assert!(
+            animals.contains(&word.to_lowercase().as_str()),
+            "{}",
+            passwd
+        );
+    }
+
+    // picked from 3 words instead of 8
+    assert_eq!(settings.calc_entropy(animals.len()), entropy);
+    assert!(entropy.seen < settings.calc_entropy(8).seen);
+
+    // no theme, all words
+    assert_eq!(
+        8,
+        pass_generator
+            .words_pool(&settings.with_word_theme(""))
+            .len()
+    );
+    assert!(pass_generator
+        .words_pool(&settings.with_word_theme("foods"))
+        .is_empty());
+}
+
+#[test]
+fn test_proper_nouns() {
+    let dict = crate::dict::load_word_list(b"Paris\nLondon\nhorse\nzebra\n");
+    let pass_generator = Xkpasswd::from_dict(dict);
+
+    let settings = Settings::default()
+        .with_words_count(4)
+        .unwrap()
+        .with_word_transforms(FieldSize::from_flag(WordTransform::Uppercase))
+        .unwrap();
+    assert_eq!(4, pass_generator.words_pool(&settings).len());
+
+    let excluded = settings.with_proper_nouns(ProperNouns::Exclude);
+    assert_eq!(&["horse", "zebra"], &*pass_generator.words_pool(&excluded));
+    assert!(excluded.to_string().contains("no proper nouns"));
+    assert!(pass_generator.gen_pass(&excluded).1.seen < pass_generator.gen_pass(&settings).1.seen);
+
+    let preserved = settings
+        .with_proper_nouns(ProperNouns::Preserve)
+        .with_separators("-")
+        .with_padding_digits(Some(0), Some(0))
+        .with_padding_symbol_lengths(Some(0), Some(0));
+    let words = preserved.rand_words(&pass_generator.words_pool(&preserved));
+
+    for word in &words {
+        assert!(
+            ["Paris", "London", "HORSE", "ZEBRA"].contains(&word.as_str()),
+            "{:?}",
+            words
+        );
+    }
+
+    let (passwd, _) = pass_generator.gen_pass(&preserved);
+    assert!(preserved.matches(&passwd, &pass_generator), "{}", passwd);
+    assert!(preserved.matches("Paris-HORSE-London-ZEBRA", &pass_generator));
+    assert!(!preserved.matches("PARIS-HORSE-London-ZEBRA", &pass_generator));
+}
+
+#[cfg(feature = "lang_en")]
+#[test]
+fn test_spelling() {
+    let pass_generator = Xkpasswd::default();
+    let settings = Settings::default();
+    let pool = pass_generator.words_pool(&settings);
+    assert!(pool.contains(&"colour") && pool.contains(&"color"));
+
+    let british = settings.with_spelling(SpellingVariant::British);
+    let pool = pass_generator.words_pool(&british);
+    assert!(pool.contains(&"colour") && !pool.contains(&"color"));
+    assert!(british.to_string().contains("British spelling"));
+
+    let american = settings.with_spelling(SpellingVariant::American);
+    let pool = pass_generator.words_pool(&american);
+    assert!(!pool.contains(&"colour") && pool.contains(&"color"));
+    assert!(pool.len() < pass_generator.words_pool(&settings).len());
+
+    // dictionaries without tagged variants are left as is
+    let dict = crate::dict::load_word_list(
+        b"colour
+color
+",
+    );
+    let pass_generator = Xkpasswd::from_dict(dict);
+    assert_eq!(2, pass_generator.words_pool(&british).len());
+}
+
+#[test]
+fn test_avoid_ambiguous() {
+    let settings = Settings::default()
+        .with_separators("-|.")
+        .with_padding_symbols("!l@0")
+        .with_padding_digits(Some(4), Some(4))
+        .with_avoid_ambiguous(true);
+    assert!(settings.to_string().contains("no ambiguous characters"));
+
+    // stripped from separators & symbols set before or after
+    assert_eq!(
+        settings,
+        Settings::default()
+            .with_avoid_ambiguous(true)
+            .with_separators("-|.")
+            .with_padding_symbols("!l@0")
+            .with_padding_digits(Some(4), Some(4))
+    );
+    assert_eq!(
+        Settings::default()
+            .with_separators("-.")
+            .with_padding_symbols("!@")
+            .with_padding_digits(Some(4), Some(4)),
+        settings.with_avoid_ambiguous(false)
+    );
+
+    let (prefix_symbols, prefix_digits) = settings.rand_prefix();
+    assert!(["", "!", "@"].contains(&prefix_symbols.as_str()));
+    assert_eq!(4, prefix_digits.len());
+    assert!(!prefix_digits.contains(['0', '1']), "{}", prefix_digits);
+
+    let dict = crate::dict::load_word_list(b"lake\nmoon\nicon\nhouse\nzebra\n");
+    let pass_generator = Xkpasswd::from_dict(dict);
+
+    // lowercase 'l', uppercase 'I' & 'O'
+    assert_eq!(&["zebra"], &*pass_generator.words_pool(&settings));
+
+    let lowercase = settings
+        .with_word_transforms(FieldSize::from_flag(WordTransform::Lowercase))
+        .unwrap();
+    assert_eq!(
+        &["moon", "icon", "house", "zebra"],
+        &*pass_generator.words_pool(&lowercase)
+    );
+
+    let (passwd, entropy) = pass_generator.gen_pass(&lowercase);
+    assert!(
+        !passwd.contains(|char| Settings::AMBIGUOUS_CHARS.contains(char)),
+        "{}",
+        passwd
+    );
+    assert!(lowercase.matches(&passwd, &pass_generator), "{}", passwd);
+    assert!(
+        entropy.seen
+            < pass_generator
+                .gen_pass(&lowercase.with_avoid_ambiguous(false))
+                .1
+                .seen
+    );
+}
+
+#[test]
+fn test_avoid_homophones() {
+    let settings = Settings::default().with_avoid_homophones(true);
+    assert!(settings
+        .to_string()
+        .contains("no words sounding like others"));
+
+    let dict = crate::dict::load_word_list(b"there\nplace\nheard\nzebra\n")
+        .with_homophones("# comment\ntheir there\nheard herd");
+    let pass_generator = Xkpasswd::from_dict(dict);
+
+    assert_eq!(&["place", "zebra"], &*pass_generator.words_pool(&settings));
+    assert_eq!(4, pass_generator.words_pool(&Settings::default()).len());
+
+    let (passwd, _) = pass_generator.gen_pass(&settings.with_words_count(2).unwrap());
+    assert!(!passwd.to_lowercase().contains("there"), "{}", passwd);
+}
+
+#[test]
+fn test_symbol_set() {
+    for symbol_set in [
+        SymbolSet::UsShiftless,
+        SymbolSet::IntlSafe,
+        SymbolSet::AsciiOnlyNoQuotes,
+    ] {
+        let settings = Settings::default().with_symbol_set(symbol_set);
+        assert_eq!(
+            Settings::default()
+                .with_separators(symbol_set.symbols())
+                .with_padding_symbols(symbol_set.symbols()),
+            settings
+        );
+
+        let symbols = symbol_set.symbols();
+        assert!(symbols.is_ascii() && !symbols.contains(char::is_alphanumeric));
+
+        let (passwd, _) = Xkpasswd::default().gen_pass(&settings);
+        assert!(passwd
+            .chars()
+            .all(|char| char.is_alphanumeric() || symbols.contains(char)));
+    }
+
+    assert!(!Symbols::ASCII_ONLY_NO_QUOTES.contains(['\'', '"', '`', '\\']));
+    assert!(!Symbols::INTL_SAFE.contains(['@', '#', '[', '\\', '|', '~', '^']));
+
+    // ambiguous ones are still left out
+    let settings = Settings::default()
+        .with_avoid_ambiguous(true)
+        .with_symbol_set(SymbolSet::UsShiftless);
+    assert_eq!("-=[]\\;,./", settings.padding_symbols);
+}
+
+#[cfg(feature = "extra_entropy")]
+#[test]
+fn test_diff() {
+    let web32 = Settings::from_preset(Preset::Web32);
+    assert_eq!(Vec::<diff::SettingDelta>::new(), web32.diff(&web32));
+
+    let customized = web32
+        .with_words_count(5)
+        .unwrap()
+        .with_separators("_")
+        .with_padding_strategy(PaddingStrategy::Adaptive(40))
+        .unwrap()
+        .with_extra_entropy(b"dice");
+
+    let deltas: Vec<String> = web32
+        .diff(&customized)
+        .iter()
+        .map(|delta| delta.to_string())
+        .collect();
+
+    assert_eq!(
+        vec![
+            "words_count (4→5)",
+            "separators (⟪-+=.*_|~,⟫→⟪_⟫)",
+            "padding_symbol_lengths (1,1→0,0)",
+            "padding_strategy (fixed→adaptive to 40)",
+            "extra_entropy (0 bytes→4 bytes)",
+        ],
+        deltas
+    );
+
+    assert_eq!(
+        vec![diff::SettingDelta {
+            field: "extra_entropy",
+            from: "4 bytes".to_string(),
+            to: "4 bytes".to_string(),
+        }],
+        customized.diff(&customized.with_extra_entropy(b"coin"))
+    );
+}
+
+#[test]
+fn test_merge() {
+    let web32 = Settings::from_preset(Preset::Web32);
+    assert_eq!(Ok(web32.clone()), web32.merge(SettingsPatch::default()));
+
+    // preset < config file < command line
+    let config_file = SettingsPatch {
+        words_count: Some(5),
+        separators: Some("_".to_string()),
+        ..Default::default()
+    };
+    let command_line = SettingsPatch {
+        separators: Some(".".to_string()),
+        padding_strategy: Some(PaddingStrategy::Adaptive(40)),
+        checksum: Some(true),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        Ok(web32
+            .with_words_count(5)
+            .unwrap()
+            .with_separators(".")
+            .with_padding_strategy(PaddingStrategy::Adaptive(40))
+            .unwrap()
+            .with_checksum(true)),
+        web32.merge(config_file.overlay(command_line))
+    );
+
+    let invalid = SettingsPatch {
+        word_length_max: Some(12),
+        ..Default::default()
+    };
+    assert_eq!(Err(MAX_WORD_LENGTH_ERR.to_string()), web32.merge(invalid));
+}
+
+#[test]
+fn test_validate_against() {
+    let dict = crate::dict::load_word_list(b"ball\ncake\ndoor\nwhale\nzebra\n");
+    let pass_generator = Xkpasswd::from_dict(dict);
+
+    let settings = Settings::default().with_words_count(5).unwrap();
+    assert_eq!(Ok(()), settings.validate_against(&pass_generator));
+
+    let settings = settings.with_word_lengths(Some(4), Some(4)).unwrap();
+    assert_eq!(
+        Err("only 3 distinct word(s) of 4 letters in the dictionary for 5 words".to_string()),
+        settings.validate_against(&pass_generator)
+    );
+
+    let settings = settings.with_word_lengths(Some(6), Some(10)).unwrap();
+    assert_eq!(
+        Err("no words of 6 to 10 letters in the dictionary".to_string()),
+        settings.validate_against(&pass_generator)
+    );
+
+    let settings = Settings::default().with_word_theme("animals");
+    assert_eq!(
+        Err("no words of 4 to 10 letters themed 'animals' in the dictionary".to_string()),
+        settings.validate_against(&pass_generator)
+    );
+
+    assert_eq!(
+        Ok(()),
+        Settings::default().validate_against(&Xkpasswd::default())
+    );
+}
+
+#[test]
+fn test_password_generator() {
+    let generator: Box<dyn PasswordGenerator> = Box::new(
+        ConfiguredGenerator::new(Xkpasswd::default(), Settings::from_preset(Preset::Xkcd)).unwrap(),
+    );
+
+    let passwd = generator.generate().unwrap();
+    assert!(
+        Settings::from_preset(Preset::Xkcd).matches(&passwd, &Xkpasswd::default()),
+        "{}",
+        passwd
+    );
+
+    let dict = crate::dict::load_word_list(b"ball\ncake\ndoor\nwhale\nzebra\n");
+    let settings = Settings::default()
+        .with_words_count(5)
+        .and_then(|settings| settings.with_word_lengths(Some(4), Some(4)))
+        .unwrap();
+    assert_eq!(
+        "only 3 distinct word(s) of 4 letters in the dictionary for 5 words",
+        ConfiguredGenerator::new(Xkpasswd::from_dict(dict), settings).unwrap_err()
+    );
+}
+
+#[test]
+fn test_gen_unique_passes() {
+    let dict = crate::dict::load_word_list(b"ball\ncake\ndoor\nlamp\nmilk\nrope\nsock\ntree\n");
+    let pass_generator = Xkpasswd::from_dict(dict);
+    let settings = Settings::default()
+        .with_words_count(2)
+        .unwrap()
+        .with_word_transforms(FieldSize::from_flag(WordTransform::AltercaseUpperFirst))
+        .unwrap();
+
+    let (passwds, entropy) = pass_generator
+        .gen_unique_passes(&settings, 4, Uniqueness::Words)
+        .unwrap();
+    assert_eq!(4, passwds.len());
+    // the last one picked from the 2 words left
+    assert_eq!(settings.calc_entropy(2), entropy);
+
+    let words: Vec<String> = passwds
+        .iter()
+        .flat_map(|passwd| {
+            passwd
+                .split(|char: char| !char.is_alphabetic())
+                .filter(|word| !word.is_empty())
+                .map(str::to_lowercase)
+                .collect::<Vec<String>>()
+        })
+        .collect();
+    assert_eq!(8, words.len());
+    assert_eq!(8, words.iter().collect::<HashSet<&String>>().len());
+
+    assert!(pass_generator
+        .gen_unique_passes(&settings, 5, Uniqueness::Words)
+        .is_err());
+
+    let (passwds, _) = pass_generator
+        .gen_unique_passes(&settings, 20, Uniqueness::Passwords)
+        .unwrap();
+    assert_eq!(20, passwds.iter().collect::<HashSet<&String>>().len());
+}
+
+#[test]
+fn test_lint() {
+    let pass_generator = Xkpasswd::default();
+    assert_eq!(
+        Vec::<lint::Warning>::new(),
+        Settings::default().lint(&pass_generator)
+    );
+
+    let settings = Settings::default()
+        .with_words_count(1)
+        .unwrap()
+        .with_separators("")
+        .with_padding_digits(Some(0), Some(0))
+        .with_padding_symbol_lengths(Some(0), Some(0));
+    let warnings = settings.lint(&pass_generator);
+    assert_eq!(3, warnings.len(), "{:?}", warnings);
+    assert_eq!(lint::Warning::SingleWord, warnings[0]);
+    assert_eq!(lint::Warning::NoSeparation, warnings[1]);
+    assert!(matches!(warnings[2], lint::Warning::LowEntropy(entropy) if entropy < 40));
+
+    let settings = Settings::default()
+        .with_words_count(4)
+        .unwrap()
+        .with_padding_symbol_lengths(Some(6), Some(6));
+    assert_eq!(
+        vec![lint::Warning::TooManySymbols(12)],
+        settings.lint(&pass_generator)
+    );
+    assert_eq!(
+        "12 padding symbols add length but little entropy, more than 10 are hard to type",
+        lint::Warning::TooManySymbols(12).to_string()
+    );
+
+    let settings = Settings::default().with_word_lengths_unchecked(Some(3), Some(8));
+    assert_eq!(
+        vec![lint::Warning::UncheckedWordLengths(3, 8)],
+        settings.lint(&pass_generator)
+    );
+    assert_eq!(
+        "word lengths of 3 to 8 are outside the usual 4 to 10, the dictionary may have few such words",
+        lint::Warning::UncheckedWordLengths(3, 8).to_string()
+    );
+}
+
+#[test]
+fn test_lint_against_preset() {
+    let settings = Settings::from_preset(Preset::DiskEncryption);
+    assert!(settings
+        .lint_against_preset(Preset::DiskEncryption)
+        .is_empty());
+
+    // symbols only count once they can be padded
+    let settings = settings.with_padding_symbols("!@");
+    assert!(settings
+        .lint_against_preset(Preset::DiskEncryption)
+        .is_empty());
+
+    let settings = settings
+        .with_separators("-.5")
+        .with_padding_symbol_lengths(Some(1), Some(1));
+    assert_eq!(
+        vec![lint::Warning::LayoutDependentSymbols("!-.@".to_string())],
+        settings.lint_against_preset(Preset::DiskEncryption)
+    );
+    assert!(settings.lint_against_preset(Preset::Default).is_empty());
+    assert_eq!(
+        "symbols !-.@ may not be where expected at pre-boot prompts forcing a US keyboard layout",
+        lint::Warning::LayoutDependentSymbols("!-.@".to_string()).to_string()
+    );
+}
+
+#[test]
+fn test_gen_all_presets() {
+    let pass_generator = Xkpasswd::default();
+    let examples = pass_generator.gen_all_presets();
+
+    assert_eq!(
+        Preset::ALL.to_vec(),
+        examples.keys().copied().collect::<Vec<Preset>>()
+    );
+
+    for (preset, example) in examples {
+        assert!(
+            preset.example_settings().matches(&example, &pass_generator),
+            "{:?}: {}",
+            preset,
+            example
+        );
+    }
+}
+
+#[test]
+fn test_preset_registry() {
+    let corp_standard = Settings::default()
+        .with_words_count(5)
+        .unwrap()
+        .with_separators("-");
+
+    let mut registry = registry::PresetRegistry::new();
+    assert_eq!(
+        Err("preset name must not be empty".to_string()),
+        registry.register(" ", corp_standard.clone())
+    );
+
+    registry
+        .register("corp-standard", corp_standard.clone())
+        .unwrap();
+    assert_eq!(Ok(corp_standard.clone()), registry.resolve("corp-standard"));
+
+    // built-in presets by name or alias, unless overridden
+    assert_eq!(
+        Ok(Settings::from_preset(Preset::Xkcd)),
+        registry.resolve("xkcd")
+    );
+    assert_eq!(
+        Ok(Settings::from_preset(Preset::AppleID)),
+        registry.resolve("apple_id")
+    );
+
+    registry.register("xkcd", corp_standard.clone()).unwrap();
+    assert_eq!(Ok(corp_standard), registry.resolve("xkcd"));
+    assert_eq!(vec!["corp-standard", "xkcd"], registry.names());
+
+    assert_eq!(
+        Err("unknown preset 'nope'".to_string()),
+        registry.resolve("nope")
+    );
+}
+
+proptest! {
+    // words are drawn from the built-in English dictionary, which has plenty of every length
+    #[test]
+    fn prop_fixed_padding_layout(
+        words_count in 1u8..=6,
+        word_lengths in (4u8..=10, 4u8..=10),
+        separators in "[-.~_+=]{1,4}",
+        padding_digits in (0u8..=4, 0u8..=4),
+        padding_symbols in "[!@#$%^&*]{1,4}",
+        padding_symbol_lengths in (0u8..=3, 0u8..=3),
+    ) {
+        let settings = Settings::default()
+            .with_words_count(words_count)
+            .unwrap()
+            .with_word_lengths(Some(word_lengths.0), Some(word_lengths.1))
+            .unwrap()
+            .with_separators(&separators)
+            .with_padding_digits(Some(padding_digits.0), Some(padding_digits.1))
+            .with_padding_symbols(&padding_symbols)
+            .with_padding_symbol_lengths(
+                Some(padding_symbol_lengths.0),
+                Some(padding_symbol_lengths.1),
+            );
+
+        let (passwd, _) = Xkpasswd::preloaded().gen_pass(&settings);
+        let chars: Vec<char> = passwd.chars().collect();
+
+        let (min_len, max_len) = settings.length_range();
+        prop_assert!((min_len..=max_len).contains(&chars.len()), "{}", passwd);
+
+        let (prefix, rest) = chars.split_at(padding_symbol_lengths.0 as usize);
+        let (body, suffix) = rest.split_at(rest.len() - padding_symbol_lengths.1 as usize);
+
+        for padding in [prefix, suffix] {
+            let all_symbols = padding.iter().all(|symbol| padding_symbols.contains(*symbol));
+            prop_assert!(all_symbols, "{}", passwd);
+        }
+
+        // picked once, so the same separator everywhere
+        let mut parts: Vec<String> = match body.iter().find(|char| !char.is_alphanumeric()) {
+            Some(separator) => {
+                prop_assert!(separators.contains(*separator), "{}", passwd);
+                let body: String = body.iter().collect();
+                body.split(*separator).map(str::to_string).collect()
+            }
+            None => vec![body.iter().collect()],
+        };
+
+        if padding_digits.0 > 0 {
+            let digits = parts.remove(0);
+            prop_assert!(digits.chars().all(|char| char.is_ascii_digit()), "{}", passwd);
+            prop_assert_eq!(padding_digits.0 as usize, digits.len());
+        }
+
+        if padding_digits.1 > 0 {
+            let digits = parts.pop().unwrap_or_default();
+            prop_assert!(digits.chars().all(|char| char.is_ascii_digit()), "{}", passwd);
+            prop_assert_eq!(padding_digits.1 as usize, digits.len());
+        }
+
+        prop_assert_eq!(words_count as usize, parts.len());
+
+        let (min_word, max_word) = settings.word_lengths;
+        for word in parts {
+            prop_assert!(word.chars().all(char::is_alphabetic), "{}", passwd);
+            let word_length = word.chars().count() as u8;
+            prop_assert!((min_word..=max_word).contains(&word_length), "{}", passwd);
+        }
+    }
+
+    #[test]
+    fn prop_adaptive_padding_length(
+        words_count in 1u8..=6,
+        padding_digits in (0u8..=4, 0u8..=4),
+        padding_symbols in "[!@#$%^&*]{1,4}",
+        length in 1usize..=64,
+    ) {
+        let settings = Settings::default()
+            .with_words_count(words_count)
+            .unwrap()
+            .with_padding_digits(Some(padding_digits.0), Some(padding_digits.1))
+            .with_padding_symbols(&padding_symbols)
+            .with_padding_strategy(PaddingStrategy::Adaptive(length))
+            .unwrap();
+
+        let (passwd, _) = Xkpasswd::preloaded().gen_pass(&settings);
+        prop_assert_eq!(length, passwd.chars().count(), "{}", passwd);
+    }
+}
@@ -0,0 +1,242 @@
+// Configs exported by xkpasswd.net and the original Crypt::HSXKPasswd Perl module,
+// e.g. {"num_words": 3, "case_transform": "ALTERNATE", "separator_character": "RANDOM", ...}
+
+use super::Settings;
+use crate::bit_flags::{BitFlags, FieldSize, WordTransform};
+use crate::prelude::{JoinStyle, PaddingStrategy, ProperNouns, SpellingVariant};
+use serde_json::{json, Map, Value};
+
+impl Settings {
+    pub fn from_xkpasswd_json(config: &str) -> Result<Self, String> {
+        let config: Value =
+            serde_json::from_str(config).map_err(|err| format!("invalid JSON: {}", err))?;
+        let config = config.as_object().ok_or("config must be a JSON object")?;
+
+        let mut settings = Settings::default();
+
+        if let Some(words_count) = get_number(config, "num_words")? {
+            settings.set_words_count(words_count)?;
+        }
+
+        settings.set_word_lengths(
+            get_number(config, "word_length_min")?,
+            get_number(config, "word_length_max")?,
+        )?;
+
+        if let Some(case_transform) = get_str(config, "case_transform")? {
+            settings.set_word_transforms(parse_case_transform(case_transform)?)?;
+        }
+
+        match get_str(config, "separator_character")? {
+            Some("NONE") => settings.set_separators(""),
+            Some("RANDOM") => settings.set_separators(&get_alphabet(config, "separator_alphabet")?),
+            Some(separator) => settings.set_separators(separator),
+            None => {}
+        }
+
+        settings.set_padding_digits(
+            get_number(config, "padding_digits_before")?,
+            get_number(config, "padding_digits_after")?,
+        );
+
+        match get_str(config, "padding_character")? {
+            // older exports name the padding alphabet "symbol_alphabet"
+            Some("RANDOM") => match get_alphabet(config, "padding_alphabet") {
+                Ok(symbols) => settings.set_padding_symbols(&symbols),
+                Err(_) => settings.set_padding_symbols(&get_alphabet(config, "symbol_alphabet")?),
+            },
+            Some("SEPARATOR") => settings.padding_symbols = settings.separators.clone(),
+            Some(symbol) => settings.set_padding_symbols(symbol),
+            None => {}
+        }
+
+        match get_str(config, "padding_type")? {
+            Some("NONE") => {
+                settings.set_padding_strategy(PaddingStrategy::Fixed)?;
+                settings.padding_symbols = "".to_string();
+                settings.padding_symbol_lengths = (0, 0);
+            }
+            Some("FIXED") => {
+                settings.set_padding_strategy(PaddingStrategy::Fixed)?;
+                settings.set_padding_symbol_lengths(
+                    get_number(config, "padding_characters_before")?,
+                    get_number(config, "padding_characters_after")?,
+                );
+            }
+            Some("ADAPTIVE") => {
+                let length = get_number(config, "pad_to_length")?
+                    .ok_or("'pad_to_length' is required for adaptive padding")?;
+                settings.set_padding_strategy(PaddingStrategy::Adaptive(length))?;
+            }
+            Some(padding_type) => {
+                return Err(format!("unsupported padding type '{}'", padding_type))
+            }
+            None => {}
+        }
+
+        Ok(settings)
+    }
+
+    /// Exports the settings xkpasswd configs can represent, failing rather than dropping others.
+    /// Extra entropy is left out, being a secret of its own rather than a setting.
+    pub fn to_xkpasswd_json(&self) -> Result<String, String> {
+        let unsupported = [
+            (
+                self.join_style != JoinStyle::Separated,
+                "camel case joining",
+            ),
+            (self.pronounceable, "pronounceable words"),
+            (self.checksum, "checksum digits"),
+            (self.word_theme.is_some(), "word themes"),
+            (
+                self.proper_nouns != ProperNouns::Include,
+                "proper noun filters",
+            ),
+            (self.spelling != SpellingVariant::Any, "spelling variants"),
+            (self.avoid_ambiguous, "avoiding ambiguous characters"),
+            (self.avoid_homophones, "avoiding homophones"),
+        ];
+
+        if let Some((_, feature)) = unsupported.iter().find(|(used, _)| *used) {
+            return Err(format!("xkpasswd configs have no notion of {}", feature));
+        }
+
+        let (word_length_min, word_length_max) = self.word_lengths;
+        let (padding_digits_before, padding_digits_after) = self.padding_digits;
+
+        let mut config = json!({
+            "num_words": self.words_count,
+            "word_length_min": word_length_min,
+            "word_length_max": word_length_max,
+            "case_transform": format_case_transform(self.applied_transforms())?,
+            "padding_digits_before": padding_digits_before,
+            "padding_digits_after": padding_digits_after,
+        });
+        let fields = config.as_object_mut().unwrap();
+
+        match self.separators.chars().count() {
+            0 => {
+                fields.insert("separator_character".to_string(), json!("NONE"));
+            }
+            1 => {
+                fields.insert("separator_character".to_string(), json!(self.separators));
+            }
+            _ => {
+                fields.insert("separator_character".to_string(), json!("RANDOM"));
+                fields.insert(
+                    "separator_alphabet".to_string(),
+                    to_alphabet(&self.separators),
+                );
+            }
+        }
+
+        let padding_type = match self.padding_strategy {
+            PaddingStrategy::Fixed
+                if self.padding_symbols.is_empty() || self.padding_symbol_lengths == (0, 0) =>
+            {
+                "NONE"
+            }
+            PaddingStrategy::Fixed => {
+                let (before, after) = self.padding_symbol_lengths;
+                fields.insert("padding_characters_before".to_string(), json!(before));
+                fields.insert("padding_characters_after".to_string(), json!(after));
+                "FIXED"
+            }
+            PaddingStrategy::Adaptive(length) => {
+                fields.insert("pad_to_length".to_string(), json!(length));
+                "ADAPTIVE"
+            }
+        };
+        fields.insert("padding_type".to_string(), json!(padding_type));
+
+        if padding_type != "NONE" {
+            if self.padding_symbols.chars().count() == 1 {
+                fields.insert("padding_character".to_string(), json!(self.padding_symbols));
+            } else {
+                fields.insert("padding_character".to_string(), json!("RANDOM"));
+                fields.insert(
+                    "padding_alphabet".to_string(),
+                    to_alphabet(&self.padding_symbols),
+                );
+            }
+        }
+
+        serde_json::to_string_pretty(&config).map_err(|err| err.to_string())
+    }
+}
+
+fn parse_case_transform(case_transform: &str) -> Result<FieldSize, String> {
+    let transforms = match case_transform {
+        // dictionary words are all lowercase already
+        "NONE" | "LOWER" => FieldSize::from_flag(WordTransform::Lowercase),
+        "UPPER" => FieldSize::from_flag(WordTransform::Uppercase),
+        "CAPITALISE" => FieldSize::from_flag(WordTransform::Titlecase),
+        "INVERT" => FieldSize::from_flag(WordTransform::InversedTitlecase),
+        "ALTERNATE" => FieldSize::from_flag(WordTransform::AltercaseLowerFirst),
+        "RANDOM" => WordTransform::Lowercase | WordTransform::Uppercase,
+        _ => return Err(format!("unsupported case transform '{}'", case_transform)),
+    };
+
+    Ok(transforms)
+}
+
+fn format_case_transform(transforms: FieldSize) -> Result<&'static str, String> {
+    let case_transform = match transforms.to_flags().as_slice() {
+        [WordTransform::Lowercase] => "LOWER",
+        [WordTransform::Uppercase] => "UPPER",
+        [WordTransform::Titlecase] => "CAPITALISE",
+        [WordTransform::InversedTitlecase] => "INVERT",
+        // xkpasswd always starts alternating with a lowercase word
+        [WordTransform::AltercaseLowerFirst] => "ALTERNATE",
+        [WordTransform::Lowercase, WordTransform::Uppercase] => "RANDOM",
+        _ => {
+            return Err(format!(
+                "word transforms {} have no xkpasswd equivalent",
+                transforms.to_strings().join(" & ")
+            ))
+        }
+    };
+
+    Ok(case_transform)
+}
+
+fn get_number<T: TryFrom<u64>>(
+    config: &Map<String, Value>,
+    field: &str,
+) -> Result<Option<T>, String> {
+    match config.get(field) {
+        None => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .and_then(|number| T::try_from(number).ok())
+            .map(Some)
+            .ok_or(format!("invalid number for '{}'", field)),
+    }
+}
+
+fn get_str<'a>(config: &'a Map<String, Value>, field: &str) -> Result<Option<&'a str>, String> {
+    match config.get(field) {
+        None => Ok(None),
+        Some(value) => value
+            .as_str()
+            .map(Some)
+            .ok_or(format!("invalid string for '{}'", field)),
+    }
+}
+
+fn get_alphabet(config: &Map<String, Value>, field: &str) -> Result<String, String> {
+    config
+        .get(field)
+        .and_then(|value| value.as_array())
+        .and_then(|chars| {
+            chars
+                .iter()
+                .map(|char| char.as_str())
+                .collect::<Option<String>>()
+        })
+        .ok_or(format!("invalid alphabet for '{}'", field))
+}
+
+fn to_alphabet(chars: &str) -> Value {
+    chars.chars().map(|char| char.to_string()).collect()
+}
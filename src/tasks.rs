@@ -0,0 +1,103 @@
+// Async wrappers for services built on their own tokio runtime, `xkpasswd serve` runs
+// on plain threads instead: loading dictionaries & generating batches are CPU or disk bound,
+// so they run on the blocking pool instead of stalling the workers while other requests wait.
+
+use crate::dict::load_any_dict;
+use crate::prelude::{Entropy, Randomizer, Xkpasswd};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::task;
+
+/// Reads & parses a dictionary file in any supported format, shared across requests.
+///
+/// Like `Xkpasswd::from_url`, each loaded dictionary keeps its own bytes for the rest
+/// of the program, so it's meant to be loaded once & shared, e.g. one per tenant,
+/// rather than once per request.
+#[cfg_attr(feature = "trace", tracing::instrument(level = "debug", skip_all, err))]
+pub async fn load_dict(path: PathBuf) -> Result<Arc<Xkpasswd<'static>>, String> {
+    run_blocking(move || {
+        let dict_bytes = std::fs::read(&path)
+            .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+
+        // checked before being kept, so failed loads don't hold on to anything
+        if load_any_dict(&dict_bytes)?.is_empty() {
+            return Err(format!("no words in the dictionary at {}", path.display()));
+        }
+
+        let dict = load_any_dict(dict_bytes.leak())?;
+        Ok(Arc::new(Xkpasswd::from_dict(dict)))
+    })
+    .await?
+}
+
+/// Generates `count` passwords like `Xkpasswd::gen_passes`, off the async workers.
+pub async fn gen_passes<S: Randomizer + Send + Sync + ?Sized + 'static>(
+    pass_generator: Arc<Xkpasswd<'static>>,
+    settings: Arc<S>,
+    count: usize,
+) -> Result<(Vec<String>, Entropy), String> {
+    run_blocking(move || pass_generator.gen_passes(settings.as_ref(), count)).await
+}
+
+async fn run_blocking<T: Send + 'static, F: FnOnce() -> T + Send + 'static>(
+    func: F,
+) -> Result<T, String> {
+    task::spawn_blocking(func)
+        .await
+        .map_err(|err| format!("generation task failed: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Settings;
+    use std::fs;
+    use tokio::runtime::Builder;
+
+    #[test]
+    fn test_tasks() {
+        let runtime = Builder::new_current_thread().build().unwrap();
+        let dir = std::env::temp_dir().join(format!("xkpasswd-tasks-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("words.txt");
+
+        runtime.block_on(async {
+            fs::write(&path, "").unwrap();
+            assert_eq!(
+                Err(format!("no words in the dictionary at {}", path.display())),
+                load_dict(path.clone()).await.map(|_| ())
+            );
+
+            let err = load_dict(dir.join("nope.txt")).await.unwrap_err();
+            assert!(err.starts_with("failed to read "), "{}", err);
+
+            fs::write(&path, "ball\ncake\ndoor\nlamp\nmilk\n").unwrap();
+            let pass_generator = load_dict(path.clone()).await.unwrap();
+            let settings = Arc::new(Settings::default());
+
+            // concurrent requests sharing the same generator & settings
+            let requests: Vec<_> = (1..=4)
+                .map(|count| {
+                    tokio::spawn(gen_passes(pass_generator.clone(), settings.clone(), count))
+                })
+                .collect();
+
+            for (idx, request) in requests.into_iter().enumerate() {
+                let (passwds, _) = request.await.unwrap().unwrap();
+                assert_eq!(idx + 1, passwds.len());
+                for passwd in passwds {
+                    assert!(settings.matches(&passwd, &pass_generator), "{}", passwd);
+                }
+            }
+
+            // other dictionaries load independently, leaving the first one as it was
+            let other_path = dir.join("other.txt");
+            fs::write(&other_path, "horse\nzebra\n").unwrap();
+            let other_generator = load_dict(other_path).await.unwrap();
+            assert_eq!(&["horse", "zebra"], other_generator.dict().pool(0..u8::MAX));
+            assert_eq!(5, pass_generator.dict().len());
+        });
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
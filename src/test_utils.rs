@@ -0,0 +1,183 @@
+// Deterministic stand-ins for the random parts of the generator, for downstream unit tests
+// asserting exact outputs, e.g. of code calling `Xkpasswd::gen_pass`.
+
+use crate::prelude::{Entropy, Randomizer, CHECKSUM_LENGTH};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::ops::Range;
+
+/// A `Randomizer` picking scripted words, separator & padding instead of random ones,
+/// so `gen_pass` always builds the same password from any dictionary, e.g.
+/// `MockRandomizer::new(&["correct", "horse"]).with_separator("-")` gives `correct-horse`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MockRandomizer {
+    words: Vec<String>,
+    separator: String,
+    prefix: (String, String),
+    suffix: (String, String),
+    checksum: bool,
+    entropy: Entropy,
+}
+
+impl MockRandomizer {
+    /// Words used as they are, whatever the pool they would be picked from.
+    pub fn new(words: &[&str]) -> Self {
+        MockRandomizer {
+            words: words.iter().map(|word| word.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Padding before the words, e.g. `("!!", "42")` for `!!42.correct.horse`.
+    pub fn with_prefix(mut self, symbols: &str, digits: &str) -> Self {
+        self.prefix = (symbols.to_string(), digits.to_string());
+        self
+    }
+
+    /// Padding after the words, e.g. `("42", "!!")` for `correct.horse.42!!`.
+    pub fn with_suffix(mut self, digits: &str, symbols: &str) -> Self {
+        self.suffix = (digits.to_string(), symbols.to_string());
+        self
+    }
+
+    pub fn with_checksum(mut self, checksum: bool) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// Entropy reported along the password, nothing by default.
+    pub fn with_entropy(mut self, entropy: Entropy) -> Self {
+        self.entropy = entropy;
+        self
+    }
+
+    /// Length of the password the mock always builds, in characters.
+    pub fn length_range(&self) -> (usize, usize) {
+        let parts = [&self.prefix.1, &self.suffix.0]
+            .into_iter()
+            .filter(|digits| !digits.is_empty())
+            .chain(&self.words);
+        let separators = parts.clone().count().saturating_sub(1) * self.separator.chars().count();
+
+        let length = parts.map(|part| part.chars().count()).sum::<usize>()
+            + separators
+            + self.prefix.0.chars().count()
+            + self.suffix.1.chars().count()
+            + if self.checksum { CHECKSUM_LENGTH } else { 0 };
+
+        (length, length)
+    }
+}
+
+impl Randomizer for MockRandomizer {
+    fn word_lengths(&self) -> Range<u8> {
+        1..u8::MAX
+    }
+
+    fn rand_words(&self, _pool: &[&str]) -> Vec<String> {
+        self.words.clone()
+    }
+
+    fn rand_separator(&self) -> String {
+        self.separator.clone()
+    }
+
+    fn rand_prefix(&self) -> (String, String) {
+        self.prefix.clone()
+    }
+
+    fn rand_suffix(&self) -> (String, String) {
+        self.suffix.clone()
+    }
+
+    fn appends_checksum(&self) -> bool {
+        self.checksum
+    }
+
+    fn calc_entropy(&self, _pool_size: usize) -> Entropy {
+        self.entropy
+    }
+}
+
+/// A generator replaying the same numbers for the same seed, for functions taking one,
+/// like `gen_pin_with` or `Codenames::gen_codename`. Numbers may differ across `rand` versions.
+#[derive(Clone, Debug)]
+pub struct FixedRng(StdRng);
+
+impl FixedRng {
+    pub fn new(seed: u64) -> Self {
+        FixedRng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl RngCore for FixedRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pin::gen_pin_with;
+    use crate::prelude::Xkpasswd;
+
+    #[test]
+    fn test_mock_randomizer() {
+        let pass_generator = Xkpasswd::default();
+
+        let mock = MockRandomizer::new(&["correct", "horse", "battery", "staple"]);
+        assert_eq!(
+            "correcthorsebatterystaple",
+            pass_generator.gen_pass(&mock).0
+        );
+
+        let mock = mock
+            .with_separator("-")
+            .with_prefix("!", "12")
+            .with_suffix("34", "?")
+            .with_entropy(Entropy {
+                seen: 44,
+                ..Default::default()
+            });
+        let (passwd, entropy) = pass_generator.gen_pass(&mock);
+        assert_eq!("!12-correct-horse-battery-staple-34?", passwd);
+        assert_eq!(44, entropy.seen);
+        assert_eq!((passwd.len(), passwd.len()), mock.length_range());
+
+        let mock = MockRandomizer::new(&["correct", "horse"]).with_checksum(true);
+        let passwd = pass_generator.gen_pass(&mock).0;
+        assert!(passwd.starts_with("correcthorse"), "{}", passwd);
+        assert_eq!((passwd.len(), passwd.len()), mock.length_range());
+        assert_eq!(passwd, pass_generator.gen_pass(&mock).0);
+    }
+
+    #[test]
+    fn test_fixed_rng() {
+        let pins: Vec<String> = (0..2)
+            .map(|_| gen_pin_with(&mut FixedRng::new(42), 6).unwrap())
+            .collect();
+        assert_eq!(pins[0], pins[1]);
+
+        let mut rng = FixedRng::new(42);
+        assert_ne!(rng.next_u64(), rng.next_u64());
+        assert_ne!(FixedRng::new(1).next_u64(), FixedRng::new(2).next_u64());
+    }
+}
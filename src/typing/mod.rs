@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod tests;
+
+use crate::prelude::{Entropy, Randomizer, Xkpasswd};
+use std::fmt;
+
+// typed with Shift on a US keyboard, along with capitals
+const US_SHIFTED_SYMBOLS: &str = "~!@#$%^&*()_+{}|:\"<>?";
+// the first layer of symbols on phone keyboards, the others need one more switch
+const MOBILE_NUMBERS_LAYER: &str = "1234567890-/:;()$&@\".,?!'";
+
+/// Keyboards a password is typed on, each with its own way of reaching capitals & symbols.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeyboardLayout {
+    /// A US QWERTY keyboard, where capitals & most symbols need Shift
+    #[default]
+    Desktop,
+    /// A phone's on-screen keyboard, with letters, numbers & other symbols on separate layers
+    Mobile,
+}
+
+/// Estimated effort to type a password, see `typing_effort`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TypingEffort {
+    /// Key presses or taps, including Shift, caps lock & layer keys
+    pub keystrokes: usize,
+    /// Times Shift is held or the on-screen keyboard changes layer
+    pub layer_switches: usize,
+}
+
+impl TypingEffort {
+    /// A single figure to compare efforts, where layer switches count twice
+    /// as they also break the typing flow.
+    pub fn score(&self) -> usize {
+        self.keystrokes + self.layer_switches
+    }
+}
+
+impl fmt::Display for TypingEffort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} keystroke(s) & {} layer switch(es)",
+            self.keystrokes, self.layer_switches
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MobileLayer {
+    Letters,
+    Numbers,
+    Symbols,
+}
+
+impl MobileLayer {
+    fn of(char: char) -> Self {
+        if char.is_alphabetic() || char == ' ' {
+            Self::Letters
+        } else if MOBILE_NUMBERS_LAYER.contains(char) {
+            Self::Numbers
+        } else {
+            Self::Symbols
+        }
+    }
+
+    // symbols are only reached through the numbers layer
+    fn taps_to(self, layer: Self) -> usize {
+        match (self, layer) {
+            (Self::Letters, Self::Symbols) => 2,
+            _ if self == layer => 0,
+            _ => 1,
+        }
+    }
+}
+
+/// Estimates keystrokes & layer switches needed to type `passwd` on `layout`.
+/// Characters out of the layout, like accented ones, take one more keystroke
+/// for a long press, a dead key or AltGr.
+pub fn typing_effort(passwd: &str, layout: KeyboardLayout) -> TypingEffort {
+    match layout {
+        KeyboardLayout::Desktop => desktop_effort(passwd),
+        KeyboardLayout::Mobile => mobile_effort(passwd),
+    }
+}
+
+// Shift is held once for a run of shifted characters
+fn desktop_effort(passwd: &str) -> TypingEffort {
+    let mut effort = TypingEffort::default();
+    let mut shifted_run = 0;
+
+    for char in passwd.chars() {
+        effort.keystrokes += if char.is_ascii() { 1 } else { 2 };
+
+        if char.is_uppercase() || US_SHIFTED_SYMBOLS.contains(char) {
+            shifted_run += 1;
+
+            if shifted_run == 1 {
+                effort.keystrokes += 1;
+                effort.layer_switches += 1;
+            }
+        } else {
+            shifted_run = 0;
+        }
+    }
+
+    effort
+}
+
+// a capital takes a Shift tap, from the 3rd in a row caps lock is cheaper:
+// a double tap to lock & one more to unlock, i.e. min(run, 3) taps & min(run, 2) switches
+fn mobile_effort(passwd: &str) -> TypingEffort {
+    let mut effort = TypingEffort::default();
+    let mut layer = MobileLayer::Letters;
+    let mut capitals_run = 0;
+
+    for char in passwd.chars() {
+        let target = MobileLayer::of(char);
+
+        // space is on every layer
+        if char != ' ' && target != layer {
+            effort.keystrokes += layer.taps_to(target);
+            effort.layer_switches += 1;
+            layer = target;
+        }
+
+        effort.keystrokes += if char.is_ascii() { 1 } else { 2 };
+
+        if char.is_uppercase() {
+            capitals_run += 1;
+            effort.keystrokes += usize::from(capitals_run <= 3);
+            effort.layer_switches += usize::from(capitals_run <= 2);
+        } else {
+            capitals_run = 0;
+        }
+    }
+
+    effort
+}
+
+impl Xkpasswd<'_> {
+    /// Generates `candidates` passwords & returns the easiest to type on `layout`,
+    /// see `gen_pass_min_by_key` for the entropy it costs.
+    pub fn gen_easiest_pass<S: Randomizer + ?Sized>(
+        &self,
+        settings: &S,
+        layout: KeyboardLayout,
+        candidates: usize,
+    ) -> (String, Entropy) {
+        self.gen_pass_min_by_key(settings, candidates, |passwd| {
+            typing_effort(passwd, layout).score()
+        })
+    }
+}
@@ -0,0 +1,101 @@
+use super::*;
+use crate::bit_flags::{BitFlags, FieldSize, WordTransform};
+use crate::prelude::{Builder, Xkpasswd};
+use crate::settings::Settings;
+
+fn effort(keystrokes: usize, layer_switches: usize) -> TypingEffort {
+    TypingEffort {
+        keystrokes,
+        layer_switches,
+    }
+}
+
+#[test]
+fn test_desktop_effort() {
+    let expectations = [
+        ("", effort(0, 0)),
+        ("correct.horse", effort(13, 0)),
+        ("Correct", effort(8, 1)),
+        // Shift held once for the whole run
+        ("HORSE", effort(6, 1)),
+        ("HORSE!!horse", effort(13, 1)),
+        ("Horse-Staple", effort(14, 2)),
+        ("12-34", effort(5, 0)),
+        ("café", effort(5, 0)),
+    ];
+
+    for (passwd, expected) in expectations {
+        assert_eq!(
+            expected,
+            typing_effort(passwd, KeyboardLayout::Desktop),
+            "{}",
+            passwd
+        );
+    }
+}
+
+#[test]
+fn test_mobile_effort() {
+    let expectations = [
+        ("", effort(0, 0)),
+        ("correct horse", effort(13, 0)),
+        ("Correct", effort(8, 1)),
+        // caps lock from the 3rd capital
+        ("HO", effort(4, 2)),
+        ("HORSE", effort(8, 2)),
+        // to numbers & back
+        ("horse12horse", effort(14, 2)),
+        // symbols through numbers
+        ("horse#horse", effort(14, 2)),
+        ("12#", effort(5, 2)),
+        ("café", effort(5, 0)),
+    ];
+
+    for (passwd, expected) in expectations {
+        assert_eq!(
+            expected,
+            typing_effort(passwd, KeyboardLayout::Mobile),
+            "{}",
+            passwd
+        );
+    }
+
+    assert_eq!(16, effort(14, 2).score());
+    assert_eq!(
+        "14 keystroke(s) & 2 layer switch(es)",
+        effort(14, 2).to_string()
+    );
+}
+
+#[test]
+fn test_gen_easiest_pass() {
+    let pass_generator = Xkpasswd::default();
+    let settings = Settings::default()
+        .with_word_transforms(WordTransform::Lowercase | WordTransform::Uppercase)
+        .unwrap();
+
+    let (_, entropy) = pass_generator.gen_pass(&settings);
+    let (passwd, easiest_entropy) =
+        pass_generator.gen_easiest_pass(&settings, KeyboardLayout::Mobile, 16);
+
+    assert!(settings.matches(&passwd, &pass_generator), "{}", passwd);
+    assert_eq!(entropy.seen - 4, easiest_entropy.seen);
+    assert_eq!(entropy.blind_min, easiest_entropy.blind_min);
+
+    // nothing to pick from
+    let (_, single_entropy) =
+        pass_generator.gen_easiest_pass(&settings, KeyboardLayout::Desktop, 0);
+    assert_eq!(entropy, single_entropy);
+
+    let lowercase = settings
+        .with_word_transforms(FieldSize::from_flag(WordTransform::Lowercase))
+        .unwrap();
+    let (passwd, _) = pass_generator.gen_easiest_pass(&lowercase, KeyboardLayout::Desktop, 64);
+    let effort = typing_effort(&passwd, KeyboardLayout::Desktop);
+
+    // the odds of 64 other candidates all being easier are negligible
+    let (candidates, _) = pass_generator.gen_passes(&lowercase, 64);
+    assert!(candidates.iter().any(
+        |candidate| typing_effort(candidate, KeyboardLayout::Desktop).score() >= effort.score()
+    ));
+}
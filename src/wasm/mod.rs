@@ -1,3 +1,5 @@
+#[cfg(feature = "wasm_objects")]
+mod object;
 mod utils;
 
 use crate::prelude::*;
@@ -57,6 +59,12 @@ impl WasmSettings {
         WasmSettings { settings }
     }
 
+    #[wasm_bindgen(js_name = "withSymbolSet")]
+    pub fn with_symbol_set(&self, symbol_set: SymbolSet) -> WasmSettings {
+        let settings = self.settings.with_symbol_set(symbol_set);
+        WasmSettings { settings }
+    }
+
     #[wasm_bindgen(js_name = "withPaddingSymbolLengths")]
     pub fn with_padding_symbol_lengths(
         &self,
@@ -94,12 +102,76 @@ impl WasmSettings {
         WasmSettings { settings }
     }
 
+    #[wasm_bindgen(js_name = "withJoinStyle")]
+    pub fn with_join_style(&self, style: JoinStyle) -> WasmSettings {
+        let settings = self.settings.with_join_style(style);
+        WasmSettings { settings }
+    }
+
+    #[wasm_bindgen(js_name = "withPronounceable")]
+    pub fn with_pronounceable(&self, pronounceable: bool) -> WasmSettings {
+        let settings = self.settings.with_pronounceable(pronounceable);
+        WasmSettings { settings }
+    }
+
+    #[cfg(feature = "extra_entropy")]
+    #[wasm_bindgen(js_name = "withExtraEntropy")]
+    pub fn with_extra_entropy(&self, entropy: &[u8]) -> WasmSettings {
+        let settings = self.settings.with_extra_entropy(entropy);
+        WasmSettings { settings }
+    }
+
+    #[wasm_bindgen(js_name = "withChecksum")]
+    pub fn with_checksum(&self, checksum: bool) -> WasmSettings {
+        let settings = self.settings.with_checksum(checksum);
+        WasmSettings { settings }
+    }
+
+    #[wasm_bindgen(js_name = "withSpelling")]
+    pub fn with_spelling(&self, spelling: SpellingVariant) -> WasmSettings {
+        let settings = self.settings.with_spelling(spelling);
+        WasmSettings { settings }
+    }
+
+    #[wasm_bindgen(js_name = "withAvoidAmbiguous")]
+    pub fn with_avoid_ambiguous(&self, avoid_ambiguous: bool) -> WasmSettings {
+        let settings = self.settings.with_avoid_ambiguous(avoid_ambiguous);
+        WasmSettings { settings }
+    }
+
+    #[wasm_bindgen(js_name = "withAvoidHomophones")]
+    pub fn with_avoid_homophones(&self, avoid_homophones: bool) -> WasmSettings {
+        let settings = self.settings.with_avoid_homophones(avoid_homophones);
+        WasmSettings { settings }
+    }
+
     #[wasm_bindgen(js_name = "fromPreset")]
     pub fn from_preset(preset: Preset) -> WasmSettings {
         WasmSettings {
             settings: Settings::from_preset(preset),
         }
     }
+
+    #[wasm_bindgen(js_name = "toQueryString")]
+    pub fn to_query_string(&self) -> String {
+        self.settings.to_query_string()
+    }
+
+    #[wasm_bindgen(js_name = "fromQueryString")]
+    pub fn from_query_string(query: &str) -> Result<WasmSettings, String> {
+        let settings = Settings::from_query_string(query)?;
+        Ok(WasmSettings { settings })
+    }
+}
+
+#[wasm_bindgen(js_name = "presetDescription")]
+pub fn preset_description(preset: Preset) -> String {
+    preset.description().to_string()
+}
+
+#[wasm_bindgen(js_name = "presetRecommendedUse")]
+pub fn preset_recommended_use(preset: Preset) -> String {
+    preset.recommended_use().to_string()
 }
 
 #[wasm_bindgen]
@@ -120,7 +192,7 @@ impl PasswdResult {
 #[wasm_bindgen(js_name = "Xkpasswd")]
 #[derive(Debug, Default)]
 pub struct WasmXkpasswd {
-    pass_generator: Xkpasswd,
+    pass_generator: Xkpasswd<'static>,
 }
 
 #[wasm_bindgen(js_class = "Xkpasswd")]
@@ -140,4 +212,22 @@ impl WasmXkpasswd {
 
         PasswdResult { passwd, entropy }
     }
+
+    /// Example passwords indexed by preset.
+    #[wasm_bindgen(js_name = "genAllPresets")]
+    pub fn gen_all_presets(&self) -> Vec<String> {
+        self.pass_generator
+            .gen_all_presets()
+            .into_values()
+            .collect()
+    }
+
+    pub fn lint(&self, js_settings: &WasmSettings) -> Vec<String> {
+        js_settings
+            .settings
+            .lint(&self.pass_generator)
+            .iter()
+            .map(|warning| warning.to_string())
+            .collect()
+    }
 }
@@ -0,0 +1,146 @@
+use super::WasmSettings;
+use crate::bit_flags::FieldSize;
+use crate::prelude::*;
+use crate::settings::{Settings, SettingsPatch};
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const SETTINGS_OBJECT_TS: &'static str = r#"
+/**
+ * Settings as a plain object, unset fields keep the value of `preset`
+ * or the default settings. Enums take the values of their exported counterparts.
+ */
+export interface SettingsObject {
+  preset?: Preset;
+  wordsCount?: number;
+  wordLengthMin?: number;
+  wordLengthMax?: number;
+  wordTransforms?: number;
+  separators?: string;
+  paddingDigitsBefore?: number;
+  paddingDigitsAfter?: number;
+  paddingSymbols?: string;
+  paddingSymbolsBefore?: number;
+  paddingSymbolsAfter?: number;
+  adaptiveLength?: number;
+  joinStyle?: JoinStyle;
+  pronounceable?: boolean;
+  checksum?: boolean;
+  wordTheme?: string;
+  properNouns?: ProperNouns;
+  spelling?: SpellingVariant;
+  avoidAmbiguous?: boolean;
+  avoidHomophones?: boolean;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "SettingsObject")]
+    pub type SettingsObject;
+}
+
+// mirrors the TypeScript interface above, enums as their JS numbers
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+struct SettingsFields {
+    preset: Option<u8>,
+    words_count: Option<u8>,
+    word_length_min: Option<u8>,
+    word_length_max: Option<u8>,
+    word_transforms: Option<FieldSize>,
+    separators: Option<String>,
+    padding_digits_before: Option<u8>,
+    padding_digits_after: Option<u8>,
+    padding_symbols: Option<String>,
+    padding_symbols_before: Option<u8>,
+    padding_symbols_after: Option<u8>,
+    adaptive_length: Option<usize>,
+    join_style: Option<u8>,
+    pronounceable: Option<bool>,
+    checksum: Option<bool>,
+    word_theme: Option<String>,
+    proper_nouns: Option<u8>,
+    spelling: Option<u8>,
+    avoid_ambiguous: Option<bool>,
+    avoid_homophones: Option<bool>,
+}
+
+// wasm_bindgen numbers enum variants in declaration order
+fn js_enum<T: Copy>(variants: &[T], value: Option<u8>, name: &str) -> Result<Option<T>, String> {
+    value
+        .map(|value| {
+            variants
+                .get(value as usize)
+                .copied()
+                .ok_or_else(|| format!("invalid {} {}", name, value))
+        })
+        .transpose()
+}
+
+impl SettingsFields {
+    fn settings(&self) -> Result<Settings, String> {
+        let base = match js_enum(&Preset::ALL, self.preset, "preset")? {
+            Some(preset) => Settings::from_preset(preset),
+            None => Settings::default(),
+        };
+
+        let patch = SettingsPatch {
+            words_count: self.words_count,
+            word_length_min: self.word_length_min,
+            word_length_max: self.word_length_max,
+            word_transforms: self.word_transforms,
+            separators: self.separators.clone(),
+            padding_digits_before: self.padding_digits_before,
+            padding_digits_after: self.padding_digits_after,
+            padding_symbols: self.padding_symbols.clone(),
+            padding_symbols_before: self.padding_symbols_before,
+            padding_symbols_after: self.padding_symbols_after,
+            padding_strategy: self.adaptive_length.map(PaddingStrategy::Adaptive),
+            join_style: js_enum(
+                &[JoinStyle::Separated, JoinStyle::Camel],
+                self.join_style,
+                "join style",
+            )?,
+            pronounceable: self.pronounceable,
+            checksum: self.checksum,
+            word_theme: self.word_theme.clone(),
+            proper_nouns: js_enum(
+                &[
+                    ProperNouns::Include,
+                    ProperNouns::Exclude,
+                    ProperNouns::Preserve,
+                ],
+                self.proper_nouns,
+                "proper nouns",
+            )?,
+            spelling: js_enum(
+                &[
+                    SpellingVariant::Any,
+                    SpellingVariant::British,
+                    SpellingVariant::American,
+                ],
+                self.spelling,
+                "spelling variant",
+            )?,
+            avoid_ambiguous: self.avoid_ambiguous,
+            avoid_homophones: self.avoid_homophones,
+            ..SettingsPatch::default()
+        };
+
+        patch.apply(&base)
+    }
+}
+
+#[wasm_bindgen(js_class = "Settings")]
+impl WasmSettings {
+    /// e.g. `Settings.fromObject({ wordsCount: 4, separators: "-" })`
+    #[wasm_bindgen(js_name = "fromObject")]
+    pub fn from_object(object: SettingsObject) -> Result<WasmSettings, String> {
+        let fields: SettingsFields =
+            serde_wasm_bindgen::from_value(object.into()).map_err(|err| err.to_string())?;
+        let settings = fields.settings()?;
+        Ok(WasmSettings { settings })
+    }
+}
@@ -18,6 +18,13 @@ const PRESET_OPTIONS = [
   { text: 'Web32', preset: xkpasswd.Preset.Web32 },
   { text: 'Wifi', preset: xkpasswd.Preset.Wifi },
   { text: 'XKCD', preset: xkpasswd.Preset.Xkcd },
+  { text: 'NIST', preset: xkpasswd.Preset.Nist },
+  { text: 'Temporary', preset: xkpasswd.Preset.Temporary },
+  { text: 'Wifi (easy typing)', preset: xkpasswd.Preset.WifiEasyType },
+  { text: 'Mobile', preset: xkpasswd.Preset.Mobile },
+  { text: 'SSH passphrase', preset: xkpasswd.Preset.SshPassphrase },
+  { text: 'Disk encryption', preset: xkpasswd.Preset.DiskEncryption },
+  { text: 'Database', preset: xkpasswd.Preset.DatabaseSafe },
 ];
 
 type Props = {